@@ -71,6 +71,10 @@ async function exportAnalyticsHook() {
 async function main() {
   checkNodeVersion();
 
+  // the compiled Wing code comes with source maps, so errors thrown by it (e.g. during preflight or
+  // in tests) point back to the Wing source code
+  process.setSourceMapsEnabled(true);
+
   const program = new Command();
 
   program.name("wing").version(PACKAGE_VERSION);
@@ -136,6 +140,8 @@ async function main() {
         .default("sim")
     )
     .option("-p, --plugins [plugin...]", "Compiler plugins")
+    .option("--warnings-as-errors", "Report compiler warnings as errors")
+    .option("--library", "Compile the entrypoint as a library, emitting a JSII assembly of its public types")
     .hook("preAction", progressHook)
     .hook("preAction", collectAnalyticsHook)
     .action(runSubCommand("compile"));
@@ -152,6 +158,7 @@ async function main() {
         .default("sim")
     )
     .option("-p, --plugins [plugin...]", "Compiler plugins")
+    .option("--warnings-as-errors", "Report compiler warnings as errors")
     .hook("preAction", progressHook)
     .hook("preAction", collectAnalyticsHook)
     .action(runSubCommand("test"));
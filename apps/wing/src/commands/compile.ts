@@ -24,6 +24,14 @@ export interface CompileOptions {
    * copies of the application resources in order to run tests in parallel.
    */
   readonly testing?: boolean;
+  /**
+   * Whether to report compiler warnings as errors.
+   */
+  readonly warningsAsErrors?: boolean;
+  /**
+   * Whether to compile the entrypoint as a library instead of an app.
+   */
+  readonly library?: boolean;
 }
 
 /**
@@ -33,54 +41,21 @@ export interface CompileOptions {
  * @returns the output directory
  */
 export async function compile(entrypoint: string, options: CompileOptions): Promise<string> {
+  let warnings: wingCompiler.WingDiagnostic[] = [];
   try {
-    return await wingCompiler.compile(entrypoint, {
+    const synthDir = await wingCompiler.compile(entrypoint, {
       ...options,
       log,
+      onWarnings: (diagnostics) => (warnings = diagnostics),
     });
+    if (warnings.length > 0) {
+      console.error(await renderDiagnostics(warnings));
+    }
+    return synthDir;
   } catch (error) {
     if (error instanceof wingCompiler.CompileError) {
       // This is a bug in the user's code. Print the compiler diagnostics.
-      const errors = error.diagnostics;
-      const result = [];
-      const coloring = chalk.supportsColor ? chalk.supportsColor.hasBasic : false;
-
-      for (const error of errors) {
-        const { message, span } = error;
-        let files: File[] = [];
-        let labels: Label[] = [];
-
-        // file_id might be "" if the span is synthetic (see #2521)
-        if (span?.file_id) {
-          // `span` should only be null if source file couldn't be read etc.
-          const source = await fsPromise.readFile(span.file_id, "utf8");
-          const start = byteOffsetFromLineAndColumn(source, span.start.line, span.start.col);
-          const end = byteOffsetFromLineAndColumn(source, span.end.line, span.end.col);
-          files.push({ name: span.file_id, source });
-          labels.push({
-            fileId: span.file_id,
-            rangeStart: start,
-            rangeEnd: end,
-            message,
-            style: "primary",
-          });
-        }
-
-        const diagnosticText = emitDiagnostic(
-          files,
-          {
-            message,
-            severity: "error",
-            labels,
-          },
-          {
-            chars: CHARS_ASCII,
-          },
-          coloring
-        );
-        result.push(diagnosticText);
-      }
-      throw new Error(result.join("\n"));
+      throw new Error(await renderDiagnostics(error.diagnostics));
     } else if (error instanceof wingCompiler.PreflightError) {
       const causedBy = annotatePreflightError(error.causedBy);
 
@@ -127,6 +102,67 @@ export async function compile(entrypoint: string, options: CompileOptions): Prom
   }
 }
 
+/**
+ * Renders compiler diagnostics the same way the compiler CLI does.
+ */
+async function renderDiagnostics(diagnostics: wingCompiler.WingDiagnostic[]): Promise<string> {
+  const result = [];
+  const coloring = chalk.supportsColor ? chalk.supportsColor.hasBasic : false;
+
+  for (const diagnostic of diagnostics) {
+    const { message, span, severity, code, annotations, fixes } = diagnostic;
+    let files: File[] = [];
+    let labels: Label[] = [];
+
+    // file_id might be "" if the span is synthetic (see #2521)
+    if (span?.file_id) {
+      // `span` should only be null if source file couldn't be read etc.
+      const source = await fsPromise.readFile(span.file_id, "utf8");
+      const start = byteOffsetFromLineAndColumn(source, span.start.line, span.start.col);
+      const end = byteOffsetFromLineAndColumn(source, span.end.line, span.end.col);
+      files.push({ name: span.file_id, source });
+      labels.push({
+        fileId: span.file_id,
+        rangeStart: start,
+        rangeEnd: end,
+        message,
+        style: "primary",
+      });
+
+      for (const annotation of annotations) {
+        // related spans may point to other files (e.g. a class brought from another module)
+        if (annotation.span.file_id !== span.file_id) {
+          continue;
+        }
+        labels.push({
+          fileId: span.file_id,
+          rangeStart: byteOffsetFromLineAndColumn(source, annotation.span.start.line, annotation.span.start.col),
+          rangeEnd: byteOffsetFromLineAndColumn(source, annotation.span.end.line, annotation.span.end.col),
+          message: annotation.message,
+          style: "secondary",
+        });
+      }
+    }
+
+    const diagnosticText = emitDiagnostic(
+      files,
+      {
+        message,
+        code,
+        severity: toCodespanSeverity(severity),
+        labels,
+        notes: fixes.map((fix) => `help: ${fix.message}`),
+      },
+      {
+        chars: CHARS_ASCII,
+      },
+      coloring
+    );
+    result.push(diagnosticText);
+  }
+  return result.join("\n");
+}
+
 function annotatePreflightError(error: Error): Error {
   if (error.message.startsWith("There is already a Construct with name")) {
     const newMessage = [];
@@ -147,6 +183,19 @@ function annotatePreflightError(error: Error): Error {
   return error;
 }
 
+function toCodespanSeverity(severity: wingCompiler.WingDiagnostic["severity"]) {
+  switch (severity) {
+    case "warning":
+      return "warning";
+    case "info":
+      return "note";
+    case "hint":
+      return "help";
+    default:
+      return "error";
+  }
+}
+
 function byteOffsetFromLineAndColumn(source: string, line: number, column: number) {
   const lines = source.split("\n");
   let offset = 0;
@@ -4,37 +4,21 @@ import {
   TextDocumentSyncKind,
   InitializeResult,
   DiagnosticSeverity,
-  Diagnostic,
-  Range,
   DocumentUri,
+  Position,
+  PublishDiagnosticsParams,
+  TextDocumentContentChangeEvent,
 } from "vscode-languageserver/node";
 
 import * as wingCompiler from "@winglang/compiler";
 
 export async function lsp() {
-  let wingc = await wingCompiler.load({
-    imports: {
-      env: {
-        send_diagnostic
-      },
-    },
-  });
+  let wingc = await wingCompiler.load({});
   let badState = false;
 
-  const raw_diagnostics: wingCompiler.WingDiagnostic[] = [];
-
-  function send_diagnostic(
-    data_ptr: number,
-    data_len: number
-  ) {
-    const data_buf = Buffer.from(
-      (wingc.exports.memory as WebAssembly.Memory).buffer,
-      data_ptr,
-      data_len
-    );
-    const data_str = new TextDecoder().decode(data_buf);
-    raw_diagnostics.push(JSON.parse(data_str));
-  }
+  // The latest text of each open document. Change events only carry the changed ranges, so this is needed to open
+  // the documents again when wingc is reloaded after a crash.
+  const documents = new Map<DocumentUri, { version: number; text: string }>();
 
   const callWing = (func: wingCompiler.WingCompilerFunction, args: any): any | null => {
     if (badState) {
@@ -84,7 +68,7 @@ export async function lsp() {
   connection.onInitialize((_params: InitializeParams) => {
     const result: InitializeResult = {
       capabilities: {
-        textDocumentSync: TextDocumentSyncKind.Full,
+        textDocumentSync: TextDocumentSyncKind.Incremental,
         completionProvider: {
           triggerCharacters: [".", ":"],
         },
@@ -94,6 +78,12 @@ export async function lsp() {
         hoverProvider: true,
         documentSymbolProvider: true,
         definitionProvider: true,
+        referencesProvider: true,
+        renameProvider: {
+          prepareProvider: true,
+        },
+        documentFormattingProvider: true,
+        documentRangeFormattingProvider: true,
       },
     };
     return result;
@@ -101,37 +91,38 @@ export async function lsp() {
 
   async function handle_event_and_update_diagnostics(wingc_handler_name: wingCompiler.WingCompilerFunction, params: any, uri: DocumentUri) {
     if (badState) {
-      wingc = await wingCompiler.load({
-        imports: {
-          env: {
-            send_diagnostic,
-          },
-        },
-      });
+      wingc = await wingCompiler.load({});
       badState = false;
+      // The new instance of wingc doesn't know about any document, so open all of them with their latest text
+      // (which already includes this event)
+      for (const [uri, { version, text }] of documents) {
+        callWing("wingc_on_did_open_text_document", { textDocument: { uri, languageId: "wing", version, text } });
+      }
+    } else {
+      // Call wingc handler
+      callWing(wingc_handler_name, params);
+    }
+    // wingc converts the diagnostics of the compilation to LSP diagnostics
+    const published: PublishDiagnosticsParams | null = callWing("wingc_on_diagnostics", { textDocument: { uri } });
+    if (published) {
+      // purposely not awaiting this, notifications are fire-and-forget
+      connection.sendDiagnostics(published);
     }
-    // Reset diagnostics list
-    raw_diagnostics.length = 0;
-    // Call wingc handler
-    callWing(wingc_handler_name, params);
-    // purposely not awaiting this, notifications are fire-and-forget
-    connection.sendDiagnostics({
-      uri,
-      diagnostics: raw_diagnostics.map((rd) => {
-        if(rd.span) {
-          return Diagnostic.create(Range.create(rd.span.start.line, rd.span.start.col, rd.span.end.line, rd.span.end.col), rd.message)
-        } else {
-          return Diagnostic.create(Range.create(0, 0, 0, 0), rd.message)
-        }
-      })
-    });
   }
 
   connection.onDidOpenTextDocument(async (params) => {
-    handle_event_and_update_diagnostics("wingc_on_did_open_text_document", params, params.textDocument.uri);
+    const { uri, version, text } = params.textDocument;
+    documents.set(uri, { version, text });
+    handle_event_and_update_diagnostics("wingc_on_did_open_text_document", params, uri);
   });
   connection.onDidChangeTextDocument(async (params) => {
-    handle_event_and_update_diagnostics("wingc_on_did_change_text_document", params, params.textDocument.uri);
+    const { uri, version } = params.textDocument;
+    const text = applyChanges(documents.get(uri)?.text ?? "", params.contentChanges);
+    documents.set(uri, { version, text });
+    handle_event_and_update_diagnostics("wingc_on_did_change_text_document", params, uri);
+  });
+  connection.onDidCloseTextDocument(async (params) => {
+    documents.delete(params.textDocument.uri);
   });
   connection.onCompletion(async (params) => {
     return callWing("wingc_on_completion", params);
@@ -148,6 +139,49 @@ export async function lsp() {
   connection.onHover(async (params) => {
     return callWing("wingc_on_hover", params);
   });
+  connection.onReferences(async (params) => {
+    return callWing("wingc_on_references", params);
+  });
+  connection.onPrepareRename(async (params) => {
+    return callWing("wingc_on_prepare_rename", params);
+  });
+  connection.onRenameRequest(async (params) => {
+    return callWing("wingc_on_rename", params);
+  });
+  connection.onDocumentFormatting(async (params) => {
+    return callWing("wingc_on_formatting", params);
+  });
+  connection.onDocumentRangeFormatting(async (params) => {
+    return callWing("wingc_on_range_formatting", params);
+  });
 
   connection.listen();
 }
+
+/** Applies the changes of a `textDocument/didChange` notification to the text of a document */
+function applyChanges(text: string, changes: TextDocumentContentChangeEvent[]): string {
+  for (const change of changes) {
+    if ("range" in change) {
+      const start = offsetAt(text, change.range.start);
+      const end = Math.max(start, offsetAt(text, change.range.end));
+      text = text.slice(0, start) + change.text + text.slice(end);
+    } else {
+      text = change.text;
+    }
+  }
+  return text;
+}
+
+/** Returns the offset of a position in `text`. LSP positions count UTF-16 code units, just like JavaScript strings. */
+function offsetAt(text: string, position: Position): number {
+  let lineStart = 0;
+  for (let line = 0; line < position.line; line++) {
+    const lineEnd = text.indexOf("\n", lineStart);
+    if (lineEnd === -1) {
+      return text.length;
+    }
+    lineStart = lineEnd + 1;
+  }
+  const lineEnd = text.indexOf("\n", lineStart);
+  return Math.min(lineStart + position.character, lineEnd === -1 ? text.length : lineEnd);
+}
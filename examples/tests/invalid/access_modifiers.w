@@ -0,0 +1,47 @@
+class Foo {
+  private secret: str;
+  protected shared: str;
+
+  init() {
+    this.secret = "secret";
+    this.shared = "shared";
+  }
+
+  private hidden() {}
+  protected method() {}
+  private static staticHidden() {}
+  greet() {}
+}
+
+class Bar extends Foo {
+  init() {
+    log(this.shared);
+    this.method();
+    log(this.secret);
+//           ^^^^^^ Cannot access private member "secret" of class "Bar"
+    this.hidden();
+//       ^^^^^^ Cannot access private member "hidden" of class "Bar"
+  }
+}
+
+let foo = new Foo();
+log(foo.secret);
+//      ^^^^^^ Cannot access private member "secret" of class "Foo"
+log(foo.shared);
+//      ^^^^^^ Cannot access protected member "shared" of class "Foo"
+foo.hidden();
+//  ^^^^^^ Cannot access private member "hidden" of class "Foo"
+foo.method();
+//  ^^^^^^ Cannot access protected member "method" of class "Foo"
+Foo.staticHidden();
+//  ^^^^^^^^^^^^ Cannot access private member "staticHidden" of class "Foo"
+
+class Baz extends Foo {
+  init() {}
+  hidden() {}
+//^^^^^^ Cannot override private member "hidden" of class "Foo"
+  private method() {}
+//        ^^^^^^ Cannot override protected member "method" of class "Foo" with a private member
+  protected greet() {}
+//          ^^^^^ Cannot override public member "greet" of class "Foo" with a protected member
+}
@@ -1,7 +1,7 @@
 class Foo {
   instanceField: num;
 
-  static f: num;
+  static f: num = 1;
 
   static m() {
     this.instanceField = 1; // Can't access instance fields from static methods
@@ -0,0 +1,29 @@
+let x = defer 1;
+     // ^^^^^^^ "defer" can only be used in inflight code
+
+let y = await x;
+     // ^^^^^^^ "await" can only be used in inflight code
+
+class Bar {
+  async inflight f(): num {
+//^^^^^ Functions cannot be declared "async", preflight functions are synchronous and inflight functions are always asynchronous (use "defer" to call a function without waiting for it)
+    return 1;
+  }
+}
+
+let f = inflight () => {
+  let a = await 1;
+              //^ Expected a promise or an array of promises, but got "num" instead
+
+  let b: Promise<num> = defer "hello";
+                      //^^^^^^^^^^^^^ Expected type to be "Promise<num>", but got "Promise<str>" instead
+};
+
+class Foo {
+  static inflight a: num = await defer Foo.g();
+                        // ^^^^^^^^^^^^^^^^^^^ Cannot use "await" in the initializer of static field "a"
+
+  static inflight g(): num {
+    return 1;
+  }
+}
@@ -0,0 +1,11 @@
+bring "./bring_local_self.w" as foo;
+//^ Cyclic bring of "bring_local_self.w"
+
+bring "./non-existent.w" as bar;
+//^ Cannot find Wing module "non-existent.w"
+
+bring "./subdir/statements.w" as baz;
+//^ Brought Wing module "subdir/statements.w" has top-level statements that are not type definitions
+
+bring "./subdir/statements.w";
+//^ bring "./subdir/statements.w" must be assigned to an identifier
@@ -0,0 +1,75 @@
+let f1 = (x: num): str => {
+                // ^^^ Not all paths return a value of type "str"
+  if x > 0 {
+    return "positive";
+  }
+};
+
+let f2 = (x: num): num => {
+                // ^^^ Not all paths return a value of type "num"
+  if x > 0 {
+    return 1;
+  } elif x < 0 {
+    return -1;
+  }
+};
+
+let f3 = (items: Array<num>): num => {
+                           // ^^^ Not all paths return a value of type "num"
+  for item in items {
+    return item;
+  }
+};
+
+let f4 = (): num => {
+          // ^^^ Not all paths return a value of type "num"
+  while true {
+    break;
+  }
+};
+
+class C {
+  m(x: bool): bool {
+          // ^^^^ Not all paths return a value of type "bool"
+    try {
+      return x;
+    } catch {
+      log("error");
+    }
+  }
+}
+
+let f5 = (): num => {
+  return 1;
+  log("unreachable");
+//^^^^^^^^^^^^^^^^^^^ Unreachable code
+};
+
+let f6 = (x: bool) => {
+  if x {
+    throw("error");
+    let y = 1;
+  //^^^^^^^^^^ Unreachable code
+  } else {
+    panic("error");
+  }
+  log("done");
+//^^^^^^^^^^^^ Unreachable code
+};
+
+let f7 = (): num => {
+  return 1;
+  log("first");
+//^^^^^^^^^^^^^ Unreachable code
+  log("second");
+//^^^^^^^^^^^^^^ Unreachable code
+};
+
+let f8 = (): num => {
+          // ^^^ Not all paths return a value of type "num"
+  let panic = (message: str) => {
+    log(message);
+  };
+  // calls the local function, not the builtin `panic`
+  panic("error");
+};
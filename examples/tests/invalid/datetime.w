@@ -0,0 +1,13 @@
+let d = datetime.utcNow();
+
+let a: num = d;
+          // ^ Expected type to be "num", but got "datetime" instead
+
+let b = d + 1;
+         // ^ Expected type to be "duration", but got "num" instead
+
+let c = d - 1;
+         // ^ Expected type to be "duration", but got "num" instead
+
+let e = d < 1;
+         // ^ Expected type to be "datetime", but got "num" instead
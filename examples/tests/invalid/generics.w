@@ -0,0 +1,40 @@
+interface IShape {
+  area(): num;
+}
+
+class Box<T extends IShape> {
+  item: T;
+  init(item: T) {
+    this.item = item;
+  }
+}
+
+let b1 = new Box<str>("hello");
+           //^^^^^^^^ Type "str" does not satisfy the bound "IShape" of type parameter "T"
+
+class Pair<K, V> {
+  init() {}
+}
+
+let p = new Pair<str>();
+          //^^^^^^^^^ Type "Pair<K, V>" expects 2 type arguments, but 1 were provided
+
+let p2: Pair = new Pair<str, num>();
+      //^^^^ Generic type "Pair" requires type arguments (K, V)
+
+class Plain {
+  init() {}
+}
+
+let x = new Plain<num>();
+          //^^^^^^^^^^ Type "Plain" is not generic and cannot have type arguments
+
+let make = <T>(): Array<T> => {
+  return [];
+};
+
+make();
+//^^^^^^ Cannot infer the type argument of type parameter "T" from the arguments
+
+class Dup<T, T> {}
+          // ^ Symbol "T" already defined in this scope
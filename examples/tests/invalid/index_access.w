@@ -0,0 +1,35 @@
+let arr = [1, 2, 3];
+arr[0] = 4;
+//^^^^ Cannot assign to an element of "Array<num>", only elements of mutable arrays, maps and Json values can be assigned
+
+let x: str = arr[0];
+          // ^^^^^^ Expected type to be "str", but got "num" instead
+
+arr["a"];
+ // ^^^ Expected type to be "num", but got "str" instead
+
+let map = {"a" => 1};
+let y: num = map["a"];
+          // ^^^^^^^^ Expected type to be "num", but got "num?" instead
+
+let mutMap = MutMap<num>{};
+mutMap["a"] = nil;
+           // ^^^ Expected type to be "num", but got "nil" instead
+
+let j = Json { a: 1 };
+j["a"] = 2;
+//^^^^ Cannot assign to an element of "Json", only elements of mutable arrays, maps and Json values can be assigned
+
+j[true];
+//^^^^ Expected type to be "num | str", but got "bool" instead
+
+let s = "hello";
+s[0];
+//^ Type "str" cannot be indexed, only arrays, maps and Json values can
+
+let mutArr = MutArray<num>[1, 2];
+test "modify a preflight array from inflight" {
+  let i = 0;
+  mutArr[i] = 3;
+//^^^^^^^^^^^^^^ Preflight values cannot be modified from inflight
+}
@@ -26,7 +26,66 @@ interface IWithSameName {
  // ^^^ Symbol "foo" already defined in this scope
 }
 
-interface INoProps {
-  bar: str;
-//^^^^^^^^ interfaces can't have properties 
+interface IWithProps {
+  name: str;
+  var count: num;
+  inflight id: str;
+  static foo: num;
+//^^^^^^ Static members are not allowed in interfaces
+  bar: str = "bar";
+          // ^^^^^ Interface properties cannot be initialized
+}
+
+class WrongType impl IWithProps {
+  name: num;
+//^^^^ Expected type to be "str", but got "num" instead
+  var count: num;
+  inflight id: str;
+  init() {
+    this.name = 1;
+    this.count = 0;
+  }
+}
+
+class NotReassignable impl IWithProps {
+  name: str;
+  count: num;
+//^^^^^ Field "count" of class "NotReassignable" must be reassignable to implement interface "IWithProps"
+  inflight id: str;
+  init() {
+    this.name = "a";
+    this.count = 0;
+  }
+}
+
+class WrongPhase impl IWithProps {
+  name: str;
+  var count: num;
+  id: str;
+//^^ Field "id" of class "WrongPhase" is preflight but interface "IWithProps" declares it inflight
+  init() {
+    this.name = "a";
+    this.count = 0;
+    this.id = "b";
+  }
+}
+
+class Private impl IWithProps {
+  private name: str;
+        //^^^^ Field "name" of class "Private" must be public to implement interface "IWithProps"
+  var count: num;
+  inflight id: str;
+  init() {
+    this.name = "a";
+    this.count = 0;
+  }
+}
+
+class Missing impl IWithProps {
+   // ^^^^^^^ Class "Missing" does not implement field "name" of interface "IWithProps"
+  var count: num;
+  inflight id: str;
+  init() {
+    this.count = 0;
+  }
 }
\ No newline at end of file
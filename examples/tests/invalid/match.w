@@ -0,0 +1,73 @@
+enum Color {
+  RED, GREEN, BLUE
+}
+
+let c = Color.RED;
+
+match c {
+   // ^ Match is not exhaustive, missing: Color.GREEN, Color.BLUE
+  Color.RED => {}
+}
+
+let maybe: Color? = nil;
+
+match maybe {
+   // ^^^^^ Match is not exhaustive, missing: Color.BLUE, nil
+  Color.RED, Color.GREEN => {}
+}
+
+let s = "hello";
+
+match s {
+   // ^ Match over a value of type "str" is not exhaustive, add an "else" arm
+  "hello" => {}
+}
+
+match c {
+  Color.RED, Color.GREEN => {}
+  Color.BLUE, Color.RED => {}
+           // ^^^^^^^^^ Pattern "Color.RED" is already matched by a previous arm
+  else => {}
+        //^^ Unreachable "else" arm, all values are already matched
+}
+
+match c {
+  "red" => {}
+//^^^^^ Expected type to be "Color", but got "str" instead
+  else => {}
+}
+
+match s {
+  s => {}
+//^ Match patterns must be literals, "nil" or enum values
+  else => {}
+}
+
+let f = (b: bool): num => {
+                // ^^^ Not all paths return a value of type "num"
+  match b {
+    true => {
+      return 1;
+    }
+    false => {}
+  }
+};
+
+let g = (c: Color): num => {
+  match c {
+    Color.RED => {
+      return 1;
+    }
+    else => {
+      return 2;
+    }
+  }
+  log("done");
+//^^^^^^^^^^^^ Unreachable code
+};
+
+let e = match c { Color.RED => 1 };
+           // ^ Match is not exhaustive, missing: Color.GREEN, Color.BLUE
+
+let v: num = match s { "one" => 1, else => "many" };
+          // ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ Expected type to be "num", but got "num | str" instead
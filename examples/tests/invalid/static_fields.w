@@ -0,0 +1,28 @@
+class Foo {
+  static a: num;
+//^^^^^^^^^^^^^^ Static field "a" must be initialized
+
+  b: num = 1;
+        // ^ Field "b" is not static and must be initialized in the class initializer
+
+  static c: str = 1;
+               // ^ Expected type to be "str", but got "num" instead
+
+  static d: num = this.b;
+               // ^^^^ Unknown symbol "this"
+
+  static e: num = 2;
+
+  static inflight f: str = Foo.g();
+                        // ^^^^^^^ Cannot call inflight functions in the initializer of static field "f"
+
+  static inflight g(): str {
+    return "g";
+  }
+
+  init() {
+    this.b = 1;
+    this.e = 3;
+       //  ^ "e" cannot be initialized in the preflight initializer
+  }
+}
@@ -0,0 +1,5 @@
+class Foo {
+  init() {}
+}
+
+let x = 5;
@@ -0,0 +1,21 @@
+let x: str | num = true;
+                 //^^^^ Expected type to be "str | num", but got "bool" instead
+
+let y: str | num = "hello";
+let z: str = y;
+          // ^ Expected type to be "str", but got "str | num" instead
+
+y.length;
+//^^^^^^ Property "length" cannot be accessed on a value of union type "str | num" (use "is" to narrow its type first)
+
+if y is bool {}
+// ^^^^^^^^^ Expression of type "str | num" can never be of type "bool"
+
+if y is Array<str> {}
+//      ^^^^^^^^^^ Cannot test whether a value is of type "Array<str>" at runtime, only primitive types, "duration", "datetime" and classes can be tested
+
+let var w: str | num = 1;
+if w is str {
+  w.length;
+  //^^^^^^ Property "length" cannot be accessed on a value of union type "str | num" (use "is" to narrow its type first)
+}
@@ -0,0 +1,16 @@
+let f = (...items: Array<num>, last: str) => {};
+                            // ^^^^^^^^^ Variadic parameter "items" must be the last parameter
+
+let g = (...items: num) => {};
+                // ^^^ Variadic parameter "items" must be of type "Array<T>", but got "num" instead
+
+let h = (prefix: str, ...items: Array<num>) => {};
+
+h();
+//^ Expected 1 positional argument(s) but got 0
+
+h("a", 1, "2");
+        //^^^ Expected type to be "num", but got "str" instead
+
+let k: (num): void = h;
+                  // ^ Expected type to be "preflight (num): void", but got "preflight (prefix: str, ...items: Array<num>): void" instead
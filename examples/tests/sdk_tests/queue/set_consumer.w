@@ -19,7 +19,7 @@ class Predicate {
 
 
 
-q.setConsumer(inflight (msg: str): str => {
+q.setConsumer(inflight (msg: str) => {
   c.inc();
 });
 
@@ -0,0 +1,45 @@
+class Counter {
+  private var count: num;
+  protected step: num;
+  public name: str;
+
+  init(name: str) {
+    this.count = 0;
+    this.step = 1;
+    this.name = name;
+  }
+
+  private static zero(): num {
+    return 0;
+  }
+
+  protected advance() {
+    this.count = this.count + this.step;
+  }
+
+  public current(): num {
+    return this.count + Counter.zero();
+  }
+
+  public increment() {
+    this.advance();
+  }
+}
+
+class DoubleCounter extends Counter {
+  init() {
+    super("double");
+    this.step = 2;
+  }
+
+  public twice() {
+    this.advance();
+    this.advance();
+  }
+}
+
+let c = new DoubleCounter();
+c.increment();
+c.twice();
+assert(c.current() == 6);
+assert(c.name == "double");
@@ -0,0 +1,44 @@
+bring cloud;
+
+let b = new cloud.Bucket();
+
+class Loader {
+  static inflight greeting: Promise<str> = defer Loader.load();
+
+  static inflight load(): str {
+    return "hello";
+  }
+}
+
+let fetch = inflight (key: str): str => {
+  return b.get(key);
+};
+
+test "defer and await a single call" {
+  let p: Promise<void> = defer b.put("a", "1");
+  await p;
+  assert(b.get("a") == "1");
+}
+
+test "await an array of promises" {
+  let writes = [
+    defer b.put("x", "10"),
+    defer b.put("y", "20"),
+    defer b.put("z", "30"),
+  ];
+  await writes;
+
+  let reads: Array<str> = await [defer fetch("x"), defer fetch("y"), defer fetch("z")];
+  assert(reads.length == 3);
+  assert(reads.at(0) == "10");
+  assert(reads.at(2) == "30");
+}
+
+test "defer a promise" {
+  // every test runs against a fresh simulator, so the key has to be written here
+  await defer b.put("a", "1");
+  let p = defer fetch("a");
+  let q: Promise<str> = defer p;
+  assert((await q) == "1");
+  assert((await Loader.greeting) == "hello");
+}
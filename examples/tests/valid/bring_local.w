@@ -0,0 +1,12 @@
+bring "./subdir/subfile.w" as file1;
+bring "./subdir/empty.w" as file2;
+
+// classes from other files can be used
+let q = new file1.Q();
+assert(file1.Q.greet("world") == "Hello, world");
+
+// structs and enums from other files can be used
+let p = file1.Point { x: 1, y: 2 };
+assert(p.x + p.y == 3);
+let c = file1.Color.GREEN;
+assert(c != file1.Color.RED);
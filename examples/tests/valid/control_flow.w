@@ -0,0 +1,69 @@
+let sign = (x: num): str => {
+  if x > 0 {
+    return "positive";
+  } elif x < 0 {
+    return "negative";
+  } else {
+    return "zero";
+  }
+};
+
+assert(sign(1) == "positive");
+assert(sign(-1) == "negative");
+assert(sign(0) == "zero");
+
+let orDefault = (x: str?): str => {
+  if let y = x {
+    return y;
+  }
+  return "default";
+};
+
+assert(orDefault("hello") == "hello");
+assert(orDefault(nil) == "default");
+
+let firstAbove = (items: Array<num>, min: num): num => {
+  for item in items {
+    if item > min {
+      return item;
+    }
+  }
+  throw("no item above ${min}");
+};
+
+assert(firstAbove([1, 2, 3], 1) == 2);
+
+let countUntil = (n: num): num => {
+  let var i = 0;
+  while true {
+    i = i + 1;
+    if i == n {
+      return i;
+    }
+  }
+};
+
+assert(countUntil(3) == 3);
+
+let parse = (s: str): num => {
+  try {
+    return num.fromStr(s);
+  } catch {
+    return 0;
+  }
+};
+
+assert(parse("12") == 12);
+assert(parse("twelve") == 0);
+
+class Checker {
+  check(x: num): bool {
+    if x > 10 {
+      return true;
+    }
+    panic("value too small");
+  }
+}
+
+let checker = new Checker();
+assert(checker.check(11));
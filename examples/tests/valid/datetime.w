@@ -0,0 +1,38 @@
+let launch = datetime.fromIso("2023-07-18T17:18:25.177Z");
+assert(launch.year() == 2023);
+assert(launch.month() == 6);
+assert(launch.dayOfMonth() == 18);
+assert(launch.hours() == 17);
+assert(launch.min() == 18);
+assert(launch.sec() == 25);
+assert(launch.ms() == 177);
+assert(launch.timestampMs() == 1689700705177);
+assert(launch.toIso() == "2023-07-18T17:18:25.177Z");
+
+let epoch: datetime = datetime.fromTimestamp(0);
+assert(epoch.toIso() == "1970-01-01T00:00:00.000Z");
+assert(epoch.dayOfWeek() == 4);
+
+let nextDay = launch + 24h;
+assert(nextDay.dayOfMonth() == 19);
+assert(nextDay - 1d == launch);
+assert(nextDay != launch);
+assert(epoch < launch);
+assert(launch <= launch);
+assert(nextDay > launch);
+
+let maybeLaunch: datetime? = datetime.fromIso("2023-07-18T17:18:25.177Z");
+let noLaunch: datetime? = nil;
+assert(maybeLaunch == launch);
+assert(maybeLaunch != nextDay);
+assert(maybeLaunch != noLaunch);
+assert(noLaunch == nil);
+
+let now = datetime.utcNow();
+assert(now >= launch);
+
+test "datetime is captured by value" {
+  assert(launch.toIso() == "2023-07-18T17:18:25.177Z");
+  assert(launch + 1s > launch);
+  assert(datetime.utcNow() > epoch);
+}
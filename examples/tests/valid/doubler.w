@@ -7,7 +7,7 @@ class Doubler {
   init(func: cloud.IFunctionHandler) {
     this.func = func;
   }
-  inflight invoke(message: str): str {
+  inflight invoke(message: str) {
     this.func.handle(message);
     this.func.handle(message);
   }
@@ -82,6 +82,7 @@ class DynamoTable {
     } elif type == AttributeType.Binary {
       return "B";
     }
+    throw("unknown attribute type");
   }
 }
 
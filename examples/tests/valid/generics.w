@@ -0,0 +1,98 @@
+interface IShape {
+  area(): num;
+}
+
+class Square impl IShape {
+  side: num;
+  init(side: num) {
+    this.side = side;
+  }
+  area(): num {
+    return this.side * this.side;
+  }
+}
+
+// generic interface
+interface IContainer<T> {
+  get(): T;
+}
+
+// generic class with a bound, implementing a generic interface
+class Box<T extends IShape> impl IContainer<T> {
+  item: T;
+  init(item: T) {
+    this.item = item;
+  }
+  get(): T {
+    return this.item;
+  }
+  area(): num {
+    return this.item.area();
+  }
+}
+
+let b1 = new Box<Square>(new Square(2));
+assert(b1.get().side == 2);
+assert(b1.area() == 4);
+
+// type arguments of `new` can be inferred from the initializer arguments
+let b2 = new Box(new Square(3));
+assert(b2.area() == 9);
+
+let c: IContainer<Square> = b2;
+assert(c.get().area() == 9);
+
+// generic class with several type parameters
+class Pair<K, V> {
+  key: K;
+  value: V;
+  init(key: K, value: V) {
+    this.key = key;
+    this.value = value;
+  }
+  swap(): Pair<V, K> {
+    return new Pair<V, K>(this.value, this.key);
+  }
+}
+
+let p = new Pair<str, num>("one", 1);
+let swapped = p.swap();
+assert(swapped.key == 1);
+assert(swapped.value == "one");
+
+// generic struct
+struct Entry<T> {
+  name: str;
+  value: T;
+}
+
+let e1 = Entry<num> { name: "a", value: 1 };
+assert(e1.value == 1);
+
+// type arguments of struct literals can be inferred from the field values
+let e2 = Entry { name: "b", value: "hello" };
+assert(e2.value.length == 5);
+
+// generic closures
+let firstOr = <T>(items: Array<T>, fallback: T): T => {
+  if items.length > 0 {
+    return items.at(0);
+  }
+  return fallback;
+};
+
+assert(firstOr([1, 2, 3], 0) == 1);
+assert(firstOr(Array<str>[], "none") == "none");
+
+// generic methods
+class Util {
+  static identity<T>(value: T): T {
+    return value;
+  }
+  wrap<T>(value: T): Array<T> {
+    return [value];
+  }
+}
+
+assert(Util.identity("x") == "x");
+assert(new Util().wrap(5).at(0) == 5);
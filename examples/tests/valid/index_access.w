@@ -0,0 +1,68 @@
+let arr = [1, 2, 3];
+assert(arr[0] == 1);
+assert(arr[arr.length - 1] == 3);
+
+let mutArr = MutArray<str>["a", "b"];
+mutArr[1] = "c";
+assert(mutArr[1] == "c");
+
+// Indices out of bounds throw instead of returning nil or growing the array
+let var readFailed = false;
+try {
+  arr[3];
+} catch {
+  readFailed = true;
+}
+assert(readFailed);
+
+let var writeFailed = false;
+try {
+  mutArr[2] = "d";
+} catch {
+  writeFailed = true;
+}
+assert(writeFailed);
+assert(mutArr.length == 2);
+
+// So do fractional indices
+let var fractionalReadFailed = false;
+try {
+  arr[0.5];
+} catch {
+  fractionalReadFailed = true;
+}
+assert(fractionalReadFailed);
+
+let var fractionalWriteFailed = false;
+try {
+  mutArr[0.5] = "e";
+} catch {
+  fractionalWriteFailed = true;
+}
+assert(fractionalWriteFailed);
+assert(mutArr.at(0) == "a");
+
+let map = {"a" => 1, "b" => 2};
+assert(map["a"] == 1);
+assert(map["c"] == nil);
+
+let value: num? = map["b"];
+assert(value == 2);
+
+let mutMap = MutMap<num>{};
+mutMap["x"] = 10;
+assert(mutMap["x"] == 10);
+
+let j = Json { name: "wing", tags: ["a", "b"] };
+assert(j["name"].asStr() == "wing");
+assert(j["tags"][1].asStr() == "b");
+
+let mutJ = MutJson { count: 1 };
+mutJ["count"] = 2;
+assert(mutJ["count"].asNum() == 2);
+
+test "index access in inflight code" {
+  let i = 1;
+  assert(arr[i] == 2);
+  assert(map["a"] == 1);
+}
@@ -14,3 +14,43 @@ interface IPointy {
 interface ISquare extends IShape, IPointy {
     
 }
+
+interface INamed {
+    name: str;
+    var visits: num;
+    inflight greeting: str;
+}
+
+interface IPerson extends INamed {
+    age(): num;
+}
+
+class Person impl IPerson {
+    name: str;
+    var visits: num;
+    inflight greeting: str;
+
+    init(name: str) {
+        this.name = name;
+        this.visits = 0;
+    }
+
+    inflight init() {
+        this.greeting = "hello";
+    }
+
+    age(): num {
+        return 42;
+    }
+}
+
+let p: IPerson = new Person("bob");
+p.visits = p.visits + 1;
+assert(p.name == "bob");
+assert(p.visits == 1);
+assert(p.age() == 42);
+
+test "access interface properties" {
+    assert(p.name == "bob");
+    assert(p.greeting == "hello");
+}
@@ -17,7 +17,7 @@ let j = Json {
 };
 
 
-let getJson = new cloud.Function(inflight(msg: str): str => {
+let getJson = new cloud.Function(inflight(msg: str) => {
   let x = b.getJson(fileName);
   assert(x.get("persons").getAt(0).get("fears").getAt(1) == "failure");
 });
@@ -0,0 +1,115 @@
+enum Color {
+  RED, GREEN, BLUE
+}
+
+let describe = (c: Color): str => {
+  match c {
+    Color.RED => {
+      return "warm";
+    }
+    Color.GREEN, Color.BLUE => {
+      return "cold";
+    }
+  }
+};
+
+assert(describe(Color.RED) == "warm");
+assert(describe(Color.GREEN) == "cold");
+assert(describe(Color.BLUE) == "cold");
+
+let describeOptional = (c: Color?): str => {
+  match c {
+    Color.RED => {
+      return "red";
+    }
+    nil => {
+      return "no color";
+    }
+    else => {
+      return "other";
+    }
+  }
+};
+
+assert(describeOptional(Color.RED) == "red");
+assert(describeOptional(Color.BLUE) == "other");
+assert(describeOptional(nil) == "no color");
+
+let toNum = (b: bool): num => {
+  match b {
+    true => {
+      return 1;
+    }
+    false => {
+      return 0;
+    }
+  }
+};
+
+assert(toNum(true) == 1);
+assert(toNum(false) == 0);
+
+let greet = (name: str?): str => {
+  match name {
+    nil => {
+      return "hello, stranger";
+    }
+    "wing" => {
+      return "hello, bird";
+    }
+    else => {
+      // `name` is known not to be nil here
+      return "hello, " + name;
+    }
+  }
+};
+
+assert(greet(nil) == "hello, stranger");
+assert(greet("wing") == "hello, bird");
+assert(greet("world") == "hello, world");
+
+let var count = 0;
+for i in 0..5 {
+  match i {
+    1, 3 => {
+      continue;
+    }
+    4 => {
+      break;
+    }
+    else => {
+      count = count + 1;
+    }
+  }
+}
+
+assert(count == 2);
+
+let label = (c: Color): str => {
+  return match c { Color.RED => "red", Color.GREEN, Color.BLUE => "other" };
+};
+
+assert(label(Color.RED) == "red");
+assert(label(Color.BLUE) == "other");
+
+let size = (n: num?): str => {
+  return match n {
+    nil => "none",
+    0 => "zero",
+    1, 2, 3 => "few",
+    else => "many",
+  };
+};
+
+assert(size(nil) == "none");
+assert(size(0) == "zero");
+assert(size(2) == "few");
+assert(size(7) == "many");
+
+let sign: num = match toNum(true) { 1 => 1, else => -1 };
+assert(sign == 1);
+
+test "match expressions inflight" {
+  let name = match Color.GREEN { Color.RED => "red", else => "not red" };
+  assert(name == "not red");
+}
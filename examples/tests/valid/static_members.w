@@ -3,25 +3,30 @@ bring cloud;
 class Foo {
   instanceField: num;
 
-  // TODO: Static field initialization not supported yet (https://github.com/winglang/wing/issues/1668)
-  // static staticField: str = "Static resource value"; 
-  // static inflight inflightStaticField: str = "Inflight static resource value";
+  static staticField: str = "Static resource value";
+  static var counter: num = 0;
+  static inflight inflightStaticField: str = "Inflight static resource value";
 
   static m(): num { return 99; }
 
   init() {
     this.instanceField = 100;
+    Foo.counter = Foo.counter + 1;
   }
 
   static inflight get123(): num {
     return 123;
   }
+
+  inflight getStaticFields(): str {
+    return "${Foo.staticField}, ${Foo.inflightStaticField}";
+  }
 }
 
 let foo = new Foo();
 assert(foo.instanceField == 100);
-// TODO: Static field initialization not supported yet (https://github.com/winglang/wing/issues/1668)
-// assert(Foo.staticField == "Static resource value"); 
+assert(Foo.staticField == "Static resource value");
+assert(Foo.counter == 1);
 assert(Foo.m() == 99);
 
 test "test" {
@@ -34,18 +39,17 @@ test "test" {
       return "Static inflight method";
     }
 
-    // TODO: Static field initialization not supported yet (https://github.com/winglang/wing/issues/1668)
-    // static staticInflightField: str = "Static inflight value";
+    static staticInflightField: str = "Static inflight value";
   }
 
   // TODO: acess to preflight types (`Foo`) not supported yet (https://github.com/winglang/wing/issues/1669)
   // assert(Foo.get123() == 123);
-  // TODO: Static field initialization not supported yet (https://github.com/winglang/wing/issues/1668)
+  // TODO: acess to preflight types (`Foo`) not supported yet (https://github.com/winglang/wing/issues/1669)
   // assert(Foo.inflightStaticField == "Inflight static resource value");
+  assert(foo.getStaticFields() == "Static resource value, Inflight static resource value");
 
   let inflightClass = new InflightClass();
   assert(inflightClass.inflightMethod() == "Inflight method");
   assert(InflightClass.staticInflightMethod() == "Static inflight method");
-  // TODO: Static field initialization not supported yet (https://github.com/winglang/wing/issues/1668)
-  // assert(InflightClass.staticInflightField == "Static inflight value");
+  assert(InflightClass.staticInflightField == "Static inflight value");
 }
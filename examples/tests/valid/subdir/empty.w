@@ -0,0 +1 @@
+// this file is intentionally empty
@@ -0,0 +1,20 @@
+bring math;
+
+class Q {
+  init() {}
+
+  static greet(name: str): str {
+    return "Hello, ${name}";
+  }
+}
+
+struct Point {
+  x: num;
+  y: num;
+}
+
+enum Color {
+  RED,
+  GREEN,
+  BLUE
+}
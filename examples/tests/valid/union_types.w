@@ -0,0 +1,76 @@
+let describe = (x: str | num | bool): str => {
+  if x is str {
+    return "string of length ${x.length}";
+  } elif x is num {
+    return "number ${x + 1}";
+  } else {
+    // `x` can only be a `bool` here
+    let b: bool = x;
+    return "bool ${b}";
+  }
+};
+
+assert(describe("hello") == "string of length 5");
+assert(describe(1) == "number 2");
+assert(describe(true) == "bool true");
+
+// a member of a union can be used where the union is expected
+let values: Array<str | num> = ["a", 1, "b", 2];
+let var total = 0;
+for v in values {
+  if v is num {
+    total = total + v;
+  }
+}
+assert(total == 3);
+
+// optional unions can be unwrapped with `if let`
+let firstOf = (items: Array<str | num>): str | num? => {
+  if items.length == 0 {
+    return nil;
+  }
+  return items.at(0);
+};
+
+if let first = firstOf(values) {
+  assert(first is str);
+}
+
+class Animal {
+  name: str;
+  init(name: str) {
+    this.name = name;
+  }
+}
+
+class Dog extends Animal {
+  init() {
+    super("dog");
+  }
+  bark(): str {
+    return "woof";
+  }
+}
+
+let speak = (a: Animal | str): str => {
+  if a is Dog {
+    return a.bark();
+  } elif a is Animal {
+    return a.name;
+  } else {
+    return a;
+  }
+};
+
+assert(speak(new Dog()) == "woof");
+assert(speak(new Animal("cat")) == "cat");
+assert(speak("hi") == "hi");
+
+test "type tests inflight" {
+  let x: str | num = 12;
+  assert(x is num);
+  assert(!(x is str));
+  if x is num {
+    assert(x * 2 == 24);
+  }
+}
@@ -0,0 +1,49 @@
+let sum = (label: str, ...numbers: Array<num>): str => {
+  let var total = 0;
+  for n in numbers {
+    total = total + n;
+  }
+  return "${label}: ${total}";
+};
+
+assert(sum("none") == "none: 0");
+assert(sum("one", 1) == "one: 1");
+assert(sum("all", 1, 2, 3) == "all: 6");
+
+let count = <T>(...items: Array<T>): num => {
+  return items.length;
+};
+
+assert(count("a", "b") == 2);
+assert(count(1) == 1);
+
+class Joiner {
+  sep: str;
+  init(sep: str) {
+    this.sep = sep;
+  }
+
+  join(...parts: Array<str>): str {
+    let var s = "";
+    for p in parts {
+      if s != "" {
+        s = s + this.sep;
+      }
+      s = s + p;
+    }
+    return s;
+  }
+
+  inflight count(...parts: Array<str>): num {
+    return parts.length;
+  }
+}
+
+let j = new Joiner(",");
+assert(j.join("a", "b", "c") == "a,b,c");
+assert(j.join() == "");
+
+test "variadic inflight method" {
+  assert(j.count("x", "y") == 2);
+  assert(j.count() == 0);
+}
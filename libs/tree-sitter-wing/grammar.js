@@ -29,11 +29,15 @@ module.exports = grammar({
   precedences: ($) => [
     // Handle ambiguity in case of empty literal: `a = {}`
     // In this case tree-sitter doesn't know if it's a set or a map literal so just assume its a map
-    [$.json_map_literal, $.map_literal, $.set_literal],
+    // (or an empty block, in the arms of a `match` statement: `match x { 1 => {} }`)
+    [$.block, $.json_map_literal, $.map_literal, $.set_literal],
     [$.json_literal, $.structured_access_expression],
   ],
 
-  conflicts: ($) => [[$._reference_identifier, $._type_identifier]],
+  conflicts: ($) => [
+    [$._reference_identifier, $._type_identifier],
+    [$.match_statement, $.match_expression],
+  ],
 
   supertypes: ($) => [$.expression, $._literal],
 
@@ -66,10 +70,22 @@ module.exports = grammar({
         seq(
           field("object", $._type_identifier),
           // While the final "fields" identifier is optional in this grammar, upstream parsing will fail if it is not present
-          repeat(seq(".", optional(field("fields", $._type_identifier))))
+          repeat(seq(".", optional(field("fields", $._type_identifier)))),
+          optional(field("type_arguments", $.type_argument_list))
         )
       ),
 
+    // Generics
+    type_parameter_list: ($) =>
+      seq("<", commaSep1(field("parameter", $.type_parameter)), ">"),
+    type_parameter: ($) =>
+      seq(
+        field("name", $.identifier),
+        optional(seq("extends", field("bound", $.custom_type)))
+      ),
+    type_argument_list: ($) =>
+      seq("<", commaSep1(field("argument", $._type)), ">"),
+
     nested_identifier: ($) =>
       prec(
         PREC.MEMBER,
@@ -110,6 +126,7 @@ module.exports = grammar({
         $.continue_statement,
         $.if_statement,
         $.if_let_statement,
+        $.match_statement,
         $.struct_definition,
         $.enum_definition,
         $.try_catch_statement,
@@ -129,6 +146,7 @@ module.exports = grammar({
       seq(
         "struct",
         field("name", $.identifier),
+        optional(field("type_parameters", $.type_parameter_list)),
         optional(seq("extends", commaSep(field("extends", $.custom_type)))),
         braced(repeat(field("field", $.struct_field)))
       ),
@@ -178,6 +196,7 @@ module.exports = grammar({
         $.inflight_specifier,
         "class",
         field("name", $.identifier),
+        optional(field("type_parameters", $.type_parameter_list)),
         optional(seq("extends", field("parent", $.custom_type))),
         optional(seq("impl", field("implements", commaSep1($.custom_type)))),
         field("implementation", $.class_implementation)
@@ -211,6 +230,7 @@ module.exports = grammar({
       seq(
         "class",
         field("name", $.identifier),
+        optional(field("type_parameters", $.type_parameter_list)),
         optional(seq("extends", field("parent", $.custom_type))),
         optional(seq("impl", field("implements", commaSep1($.custom_type)))),
         field("implementation", $.resource_implementation)
@@ -231,6 +251,7 @@ module.exports = grammar({
       seq(
         "interface",
         field("name", $.identifier),
+        optional(field("type_parameters", $.type_parameter_list)),
         optional(seq("extends", field("extends", commaSep1($.custom_type)))),
         field("implementation", $.interface_implementation)
       ),
@@ -292,6 +313,49 @@ module.exports = grammar({
     elif_block: ($) =>
       seq("elif", field("condition", $.expression), field("block", $.block)),
 
+    // A `match` at the start of a statement is a statement (with blocks as arms) when it can be parsed as one
+    match_statement: ($) =>
+      prec.dynamic(
+        1,
+        seq(
+          "match",
+          field("value", $.expression),
+          "{",
+          repeat(field("arm", $.match_arm)),
+          optional(seq("else", "=>", field("else_block", $.block))),
+          "}"
+        )
+      ),
+
+    match_arm: ($) =>
+      seq(
+        commaSep1(field("pattern", $.expression)),
+        "=>",
+        field("block", $.block)
+      ),
+
+    match_expression: ($) =>
+      seq(
+        "match",
+        field("value", $.expression),
+        "{",
+        repeat(seq(field("arm", $.match_expression_arm), ",")),
+        optional(
+          choice(
+            field("arm", $.match_expression_arm),
+            seq("else", "=>", field("else_value", $.expression), optional(","))
+          )
+        ),
+        "}"
+      ),
+
+    match_expression_arm: ($) =>
+      seq(
+        commaSep1(field("pattern", $.expression)),
+        "=>",
+        field("value", $.expression)
+      ),
+
     try_catch_statement: ($) =>
       seq(
         "try",
@@ -323,6 +387,8 @@ module.exports = grammar({
         $.json_literal,
         $.struct_literal,
         $.optional_test,
+        $.type_test,
+        $.match_expression,
         $.compiler_dbg_panic,
       ),
 
@@ -377,6 +443,12 @@ module.exports = grammar({
     optional_test: ($) =>
       prec.right(PREC.OPTIONAL_TEST, seq($.expression, "?")),
 
+    type_test: ($) =>
+      prec.left(
+        PREC.RELATIONAL,
+        seq(field("expression", $.expression), "is", field("type", $._type))
+      ),
+
     compiler_dbg_panic: ($) => "😱",
     compiler_dbg_env: ($) => seq("🗺️", optional(";")),
 
@@ -440,11 +512,15 @@ module.exports = grammar({
         $._builtin_container_type,
         $.json_container_type,
         $.function_type,
-        $.optional
+        $.optional,
+        $.union_type
       ),
 
     optional: ($) => seq($._type, "?"),
 
+    union_type: ($) =>
+      prec.left(seq(field("types", $._type), "|", field("types", $._type))),
+
     function_type: ($) =>
       prec.right(
         seq(
@@ -457,7 +533,7 @@ module.exports = grammar({
     parameter_type_list: ($) => seq("(", commaSep($._type), ")"),
 
     builtin_type: ($) =>
-      choice("num", "bool", "any", "str", "void", "duration"),
+      choice("num", "bool", "any", "str", "void", "duration", "datetime"),
 
     initializer: ($) =>
       seq(
@@ -475,6 +551,7 @@ module.exports = grammar({
       seq(
         optional(field("async", $.async_modifier)),
         field("name", $.identifier),
+        optional(field("type_parameters", $.type_parameter_list)),
         field("parameter_list", $.parameter_list),
         optional($._return_type),
         $._semicolon
@@ -487,6 +564,7 @@ module.exports = grammar({
         optional(field("static", $.static)),
         optional(field("async", $.async_modifier)),
         field("name", $.identifier),
+        optional(field("type_parameters", $.type_parameter_list)),
         field("parameter_list", $.parameter_list),
         optional($._return_type),
         choice(field("block", $.block), $._semicolon)
@@ -496,6 +574,7 @@ module.exports = grammar({
       seq(
         field("phase_modifier", $.inflight_specifier),
         field("name", $.identifier),
+        optional(field("type_parameters", $.type_parameter_list)),
         field("parameter_list", $.parameter_list),
         optional($._return_type),
         $._semicolon
@@ -508,6 +587,7 @@ module.exports = grammar({
         optional(field("static", $.static)),
         field("phase_modifier", $.inflight_specifier),
         field("name", $.identifier),
+        optional(field("type_parameters", $.type_parameter_list)),
         field("parameter_list", $.parameter_list),
         optional($._return_type),
         choice(field("block", $.block), $._semicolon)
@@ -520,10 +600,13 @@ module.exports = grammar({
     parameter_definition: ($) =>
       seq(
         optional(field("reassignable", $.reassignable)),
+        optional(field("variadic", $.variadic)),
         field("name", $.identifier),
         $._type_annotation
       ),
 
+    variadic: ($) => "...",
+
     parameter_list: ($) => seq("(", commaSep($.parameter_definition), ")"),
 
     immutable_container_type: ($) =>
@@ -608,6 +691,7 @@ module.exports = grammar({
 
     preflight_closure: ($) =>
       seq(
+        optional(field("type_parameters", $.type_parameter_list)),
         field("parameter_list", $.parameter_list),
         optional($._return_type),
         "=>",
@@ -617,6 +701,7 @@ module.exports = grammar({
     inflight_closure: ($) =>
       seq(
         $.inflight_specifier,
+        optional(field("type_parameters", $.type_parameter_list)),
         field("parameter_list", $.parameter_list),
         optional($._return_type),
         "=>",
@@ -646,17 +731,29 @@ module.exports = grammar({
         optional(field("type", $._builtin_container_type)),
         braced(commaSep(field("member", $.map_literal_member)))
       ),
+    // Dynamic precedence prefers `Foo<T> { ... }` as a struct literal with type arguments over comparisons
+    // (e.g. `Foo < T > { ... }`)
     struct_literal: ($) =>
-      seq(
-        field("type", $.custom_type),
-        braced(commaSep(field("fields", $.struct_literal_member)))
+      prec.dynamic(
+        1,
+        seq(
+          field("type", $.custom_type),
+          braced(commaSep(field("fields", $.struct_literal_member)))
+        )
       ),
 
     map_literal_member: ($) =>
       seq(choice($.string), "=>", $.expression),
     struct_literal_member: ($) => seq($.identifier, ":", $.expression),
     structured_access_expression: ($) =>
-      prec.right(seq($.expression, "[", $.expression, "]")),
+      prec.right(
+        seq(
+          field("object", $.expression),
+          "[",
+          field("index", $.expression),
+          "]"
+        )
+      ),
 
     json_literal: ($) =>
       choice(
@@ -7,6 +7,9 @@
 (class_definition 
   name: (identifier) @type
 )
+(type_parameter
+  name: (identifier) @type
+)
 (method_definition
   name: (identifier) @function
 )
@@ -82,8 +85,10 @@
   "for"
   "if"
   "in"
+  "is"
   "init"
   "let"
+  "match"
   "new"
   "return"
   (inflight_specifier)
@@ -132,6 +132,7 @@ pub enum TypeAnnotationKind {
 	String,
 	Bool,
 	Duration,
+	Datetime,
 	Void,
 	Json,
 	MutJson,
@@ -142,8 +143,11 @@ pub enum TypeAnnotationKind {
 	MutMap(Box<TypeAnnotation>),
 	Set(Box<TypeAnnotation>),
 	MutSet(Box<TypeAnnotation>),
+	Promise(Box<TypeAnnotation>),
 	Function(FunctionSignature),
 	UserDefined(UserDefinedType),
+	/// A value of any of the given types (e.g. `str | num`)
+	Union(Vec<TypeAnnotation>),
 }
 
 // In the future this may be an enum for type-alias, class, etc. For now its just a nested name.
@@ -152,6 +156,8 @@ pub enum TypeAnnotationKind {
 pub struct UserDefinedType {
 	pub root: Symbol,
 	pub fields: Vec<Symbol>,
+	/// The type arguments of a generic type (e.g. `str` in `Cache<str>`)
+	pub type_arguments: Vec<TypeAnnotation>,
 	pub span: WingSpan,
 }
 
@@ -160,6 +166,7 @@ impl UserDefinedType {
 		Self {
 			root: class.name.clone(),
 			fields: vec![],
+			type_arguments: vec![],
 			span: class.name.span.clone(),
 		}
 	}
@@ -189,10 +196,30 @@ impl Display for UserDefinedType {
 			name.push('.');
 			name.push_str(&field.name);
 		}
+		if !self.type_arguments.is_empty() {
+			name.push_str(&format!("<{}>", self.type_arguments.iter().join(", ")));
+		}
 		write!(f, "{}", name)
 	}
 }
 
+/// A type parameter of a generic declaration (e.g. `T extends IFoo` in `class Cache<T extends IFoo>`)
+#[derive(Debug, Clone)]
+pub struct TypeParameter {
+	pub name: Symbol,
+	/// The type that type arguments must be assignable to
+	pub bound: Option<UserDefinedType>,
+}
+
+impl Display for TypeParameter {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match &self.bound {
+			Some(bound) => write!(f, "{} extends {}", self.name, bound),
+			None => write!(f, "{}", self.name),
+		}
+	}
+}
+
 impl Display for TypeAnnotationKind {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		match self {
@@ -200,6 +227,7 @@ impl Display for TypeAnnotationKind {
 			TypeAnnotationKind::String => write!(f, "str"),
 			TypeAnnotationKind::Bool => write!(f, "bool"),
 			TypeAnnotationKind::Duration => write!(f, "duration"),
+			TypeAnnotationKind::Datetime => write!(f, "datetime"),
 			TypeAnnotationKind::Void => write!(f, "void"),
 			TypeAnnotationKind::Json => write!(f, "Json"),
 			TypeAnnotationKind::MutJson => write!(f, "MutJson"),
@@ -210,8 +238,10 @@ impl Display for TypeAnnotationKind {
 			TypeAnnotationKind::MutMap(t) => write!(f, "MutMap<{}>", t),
 			TypeAnnotationKind::Set(t) => write!(f, "Set<{}>", t),
 			TypeAnnotationKind::MutSet(t) => write!(f, "MutSet<{}>", t),
+			TypeAnnotationKind::Promise(t) => write!(f, "Promise<{}>", t),
 			TypeAnnotationKind::Function(t) => write!(f, "{}", t),
 			TypeAnnotationKind::UserDefined(user_defined_type) => write!(f, "{}", user_defined_type),
+			TypeAnnotationKind::Union(types) => write!(f, "{}", types.iter().join(" | ")),
 		}
 	}
 }
@@ -233,22 +263,31 @@ impl Display for FunctionSignature {
 			.parameters
 			.iter()
 			.map(|a| {
+				let variadic = if a.variadic { "..." } else { "" };
 				if a.name.name.is_empty() {
-					format!("{}", a.type_annotation)
+					format!("{}{}", variadic, a.type_annotation)
 				} else {
-					format!("{}: {}", a.name, a.type_annotation)
+					format!("{}{}: {}", variadic, a.name, a.type_annotation)
 				}
 			})
 			.collect::<Vec<String>>()
 			.join(", ");
 
+		let type_params_str = if self.type_parameters.is_empty() {
+			String::new()
+		} else {
+			format!("<{}>", self.type_parameters.iter().join(", "))
+		};
+
 		let ret_type_str = format!("{}", &self.return_type);
-		write!(f, "{phase_str}({params_str}): {ret_type_str}")
+		write!(f, "{phase_str}{type_params_str}({params_str}): {ret_type_str}")
 	}
 }
 
 #[derive(Debug, Clone)]
 pub struct FunctionSignature {
+	/// The type parameters of a generic function (e.g. `T` in `<T>(items: Array<T>): T`)
+	pub type_parameters: Vec<TypeParameter>,
 	pub parameters: Vec<FunctionParameter>,
 	pub return_type: Box<TypeAnnotation>,
 	pub phase: Phase,
@@ -269,6 +308,8 @@ pub struct FunctionParameter {
 	pub name: Symbol,
 	pub type_annotation: TypeAnnotation,
 	pub reassignable: bool,
+	/// Whether this is a variadic parameter (e.g. `...rest: Array<str>`)
+	pub variadic: bool,
 }
 
 #[derive(Debug)]
@@ -289,9 +330,28 @@ pub struct FunctionDefinition {
 	pub signature: FunctionSignature,
 	/// Whether this function is static or not. In case of a closure, this is always true.
 	pub is_static: bool,
+	/// Who can access this function (only meaningful for class methods). In case of a closure, this is always public.
+	pub access_modifier: AccessModifier,
 	pub span: WingSpan,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AccessModifier {
+	Public,
+	Private,
+	Protected,
+}
+
+impl Display for AccessModifier {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			AccessModifier::Public => write!(f, "public"),
+			AccessModifier::Private => write!(f, "private"),
+			AccessModifier::Protected => write!(f, "protected"),
+		}
+	}
+}
+
 #[derive(Debug)]
 pub struct Stmt {
 	pub kind: StmtKind,
@@ -336,9 +396,22 @@ pub struct ElifBlock {
 	pub statements: Scope,
 }
 
+#[derive(Debug)]
+pub struct MatchArm {
+	pub patterns: Vec<Expr>,
+	pub statements: Scope,
+}
+
+#[derive(Debug)]
+pub struct MatchExprArm {
+	pub patterns: Vec<Expr>,
+	pub value: Expr,
+}
+
 #[derive(Debug)]
 pub struct Class {
 	pub name: Symbol,
+	pub type_parameters: Vec<TypeParameter>,
 	pub fields: Vec<ClassField>,
 	pub methods: Vec<(Symbol, FunctionDefinition)>,
 	pub initializer: FunctionDefinition,
@@ -384,7 +457,19 @@ impl Class {
 	}
 
 	pub fn inflight_fields(&self) -> Vec<&ClassField> {
-		self.fields.iter().filter(|f| f.phase == Phase::Inflight).collect_vec()
+		self
+			.fields
+			.iter()
+			.filter(|f| f.phase == Phase::Inflight && !f.is_static)
+			.collect_vec()
+	}
+
+	pub fn static_fields(&self, phase: Phase) -> Vec<&ClassField> {
+		self
+			.fields
+			.iter()
+			.filter(|f| f.is_static && f.phase == phase)
+			.collect_vec()
 	}
 
 	/// Returns the function definition of the "handle" method of this class (if this is a closure
@@ -414,10 +499,20 @@ impl Class {
 #[derive(Debug)]
 pub struct Interface {
 	pub name: Symbol,
+	pub type_parameters: Vec<TypeParameter>,
+	pub fields: Vec<InterfaceField>,
 	pub methods: Vec<(Symbol, FunctionSignature)>,
 	pub extends: Vec<UserDefinedType>,
 }
 
+#[derive(Debug)]
+pub struct InterfaceField {
+	pub name: Symbol,
+	pub member_type: TypeAnnotation,
+	pub reassignable: bool,
+	pub phase: Phase,
+}
+
 #[derive(Debug)]
 pub enum StmtKind {
 	Bring {
@@ -454,6 +549,11 @@ pub enum StmtKind {
 		elif_statements: Vec<ElifBlock>,
 		else_statements: Option<Scope>,
 	},
+	Match {
+		value: Expr,
+		arms: Vec<MatchArm>,
+		else_statements: Option<Scope>,
+	},
 	Break,
 	Continue,
 	Return(Option<Expr>),
@@ -467,6 +567,7 @@ pub enum StmtKind {
 	Interface(Interface),
 	Struct {
 		name: Symbol,
+		type_parameters: Vec<TypeParameter>,
 		extends: Vec<UserDefinedType>,
 		fields: Vec<StructField>,
 	},
@@ -495,6 +596,9 @@ pub struct ClassField {
 	pub reassignable: bool,
 	pub phase: Phase,
 	pub is_static: bool,
+	pub access_modifier: AccessModifier,
+	/// The value of a static field, evaluated once when the class is defined
+	pub initializer: Option<Expr>,
 }
 
 #[derive(Debug)]
@@ -554,6 +658,21 @@ pub enum ExprKind {
 		element: Box<Expr>,
 	},
 	FunctionClosure(FunctionDefinition),
+	/// Waits for a promise (or an array of promises) to resolve (`await x`)
+	Await(Box<Expr>),
+	/// Evaluates an inflight expression without waiting for it, resulting in a promise (`defer x`)
+	Defer(Box<Expr>),
+	/// Tests whether a value is of a given type at runtime (`x is str`)
+	TypeTest {
+		expr: Box<Expr>,
+		type_: TypeAnnotation,
+	},
+	/// Evaluates to the value of the first arm matching a value (`match x { 1 => "one", else => "many" }`)
+	Match {
+		value: Box<Expr>,
+		arms: Vec<MatchExprArm>,
+		else_value: Option<Box<Expr>>,
+	},
 	CompilerDebugPanic,
 }
 
@@ -694,6 +813,8 @@ pub enum Reference {
 	TypeReference(UserDefinedType),
 	/// A reference to a member inside a type: `MyType.x` or `MyEnum.A`
 	TypeMember { typeobject: Box<Expr>, property: Symbol },
+	/// A reference to an element of an array, a map or a Json value: `arr[0]`, `map["key"]` or `json["field"]`
+	ElementAccess { object: Box<Expr>, index: Box<Expr> },
 }
 
 impl Display for Reference {
@@ -719,6 +840,13 @@ impl Display for Reference {
 
 				write!(f, "{}.{}", r, property.name)
 			}
+			Reference::ElementAccess { object, .. } => {
+				let obj_str = match &object.kind {
+					ExprKind::Reference(r) => format!("{}", r),
+					_ => "object".to_string(),
+				};
+				write!(f, "{}[...]", obj_str)
+			}
 		}
 	}
 }
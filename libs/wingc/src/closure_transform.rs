@@ -2,9 +2,9 @@ use indexmap::IndexMap;
 
 use crate::{
 	ast::{
-		ArgList, Class, ClassField, Expr, ExprKind, FunctionBody, FunctionDefinition, FunctionParameter, FunctionSignature,
-		Literal, NewExpr, Phase, Reference, Scope, Stmt, StmtKind, Symbol, TypeAnnotation, TypeAnnotationKind,
-		UserDefinedType,
+		AccessModifier, ArgList, Class, ClassField, Expr, ExprKind, FunctionBody, FunctionDefinition, FunctionParameter,
+		FunctionSignature, Literal, NewExpr, Phase, Reference, Scope, Stmt, StmtKind, Symbol, TypeAnnotation,
+		TypeAnnotationKind, UserDefinedType,
 	},
 	diagnostic::WingSpan,
 	fold::{self, Fold},
@@ -111,6 +111,18 @@ impl Fold for ClosureTransformer {
 		new_node
 	}
 
+	fn fold_class_field(&mut self, node: ClassField) -> ClassField {
+		// Static field initializers are evaluated in the phase of the field, and "this" isn't valid in them
+		let prev_phase = self.phase;
+		self.phase = node.phase;
+		let prev_inside_scope_with_this = self.inside_scope_with_this;
+		self.inside_scope_with_this = false;
+		let new_node = fold::fold_class_field(self, node);
+		self.inside_scope_with_this = prev_inside_scope_with_this;
+		self.phase = prev_phase;
+		new_node
+	}
+
 	fn fold_expr(&mut self, expr: Expr) -> Expr {
 		// Inflight closures that are themselves defined inflight do not need
 		// to be transformed, since they are not created in preflight.
@@ -143,6 +155,7 @@ impl Fold for ClosureTransformer {
 				let class_udt = UserDefinedType {
 					root: new_class_name.clone(),
 					fields: vec![],
+					type_arguments: vec![],
 					span: WingSpan::default(),
 				};
 
@@ -178,6 +191,7 @@ impl Fold for ClosureTransformer {
 					// Anonymous functions are always static -- since the function code is now an instance method on a class,
 					// we need to set this to false.
 					is_static: false,
+					access_modifier: AccessModifier::Public,
 				};
 
 				// class_init_body :=
@@ -246,15 +260,18 @@ impl Fold for ClosureTransformer {
 				let class_def = Stmt {
 					kind: StmtKind::Class(Class {
 						name: new_class_name.clone(),
+						type_parameters: vec![],
 						phase: Phase::Preflight,
 						initializer: FunctionDefinition {
 							name: Some(CLASS_INIT_NAME.into()),
 							signature: FunctionSignature {
+								type_parameters: vec![],
 								parameters: class_init_params,
 								return_type: Box::new(class_type_annotation.clone()),
 								phase: Phase::Preflight,
 							},
 							is_static: true,
+							access_modifier: AccessModifier::Public,
 							body: FunctionBody::Statements(Scope::new(class_init_body, WingSpan::default())),
 							span: WingSpan::default(),
 						},
@@ -265,6 +282,7 @@ impl Fold for ClosureTransformer {
 						inflight_initializer: FunctionDefinition {
 							name: Some(CLASS_INFLIGHT_INIT_NAME.into()),
 							signature: FunctionSignature {
+								type_parameters: vec![],
 								parameters: vec![],
 								return_type: Box::new(TypeAnnotation {
 									kind: TypeAnnotationKind::Void,
@@ -273,6 +291,7 @@ impl Fold for ClosureTransformer {
 								phase: Phase::Inflight,
 							},
 							is_static: false,
+							access_modifier: AccessModifier::Public,
 							body: FunctionBody::Statements(Scope::new(vec![], WingSpan::default())),
 							span: WingSpan::default(),
 						},
@@ -348,9 +367,10 @@ impl<'a> Fold for RenameThisTransformer<'a> {
 					Reference::Identifier(ident)
 				}
 			}
-			Reference::InstanceMember { .. } | Reference::TypeMember { .. } | Reference::TypeReference(_) => {
-				fold::fold_reference(self, node)
-			}
+			Reference::InstanceMember { .. }
+			| Reference::TypeMember { .. }
+			| Reference::TypeReference(_)
+			| Reference::ElementAccess { .. } => fold::fold_reference(self, node),
 		}
 	}
 }
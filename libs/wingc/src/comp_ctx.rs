@@ -5,7 +5,7 @@ use std::{
 
 use strum::{Display, EnumString};
 
-use crate::diagnostic::{report_diagnostic, Diagnostic, WingSpan};
+use crate::diagnostic::{report_diagnostic, Diagnostic, DiagnosticCode, DiagnosticSeverity, WingSpan};
 
 /// The different phases of compilation, used for tracking compilation context
 /// for diagnostic purposes. Feel free to add new phases as needed.
@@ -110,6 +110,10 @@ pub fn set_custom_panic_hook() {
 				CompilationContext::get_phase()
 			),
 			span: Some(CompilationContext::get_span()),
+			severity: DiagnosticSeverity::Error,
+			code: DiagnosticCode::CompilerBug,
+			annotations: vec![],
+			fixes: vec![],
 		})
 	}));
 }
@@ -1,8 +1,20 @@
+use codespan_reporting::{
+	diagnostic::{Label, LabelStyle},
+	files::SimpleFiles,
+	term::{self, termcolor::Buffer},
+};
 use colored::Colorize;
-use std::{cell::RefCell, fmt::Display};
+use std::{
+	cell::{Cell, RefCell},
+	collections::HashMap,
+	fmt::Display,
+	ops::Range as ByteRange,
+};
 use tree_sitter::Point;
 
-use lsp_types::{Position, Range};
+use crate::files::Files;
+
+use lsp_types::{DiagnosticRelatedInformation, Location, NumberOrString, Position, Range, Url};
 
 use serde::Serialize;
 
@@ -160,22 +172,523 @@ impl PartialOrd for WingSpan {
 	}
 }
 
+/// The severity of a diagnostic. Only errors cause the compilation to fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticSeverity {
+	Error,
+	Warning,
+	Info,
+	Hint,
+}
+
+impl Display for DiagnosticSeverity {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			DiagnosticSeverity::Error => write!(f, "Error"),
+			DiagnosticSeverity::Warning => write!(f, "Warning"),
+			DiagnosticSeverity::Info => write!(f, "Info"),
+			DiagnosticSeverity::Hint => write!(f, "Hint"),
+		}
+	}
+}
+
+/// DiagnosticSeverity => LSP-based DiagnosticSeverity
+impl Into<lsp_types::DiagnosticSeverity> for DiagnosticSeverity {
+	fn into(self) -> lsp_types::DiagnosticSeverity {
+		match self {
+			DiagnosticSeverity::Error => lsp_types::DiagnosticSeverity::ERROR,
+			DiagnosticSeverity::Warning => lsp_types::DiagnosticSeverity::WARNING,
+			DiagnosticSeverity::Info => lsp_types::DiagnosticSeverity::INFORMATION,
+			DiagnosticSeverity::Hint => lsp_types::DiagnosticSeverity::HINT,
+		}
+	}
+}
+
+/// Extra information about the code a diagnostic applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticTag {
+	/// The code is unused or unreachable
+	Unnecessary,
+	/// The code uses a deprecated symbol
+	Deprecated,
+}
+
+/// DiagnosticTag => LSP-based DiagnosticTag
+impl Into<lsp_types::DiagnosticTag> for DiagnosticTag {
+	fn into(self) -> lsp_types::DiagnosticTag {
+		match self {
+			DiagnosticTag::Unnecessary => lsp_types::DiagnosticTag::UNNECESSARY,
+			DiagnosticTag::Deprecated => lsp_types::DiagnosticTag::DEPRECATED,
+		}
+	}
+}
+
+/// A stable identifier for each kind of diagnostic reported by the compiler.
+///
+/// Codes are grouped by category: `E0xxx` for internal and file system errors, `E1xxx` for syntax errors,
+/// `E2xxx` for type errors, `E3xxx` for module errors and `E4xxx` for code generation errors.
+/// Warnings use the same numbering with a `W` prefix. Once released, a code must never be reused
+/// for a different kind of diagnostic (tools rely on them to group and filter diagnostics).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum DiagnosticCode {
+	#[serde(rename = "E0001")]
+	CompilerBug,
+	#[serde(rename = "E0002")]
+	SourceFileNotFound,
+	#[serde(rename = "E0003")]
+	InvalidProjectRoot,
+	#[serde(rename = "E0004")]
+	OutputFileError,
+	#[serde(rename = "E0005")]
+	InvalidPackageManifest,
+	#[serde(rename = "E1001")]
+	SyntaxError,
+	#[serde(rename = "E1002")]
+	MissingToken,
+	#[serde(rename = "E1003")]
+	UnexpectedToken,
+	#[serde(rename = "E1004")]
+	UnsupportedFeature,
+	#[serde(rename = "E2001")]
+	TypeError,
+	#[serde(rename = "E2002")]
+	TypeMismatch,
+	#[serde(rename = "E2003")]
+	UnknownSymbol,
+	#[serde(rename = "E2004")]
+	DuplicateSymbol,
+	#[serde(rename = "E2005")]
+	InaccessibleMember,
+	#[serde(rename = "E2006")]
+	InvalidInheritance,
+	#[serde(rename = "E2007")]
+	InvalidTypeArguments,
+	#[serde(rename = "E2008")]
+	MissingReturn,
+	#[serde(rename = "E2009")]
+	NonExhaustiveMatch,
+	#[serde(rename = "E2010")]
+	PhaseViolation,
+	#[serde(rename = "E2011")]
+	NotReassignable,
+	#[serde(rename = "E2012")]
+	InvalidArguments,
+	#[serde(rename = "E2013")]
+	MissingImplementation,
+	#[serde(rename = "E3001")]
+	ModuleNotFound,
+	#[serde(rename = "E3002")]
+	CyclicBring,
+	#[serde(rename = "E3003")]
+	InvalidModuleStatement,
+	#[serde(rename = "E4001")]
+	ShadowedCapture,
+	#[serde(rename = "E4002")]
+	UnqualifiedLift,
+	#[serde(rename = "E4003")]
+	InflightInPreflight,
+	#[serde(rename = "E4004")]
+	UnresolvedExtern,
+	#[serde(rename = "E4005")]
+	UnexportableType,
+	#[serde(rename = "W2001")]
+	DeprecatedMember,
+	#[serde(rename = "W2002")]
+	UnreachableCode,
+}
+
+impl DiagnosticCode {
+	pub const ALL: &'static [DiagnosticCode] = &[
+		DiagnosticCode::CompilerBug,
+		DiagnosticCode::SourceFileNotFound,
+		DiagnosticCode::InvalidProjectRoot,
+		DiagnosticCode::OutputFileError,
+		DiagnosticCode::InvalidPackageManifest,
+		DiagnosticCode::SyntaxError,
+		DiagnosticCode::MissingToken,
+		DiagnosticCode::UnexpectedToken,
+		DiagnosticCode::UnsupportedFeature,
+		DiagnosticCode::TypeError,
+		DiagnosticCode::TypeMismatch,
+		DiagnosticCode::UnknownSymbol,
+		DiagnosticCode::DuplicateSymbol,
+		DiagnosticCode::InaccessibleMember,
+		DiagnosticCode::InvalidInheritance,
+		DiagnosticCode::InvalidTypeArguments,
+		DiagnosticCode::MissingReturn,
+		DiagnosticCode::NonExhaustiveMatch,
+		DiagnosticCode::PhaseViolation,
+		DiagnosticCode::NotReassignable,
+		DiagnosticCode::InvalidArguments,
+		DiagnosticCode::MissingImplementation,
+		DiagnosticCode::ModuleNotFound,
+		DiagnosticCode::CyclicBring,
+		DiagnosticCode::InvalidModuleStatement,
+		DiagnosticCode::ShadowedCapture,
+		DiagnosticCode::UnqualifiedLift,
+		DiagnosticCode::InflightInPreflight,
+		DiagnosticCode::UnresolvedExtern,
+		DiagnosticCode::UnexportableType,
+		DiagnosticCode::DeprecatedMember,
+		DiagnosticCode::UnreachableCode,
+	];
+
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			DiagnosticCode::CompilerBug => "E0001",
+			DiagnosticCode::SourceFileNotFound => "E0002",
+			DiagnosticCode::InvalidProjectRoot => "E0003",
+			DiagnosticCode::OutputFileError => "E0004",
+			DiagnosticCode::InvalidPackageManifest => "E0005",
+			DiagnosticCode::SyntaxError => "E1001",
+			DiagnosticCode::MissingToken => "E1002",
+			DiagnosticCode::UnexpectedToken => "E1003",
+			DiagnosticCode::UnsupportedFeature => "E1004",
+			DiagnosticCode::TypeError => "E2001",
+			DiagnosticCode::TypeMismatch => "E2002",
+			DiagnosticCode::UnknownSymbol => "E2003",
+			DiagnosticCode::DuplicateSymbol => "E2004",
+			DiagnosticCode::InaccessibleMember => "E2005",
+			DiagnosticCode::InvalidInheritance => "E2006",
+			DiagnosticCode::InvalidTypeArguments => "E2007",
+			DiagnosticCode::MissingReturn => "E2008",
+			DiagnosticCode::NonExhaustiveMatch => "E2009",
+			DiagnosticCode::PhaseViolation => "E2010",
+			DiagnosticCode::NotReassignable => "E2011",
+			DiagnosticCode::InvalidArguments => "E2012",
+			DiagnosticCode::MissingImplementation => "E2013",
+			DiagnosticCode::ModuleNotFound => "E3001",
+			DiagnosticCode::CyclicBring => "E3002",
+			DiagnosticCode::InvalidModuleStatement => "E3003",
+			DiagnosticCode::ShadowedCapture => "E4001",
+			DiagnosticCode::UnqualifiedLift => "E4002",
+			DiagnosticCode::InflightInPreflight => "E4003",
+			DiagnosticCode::UnresolvedExtern => "E4004",
+			DiagnosticCode::UnexportableType => "E4005",
+			DiagnosticCode::DeprecatedMember => "W2001",
+			DiagnosticCode::UnreachableCode => "W2002",
+		}
+	}
+
+	/// Looks up a code by its string representation (e.g. `E2003`), ignoring case
+	pub fn from_code(code: &str) -> Option<DiagnosticCode> {
+		Self::ALL
+			.iter()
+			.copied()
+			.find(|c| c.as_str().eq_ignore_ascii_case(code))
+	}
+
+	/// A longer description of the diagnostic and how to fix it, shown by `wingc explain <code>`
+	pub fn explanation(&self) -> &'static str {
+		match self {
+			DiagnosticCode::CompilerBug => {
+				"The compiler crashed while compiling the program. This is a bug in the compiler and not in your code, \
+				please report it at https://www.winglang.io/contributing/start-here/bugs."
+			}
+			DiagnosticCode::SourceFileNotFound => {
+				"A source file couldn't be read. Make sure the path points to an existing file (not a directory) and \
+				that it is readable."
+			}
+			DiagnosticCode::InvalidProjectRoot => {
+				"The project root passed to the compiler must be an absolute path to the directory of the project."
+			}
+			DiagnosticCode::OutputFileError => {
+				"The compiler failed to write one of the output files. Make sure the output directory is writable and \
+				that no two modules are compiled to the same file."
+			}
+			DiagnosticCode::InvalidPackageManifest => {
+				"A library is compiled but the `package.json` file at the root of the project is missing or invalid. \
+				The manifest must contain the `name` and `version` of the library's package, they are used as the name \
+				and version of the library's JSII assembly."
+			}
+			DiagnosticCode::SyntaxError => {
+				"The code is not valid Wing syntax. The message describes which construct is invalid and where it is \
+				allowed to appear."
+			}
+			DiagnosticCode::MissingToken => {
+				"The parser expected a token (e.g. a `;` or a closing bracket) which is missing. The suggested fix \
+				inserts the missing token."
+			}
+			DiagnosticCode::UnexpectedToken => {
+				"The parser found code it didn't expect at this position. This is usually caused by a typo or by a \
+				missing token earlier in the file."
+			}
+			DiagnosticCode::UnsupportedFeature => {
+				"The code uses a language feature which is part of the Wing language specification but is not \
+				implemented by the compiler yet. The message links to the issue tracking it."
+			}
+			DiagnosticCode::TypeError => {
+				"The program is not well typed. The message describes which rule of the type system is violated."
+			}
+			DiagnosticCode::TypeMismatch => {
+				"An expression has a different type than the one expected in its context (e.g. a variable \
+				declaration, an argument or a return value). Convert the value to the expected type, or change the \
+				expected type. To allow `nil`, use an optional type (e.g. `str?`)."
+			}
+			DiagnosticCode::UnknownSymbol => {
+				"A name couldn't be resolved. Make sure it is spelled correctly, that it is defined before it is used \
+				and that the module defining it is brought into scope with `bring`."
+			}
+			DiagnosticCode::DuplicateSymbol => {
+				"A name is defined more than once in the same scope. Rename one of the definitions."
+			}
+			DiagnosticCode::InaccessibleMember => {
+				"A class member is accessed from outside of the scope allowed by its access modifier. `private` \
+				members can only be accessed from within the class that defines them and `protected` members can also \
+				be accessed from classes that extend it. The related span points to the member's declaration."
+			}
+			DiagnosticCode::InvalidInheritance => {
+				"A class, interface or struct extends a type it cannot extend, e.g. a preflight class extending an \
+				inflight class, a class extending a non-class type, or a struct whose parents have conflicting members. \
+				It is also reported when a class member overrides a private member of its parent class, or overrides a \
+				member with a more restrictive access modifier (e.g. a public method with a protected one)."
+			}
+			DiagnosticCode::InvalidTypeArguments => {
+				"A generic class, interface, struct or function is used with the wrong type arguments: the number of \
+				type arguments doesn't match its type parameters, a type argument doesn't satisfy the bound of its type \
+				parameter (`T extends IFoo`), or the type arguments are missing and can't be inferred from the arguments."
+			}
+			DiagnosticCode::MissingReturn => {
+				"A function with a return type other than `void` can reach the end of its body without returning a \
+				value. Make sure every path through the function ends with a `return` statement (or with a call to \
+				`throw` or `panic`)."
+			}
+			DiagnosticCode::NonExhaustiveMatch => {
+				"A `match` statement doesn't cover all the values its subject can have. Add arms for the missing enum \
+				values (or `true`/`false`, or `nil` for optionals) listed in the message, or add an `else` arm. Matches \
+				over numbers and strings always need an `else` arm."
+			}
+			DiagnosticCode::PhaseViolation => {
+				"Code is used in a phase it doesn't belong to, e.g. an inflight method is called from preflight code, \
+				a preflight value is modified from inflight code, a preflight class is instantiated in inflight code or \
+				`await` is used outside of inflight code."
+			}
+			DiagnosticCode::NotReassignable => {
+				"A variable, field or argument which isn't reassignable is assigned a new value. Declare variables \
+				with `let var` and fields with `var` to make them reassignable. Elements can only be assigned in mutable \
+				collections (e.g. `MutArray`)."
+			}
+			DiagnosticCode::InvalidArguments => {
+				"A call passes arguments that don't match the function's parameters, e.g. the wrong number of \
+				arguments, named arguments to a function whose last parameter is not a struct, unknown struct fields or \
+				missing required struct fields."
+			}
+			DiagnosticCode::MissingImplementation => {
+				"A class doesn't implement one of the members declared by an interface it implements, or implements it \
+				with a different type, phase or access modifier. The message names the missing or incompatible member."
+			}
+			DiagnosticCode::ModuleNotFound => {
+				"A module referenced by a `bring` statement couldn't be found. Wing files are resolved relative to \
+				the file containing the `bring` statement, and JSII modules are resolved from `node_modules`."
+			}
+			DiagnosticCode::CyclicBring => {
+				"Wing files bring each other in a cycle. Move the shared definitions to a separate file which is \
+				brought by both."
+			}
+			DiagnosticCode::InvalidModuleStatement => {
+				"A Wing file brought as a module contains a statement other than a type definition (class, interface, \
+				struct or enum) or a `bring` statement."
+			}
+			DiagnosticCode::ShadowedCapture => {
+				"An inflight closure references a preflight symbol which is shadowed by a symbol with the same name \
+				defined later in the same scope. Rename one of the symbols."
+			}
+			DiagnosticCode::UnqualifiedLift => {
+				"A preflight object is referenced from inflight code in a way that doesn't allow the compiler to \
+				determine which of its operations are used (e.g. by passing it around as a value). Call the \
+				operations directly on the object instead."
+			}
+			DiagnosticCode::InflightInPreflight => {
+				"A preflight expression references a value which only exists inflight. Preflight code runs at \
+				compile time, before any inflight value is available."
+			}
+			DiagnosticCode::UnresolvedExtern => {
+				"The JavaScript module referenced by an `extern` function couldn't be resolved. The path is resolved \
+				relative to the file which declares the function."
+			}
+			DiagnosticCode::UnexportableType => {
+				"A type used by the public API of a library can't be represented in its JSII assembly. Sets, \
+				function types, generic types and types defined in other Wing files can't be exported. Make the member \
+				private or change its type."
+			}
+			DiagnosticCode::DeprecatedMember => {
+				"A member which is marked as deprecated by its library is used. Deprecated members may be removed in a \
+				future version of the library, the message includes the library's suggested replacement (if any)."
+			}
+			DiagnosticCode::UnreachableCode => {
+				"A statement can never be executed because it follows a `return` statement or a call to `throw` or \
+				`panic` (directly or in all branches of a preceding statement). Remove the statement or move it before \
+				the statement which exits. It is also reported for `match` arms which can never be taken because \
+				their pattern is already matched by a previous arm."
+			}
+		}
+	}
+
+	/// Tags which editors use to render the code a diagnostic applies to (e.g. striking through deprecated code)
+	pub fn tags(&self) -> &'static [DiagnosticTag] {
+		match self {
+			DiagnosticCode::DeprecatedMember => &[DiagnosticTag::Deprecated],
+			DiagnosticCode::UnreachableCode => &[DiagnosticTag::Unnecessary],
+			_ => &[],
+		}
+	}
+}
+
+impl Display for DiagnosticCode {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.as_str())
+	}
+}
+
+/// A secondary span related to a diagnostic (e.g. the declaration of a symbol which is misused)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct DiagnosticAnnotation {
+	pub message: String,
+	pub span: WingSpan,
+}
+
+/// A suggested fix for a diagnostic, replacing the text in `span` with `replacement`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct DiagnosticFix {
+	pub message: String,
+	pub span: WingSpan,
+	pub replacement: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
 pub struct Diagnostic {
 	pub message: String,
 	pub span: Option<WingSpan>,
+	pub severity: DiagnosticSeverity,
+	pub code: DiagnosticCode,
+	pub annotations: Vec<DiagnosticAnnotation>,
+	pub fixes: Vec<DiagnosticFix>,
 }
 
 impl std::fmt::Display for Diagnostic {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		if let Some(span) = &self.span {
-			write!(f, "Error at {} | {}", span, self.message.bold().white())
+			write!(
+				f,
+				"{}[{}] at {} | {}",
+				self.severity,
+				self.code,
+				span,
+				self.message.bold().white()
+			)?;
 		} else {
-			write!(f, "Error | {}", self.message.bold().white())
+			write!(f, "{}[{}] | {}", self.severity, self.code, self.message.bold().white())?;
+		}
+		for annotation in &self.annotations {
+			write!(f, "\n  Note at {} | {}", annotation.span, annotation.message)?;
+		}
+		for fix in &self.fixes {
+			write!(f, "\n  Help at {} | {}", fix.span, fix.message)?;
 		}
+		Ok(())
+	}
+}
+
+/// Diagnostic => LSP-based Diagnostic
+impl Into<lsp_types::Diagnostic> for Diagnostic {
+	fn into(self) -> lsp_types::Diagnostic {
+		let related_information = self
+			.annotations
+			.into_iter()
+			.filter_map(|annotation| {
+				Some(DiagnosticRelatedInformation {
+					location: Location {
+						uri: Url::from_file_path(&annotation.span.file_id).ok()?,
+						range: annotation.span.into(),
+					},
+					message: annotation.message,
+				})
+			})
+			.collect::<Vec<_>>();
+		let tags = self.code.tags().iter().map(|tag| (*tag).into()).collect::<Vec<_>>();
+
+		lsp_types::Diagnostic {
+			range: self.span.map(|span| span.into()).unwrap_or_default(),
+			severity: Some(self.severity.into()),
+			code: Some(NumberOrString::String(self.code.to_string())),
+			code_description: None,
+			source: Some("wing".to_string()),
+			message: self.message,
+			related_information: if related_information.is_empty() {
+				None
+			} else {
+				Some(related_information)
+			},
+			tags: if tags.is_empty() { None } else { Some(tags) },
+			data: None,
+		}
+	}
+}
+
+impl Diagnostic {
+	/// Renders the diagnostic along with the source lines it refers to, underlining its span and the spans of its
+	/// annotations. Spans in files which are not found in `files` are rendered as locations instead.
+	pub fn render(&self, files: &Files, colors: bool) -> String {
+		let mut sources = SimpleFiles::new();
+		let mut file_ids = HashMap::new();
+		let mut labels = vec![];
+		let mut notes = vec![];
+
+		let mut label = |span: &WingSpan, style: LabelStyle, message: &str| {
+			let source = files.get_file(&span.file_id)?;
+			let file_id = *file_ids
+				.entry(span.file_id.clone())
+				.or_insert_with(|| sources.add(span.file_id.clone(), source.as_str()));
+			Some(Label::new(style, file_id, byte_range(source, span)).with_message(message))
+		};
+
+		if let Some(span) = &self.span {
+			match label(span, LabelStyle::Primary, "") {
+				Some(primary) => labels.push(primary),
+				None => notes.push(format!("at {}", span)),
+			}
+		}
+		for annotation in &self.annotations {
+			match label(&annotation.span, LabelStyle::Secondary, &annotation.message) {
+				Some(secondary) => labels.push(secondary),
+				None => notes.push(format!("{} (at {})", annotation.message, annotation.span)),
+			}
+		}
+		notes.extend(self.fixes.iter().map(|fix| format!("help: {}", fix.message)));
+
+		let severity = match self.severity {
+			DiagnosticSeverity::Error => codespan_reporting::diagnostic::Severity::Error,
+			DiagnosticSeverity::Warning => codespan_reporting::diagnostic::Severity::Warning,
+			DiagnosticSeverity::Info => codespan_reporting::diagnostic::Severity::Note,
+			DiagnosticSeverity::Hint => codespan_reporting::diagnostic::Severity::Help,
+		};
+		let diagnostic = codespan_reporting::diagnostic::Diagnostic::new(severity)
+			.with_code(self.code.as_str())
+			.with_message(&self.message)
+			.with_labels(labels)
+			.with_notes(notes);
+
+		let mut buffer = if colors { Buffer::ansi() } else { Buffer::no_color() };
+		term::emit(&mut buffer, &term::Config::default(), &sources, &diagnostic).expect("Failed to render diagnostic");
+		String::from_utf8_lossy(buffer.as_slice()).into_owned()
 	}
 }
 
+/// Converts the line/column locations of a span to a byte range in `source`
+fn byte_range(source: &str, span: &WingSpan) -> ByteRange<usize> {
+	let byte_offset = |location: WingLocation| {
+		let line_start: usize = source
+			.split_inclusive('\n')
+			.take(location.line as usize)
+			.map(str::len)
+			.sum();
+		(line_start + location.col as usize).min(source.len())
+	};
+	byte_offset(span.start)..byte_offset(span.end)
+}
+
 impl Ord for Diagnostic {
 	fn cmp(&self, other: &Self) -> std::cmp::Ordering {
 		self.span.cmp(&other.span)
@@ -190,10 +703,20 @@ impl PartialOrd for Diagnostic {
 
 thread_local! {
 	pub static DIAGNOSTICS: RefCell<Diagnostics> = RefCell::new(Diagnostics::new());
+	static WARNINGS_AS_ERRORS: Cell<bool> = Cell::new(false);
+}
+
+/// Treat all warnings reported from now on as errors (so they fail the compilation)
+pub fn set_warnings_as_errors(enabled: bool) {
+	WARNINGS_AS_ERRORS.with(|warnings_as_errors| warnings_as_errors.set(enabled));
 }
 
 /// Report a compilation diagnostic
-pub fn report_diagnostic(diagnostic: Diagnostic) {
+pub fn report_diagnostic(mut diagnostic: Diagnostic) {
+	if diagnostic.severity == DiagnosticSeverity::Warning && WARNINGS_AS_ERRORS.with(|w| w.get()) {
+		diagnostic.severity = DiagnosticSeverity::Error;
+	}
+
 	// Add the diagnostic to the list of diagnostics
 	DIAGNOSTICS.with(|diagnostics| {
 		diagnostics.borrow_mut().push(diagnostic.clone());
@@ -219,7 +742,7 @@ extern "C" {
 pub fn found_errors() -> bool {
 	DIAGNOSTICS.with(|diagnostics| {
 		let diagnostics = diagnostics.borrow();
-		!diagnostics.is_empty()
+		diagnostics.iter().any(|d| d.severity == DiagnosticSeverity::Error)
 	})
 }
 
@@ -259,6 +782,8 @@ pub fn reset_diagnostics() {
 pub struct TypeError {
 	pub message: String,
 	pub span: WingSpan,
+	pub code: DiagnosticCode,
+	pub annotations: Vec<DiagnosticAnnotation>,
 }
 
 impl std::fmt::Display for TypeError {
@@ -313,4 +838,126 @@ mod tests {
 		assert!(span1 > sooner);
 		assert!(span1 >= sooner);
 	}
+
+	fn diagnostic(severity: DiagnosticSeverity) -> Diagnostic {
+		Diagnostic {
+			message: "test".to_string(),
+			span: None,
+			severity,
+			code: DiagnosticCode::TypeError,
+			annotations: vec![],
+			fixes: vec![],
+		}
+	}
+
+	#[test]
+	fn warnings_are_not_errors() {
+		reset_diagnostics();
+		report_diagnostic(diagnostic(DiagnosticSeverity::Warning));
+		report_diagnostic(diagnostic(DiagnosticSeverity::Info));
+		report_diagnostic(diagnostic(DiagnosticSeverity::Hint));
+		assert!(!found_errors());
+
+		report_diagnostic(diagnostic(DiagnosticSeverity::Error));
+		assert!(found_errors());
+		reset_diagnostics();
+	}
+
+	#[test]
+	fn warnings_as_errors() {
+		reset_diagnostics();
+		set_warnings_as_errors(true);
+		report_diagnostic(diagnostic(DiagnosticSeverity::Warning));
+		set_warnings_as_errors(false);
+
+		assert!(found_errors());
+		assert_eq!(get_diagnostics()[0].severity, DiagnosticSeverity::Error);
+		reset_diagnostics();
+	}
+
+	#[test]
+	fn diagnostic_codes_are_unique() {
+		for code in DiagnosticCode::ALL {
+			assert_eq!(DiagnosticCode::from_code(code.as_str()), Some(*code));
+			assert_eq!(
+				serde_json::to_value(code).unwrap(),
+				serde_json::Value::String(code.as_str().to_string())
+			);
+		}
+		assert_eq!(DiagnosticCode::from_code("e2003"), Some(DiagnosticCode::UnknownSymbol));
+		assert_eq!(DiagnosticCode::from_code("E9999"), None);
+	}
+
+	#[test]
+	fn render_diagnostic_with_source() {
+		let mut files = Files::new();
+		files
+			.add_file("main.w", "let x = 1;\nlet x = 2;\n".to_string())
+			.unwrap();
+		let span = |line, col_start, col_end| WingSpan {
+			start: WingLocation { line, col: col_start },
+			end: WingLocation { line, col: col_end },
+			file_id: "main.w".to_string(),
+		};
+		let diagnostic = Diagnostic {
+			message: "Symbol \"x\" already defined in this scope".to_string(),
+			span: Some(span(1, 4, 5)),
+			severity: DiagnosticSeverity::Error,
+			code: DiagnosticCode::DuplicateSymbol,
+			annotations: vec![DiagnosticAnnotation {
+				message: "\"x\" is first defined here".to_string(),
+				span: span(0, 4, 5),
+			}],
+			fixes: vec![],
+		};
+
+		let rendered = diagnostic.render(&files, false);
+		assert!(rendered.starts_with("error[E2004]: Symbol \"x\" already defined in this scope"));
+		assert!(rendered.contains("main.w:2:5"));
+		assert!(rendered.contains("let x = 2;"));
+		assert!(rendered.contains("^"));
+		assert!(rendered.contains("\"x\" is first defined here"));
+
+		// Without the source, the location is rendered as a note
+		let rendered = diagnostic.render(&Files::new(), false);
+		assert!(rendered.contains("at main.w:2:5"));
+		assert!(rendered.contains("\"x\" is first defined here (at main.w:1:5)"));
+	}
+
+	#[test]
+	fn lsp_diagnostic_conversion() {
+		let span = |line| WingSpan {
+			start: WingLocation { line, col: 4 },
+			end: WingLocation { line, col: 7 },
+			file_id: "/project/main.w".to_string(),
+		};
+		let deprecated_use = Diagnostic {
+			message: "\"foo\" is deprecated".to_string(),
+			span: Some(span(1)),
+			severity: DiagnosticSeverity::Warning,
+			code: DiagnosticCode::DeprecatedMember,
+			annotations: vec![DiagnosticAnnotation {
+				message: "\"foo\" is declared here".to_string(),
+				span: span(0),
+			}],
+			fixes: vec![],
+		};
+
+		let lsp_diagnostic: lsp_types::Diagnostic = deprecated_use.into();
+		assert_eq!(lsp_diagnostic.range, span(1).into());
+		assert_eq!(lsp_diagnostic.severity, Some(lsp_types::DiagnosticSeverity::WARNING));
+		assert_eq!(lsp_diagnostic.code, Some(NumberOrString::String("W2001".to_string())));
+		assert_eq!(lsp_diagnostic.source, Some("wing".to_string()));
+		assert_eq!(lsp_diagnostic.tags, Some(vec![lsp_types::DiagnosticTag::DEPRECATED]));
+		let related_information = lsp_diagnostic.related_information.unwrap();
+		assert_eq!(related_information.len(), 1);
+		assert_eq!(related_information[0].location.uri.path(), "/project/main.w");
+		assert_eq!(related_information[0].location.range, span(0).into());
+
+		// Diagnostics without a span are reported at the start of the document
+		let lsp_diagnostic: lsp_types::Diagnostic = diagnostic(DiagnosticSeverity::Error).into();
+		assert_eq!(lsp_diagnostic.range, Range::default());
+		assert_eq!(lsp_diagnostic.tags, None);
+		assert_eq!(lsp_diagnostic.related_information, None);
+	}
 }
@@ -7,8 +7,8 @@ use crate::{
 	closure_transform::CLOSURE_CLASS_PREFIX,
 	jsify::codemaker::CodeMaker,
 	type_check::{
-		jsii_importer::is_construct_base, Class, ClassLike, Enum, FunctionSignature, Interface, Namespace, Struct,
-		SymbolKind, Type, TypeRef, VariableInfo, VariableKind,
+		jsii_importer::is_construct_base, Class, ClassLike, Enum, FunctionSignature, GenericInstance, Interface, Namespace,
+		Struct, SymbolKind, Type, TypeParameter, TypeRef, VariableInfo, VariableKind,
 	},
 };
 
@@ -68,12 +68,14 @@ impl Documented for TypeRef {
 			Type::Struct(s) => render_struct(s),
 			Type::Optional(t) => t.render_docs(),
 			Type::Enum(e) => render_enum(e),
+			Type::TypeParameter(p) => render_type_parameter(p),
 
 			// primitive types don't have docs yet
 			Type::Anything
 			| Type::Number
 			| Type::String
 			| Type::Duration
+			| Type::Datetime
 			| Type::Boolean
 			| Type::Void
 			| Type::Json
@@ -85,7 +87,9 @@ impl Documented for TypeRef {
 			| Type::Map(_)
 			| Type::MutMap(_)
 			| Type::Set(_)
-			| Type::MutSet(_) => "".to_string(),
+			| Type::MutSet(_)
+			| Type::Promise(_)
+			| Type::Union(_) => "".to_string(),
 		}
 	}
 }
@@ -248,7 +252,10 @@ fn render_struct(s: &Struct) -> String {
 	};
 
 	markdown.line("```wing");
-	markdown.line(format!("struct {}{extends}", s.name));
+	markdown.line(format!(
+		"struct {}{extends}",
+		render_generic_name(&s.name.name, s.type_parameters.as_ref(), s.generic_instance.as_ref())
+	));
 	markdown.line("```");
 	markdown.line("---");
 
@@ -295,7 +302,10 @@ fn render_interface(i: &Interface) -> String {
 	};
 
 	markdown.line("```wing");
-	markdown.line(format!("interface {}{extends}", i.name));
+	markdown.line(format!(
+		"interface {}{extends}",
+		render_generic_name(&i.name.name, i.type_parameters.as_ref(), i.generic_instance.as_ref())
+	));
 	markdown.line("```");
 	markdown.line("---");
 
@@ -303,17 +313,26 @@ fn render_interface(i: &Interface) -> String {
 		markdown.line(s);
 	}
 
-	if i.env.iter(true).next().is_some() {
-		markdown.line("### Methods");
-	}
+	let (methods, fields): (Vec<_>, Vec<_>) = i
+		.env
+		.iter(true)
+		.partition(|prop| prop.1.as_variable().unwrap().type_.as_function_sig().is_some());
 
-	for prop in i.env.iter(true) {
-		let prop_docs = prop.1.as_variable().and_then(|v| v.docs.and_then(|d| d.summary));
-		markdown.line(&format!(
-			"- `{}` — {}\n",
-			prop.0,
-			prop_docs.unwrap_or(format!("`{}`", prop.1.as_variable().unwrap().type_))
-		));
+	for (title, members) in [("### Properties", fields), ("### Methods", methods)] {
+		if members.is_empty() {
+			continue;
+		}
+
+		markdown.line(title);
+
+		for prop in members {
+			let prop_docs = prop.1.as_variable().and_then(|v| v.docs.and_then(|d| d.summary));
+			markdown.line(&format!(
+				"- `{}` — {}\n",
+				prop.0,
+				prop_docs.unwrap_or(format!("`{}`", prop.1.as_variable().unwrap().type_))
+			));
+		}
 	}
 
 	markdown.empty_line();
@@ -376,7 +395,12 @@ fn render_class(c: &Class) -> String {
 		String::default()
 	};
 
-	markdown.line(format!("class {}{}{}", c.name, extends, implements));
+	markdown.line(format!(
+		"class {}{}{}",
+		render_generic_name(&c.name.name, c.generic_type_parameters(), c.generic_instance.as_ref()),
+		extends,
+		implements
+	));
 
 	markdown.line("```");
 	markdown.line("---");
@@ -394,6 +418,44 @@ fn render_class(c: &Class) -> String {
 	markdown.to_string().trim().to_string()
 }
 
+fn render_type_parameter(p: &TypeParameter) -> String {
+	let mut markdown = CodeMaker::default();
+
+	markdown.line("```wing");
+	markdown.line(format_type_parameter(p));
+	markdown.line("```");
+
+	markdown.to_string().trim().to_string()
+}
+
+fn format_type_parameter(p: &TypeParameter) -> String {
+	if let Some(bound) = &p.bound {
+		format!("{} extends {}", p.name, bound)
+	} else {
+		p.name.name.clone()
+	}
+}
+
+/// Renders the name of a generic definition with its type parameters (e.g. `Cache<T extends IValue>`), or of a
+/// generic instance with its type arguments (e.g. `Cache<str>`)
+fn render_generic_name(
+	name: &str,
+	type_parameters: Option<&Vec<TypeRef>>,
+	generic_instance: Option<&GenericInstance>,
+) -> String {
+	if let Some(instance) = generic_instance {
+		format!("{}<{}>", name, instance.type_arguments.iter().join(", "))
+	} else if let Some(params) = type_parameters.filter(|params| !params.is_empty()) {
+		let params = params
+			.iter()
+			.map(|p| p.as_type_parameter().map_or(p.to_string(), format_type_parameter))
+			.join(", ");
+		format!("{}<{}>", name, params)
+	} else {
+		name.to_string()
+	}
+}
+
 fn render_typeref(typeref: &Option<TypeRef>) -> Option<String> {
 	let Some(t) = typeref else {
 		return None;
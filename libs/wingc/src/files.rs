@@ -7,7 +7,13 @@ use std::{
 	path::{Path, PathBuf},
 };
 
-use crate::diagnostic::Diagnostic;
+use crate::{
+	diagnostic::{Diagnostic, DiagnosticCode, DiagnosticSeverity},
+	source_map::SourceMap,
+};
+
+/// Extension of the source map files written next to the files that have a source map
+const SOURCE_MAP_EXTENSION: &str = ".map";
 
 #[derive(Debug)]
 pub enum FilesError {
@@ -29,6 +35,10 @@ impl From<FilesError> for Diagnostic {
 		Self {
 			message: err.to_string(),
 			span: None,
+			severity: DiagnosticSeverity::Error,
+			code: DiagnosticCode::OutputFileError,
+			annotations: vec![],
+			fixes: vec![],
 		}
 	}
 }
@@ -38,11 +48,15 @@ impl Error for FilesError {}
 #[derive(Default)]
 pub struct Files {
 	data: HashMap<PathBuf, String>,
+	source_maps: HashMap<PathBuf, SourceMap>,
 }
 
 impl Files {
 	pub fn new() -> Self {
-		Self { data: HashMap::new() }
+		Self {
+			data: HashMap::new(),
+			source_maps: HashMap::new(),
+		}
 	}
 
 	pub fn add_file<S: Into<PathBuf>>(&mut self, path: S, content: String) -> Result<(), FilesError> {
@@ -54,10 +68,38 @@ impl Files {
 		Ok(())
 	}
 
+	/// Adds a generated JS file along with a source map pointing back to the code it was generated from. The
+	/// source map is emitted next to the file (as `<file>.map`).
+	pub fn add_file_with_source_map<S: Into<PathBuf>>(
+		&mut self,
+		path: S,
+		content: String,
+		source_map: SourceMap,
+	) -> Result<(), FilesError> {
+		let path = path.into();
+		self.add_file(path.clone(), content)?;
+		if !source_map.is_empty() {
+			self.source_maps.insert(path, source_map);
+		}
+		Ok(())
+	}
+
 	pub fn get_file<S: AsRef<Path>>(&self, path: S) -> Option<&String> {
 		self.data.get(path.as_ref())
 	}
 
+	/// Moves all the files from `other` into this collection, failing on the first file that already exists.
+	pub fn add_files(&mut self, other: Files) -> Result<(), FilesError> {
+		let mut source_maps = other.source_maps;
+		for (path, content) in other.data {
+			match source_maps.remove(&path) {
+				Some(source_map) => self.add_file_with_source_map(path, content, source_map)?,
+				None => self.add_file(path, content)?,
+			}
+		}
+		Ok(())
+	}
+
 	pub fn emit_files(&self, out_dir: &Path) -> Result<(), FilesError> {
 		for (path, content) in &self.data {
 			let full_path = out_dir.join(path);
@@ -67,17 +109,35 @@ impl Files {
 				fs::create_dir_all(parent).map_err(FilesError::IoError)?;
 			}
 
-			let mut file = File::create(full_path).map_err(FilesError::IoError)?;
-			file.write_all(content.as_bytes()).map_err(FilesError::IoError)?;
-			file.flush().map_err(FilesError::IoError)?;
+			let Some(source_map) = self.source_maps.get(path) else {
+				write_file(&full_path, content)?;
+				continue;
+			};
+
+			let file_name = full_path
+				.file_name()
+				.map(|name| name.to_string_lossy().to_string())
+				.unwrap_or_default();
+			let map_file_name = format!("{file_name}{SOURCE_MAP_EXTENSION}");
+			let map_dir = full_path.parent().unwrap_or(out_dir);
+
+			write_file(&full_path, &format!("{content}//# sourceMappingURL={map_file_name}\n"))?;
+			write_file(&map_dir.join(&map_file_name), &source_map.to_json(&file_name, map_dir))?;
 		}
 		Ok(())
 	}
 }
 
+fn write_file(path: &Path, content: &str) -> Result<(), FilesError> {
+	let mut file = File::create(path).map_err(FilesError::IoError)?;
+	file.write_all(content.as_bytes()).map_err(FilesError::IoError)?;
+	file.flush().map_err(FilesError::IoError)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::diagnostic::{WingLocation, WingSpan};
 
 	#[test]
 	fn test_add_file() {
@@ -88,6 +148,28 @@ mod tests {
 		assert!(files.add_file("file1", "content3".to_owned()).is_err());
 	}
 
+	#[test]
+	fn test_add_files() {
+		let mut files = Files::new();
+		files
+			.add_file("file1", "content1".to_owned())
+			.expect("Failed to add file");
+
+		let mut other = Files::new();
+		other
+			.add_file("file2", "content2".to_owned())
+			.expect("Failed to add file");
+		assert!(files.add_files(other).is_ok());
+		assert_eq!(files.get_file("file2"), Some(&"content2".to_owned()));
+
+		// Adding a file that already exists should return an error
+		let mut duplicate = Files::new();
+		duplicate
+			.add_file("file1", "content3".to_owned())
+			.expect("Failed to add file");
+		assert!(files.add_files(duplicate).is_err());
+	}
+
 	#[test]
 	fn test_emit_files() {
 		let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
@@ -134,4 +216,39 @@ mod tests {
 		let file1_content = fs::read_to_string(file1_path).expect("Failed to read file");
 		assert_eq!(file1_content, "content1");
 	}
+
+	#[test]
+	fn test_emit_files_with_source_map() {
+		let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+		let out_dir = temp_dir.path();
+
+		let mut source_map = SourceMap::default();
+		source_map.add_mapping(
+			0,
+			0,
+			&WingSpan {
+				start: WingLocation { line: 2, col: 0 },
+				end: WingLocation { line: 2, col: 10 },
+				file_id: out_dir.join("main.w").to_string_lossy().to_string(),
+			},
+		);
+
+		let mut files = Files::new();
+		files
+			.add_file_with_source_map("subdir/file1.js", "content1\n".to_owned(), source_map)
+			.expect("Failed to add file");
+
+		assert!(files.emit_files(out_dir).is_ok());
+
+		// The file should reference its source map, which is written next to it
+		let file1_content = fs::read_to_string(out_dir.join("subdir").join("file1.js")).expect("Failed to read file");
+		assert_eq!(file1_content, "content1\n//# sourceMappingURL=file1.js.map\n");
+
+		let map_content =
+			fs::read_to_string(out_dir.join("subdir").join("file1.js.map")).expect("Failed to read source map");
+		let map: serde_json::Value = serde_json::from_str(&map_content).expect("Invalid source map");
+		assert_eq!(map["file"], "file1.js");
+		assert_eq!(map["sources"], serde_json::json!(["../main.w"]));
+		assert_eq!(map["mappings"], "AAEA");
+	}
 }
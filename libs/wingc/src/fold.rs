@@ -1,8 +1,9 @@
 use crate::{
 	ast::{
 		ArgList, CatchBlock, Class, ClassField, ElifBlock, Expr, ExprKind, FunctionBody, FunctionDefinition,
-		FunctionParameter, FunctionSignature, Interface, InterpolatedString, InterpolatedStringPart, Literal, NewExpr,
-		Reference, Scope, Stmt, StmtKind, StructField, Symbol, TypeAnnotation, TypeAnnotationKind, UserDefinedType,
+		FunctionParameter, FunctionSignature, Interface, InterfaceField, InterpolatedString, InterpolatedStringPart,
+		Literal, MatchArm, MatchExprArm, NewExpr, Reference, Scope, Stmt, StmtKind, StructField, Symbol, TypeAnnotation,
+		TypeAnnotationKind, TypeParameter, UserDefinedType,
 	},
 	dbg_panic,
 };
@@ -26,6 +27,9 @@ pub trait Fold {
 	fn fold_struct_field(&mut self, node: StructField) -> StructField {
 		fold_struct_field(self, node)
 	}
+	fn fold_interface_field(&mut self, node: InterfaceField) -> InterfaceField {
+		fold_interface_field(self, node)
+	}
 	fn fold_interface(&mut self, node: Interface) -> Interface {
 		fold_interface(self, node)
 	}
@@ -59,6 +63,9 @@ pub trait Fold {
 	fn fold_user_defined_type(&mut self, node: UserDefinedType) -> UserDefinedType {
 		fold_user_defined_type(self, node)
 	}
+	fn fold_type_parameter(&mut self, node: TypeParameter) -> TypeParameter {
+		fold_type_parameter(self, node)
+	}
 	fn fold_symbol(&mut self, node: Symbol) -> Symbol {
 		fold_symbol(self, node)
 	}
@@ -139,6 +146,21 @@ where
 				.collect(),
 			else_statements: else_statements.map(|statements| f.fold_scope(statements)),
 		},
+		StmtKind::Match {
+			value,
+			arms,
+			else_statements,
+		} => StmtKind::Match {
+			value: f.fold_expr(value),
+			arms: arms
+				.into_iter()
+				.map(|arm| MatchArm {
+					patterns: arm.patterns.into_iter().map(|pattern| f.fold_expr(pattern)).collect(),
+					statements: f.fold_scope(arm.statements),
+				})
+				.collect(),
+			else_statements: else_statements.map(|statements| f.fold_scope(statements)),
+		},
 		StmtKind::Break => StmtKind::Break,
 		StmtKind::Continue => StmtKind::Continue,
 		StmtKind::Return(value) => StmtKind::Return(value.map(|value| f.fold_expr(value))),
@@ -150,8 +172,14 @@ where
 		StmtKind::Scope(scope) => StmtKind::Scope(f.fold_scope(scope)),
 		StmtKind::Class(class) => StmtKind::Class(f.fold_class(class)),
 		StmtKind::Interface(interface) => StmtKind::Interface(f.fold_interface(interface)),
-		StmtKind::Struct { name, extends, fields } => StmtKind::Struct {
+		StmtKind::Struct {
+			name,
+			type_parameters,
+			extends,
+			fields,
+		} => StmtKind::Struct {
 			name: f.fold_symbol(name),
+			type_parameters: type_parameters.into_iter().map(|p| f.fold_type_parameter(p)).collect(),
 			extends: extends.into_iter().map(|e| f.fold_user_defined_type(e)).collect(),
 			fields: fields.into_iter().map(|field| f.fold_struct_field(field)).collect(),
 		},
@@ -189,6 +217,11 @@ where
 {
 	Class {
 		name: f.fold_symbol(node.name),
+		type_parameters: node
+			.type_parameters
+			.into_iter()
+			.map(|p| f.fold_type_parameter(p))
+			.collect(),
 		fields: node.fields.into_iter().map(|field| f.fold_class_field(field)).collect(),
 		methods: node
 			.methods
@@ -217,6 +250,20 @@ where
 		reassignable: node.reassignable,
 		phase: node.phase,
 		is_static: node.is_static,
+		access_modifier: node.access_modifier,
+		initializer: node.initializer.map(|initializer| f.fold_expr(initializer)),
+	}
+}
+
+pub fn fold_interface_field<F>(f: &mut F, node: InterfaceField) -> InterfaceField
+where
+	F: Fold + ?Sized,
+{
+	InterfaceField {
+		name: f.fold_symbol(node.name),
+		member_type: f.fold_type_annotation(node.member_type),
+		reassignable: node.reassignable,
+		phase: node.phase,
 	}
 }
 
@@ -236,6 +283,16 @@ where
 {
 	Interface {
 		name: f.fold_symbol(node.name),
+		type_parameters: node
+			.type_parameters
+			.into_iter()
+			.map(|p| f.fold_type_parameter(p))
+			.collect(),
+		fields: node
+			.fields
+			.into_iter()
+			.map(|field| f.fold_interface_field(field))
+			.collect(),
 		methods: node
 			.methods
 			.into_iter()
@@ -308,6 +365,27 @@ where
 			element: Box::new(f.fold_expr(*element)),
 		},
 		ExprKind::FunctionClosure(def) => ExprKind::FunctionClosure(f.fold_function_definition(def)),
+		ExprKind::Await(exp) => ExprKind::Await(Box::new(f.fold_expr(*exp))),
+		ExprKind::Defer(exp) => ExprKind::Defer(Box::new(f.fold_expr(*exp))),
+		ExprKind::TypeTest { expr, type_ } => ExprKind::TypeTest {
+			expr: Box::new(f.fold_expr(*expr)),
+			type_: f.fold_type_annotation(type_),
+		},
+		ExprKind::Match {
+			value,
+			arms,
+			else_value,
+		} => ExprKind::Match {
+			value: Box::new(f.fold_expr(*value)),
+			arms: arms
+				.into_iter()
+				.map(|arm| MatchExprArm {
+					patterns: arm.patterns.into_iter().map(|pattern| f.fold_expr(pattern)).collect(),
+					value: f.fold_expr(arm.value),
+				})
+				.collect(),
+			else_value: else_value.map(|else_value| Box::new(f.fold_expr(*else_value))),
+		},
 		ExprKind::CompilerDebugPanic => {
 			dbg_panic!(); // Handle the debug panic expression (during folding)
 			ExprKind::CompilerDebugPanic
@@ -374,6 +452,10 @@ where
 			typeobject: Box::new(f.fold_expr(*typeobject)),
 			property: f.fold_symbol(property),
 		},
+		Reference::ElementAccess { object, index } => Reference::ElementAccess {
+			object: Box::new(f.fold_expr(*object)),
+			index: Box::new(f.fold_expr(*index)),
+		},
 	}
 }
 
@@ -389,6 +471,7 @@ where
 		},
 		signature: f.fold_function_signature(node.signature),
 		is_static: node.is_static,
+		access_modifier: node.access_modifier,
 		span: node.span,
 	}
 }
@@ -398,6 +481,11 @@ where
 	F: Fold + ?Sized,
 {
 	FunctionSignature {
+		type_parameters: node
+			.type_parameters
+			.into_iter()
+			.map(|p| f.fold_type_parameter(p))
+			.collect(),
 		parameters: node
 			.parameters
 			.into_iter()
@@ -416,6 +504,7 @@ where
 		name: f.fold_symbol(node.name),
 		type_annotation: f.fold_type_annotation(node.type_annotation),
 		reassignable: node.reassignable,
+		variadic: node.variadic,
 	}
 }
 
@@ -443,6 +532,7 @@ where
 		TypeAnnotationKind::String => TypeAnnotationKind::String,
 		TypeAnnotationKind::Bool => TypeAnnotationKind::Bool,
 		TypeAnnotationKind::Duration => TypeAnnotationKind::Duration,
+		TypeAnnotationKind::Datetime => TypeAnnotationKind::Datetime,
 		TypeAnnotationKind::Void => TypeAnnotationKind::Void,
 		TypeAnnotationKind::Json => TypeAnnotationKind::Json,
 		TypeAnnotationKind::MutJson => TypeAnnotationKind::MutJson,
@@ -453,7 +543,16 @@ where
 		TypeAnnotationKind::MutMap(t) => TypeAnnotationKind::MutMap(Box::new(f.fold_type_annotation(*t))),
 		TypeAnnotationKind::Set(t) => TypeAnnotationKind::Set(Box::new(f.fold_type_annotation(*t))),
 		TypeAnnotationKind::MutSet(t) => TypeAnnotationKind::MutSet(Box::new(f.fold_type_annotation(*t))),
+		TypeAnnotationKind::Promise(t) => TypeAnnotationKind::Promise(Box::new(f.fold_type_annotation(*t))),
+		TypeAnnotationKind::Union(types) => {
+			TypeAnnotationKind::Union(types.into_iter().map(|t| f.fold_type_annotation(t)).collect())
+		}
 		TypeAnnotationKind::Function(t) => TypeAnnotationKind::Function(FunctionSignature {
+			type_parameters: t
+				.type_parameters
+				.into_iter()
+				.map(|p| f.fold_type_parameter(p))
+				.collect(),
 			parameters: t.parameters.into_iter().map(|p| f.fold_function_parameter(p)).collect(),
 			return_type: Box::new(f.fold_type_annotation(*t.return_type)),
 			phase: t.phase,
@@ -471,10 +570,25 @@ where
 	UserDefinedType {
 		root: f.fold_symbol(node.root),
 		fields: node.fields.into_iter().map(|s| f.fold_symbol(s)).collect(),
+		type_arguments: node
+			.type_arguments
+			.into_iter()
+			.map(|t| f.fold_type_annotation(t))
+			.collect(),
 		span: node.span,
 	}
 }
 
+pub fn fold_type_parameter<F>(f: &mut F, node: TypeParameter) -> TypeParameter
+where
+	F: Fold + ?Sized,
+{
+	TypeParameter {
+		name: f.fold_symbol(node.name),
+		bound: node.bound.map(|bound| f.fold_user_defined_type(bound)),
+	}
+}
+
 pub fn fold_symbol<F>(_f: &mut F, node: Symbol) -> Symbol
 where
 	F: Fold + ?Sized,
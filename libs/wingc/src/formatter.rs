@@ -0,0 +1,695 @@
+//! An opinionated formatter for Wing source code.
+//!
+//! The formatter works off the concrete syntax tree (rather than the AST), so it keeps every token of the source,
+//! including comments, and only changes the whitespace between them:
+//! - Statements and class, interface and struct members are placed on their own lines, indented by two spaces per
+//!   level of nesting. At most one blank line is kept between them.
+//! - Blocks always span multiple lines (unless they are empty), with `} else {` style braces.
+//! - Lists (arguments, parameters, collection and struct literals, enums and the arms of `match` expressions) are
+//!   kept on a single line, unless there is a line break right after their opening bracket or they contain comments.
+//!   Multi-line lists have one item per line and a trailing comma.
+//! - Tokens on the same line are separated by a single space, except around punctuation like `.`, `(` and `:`.
+//!
+//! Code with syntax errors isn't formatted, since it isn't clear which tokens belong together.
+
+use tree_sitter::{Node, Tree};
+use tree_sitter_traversal::{traverse, Order};
+
+const INDENT: &str = "  ";
+
+/// Nodes whose source text is kept as is, even though they have child nodes
+const ATOMIC_KINDS: &[&str] = &["string", "number", "comment", "inclusive_range"];
+
+/// Nodes that are placed on their own lines
+const STATEMENT_KINDS: &[&str] = &[
+	"test_statement",
+	"short_import_statement",
+	"expression_statement",
+	"variable_definition_statement",
+	"variable_assignment_statement",
+	"return_statement",
+	"class_definition",
+	"resource_definition",
+	"interface_definition",
+	"for_in_loop",
+	"while_statement",
+	"break_statement",
+	"continue_statement",
+	"if_statement",
+	"if_let_statement",
+	"match_statement",
+	"match_arm",
+	"struct_definition",
+	"enum_definition",
+	"try_catch_statement",
+	"compiler_dbg_env",
+	"super_constructor_statement",
+	"initializer",
+	"method_definition",
+	"inflight_method_definition",
+	"method_signature",
+	"inflight_method_signature",
+	"class_field",
+	"struct_field",
+];
+
+const DURATION_KINDS: &[&str] = &["milliseconds", "seconds", "minutes", "hours", "days", "months", "years"];
+
+/// Formats the source code of a Wing file, given its syntax tree.
+/// Returns `None` if the source has syntax errors.
+pub fn format(source: &str, tree: &Tree) -> Option<String> {
+	let root = tree.root_node();
+	if has_syntax_errors(&root) {
+		return None;
+	}
+
+	let mut tokens = vec![];
+	collect_tokens(root, source, &mut tokens);
+
+	let mut formatter = Formatter {
+		tokens,
+		output: String::new(),
+		indent: 0,
+		brackets: vec![],
+		last_code_token: None,
+	};
+	formatter.format();
+	Some(formatter.output)
+}
+
+/// Parses and formats the source code of a Wing file.
+/// Returns `None` if the source has syntax errors.
+pub fn format_source(source: &str) -> Option<String> {
+	let mut parser = tree_sitter::Parser::new();
+	parser.set_language(tree_sitter_wing::language()).unwrap();
+	let tree = parser.parse(source, None)?;
+	format(source, &tree)
+}
+
+fn has_syntax_errors(root: &Node) -> bool {
+	// Automatic semicolons and blocks are inserted by the scanner to recover from missing tokens, they aren't
+	// errors as far as tree-sitter is concerned
+	root.has_error()
+		|| traverse(root.walk(), Order::Pre)
+			.any(|node| node.kind() == "AUTOMATIC_SEMICOLON" || node.kind() == "AUTOMATIC_BLOCK")
+}
+
+fn collect_tokens<'a>(node: Node<'a>, source: &'a str, tokens: &mut Vec<Token<'a>>) {
+	if node.child_count() == 0 || ATOMIC_KINDS.contains(&node.kind()) {
+		let text = &source[node.byte_range()];
+		tokens.push(Token {
+			node,
+			text: if node.kind() == "comment" {
+				text.trim_end()
+			} else {
+				text
+			},
+		});
+		return;
+	}
+
+	// Hidden tokens (e.g. the `super` of a super constructor call) don't have nodes of their own, so their text
+	// is attributed to the parent node
+	let mut end = node.start_byte();
+	let mut cursor = node.walk();
+	for child in node.children(&mut cursor) {
+		push_hidden_token(node, &source[end..child.start_byte()], tokens);
+		collect_tokens(child, source, tokens);
+		end = child.end_byte();
+	}
+	push_hidden_token(node, &source[end..node.end_byte()], tokens);
+}
+
+fn push_hidden_token<'a>(node: Node<'a>, text: &'a str, tokens: &mut Vec<Token<'a>>) {
+	let text = text.trim();
+	if !text.is_empty() {
+		tokens.push(Token { node, text });
+	}
+}
+
+struct Token<'a> {
+	node: Node<'a>,
+	text: &'a str,
+}
+
+impl Token<'_> {
+	fn kind(&self) -> &str {
+		self.node.kind()
+	}
+
+	fn parent_kind(&self) -> &str {
+		self.node.parent().map_or("", |parent| parent.kind())
+	}
+
+	fn is_comment(&self) -> bool {
+		self.kind() == "comment"
+	}
+
+	fn is_line_comment(&self) -> bool {
+		self.is_comment() && self.text.starts_with("//")
+	}
+
+	/// Whether this is the last token of a statement or a member
+	fn ends_statement(&self) -> bool {
+		let mut node = self.node;
+		while let Some(parent) = node.parent() {
+			if parent.end_byte() != self.node.end_byte() {
+				return false;
+			}
+			if STATEMENT_KINDS.contains(&parent.kind()) {
+				return true;
+			}
+			node = parent;
+		}
+		false
+	}
+
+	/// Whether this token is the operator of a unary expression (e.g. the `-` in `-x`)
+	fn is_unary_operator(&self) -> bool {
+		self.parent_kind() == "unary_expression" && self.node.prev_sibling().is_none()
+	}
+
+	/// Whether this token is a `<` or `>` around the type parameter of a container type (e.g. `Array<str>`) or around
+	/// the type parameters or type arguments of a generic type or function (e.g. `Cache<T>`)
+	fn is_type_parameter_bracket(&self) -> bool {
+		matches!(self.kind(), "<" | ">")
+			&& matches!(
+				self.parent_kind(),
+				"immutable_container_type" | "mutable_container_type" | "type_parameter_list" | "type_argument_list"
+			)
+	}
+
+	/// Whether this token is the `<` of the type parameters of a generic closure (e.g. `<T>(x: T) => {}`)
+	fn is_closure_type_parameters_start(&self) -> bool {
+		self.kind() == "<"
+			&& self
+				.node
+				.parent()
+				.and_then(|parent| parent.parent())
+				.map_or(false, |grandparent| {
+					matches!(grandparent.kind(), "preflight_closure" | "inflight_closure")
+				})
+	}
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum BracketsStyle {
+	/// Brackets around statements or members, which always span multiple lines (unless they're empty)
+	Block,
+	/// Brackets around a comma separated list, which only span multiple lines if they did in the source
+	List,
+}
+
+/// A pair of brackets the formatter is currently inside of
+struct Brackets {
+	open_id: usize,
+	close_id: usize,
+	/// The node the brackets belong to (e.g. an `argument_list`)
+	parent_id: usize,
+	style: BracketsStyle,
+	multiline: bool,
+	/// Whether the contents are separated from the brackets by a space when on a single line (e.g. `{ a: 1 }`)
+	padded: bool,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Separator {
+	None,
+	Space,
+	Newline,
+	BlankLine,
+}
+
+struct Formatter<'a> {
+	tokens: Vec<Token<'a>>,
+	output: String,
+	indent: usize,
+	brackets: Vec<Brackets>,
+	/// The last token written that isn't a comment: its node id, whether it's a comma and the output offset after it
+	last_code_token: Option<(usize, bool, usize)>,
+}
+
+impl<'a> Formatter<'a> {
+	fn format(&mut self) {
+		let mut prev: Option<usize> = None;
+		for i in 0..self.tokens.len() {
+			let token = &self.tokens[i];
+
+			if self.is_redundant_comma(i) {
+				continue;
+			}
+
+			let separator = match prev {
+				Some(prev) => self.separator(&self.tokens[prev], token),
+				None => Separator::None,
+			};
+
+			let closes_brackets = self.brackets.last().map_or(false, |b| b.close_id == token.node.id());
+			if closes_brackets {
+				let brackets = self.brackets.pop().unwrap();
+				if brackets.multiline {
+					self.indent -= 1;
+					if brackets.style == BracketsStyle::List {
+						self.add_trailing_comma(&brackets);
+					}
+				}
+			}
+
+			self.write(separator, i);
+
+			if let Some(brackets) = self.open_brackets(i) {
+				if brackets.multiline {
+					self.indent += 1;
+				}
+				self.brackets.push(brackets);
+			}
+			prev = Some(i);
+		}
+
+		if !self.output.is_empty() {
+			self.output.push('\n');
+		}
+	}
+
+	/// Returns the brackets opened by the token at `index`, if it's an opening bracket of a block or a list
+	fn open_brackets(&self, index: usize) -> Option<Brackets> {
+		let token = &self.tokens[index];
+		let parent = token.node.parent()?;
+		let style = match (token.kind(), parent.kind()) {
+			(
+				"{",
+				"block"
+				| "class_implementation"
+				| "resource_implementation"
+				| "interface_implementation"
+				| "struct_definition"
+				| "match_statement",
+			) => BracketsStyle::Block,
+			(
+				"{",
+				"enum_definition" | "set_literal" | "map_literal" | "struct_literal" | "json_map_literal" | "match_expression",
+			)
+			| ("(", "argument_list" | "parameter_list" | "parameter_type_list")
+			| ("[", "array_literal") => BracketsStyle::List,
+			_ => return None,
+		};
+
+		let close = parent.child(parent.child_count() - 1)?;
+		let close_index = index + self.tokens[index..].iter().position(|t| t.node.id() == close.id())?;
+		let is_empty = close_index == index + 1;
+		let multiline = !is_empty
+			&& match style {
+				BracketsStyle::Block => true,
+				BracketsStyle::List => {
+					let next = &self.tokens[index + 1];
+					next.node.start_position().row > token.node.end_position().row
+						|| self.tokens[index..close_index].iter().any(Token::is_comment)
+				}
+			};
+
+		Some(Brackets {
+			open_id: token.node.id(),
+			close_id: close.id(),
+			parent_id: parent.id(),
+			style,
+			multiline,
+			padded: token.kind() == "{",
+		})
+	}
+
+	/// Whether the token at `index` is a trailing comma in a list that is formatted on a single line
+	fn is_redundant_comma(&self, index: usize) -> bool {
+		let token = &self.tokens[index];
+		let Some(brackets) = self.brackets.last() else {
+			return false;
+		};
+		token.kind() == ","
+			&& !brackets.multiline
+			&& token.node.parent().map(|p| p.id()) == Some(brackets.parent_id)
+			&& self.tokens.get(index + 1).map(|t| t.node.id()) == Some(brackets.close_id)
+	}
+
+	/// Adds a comma after the last item of a multi-line list, unless it already has one
+	fn add_trailing_comma(&mut self, brackets: &Brackets) {
+		let Some((last_id, last_is_comma, offset)) = self.last_code_token else {
+			return;
+		};
+		if last_id != brackets.open_id && !last_is_comma {
+			self.output.insert(offset, ',');
+		}
+	}
+
+	/// Returns the whitespace to write between two consecutive tokens
+	fn separator(&self, prev: &Token, token: &Token) -> Separator {
+		let same_line = token.node.start_position().row == prev.node.end_position().row;
+
+		if let Some(brackets) = self.brackets.last() {
+			if brackets.close_id == token.node.id() {
+				return if brackets.multiline {
+					Separator::Newline
+				} else if brackets.open_id == prev.node.id() || !brackets.padded {
+					Separator::None
+				} else {
+					Separator::Space
+				};
+			}
+			if brackets.open_id == prev.node.id() {
+				return if brackets.multiline {
+					if token.is_comment() && same_line {
+						Separator::Space
+					} else {
+						Separator::Newline
+					}
+				} else if brackets.padded {
+					Separator::Space
+				} else {
+					Separator::None
+				};
+			}
+		}
+
+		if prev.is_line_comment() {
+			return line_break(prev, token);
+		}
+		if token.is_comment() || prev.is_comment() {
+			return if same_line {
+				Separator::Space
+			} else {
+				line_break(prev, token)
+			};
+		}
+
+		if let Some(brackets) = self.brackets.last() {
+			if brackets.multiline && prev.kind() == "," && prev.node.parent().map(|p| p.id()) == Some(brackets.parent_id) {
+				return line_break(prev, token);
+			}
+		}
+		if prev.ends_statement() {
+			return line_break(prev, token);
+		}
+
+		if no_space_between(prev, token) {
+			Separator::None
+		} else {
+			Separator::Space
+		}
+	}
+
+	fn write(&mut self, separator: Separator, index: usize) {
+		if !self.output.is_empty() {
+			match separator {
+				Separator::None => {}
+				Separator::Space => self.output.push(' '),
+				Separator::Newline => self.new_line(),
+				Separator::BlankLine => {
+					self.output.push('\n');
+					self.new_line();
+				}
+			}
+		}
+
+		let token = &self.tokens[index];
+		let (node, text) = (token.node, token.text);
+		if !token.is_comment() {
+			self.output.push_str(text);
+			self.last_code_token = Some((node.id(), node.kind() == ",", self.output.len()));
+		} else if token.is_line_comment() {
+			self.output.push_str(text);
+		} else {
+			self.write_block_comment(text, node.start_position().column);
+		}
+	}
+
+	fn new_line(&mut self) {
+		self.output.push('\n');
+		self.output.push_str(&INDENT.repeat(self.indent));
+	}
+
+	/// Writes a block comment that starts at `column` in the source, keeping the indentation of its lines relative
+	/// to the first one
+	fn write_block_comment(&mut self, text: &str, column: usize) {
+		let mut lines = text.split('\n');
+		self.output.push_str(lines.next().unwrap_or_default().trim_end());
+		for line in lines {
+			let line = line.trim_end();
+			let original_indent = line
+				.bytes()
+				.take(column)
+				.take_while(|b| *b == b' ' || *b == b'\t')
+				.count();
+			self.output.push('\n');
+			if !line.is_empty() {
+				self.output.push_str(&INDENT.repeat(self.indent));
+				self.output.push_str(&line[original_indent..]);
+			}
+		}
+	}
+}
+
+/// Returns a line break between two tokens, keeping (at most) one blank line if there was one in the source
+fn line_break(prev: &Token, token: &Token) -> Separator {
+	if token.node.start_position().row > prev.node.end_position().row + 1 {
+		Separator::BlankLine
+	} else {
+		Separator::Newline
+	}
+}
+
+/// Whether two tokens on the same line are written without a space between them
+fn no_space_between(prev: &Token, token: &Token) -> bool {
+	match token.kind() {
+		"," | ";" | ")" | "]" | "." | "?." | ":" | "?" | ".." | "inclusive_range" => return true,
+		// Calls and function definitions, but not closures (e.g. `inflight (x) => {}`)
+		"(" if token.parent_kind() == "argument_list" => return true,
+		"(" if token.parent_kind() == "parameter_list" => {
+			let is_closure = token
+				.node
+				.parent()
+				.and_then(|parent| parent.parent())
+				.map_or(false, |grandparent| {
+					matches!(grandparent.kind(), "preflight_closure" | "inflight_closure")
+				});
+			// Generic closures have their parameters right after the type parameters (e.g. `<T>(x: T) => {}`)
+			if !is_closure || prev.is_type_parameter_bracket() {
+				return true;
+			}
+		}
+		"[" if token.parent_kind() == "structured_access_expression" => return true,
+		"[" if token.parent_kind() == "array_literal" && prev.is_type_parameter_bracket() => return true,
+		// Generic closures are spaced like other closures
+		_ if token.is_closure_type_parameters_start() => {}
+		_ if token.is_type_parameter_bracket() => return true,
+		// Duration units (e.g. `5s`)
+		_ if DURATION_KINDS.contains(&token.parent_kind()) && token.kind() != "number" => return true,
+		_ => {}
+	}
+
+	matches!(
+		prev.kind(),
+		"(" | "[" | "." | "?." | ".." | "inclusive_range" | "variadic"
+	) || (prev.kind() == "<" && prev.is_type_parameter_bracket())
+		|| prev.is_unary_operator()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::format_source;
+	use indoc::indoc;
+
+	fn assert_formatted(source: &str, expected: &str) {
+		let formatted = format_source(source).expect("Source has syntax errors");
+		assert_eq!(formatted, expected);
+		// Formatting is idempotent
+		assert_eq!(format_source(&formatted).as_deref(), Some(expected));
+	}
+
+	#[test]
+	fn formats_statements_and_blocks() {
+		assert_formatted(
+			indoc! {r#"
+				bring   cloud ;
+				let x:num=1+  2 ;  let   y = -x;
+
+
+				if x>y {log("yes");}
+				else{
+				    log( "no" ) ;
+				}
+				for i in 0..=10 { }
+			"#},
+			indoc! {r#"
+				bring cloud;
+				let x: num = 1 + 2;
+				let y = -x;
+
+				if x > y {
+				  log("yes");
+				} else {
+				  log("no");
+				}
+				for i in 0..=10 {}
+			"#},
+		);
+	}
+
+	#[test]
+	fn formats_classes_and_types() {
+		assert_formatted(
+			indoc! {r#"
+				struct Foo { a: str; b: Array<num>?; }
+				class Bar extends Baz {
+				    inflight field: MutMap < str >;
+
+
+				    init(x : num) { super ( x ); }
+				    inflight handle(f: inflight (str): void): duration { return 5 s; }
+				}
+			"#},
+			indoc! {r#"
+				struct Foo {
+				  a: str;
+				  b: Array<num>?;
+				}
+				class Bar extends Baz {
+				  inflight field: MutMap<str>;
+
+				  init(x: num) {
+				    super(x);
+				  }
+				  inflight handle(f: inflight (str): void): duration {
+				    return 5s;
+				  }
+				}
+			"#},
+		);
+	}
+
+	#[test]
+	fn formats_generics() {
+		assert_formatted(
+			indoc! {r#"
+				class Cache < K, V extends IValue > impl IStore< V > {
+				  get (key: K): V? { return nil; }
+				}
+				let c = new Cache< str, Array < num > >();
+				let first =  < T > (items: Array<T>): T => { return items.at(0); };
+			"#},
+			indoc! {r#"
+				class Cache<K, V extends IValue> impl IStore<V> {
+				  get(key: K): V? {
+				    return nil;
+				  }
+				}
+				let c = new Cache<str, Array<num>>();
+				let first = <T>(items: Array<T>): T => {
+				  return items.at(0);
+				};
+			"#},
+		);
+	}
+
+	#[test]
+	fn formats_variadic_parameters() {
+		assert_formatted(
+			indoc! {r#"
+				let join = (sep: str, ... parts :Array<str>): str => { return sep; };
+			"#},
+			indoc! {r#"
+				let join = (sep: str, ...parts: Array<str>): str => {
+				  return sep;
+				};
+			"#},
+		);
+	}
+
+	#[test]
+	fn formats_lists() {
+		assert_formatted(
+			indoc! {r#"
+				let a = MutArray<num>[ 1,2,3, ];
+				let b = Foo {x: 1, y: a[0]};
+				let c = {
+				  "a" => 1, "b" => 2
+				};
+				let f = inflight (x: num): num => { return x; };
+				foo(a,
+				  b);
+			"#},
+			indoc! {r#"
+				let a = MutArray<num>[1, 2, 3];
+				let b = Foo { x: 1, y: a[0] };
+				let c = {
+				  "a" => 1,
+				  "b" => 2,
+				};
+				let f = inflight (x: num): num => {
+				  return x;
+				};
+				foo(a, b);
+			"#},
+		);
+	}
+
+	#[test]
+	fn formats_match() {
+		assert_formatted(
+			indoc! {r#"
+				match c { Color.RED,Color.GREEN => { log("warm"); } else => {log("cold");} }
+				let x = match  c {Color.RED => 1,   else=>2,};
+				let y = match c {
+				  Color.RED => "r", Color.GREEN,Color.BLUE => "gb" };
+			"#},
+			indoc! {r#"
+				match c {
+				  Color.RED, Color.GREEN => {
+				    log("warm");
+				  }
+				  else => {
+				    log("cold");
+				  }
+				}
+				let x = match c { Color.RED => 1, else => 2 };
+				let y = match c {
+				  Color.RED => "r",
+				  Color.GREEN, Color.BLUE => "gb",
+				};
+			"#},
+		);
+	}
+
+	#[test]
+	fn preserves_comments() {
+		assert_formatted(
+			indoc! {r#"
+				// leading comment
+				let x = 1;   // trailing comment
+				    /* block
+				       comment */
+				let y = [1, // one
+				  2];
+				class Foo {
+				  // only a comment
+				}
+			"#},
+			indoc! {r#"
+				// leading comment
+				let x = 1; // trailing comment
+				/* block
+				   comment */
+				let y = [
+				  1, // one
+				  2,
+				];
+				class Foo {
+				  // only a comment
+				}
+			"#},
+		);
+	}
+
+	#[test]
+	fn does_not_format_syntax_errors() {
+		assert_eq!(format_source("let x = ;"), None);
+		assert_eq!(format_source("let x = 1"), None);
+	}
+}
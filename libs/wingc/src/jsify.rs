@@ -1,4 +1,5 @@
 pub mod codemaker;
+pub mod dts;
 mod tests;
 use aho_corasick::AhoCorasick;
 use const_format::formatcp;
@@ -13,18 +14,23 @@ use crate::{
 		InterpolatedStringPart, Literal, NewExpr, Phase, Reference, Scope, Stmt, StmtKind, Symbol, TypeAnnotationKind,
 		UnaryOperator, UserDefinedType,
 	},
+	closure_transform::CLOSURE_CLASS_PREFIX,
 	comp_ctx::{CompilationContext, CompilationPhase},
 	dbg_panic, debug,
-	diagnostic::{report_diagnostic, Diagnostic, WingSpan},
+	diagnostic::{report_diagnostic, Diagnostic, DiagnosticCode, DiagnosticSeverity, WingSpan},
 	files::Files,
+	modules::{is_wing_module, preflight_file_name, resolve_module_path},
 	type_check::{
-		lifts::Lifts, symbol_env::SymbolEnv, ClassLike, Type, TypeRef, Types, VariableKind, CLASS_INFLIGHT_INIT_NAME,
+		lifts::Lifts, symbol_env::SymbolEnv, Class, ClassLike, Type, TypeRef, Types, VariableKind, CLASS_INFLIGHT_INIT_NAME,
 	},
 	MACRO_REPLACE_ARGS, MACRO_REPLACE_ARGS_TEXT, MACRO_REPLACE_SELF, WINGSDK_ASSEMBLY_NAME, WINGSDK_RESOURCE,
 	WINGSDK_STD_MODULE,
 };
 
-use self::codemaker::CodeMaker;
+use self::{
+	codemaker::CodeMaker,
+	dts::{dts_file_name, DTSifier},
+};
 
 const PREFLIGHT_FILE_NAME: &str = "preflight.js";
 
@@ -35,6 +41,15 @@ const STDLIB_MODULE: &str = WINGSDK_ASSEMBLY_NAME;
 const ENV_WING_IS_TEST: &str = "$wing_is_test";
 const OUTDIR_VAR: &str = "$outdir";
 
+/// Reads an element of an array, throwing instead of returning `undefined` if the index is out of bounds (or isn't
+/// an integer)
+const ARRAY_ELEMENT_READ: &str =
+	"((arr, index) => { if (!Number.isInteger(index) || index < 0 || index >= arr.length) throw new Error(\"Index out of bounds\"); return arr[index]; })";
+/// Assigns an element of an array, throwing instead of growing the array (or adding a property to it) if the index
+/// is out of bounds (or isn't an integer)
+const ARRAY_ELEMENT_WRITE: &str =
+	"((arr, index, value) => { if (!Number.isInteger(index) || index < 0 || index >= arr.length) throw new Error(\"Index out of bounds\"); arr[index] = value; })";
+
 const ROOT_CLASS: &str = "$Root";
 const JS_CONSTRUCTOR: &str = "constructor";
 
@@ -46,6 +61,11 @@ pub struct JSifyContext<'a> {
 	pub phase: Phase,
 	pub files: &'a mut Files,
 	pub lifts: Option<&'a Lifts>,
+	/// Emits the declarations of the inflight modules (if they are emitted)
+	pub dtsifier: Option<&'a DTSifier<'a>>,
+	/// Whether we are emitting the initializer of a static field. Static fields are initialized synchronously
+	/// when the class is defined, so calls in their initializers are not awaited.
+	pub in_static_initializer: bool,
 }
 
 pub struct JSifier<'a> {
@@ -53,6 +73,8 @@ pub struct JSifier<'a> {
 	source_files: &'a Files,
 	/// Root of the project, used for resolving extern modules
 	absolute_project_root: &'a Path,
+	/// The file being compiled (as a `WingSpan::file_id`), which is emitted as `preflight.js`
+	entry_file: &'a str,
 	shim: bool,
 	app_name: &'a str,
 }
@@ -71,6 +93,7 @@ impl<'a> JSifier<'a> {
 		source_files: &'a Files,
 		app_name: &'a str,
 		absolute_project_root: &'a Path,
+		entry_file: &'a str,
 		shim: bool,
 	) -> Self {
 		Self {
@@ -79,39 +102,15 @@ impl<'a> JSifier<'a> {
 			shim,
 			app_name,
 			absolute_project_root,
+			entry_file,
 		}
 	}
 
 	pub fn jsify(&mut self, scope: &Scope) -> Files {
 		CompilationContext::set(CompilationPhase::Jsifying, &scope.span);
 		let mut files = Files::default();
-		let mut js = CodeMaker::default();
-		let mut imports = CodeMaker::default();
-
-		for statement in scope.statements.iter().sorted_by(|a, b| match (&a.kind, &b.kind) {
-			// Put type definitions first so JS won't complain of unknown types
-			(StmtKind::Class(AstClass { .. }), StmtKind::Class(AstClass { .. })) => Ordering::Equal,
-			(StmtKind::Class(AstClass { .. }), _) => Ordering::Less,
-			(_, StmtKind::Class(AstClass { .. })) => Ordering::Greater,
-			_ => Ordering::Equal,
-		}) {
-			let mut jsify_context = JSifyContext {
-				in_json: false,
-				phase: Phase::Preflight,
-				files: &mut files,
-				lifts: None,
-			};
-			let s = self.jsify_statement(scope.env.borrow().as_ref().unwrap(), statement, &mut jsify_context); // top level statements are always preflight
-			if let StmtKind::Bring {
-				identifier: _,
-				module_name: _,
-			} = statement.kind
-			{
-				imports.add_code(s);
-			} else {
-				js.add_code(s);
-			}
-		}
+		// Apps don't export anything, so no declarations are emitted for them
+		let (imports, js) = self.jsify_top_level_statements(scope, &mut files, None);
 
 		let mut output = CodeMaker::default();
 
@@ -147,20 +146,112 @@ impl<'a> JSifier<'a> {
 			output.add_code(js);
 		}
 
-		match files.add_file(PREFLIGHT_FILE_NAME, output.to_string()) {
+		match files.add_file_with_source_map(PREFLIGHT_FILE_NAME, output.to_string(), output.source_map()) {
+			Ok(()) => {}
+			Err(err) => report_diagnostic(err.into()),
+		}
+
+		files
+	}
+
+	/// Emits the JS module of a brought Wing module. The module exports all the classes and enums it defines.
+	pub fn jsify_module(&mut self, source_path: &Path, scope: &Scope) -> Files {
+		let preflight_file = preflight_file_name(source_path, self.absolute_project_root);
+		self.jsify_exports(scope, preflight_file)
+	}
+
+	/// Emits the preflight module of a library's entrypoint. Like a brought module, it exports all the classes and
+	/// enums it defines instead of synthesizing an app.
+	pub fn jsify_library(&mut self, scope: &Scope) -> Files {
+		self.jsify_exports(scope, PREFLIGHT_FILE_NAME.to_string())
+	}
+
+	/// Returns the JS module (named `preflight_file`) which exports the classes and enums defined in `scope`
+	fn jsify_exports(&mut self, scope: &Scope, preflight_file: String) -> Files {
+		CompilationContext::set(CompilationPhase::Jsifying, &scope.span);
+		let mut files = Files::default();
+		let dtsifier = DTSifier::new(&scope.span.file_id, &preflight_file, self.absolute_project_root);
+		let (imports, js) = self.jsify_top_level_statements(scope, &mut files, Some(&dtsifier));
+
+		let exports = scope
+			.statements
+			.iter()
+			.filter_map(|statement| match &statement.kind {
+				StmtKind::Class(class) => Some(class.name.name.clone()),
+				StmtKind::Enum { name, .. } => Some(name.name.clone()),
+				_ => None,
+			})
+			.join(", ");
+
+		let mut output = CodeMaker::default();
+		output.line(format!("const {} = require('{}');", STDLIB, STDLIB_MODULE));
+		output.line(format!("const std = {STDLIB}.{WINGSDK_STD_MODULE};"));
+		output.add_code(imports);
+		output.add_code(js);
+		output.line(format!("module.exports = {{ {exports} }};"));
+
+		match files.add_file_with_source_map(&preflight_file, output.to_string(), output.source_map()) {
 			Ok(()) => {}
 			Err(err) => report_diagnostic(err.into()),
 		}
+		if let Some(declarations) = dtsifier.dtsify_preflight(scope) {
+			match files.add_file(dts_file_name(&preflight_file), declarations.to_string()) {
+				Ok(()) => {}
+				Err(err) => report_diagnostic(err.into()),
+			}
+		}
 
 		files
 	}
 
+	/// Returns the code of the bring statements and the code of the rest of the top level statements of a file
+	fn jsify_top_level_statements(
+		&self,
+		scope: &Scope,
+		files: &mut Files,
+		dtsifier: Option<&DTSifier>,
+	) -> (CodeMaker, CodeMaker) {
+		let mut js = CodeMaker::default();
+		let mut imports = CodeMaker::default();
+
+		for statement in scope.statements.iter().sorted_by(|a, b| match (&a.kind, &b.kind) {
+			// Put type definitions first so JS won't complain of unknown types
+			(StmtKind::Class(AstClass { .. }), StmtKind::Class(AstClass { .. })) => Ordering::Equal,
+			(StmtKind::Class(AstClass { .. }), _) => Ordering::Less,
+			(_, StmtKind::Class(AstClass { .. })) => Ordering::Greater,
+			_ => Ordering::Equal,
+		}) {
+			let mut jsify_context = JSifyContext {
+				in_json: false,
+				phase: Phase::Preflight,
+				files,
+				lifts: None,
+				dtsifier,
+				in_static_initializer: false,
+			};
+			let mut s = self.jsify_statement(scope.env.borrow().as_ref().unwrap(), statement, &mut jsify_context); // top level statements are always preflight
+			s.set_origin(&statement.span);
+			if let StmtKind::Bring {
+				identifier: _,
+				module_name: _,
+			} = statement.kind
+			{
+				imports.add_code(s);
+			} else {
+				js.add_code(s);
+			}
+		}
+
+		(imports, js)
+	}
+
 	fn jsify_scope_body(&self, scope: &Scope, ctx: &mut JSifyContext) -> CodeMaker {
 		CompilationContext::set(CompilationPhase::Jsifying, &scope.span);
 		let mut code = CodeMaker::default();
 
 		for statement in scope.statements.iter() {
-			let statement_code = self.jsify_statement(scope.env.borrow().as_ref().unwrap(), statement, ctx);
+			let mut statement_code = self.jsify_statement(scope.env.borrow().as_ref().unwrap(), statement, ctx);
+			statement_code.set_origin(&statement.span);
 			code.add_code(statement_code);
 		}
 
@@ -180,6 +271,15 @@ impl<'a> JSifier<'a> {
 				let typename = self.jsify_expression(typeobject, ctx);
 				typename + "." + &property.to_string()
 			}
+			Reference::ElementAccess { object, index } => {
+				let js_object = self.jsify_expression(object, ctx);
+				let js_index = self.jsify_expression(index, ctx);
+				if matches!(*self.types.get_expr_type(object), Type::Array(_) | Type::MutArray(_)) {
+					format!("{ARRAY_ELEMENT_READ}({js_object}, {js_index})")
+				} else {
+					format!("({js_object})[{js_index}]")
+				}
+			}
 		}
 	}
 
@@ -231,6 +331,26 @@ impl<'a> JSifier<'a> {
 		udt.full_path_str()
 	}
 
+	/// Renders a JS condition which checks whether `value` is of the given type (see `ExprKind::TypeTest`)
+	fn jsify_type_test(&self, typ: &TypeAnnotationKind, value: &str) -> String {
+		match typ {
+			TypeAnnotationKind::Number => format!("typeof {value} === \"number\""),
+			TypeAnnotationKind::String => format!("typeof {value} === \"string\""),
+			TypeAnnotationKind::Bool => format!("typeof {value} === \"boolean\""),
+			TypeAnnotationKind::Datetime => format!("{value} instanceof Date"),
+			// durations are lifted to inflight code as plain objects, so we can't use `instanceof`
+			TypeAnnotationKind::Duration => {
+				format!("typeof {value} === \"object\" && {value} !== null && \"seconds\" in {value} && \"hours\" in {value}")
+			}
+			TypeAnnotationKind::UserDefined(t) => format!("{value} instanceof {}", self.jsify_user_defined_type(t)),
+			TypeAnnotationKind::Union(types) => types
+				.iter()
+				.map(|t| format!("({})", self.jsify_type_test(&t.kind, value)))
+				.join(" || "),
+			_ => panic!("Unexpected type in type test: {}", typ),
+		}
+	}
+
 	pub fn jsify_expression(&self, expression: &Expr, ctx: &mut JSifyContext) -> String {
 		CompilationContext::set(CompilationPhase::Jsifying, &expression.span);
 
@@ -254,6 +374,10 @@ impl<'a> JSifier<'a> {
 					report_diagnostic(Diagnostic {
 						message: "Cannot reference an inflight value from within a preflight expression".to_string(),
 						span: Some(expression.span.clone()),
+						severity: DiagnosticSeverity::Error,
+						code: DiagnosticCode::InflightInPreflight,
+						annotations: vec![],
+						fixes: vec![],
 					});
 
 					return "<ERROR>".to_string();
@@ -262,7 +386,7 @@ impl<'a> JSifier<'a> {
 		}
 
 		let auto_await = match ctx.phase {
-			Phase::Inflight => "await ",
+			Phase::Inflight if !ctx.in_static_initializer => "await ",
 			_ => "",
 		};
 		match &expression.kind {
@@ -431,6 +555,26 @@ impl<'a> JSifier<'a> {
 				let js_left = self.jsify_expression(left, ctx);
 				let js_right = self.jsify_expression(right, ctx);
 
+				// Datetimes are JS `Date` objects and durations are objects with a `seconds` field
+				let left_type = self.types.get_expr_type(left);
+				if left_type.maybe_unwrap_option().is_datetime() {
+					// Optional datetimes can be compared with each other (and with `nil`), which have no time when they're `nil`
+					let get_time = if left_type.is_option() {
+						"?.getTime()"
+					} else {
+						".getTime()"
+					};
+					match op {
+						BinaryOperator::AddOrConcat => {
+							return format!("new Date({js_left}.getTime() + {js_right}.seconds * 1000)");
+						}
+						BinaryOperator::Sub => return format!("new Date({js_left}.getTime() - {js_right}.seconds * 1000)"),
+						BinaryOperator::Equal => return format!("({js_left}{get_time} === {js_right}{get_time})"),
+						BinaryOperator::NotEqual => return format!("({js_left}{get_time} !== {js_right}{get_time})"),
+						_ => {}
+					}
+				}
+
 				let js_op = match op {
 					BinaryOperator::AddOrConcat => "+",
 					BinaryOperator::Sub => "-",
@@ -487,6 +631,8 @@ impl<'a> JSifier<'a> {
 					phase: ctx.phase,
 					files: ctx.files,
 					lifts: ctx.lifts,
+					dtsifier: ctx.dtsifier,
+					in_static_initializer: ctx.in_static_initializer,
 				};
 				let js_out = match &element.kind {
 					ExprKind::JsonMapLiteral { .. } => {
@@ -536,7 +682,65 @@ impl<'a> JSifier<'a> {
 					format!("Object.freeze(new Set([{}]))", item_list)
 				}
 			}
-			ExprKind::FunctionClosure(func_def) => self.jsify_function(None, func_def, ctx).to_string(),
+			ExprKind::FunctionClosure(func_def) => {
+				// closures are emitted inline so their lines can't be mapped to the source
+				let code = self.jsify_function(None, func_def, ctx);
+				format!("({})", code.to_string().trim())
+			}
+			ExprKind::Await(inner) => {
+				let inner_type = self.types.get_expr_type(inner);
+				if inner_type.as_promise().is_some() {
+					format!("(await {})", self.jsify_expression(inner, ctx))
+				} else {
+					format!("(await Promise.all({}))", self.jsify_expression(inner, ctx))
+				}
+			}
+			ExprKind::Defer(inner) => {
+				// the deferred expression is evaluated inside an async function so its own awaits don't block the caller
+				let ctx = &mut JSifyContext {
+					in_json: ctx.in_json,
+					phase: ctx.phase,
+					files: ctx.files,
+					lifts: ctx.lifts,
+					dtsifier: ctx.dtsifier,
+					in_static_initializer: false,
+				};
+				format!("(async () => {})()", self.jsify_expression(inner, ctx))
+			}
+			ExprKind::TypeTest { expr: inner, type_ } => {
+				format!(
+					"(($v) => {})({})",
+					self.jsify_type_test(&type_.kind, "$v"),
+					self.jsify_expression(inner, ctx)
+				)
+			}
+			ExprKind::Match {
+				value,
+				arms,
+				else_value,
+			} => {
+				// A match expression is jsified to a chain of conditional expressions inside an arrow function which gets
+				// the matched value, so it is evaluated only once (`(($MATCH_VALUE) => cond1 ? a : cond2 ? b : c)(x)`).
+				// Inflight, the function is async since the arms may await.
+				let match_value = "$MATCH_VALUE";
+				let mut body = match else_value {
+					Some(else_value) => self.jsify_expression(else_value, ctx),
+					None => "undefined".to_string(),
+				};
+				for arm in arms.iter().rev() {
+					body = format!(
+						"({}) ? {} : {}",
+						self.jsify_match_condition(&arm.patterns, match_value, ctx),
+						self.jsify_expression(&arm.value, ctx),
+						body
+					);
+				}
+				let value = self.jsify_expression(value, ctx);
+				match ctx.phase {
+					Phase::Inflight => format!("(await (async ({match_value}) => {body})({value}))"),
+					_ => format!("(({match_value}) => {body})({value})"),
+				}
+			}
 			ExprKind::CompilerDebugPanic => {
 				// Handle the debug panic expression (during jsifying)
 				dbg_panic!();
@@ -545,6 +749,18 @@ impl<'a> JSifier<'a> {
 		}
 	}
 
+	/// Renders a JS condition which checks whether `match_value` matches any of the patterns of a `match` arm
+	fn jsify_match_condition(&self, patterns: &[Expr], match_value: &str, ctx: &mut JSifyContext) -> String {
+		patterns
+			.iter()
+			.map(|pattern| match pattern.kind {
+				// We use the abstract equality operator here because we want to match null or undefined
+				ExprKind::Literal(Literal::Nil) => format!("{match_value} == undefined"),
+				_ => format!("{} === {}", match_value, self.jsify_expression(pattern, ctx)),
+			})
+			.join(" || ")
+	}
+
 	fn jsify_statement(&self, env: &SymbolEnv, statement: &Stmt, ctx: &mut JSifyContext) -> CodeMaker {
 		CompilationContext::set(CompilationPhase::Jsifying, &statement.span);
 		match &statement.kind {
@@ -567,7 +783,13 @@ impl<'a> JSifier<'a> {
 					} else {
 						module_name
 					},
-					if module_name.name.starts_with("\"") {
+					if is_wing_module(module_name) {
+						let module_path = resolve_module_path(Path::new(&statement.span.file_id), module_name);
+						format!(
+							"require(\"./{}\")",
+							preflight_file_name(&module_path, self.absolute_project_root)
+						)
+					} else if module_name.name.starts_with("\"") {
 						// TODO so many assumptions here, would only work with a JS file, see:
 						// https://github.com/winglang/wing/issues/477
 						// https://github.com/winglang/wing/issues/478
@@ -698,12 +920,66 @@ impl<'a> JSifier<'a> {
 
 				code
 			}
+			StmtKind::Match {
+				value,
+				arms,
+				else_statements,
+			} => {
+				// A match is jsified to a chain of `if` statements (rather than a `switch`, so `break` and `continue`
+				// statements inside the arms still apply to the enclosing loop):
+				// {
+				//   const $MATCH_VALUE = x;
+				//   if ($MATCH_VALUE === Color.RED || $MATCH_VALUE === Color.GREEN) {
+				//     ...
+				//   }
+				//   else {
+				//     ...
+				//   }
+				// }
+				let mut code = CodeMaker::default();
+				code.open("{");
+				let match_value = "$MATCH_VALUE".to_string();
+				code.line(format!(
+					"const {} = {};",
+					match_value,
+					self.jsify_expression(value, ctx)
+				));
+				for (i, arm) in arms.iter().enumerate() {
+					let condition = self.jsify_match_condition(&arm.patterns, &match_value, ctx);
+					code.open(format!("{}if ({}) {{", if i == 0 { "" } else { "else " }, condition));
+					code.add_code(self.jsify_scope_body(&arm.statements, ctx));
+					code.close("}");
+				}
+				if let Some(else_scope) = else_statements {
+					if arms.is_empty() {
+						code.add_code(self.jsify_scope_body(else_scope, ctx));
+					} else {
+						code.open("else {");
+						code.add_code(self.jsify_scope_body(else_scope, ctx));
+						code.close("}");
+					}
+				}
+				code.close("}");
+				code
+			}
 			StmtKind::Expression(e) => CodeMaker::one_line(format!("{};", self.jsify_expression(e, ctx))),
-			StmtKind::Assignment { variable, value } => CodeMaker::one_line(format!(
-				"{} = {};",
-				self.jsify_expression(variable, ctx),
-				self.jsify_expression(value, ctx)
-			)),
+			StmtKind::Assignment { variable, value } => {
+				if let ExprKind::Reference(Reference::ElementAccess { object, index }) = &variable.kind {
+					if matches!(*self.types.get_expr_type(object), Type::MutArray(_)) {
+						return CodeMaker::one_line(format!(
+							"{ARRAY_ELEMENT_WRITE}({}, {}, {});",
+							self.jsify_expression(object, ctx),
+							self.jsify_expression(index, ctx),
+							self.jsify_expression(value, ctx)
+						));
+					}
+				}
+				CodeMaker::one_line(format!(
+					"{} = {};",
+					self.jsify_expression(variable, ctx),
+					self.jsify_expression(value, ctx)
+				))
+			}
 			StmtKind::Scope(scope) => {
 				let mut code = CodeMaker::default();
 				if !scope.statements.is_empty() {
@@ -794,11 +1070,20 @@ impl<'a> JSifier<'a> {
 		code
 	}
 
-	fn jsify_function(&self, class: Option<&AstClass>, func_def: &FunctionDefinition, ctx: &mut JSifyContext) -> String {
+	fn jsify_function(
+		&self,
+		class: Option<&AstClass>,
+		func_def: &FunctionDefinition,
+		ctx: &mut JSifyContext,
+	) -> CodeMaker {
 		let mut parameter_list = vec![];
 
 		for p in &func_def.signature.parameters {
-			parameter_list.push(p.name.to_string());
+			if p.variadic {
+				parameter_list.push(format!("...{}", p.name));
+			} else {
+				parameter_list.push(p.name.to_string());
+			}
 		}
 
 		let (name, arrow) = match &func_def.name {
@@ -822,6 +1107,15 @@ impl<'a> JSifier<'a> {
 
 		let body = match &func_def.body {
 			FunctionBody::Statements(scope) => {
+				// closures defined in a static field initializer are called later, so they can await
+				let ctx = &mut JSifyContext {
+					in_json: ctx.in_json,
+					phase: ctx.phase,
+					files: ctx.files,
+					lifts: ctx.lifts,
+					dtsifier: ctx.dtsifier,
+					in_static_initializer: false,
+				};
 				let mut code = CodeMaker::default();
 				code.add_code(self.jsify_scope_body(scope, ctx));
 				code
@@ -842,6 +1136,10 @@ impl<'a> JSifier<'a> {
 							report_diagnostic(Diagnostic {
 								message: format!("Failed to resolve extern \"{external_spec}\": {err}"),
 								span: Some(func_def.span.clone()),
+								severity: DiagnosticSeverity::Error,
+								code: DiagnosticCode::UnresolvedExtern,
+								annotations: vec![],
+								fixes: vec![],
 							});
 							format!("/* unresolved: \"{external_spec}\" */")
 						}
@@ -872,13 +1170,8 @@ impl<'a> JSifier<'a> {
 		code.open(format!("{}({parameters}){arrow}{{", prefix.join(" ")));
 		code.add_code(body);
 		code.close("}");
-
-		// if prefix is empty it means this is a closure, so we need to wrap it in `(`, `)`.
-		if prefix.is_empty() {
-			format!("({})", code.to_string().trim().to_string())
-		} else {
-			code.to_string()
-		}
+		code.set_origin(&func_def.span);
+		code
 	}
 
 	fn jsify_class(&self, env: &SymbolEnv, class: &AstClass, ctx: &mut JSifyContext) -> CodeMaker {
@@ -898,6 +1191,8 @@ impl<'a> JSifier<'a> {
 			phase: ctx.phase,
 			files: ctx.files,
 			lifts,
+			dtsifier: ctx.dtsifier,
+			in_static_initializer: false,
 		};
 
 		// emit the inflight side of the class into a separate file
@@ -910,7 +1205,7 @@ impl<'a> JSifier<'a> {
 		}
 
 		// emit the inflight file
-		self.emit_inflight_file(&class, inflight_class_code, ctx);
+		self.emit_inflight_file(&class, class_type.as_class().unwrap(), inflight_class_code, ctx);
 
 		// lets write the code for the preflight side of the class
 		let mut code = CodeMaker::default();
@@ -931,7 +1226,7 @@ impl<'a> JSifier<'a> {
 
 		// emit preflight methods
 		for m in class.preflight_methods(false) {
-			code.line(self.jsify_function(Some(class), m, ctx));
+			code.add_code(self.jsify_function(Some(class), m, ctx));
 		}
 
 		// emit the `_toInflight` and `_toInflightType` methods (TODO: renamed to `_liftObject` and
@@ -944,6 +1239,33 @@ impl<'a> JSifier<'a> {
 		code.add_code(self.jsify_register_bind_method(class, class_type, BindMethod::Type, ctx));
 
 		code.close("}");
+
+		// static fields are initialized after the class is defined, in the scope it is defined in
+		code.add_code(self.jsify_static_fields(class, Phase::Preflight, ctx));
+		code
+	}
+
+	fn jsify_static_fields(&self, class: &AstClass, phase: Phase, ctx: &mut JSifyContext) -> CodeMaker {
+		let mut code = CodeMaker::default();
+		let ctx = &mut JSifyContext {
+			in_json: ctx.in_json,
+			phase: ctx.phase,
+			files: ctx.files,
+			lifts: ctx.lifts,
+			dtsifier: ctx.dtsifier,
+			in_static_initializer: true,
+		};
+		for field in class.static_fields(phase) {
+			let Some(initializer) = &field.initializer else {
+				continue;
+			};
+			code.line(format!(
+				"{}.{} = {};",
+				class.name.name,
+				field.name.name,
+				self.jsify_expression(initializer, ctx)
+			));
+		}
 		code
 	}
 
@@ -956,7 +1278,11 @@ impl<'a> JSifier<'a> {
 				.signature
 				.parameters
 				.iter()
-				.map(|p| p.name.to_string())
+				.map(|p| if p.variadic {
+					format!("...{}", p.name)
+				} else {
+					p.name.to_string()
+				})
 				.collect_vec()
 				.join(", "),
 		));
@@ -1008,7 +1334,7 @@ impl<'a> JSifier<'a> {
 	}
 
 	fn jsify_to_inflight_type_method(&self, class: &AstClass, ctx: &JSifyContext) -> CodeMaker {
-		let client_path = inflight_filename(class);
+		let client_path = self.inflight_filename(class);
 
 		let mut code = CodeMaker::default();
 
@@ -1078,6 +1404,8 @@ impl<'a> JSifier<'a> {
 			in_json: false,
 			files: ctx.files,
 			lifts: ctx.lifts,
+			dtsifier: ctx.dtsifier,
+			in_static_initializer: false,
 		};
 
 		let mut class_code = CodeMaker::default();
@@ -1098,21 +1426,29 @@ impl<'a> JSifier<'a> {
 		}
 
 		for def in class.inflight_methods(false) {
-			class_code.line(self.jsify_function(Some(class), def, &mut ctx));
+			class_code.add_code(self.jsify_function(Some(class), def, &mut ctx));
 		}
 
 		// emit the $inflight_init function (if it has a body).
 		if let FunctionBody::Statements(s) = &class.inflight_initializer.body {
 			if !s.statements.is_empty() {
-				class_code.line(self.jsify_function(Some(class), &class.inflight_initializer, &mut ctx));
+				class_code.add_code(self.jsify_function(Some(class), &class.inflight_initializer, &mut ctx));
 			}
 		}
 
 		class_code.close("}");
+
+		class_code.add_code(self.jsify_static_fields(class, Phase::Inflight, &mut ctx));
 		class_code
 	}
 
-	fn emit_inflight_file(&self, class: &AstClass, inflight_class_code: CodeMaker, ctx: &mut JSifyContext) {
+	fn emit_inflight_file(
+		&self,
+		class: &AstClass,
+		class_type: &Class,
+		inflight_class_code: CodeMaker,
+		ctx: &mut JSifyContext,
+	) {
 		let name = &class.name.name;
 		let mut code = CodeMaker::default();
 
@@ -1126,9 +1462,24 @@ impl<'a> JSifier<'a> {
 		code.add_code(inflight_class_code);
 		code.line(format!("return {name};"));
 		code.close("}");
+		code.set_origin(&class.name.span);
 
 		// emit the inflight class to a file
-		match ctx.files.add_file(inflight_filename(class), code.to_string()) {
+		match ctx
+			.files
+			.add_file_with_source_map(self.inflight_filename(class), code.to_string(), code.source_map())
+		{
+			Ok(()) => {}
+			Err(err) => report_diagnostic(err.into()),
+		}
+		// Closures aren't exported from the preflight module, so their inflight clients aren't declared either
+		let Some(dtsifier) = ctx.dtsifier.filter(|_| !name.starts_with(CLOSURE_CLASS_PREFIX)) else {
+			return;
+		};
+		match ctx.files.add_file(
+			dts_file_name(&self.inflight_filename(class)),
+			dtsifier.dtsify_inflight(class_type).to_string(),
+		) {
 			Ok(()) => {}
 			Err(err) => report_diagnostic(err.into()),
 		}
@@ -1144,7 +1495,8 @@ impl<'a> JSifier<'a> {
 		};
 
 		let parent_fields = if let Some(parent) = &class.parent {
-			let parent_type = self.types.get_expr_type(parent);
+			// The lifts of a generic class are recorded on its definition, not on its instances
+			let parent_type = self.types.get_expr_type(parent).generic_definition();
 			if let Some(parent_lifts) = &parent_type.as_class().unwrap().lifts {
 				parent_lifts.lifted_fields().keys().map(|f| f.clone()).collect_vec()
 			} else {
@@ -1243,10 +1595,37 @@ impl<'a> JSifier<'a> {
 		bind_method.close("}");
 		bind_method
 	}
+
+	/// Name of the preflight JS module emitted for a Wing file (given as a `WingSpan::file_id`)
+	fn preflight_file_of(&self, file_id: &str) -> String {
+		if file_id == self.entry_file {
+			PREFLIGHT_FILE_NAME.to_string()
+		} else {
+			preflight_file_name(Path::new(file_id), self.absolute_project_root)
+		}
+	}
+
+	fn inflight_filename(&self, class: &AstClass) -> String {
+		// Generated classes (closures) aren't declared in any file, but they are numbered across all modules so their
+		// names don't collide
+		if class.name.span.file_id.is_empty() {
+			return inflight_filename_of(&class.name.name, PREFLIGHT_FILE_NAME);
+		}
+		inflight_filename_of(&class.name.name, &self.preflight_file_of(&class.name.span.file_id))
+	}
 }
 
-fn inflight_filename(class: &AstClass) -> String {
-	format!("./inflight.{}.js", class.name.name)
+/// Name of the inflight client file of a class, qualified with the module that declares the class (by the name of
+/// its preflight file), so classes with the same name in different modules don't collide
+/// (e.g. `Foo` declared in `preflight.lib.util.js` => `inflight.lib.util.Foo.js`).
+fn inflight_filename_of(class_name: &str, preflight_file: &str) -> String {
+	match preflight_file
+		.strip_prefix("preflight.")
+		.and_then(|f| f.strip_suffix(".js"))
+	{
+		Some(module) => format!("./inflight.{}.{}.js", module, class_name),
+		None => format!("./inflight.{}.js", class_name),
+	}
 }
 
 fn lookup_span(span: &WingSpan, files: &Files) -> String {
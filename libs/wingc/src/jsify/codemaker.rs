@@ -1,10 +1,22 @@
+use crate::{diagnostic::WingSpan, source_map::SourceMap};
+
+const INDENT: &str = "  ";
+
+/// A line of generated code
+struct Line {
+	indent: usize,
+	code: String,
+	/// The span of the source code this line was generated from (if known)
+	origin: Option<WingSpan>,
+}
+
 /// A helper for generating code snippets with indentation.
 ///
 /// TODO: add `open_block` or `close_block` methods that automatically add
 /// `{` and `}`?
 #[derive(Default)]
 pub struct CodeMaker {
-	lines: Vec<(usize, String)>,
+	lines: Vec<Line>,
 	indent: usize,
 }
 
@@ -30,7 +42,11 @@ impl CodeMaker {
 
 		// if the line has newlines in it, consider each line separately
 		for subline in line.split('\n') {
-			self.lines.push((self.indent, subline.into()));
+			self.lines.push(Line {
+				indent: self.indent,
+				code: subline.into(),
+				origin: None,
+			});
 		}
 	}
 
@@ -42,9 +58,38 @@ impl CodeMaker {
 	/// Emits multiple lines of code starting with the current indent.
 	pub fn add_code(&mut self, code: CodeMaker) {
 		assert_eq!(code.indent, 0, "Cannot add code with indent");
-		for (indent, line) in code.lines {
-			self.lines.push((indent + self.indent, line));
+		for line in code.lines {
+			self.lines.push(Line {
+				indent: line.indent + self.indent,
+				..line
+			});
+		}
+	}
+
+	/// Marks all the lines which don't have an origin yet as generated from the given span of the source code.
+	/// Since code is assembled bottom-up, lines generated from nested statements keep their own (more precise)
+	/// origin.
+	pub fn set_origin(&mut self, span: &WingSpan) {
+		for line in self.lines.iter_mut().filter(|line| line.origin.is_none()) {
+			line.origin = Some(span.clone());
+		}
+	}
+
+	/// Returns a source map of the code returned by `to_string`, mapping the start of each line to the start of
+	/// its origin.
+	pub fn source_map(&self) -> SourceMap {
+		let mut source_map = SourceMap::default();
+		for (idx, line) in self.lines.iter().enumerate() {
+			if let Some(origin) = &line.origin {
+				source_map.add_mapping(idx as u32, (line.indent * INDENT.len()) as u32, origin);
+			}
 		}
+		source_map
+	}
+
+	/// Returns `true` if no lines were emitted.
+	pub fn is_empty(&self) -> bool {
+		self.lines.is_empty()
 	}
 
 	/// Decreases the current indent by one.
@@ -68,9 +113,9 @@ impl CodeMaker {
 impl ToString for CodeMaker {
 	fn to_string(&self) -> String {
 		let mut code = String::new();
-		for (indent, line) in &self.lines {
-			code.push_str(&"  ".repeat(*indent));
-			code.push_str(&line);
+		for line in &self.lines {
+			code.push_str(&INDENT.repeat(line.indent));
+			code.push_str(&line.code);
 			code.push_str(&"\n");
 		}
 		code
@@ -82,6 +127,9 @@ mod tests {
 	use super::*;
 
 	use indoc::indoc;
+	use std::path::Path;
+
+	use crate::diagnostic::WingLocation;
 
 	#[test]
 	fn codemaker_basic() {
@@ -145,4 +193,29 @@ mod tests {
 			"#}
 		);
 	}
+
+	#[test]
+	fn codemaker_source_map() {
+		let span = |line| WingSpan {
+			start: WingLocation { line, col: 0 },
+			end: WingLocation { line, col: 1 },
+			file_id: "/project/main.w".to_string(),
+		};
+
+		let mut body = CodeMaker::default();
+		body.line("console.log('a is 1');");
+		body.set_origin(&span(1));
+		let mut code = CodeMaker::default();
+		code.open("if (a === 1) {");
+		code.add_code(body);
+		code.close("}");
+		code.set_origin(&span(0));
+		code.line("let b = 2;");
+
+		let json: serde_json::Value =
+			serde_json::from_str(&code.source_map().to_json("preflight.js", Path::new("/project"))).unwrap();
+		assert_eq!(json["sources"], serde_json::json!(["main.w"]));
+		// the body is mapped to the second line of the source, the last line isn't mapped
+		assert_eq!(json["mappings"], "AAAA;EACA;AADA");
+	}
 }
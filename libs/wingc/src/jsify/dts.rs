@@ -0,0 +1,549 @@
+use std::path::Path;
+
+use itertools::Itertools;
+
+use crate::{
+	ast::{AccessModifier, Phase, Scope, StmtKind, Symbol},
+	closure_transform::CLOSURE_CLASS_PREFIX,
+	docs::Docs,
+	modules::preflight_file_name,
+	type_check::{
+		Class, ClassLike, Enum, FunctionSignature, Interface, Struct, SymbolKind, Type, TypeRef, VariableInfo,
+		VariableKind, CLASS_INFLIGHT_INIT_NAME, CLASS_INIT_NAME,
+	},
+	WINGSDK_ASSEMBLY_NAME,
+};
+
+use super::{codemaker::CodeMaker, STDLIB, STDLIB_CORE_RESOURCE};
+
+/// The type of the `scope` argument of preflight constructors
+const CONSTRUCT_TYPE: &str = "import(\"constructs\").Construct";
+
+/// Emits TypeScript declarations (`.d.ts` files) for the JS modules generated from a Wing file, so TypeScript
+/// code can consume the classes, structs and enums defined in Wing.
+pub struct DTSifier<'a> {
+	/// The Wing file (as a `WingSpan::file_id`) whose JS modules are being emitted
+	source_file: &'a str,
+	/// The name of the preflight module emitted for `source_file`
+	preflight_file: &'a str,
+	/// Root of the project, used for resolving the preflight modules of other Wing files
+	absolute_project_root: &'a Path,
+}
+
+impl<'a> DTSifier<'a> {
+	pub fn new(source_file: &'a str, preflight_file: &'a str, absolute_project_root: &'a Path) -> Self {
+		Self {
+			source_file,
+			preflight_file,
+			absolute_project_root,
+		}
+	}
+
+	/// Returns the declarations of the preflight module: the classes, interfaces, structs and enums defined at the
+	/// top level of the file. Returns `None` if the file doesn't define any of them.
+	pub fn dtsify_preflight(&self, scope: &Scope) -> Option<CodeMaker> {
+		let mut declarations = CodeMaker::default();
+
+		let env = scope.env.borrow();
+		let env = env.as_ref().unwrap();
+		for statement in &scope.statements {
+			let name = match &statement.kind {
+				// Closures are implementation details of the module, they aren't exported
+				StmtKind::Class(class) if class.name.name.starts_with(CLOSURE_CLASS_PREFIX) => continue,
+				StmtKind::Class(class) => &class.name,
+				StmtKind::Interface(interface) => &interface.name,
+				StmtKind::Struct { name, .. } => name,
+				StmtKind::Enum { name, .. } => name,
+				_ => continue,
+			};
+			let Some(t) = env.lookup(name, None).and_then(SymbolKind::as_type) else {
+				continue;
+			};
+			match &*t {
+				Type::Class(class) => declarations.add_code(self.declare_preflight_class(class)),
+				Type::Interface(interface) => declarations.add_code(self.declare_interface(interface)),
+				Type::Struct(s) => declarations.add_code(self.declare_struct(s)),
+				Type::Enum(e) => declarations.add_code(self.declare_enum(e)),
+				_ => {}
+			}
+		}
+
+		if declarations.is_empty() {
+			return None;
+		}
+		let mut code = CodeMaker::default();
+		code.line(format!("import * as {STDLIB} from \"{WINGSDK_ASSEMBLY_NAME}\";"));
+		code.add_code(declarations);
+		Some(code)
+	}
+
+	/// Returns the declarations of the inflight module of a class. The module exports a function which receives
+	/// the lifted values and returns the inflight client class.
+	pub fn dtsify_inflight(&self, class: &Class) -> CodeMaker {
+		let mut code = CodeMaker::default();
+		code.line(format!("import * as {STDLIB} from \"{WINGSDK_ASSEMBLY_NAME}\";"));
+
+		render_doc_comment(&mut code, &class.docs);
+		code.open(format!("declare class {} {{", class.name));
+		if class.phase == Phase::Preflight {
+			code.line("constructor(lifted: Record<string, any>);");
+		}
+		// the inflight client doesn't extend the inflight client of the parent class in the declarations, so we
+		// flatten the inherited members into it
+		for (name, kind, _) in class.env.iter(true) {
+			let Some(member) = kind.as_variable() else {
+				continue;
+			};
+			if member.phase == Phase::Inflight {
+				self.declare_member(&mut code, &name, &member, Phase::Inflight);
+			}
+		}
+		code.close("}");
+
+		code.line(format!(
+			"declare function $inflight(captures: Record<string, any>): typeof {};",
+			class.name
+		));
+		code.line("export = $inflight;");
+		code
+	}
+
+	fn declare_preflight_class(&self, class: &Class) -> CodeMaker {
+		let mut code = CodeMaker::default();
+
+		let extends = match &class.parent {
+			Some(parent) => self.render_type(*parent, Phase::Preflight, true),
+			None => STDLIB_CORE_RESOURCE.to_string(),
+		};
+
+		render_doc_comment(&mut code, &class.docs);
+		code.open(format!(
+			"export declare {}class {}{} extends {extends} {{",
+			if class.is_abstract { "abstract " } else { "" },
+			class.name,
+			self.render_type_parameters(class.generic_type_parameters()),
+		));
+
+		if let Some(init) = class.env.lookup(&CLASS_INIT_NAME.into(), None) {
+			let init = init.as_variable().unwrap();
+			let mut parameters = vec![];
+			if class.std_construct_args {
+				parameters.push(format!("scope: {CONSTRUCT_TYPE}"));
+				parameters.push("id: string".to_string());
+			}
+			if let Some(sig) = init.type_.as_function_sig() {
+				parameters.push(self.render_parameters(sig, Phase::Preflight, true));
+			}
+			code.line(format!(
+				"constructor({});",
+				parameters.iter().filter(|p| !p.is_empty()).join(", ")
+			));
+		}
+
+		for (name, kind, _) in class.env.iter(false) {
+			let Some(member) = kind.as_variable() else {
+				continue;
+			};
+			if member.phase != Phase::Inflight {
+				self.declare_member(&mut code, &name, &member, Phase::Preflight);
+			}
+		}
+
+		code.close("}");
+		code
+	}
+
+	fn declare_member(&self, code: &mut CodeMaker, name: &str, member: &VariableInfo, phase: Phase) {
+		if name == CLASS_INIT_NAME || name == CLASS_INFLIGHT_INIT_NAME {
+			return;
+		}
+
+		let modifiers = match member.access_modifier {
+			// private members aren't part of the API of the class
+			AccessModifier::Private => return,
+			AccessModifier::Protected => "protected ",
+			AccessModifier::Public => "",
+		};
+		let modifiers = if matches!(member.kind, VariableKind::StaticMember) {
+			format!("{modifiers}static ")
+		} else {
+			modifiers.to_string()
+		};
+
+		if let Some(docs) = &member.docs {
+			render_doc_comment(code, docs);
+		}
+
+		let local = phase == Phase::Preflight;
+		if let Some(sig) = member.type_.as_function_sig() {
+			code.line(format!(
+				"{modifiers}{name}({}): {};",
+				self.render_parameters(sig, phase, local),
+				self.render_return_type(sig, local)
+			));
+		} else {
+			let readonly = if member.reassignable { "" } else { "readonly " };
+			code.line(format!(
+				"{modifiers}{readonly}{}: {};",
+				property_name(name, member.type_),
+				self.render_type(*member.type_.maybe_unwrap_option(), phase, local)
+			));
+		}
+	}
+
+	fn declare_interface(&self, interface: &Interface) -> CodeMaker {
+		let mut code = CodeMaker::default();
+
+		render_doc_comment(&mut code, &interface.docs);
+		code.open(format!(
+			"export interface {}{}{} {{",
+			interface.name,
+			self.render_type_parameters(interface.type_parameters.as_ref()),
+			self.render_extends(&interface.extends),
+		));
+		// inflight members are part of the inflight client of the implementing classes
+		for (name, kind, _) in interface.env.iter(false) {
+			let Some(member) = kind.as_variable() else {
+				continue;
+			};
+			if member.phase != Phase::Inflight {
+				self.declare_member(&mut code, &name, &member, Phase::Preflight);
+			}
+		}
+		code.close("}");
+		code
+	}
+
+	fn declare_struct(&self, s: &Struct) -> CodeMaker {
+		let mut code = CodeMaker::default();
+
+		render_doc_comment(&mut code, &s.docs);
+		code.open(format!(
+			"export interface {}{}{} {{",
+			s.name,
+			self.render_type_parameters(s.type_parameters.as_ref()),
+			self.render_extends(&s.extends),
+		));
+		for (name, field_type) in s.fields(false) {
+			code.line(format!(
+				"readonly {}: {};",
+				property_name(&name, field_type),
+				self.render_type(*field_type.maybe_unwrap_option(), Phase::Preflight, true)
+			));
+		}
+		code.close("}");
+		code
+	}
+
+	fn declare_enum(&self, e: &Enum) -> CodeMaker {
+		let mut code = CodeMaker::default();
+
+		render_doc_comment(&mut code, &e.docs);
+		code.open(format!("export declare enum {} {{", e.name));
+		// values are numbered in order of declaration, see `JSifier::jsify_enum`
+		for (index, value) in e.values.iter().enumerate() {
+			code.line(format!("{value} = {index},"));
+		}
+		code.close("}");
+		code
+	}
+
+	fn render_extends(&self, extends: &[TypeRef]) -> String {
+		if extends.is_empty() {
+			return String::new();
+		}
+		format!(
+			" extends {}",
+			extends
+				.iter()
+				.map(|t| self.render_type(*t, Phase::Preflight, true))
+				.join(", ")
+		)
+	}
+
+	fn render_type_parameters(&self, type_parameters: Option<&Vec<TypeRef>>) -> String {
+		let Some(type_parameters) = type_parameters.filter(|params| !params.is_empty()) else {
+			return String::new();
+		};
+		let mut params = type_parameters.iter().map(|t| match &**t {
+			Type::TypeParameter(p) => match p.bound {
+				Some(bound) => format!("{} extends {}", p.name, self.render_type(bound, Phase::Preflight, true)),
+				None => p.name.name.clone(),
+			},
+			_ => self.render_type(*t, Phase::Preflight, true),
+		});
+		format!("<{}>", params.join(", "))
+	}
+
+	fn render_parameters(&self, sig: &FunctionSignature, phase: Phase, local: bool) -> String {
+		// trailing optional parameters can be omitted
+		let optional_from = sig.parameters.len()
+			- sig
+				.parameters
+				.iter()
+				.rev()
+				.take_while(|p| p.variadic || p.typeref.is_option())
+				.count();
+
+		sig
+			.parameters
+			.iter()
+			.enumerate()
+			.map(|(idx, p)| {
+				if p.variadic {
+					format!("...{}: {}", p.name, self.render_type(p.typeref, phase, local))
+				} else if idx >= optional_from {
+					format!(
+						"{}?: {}",
+						p.name,
+						self.render_type(*p.typeref.maybe_unwrap_option(), phase, local)
+					)
+				} else {
+					format!("{}: {}", p.name, self.render_type(p.typeref, phase, local))
+				}
+			})
+			.join(", ")
+	}
+
+	fn render_return_type(&self, sig: &FunctionSignature, local: bool) -> String {
+		let return_type = self.render_type(sig.return_type, sig.phase, local);
+		// inflight functions are async
+		if sig.phase == Phase::Inflight && !matches!(*sig.return_type, Type::Promise(_)) {
+			format!("Promise<{return_type}>")
+		} else {
+			return_type
+		}
+	}
+
+	/// Renders the TypeScript type of values of type `t` in the given phase. `local` is true when rendering into
+	/// the preflight module of `source_file`, where the types defined in it can be referenced by name.
+	fn render_type(&self, t: TypeRef, phase: Phase, local: bool) -> String {
+		match &*t {
+			Type::Anything | Type::Json | Type::MutJson | Type::Unresolved => "any".to_string(),
+			Type::Number => "number".to_string(),
+			Type::String => "string".to_string(),
+			Type::Boolean => "boolean".to_string(),
+			Type::Void => "void".to_string(),
+			Type::Nil => "undefined".to_string(),
+			Type::Duration => format!("{STDLIB}.std.Duration"),
+			Type::Datetime => "Date".to_string(),
+			Type::Optional(t) => format!("{} | undefined", self.render_type(*t, phase, local)),
+			Type::Array(t) => format!("ReadonlyArray<{}>", self.render_type(*t, phase, local)),
+			Type::MutArray(t) => format!("Array<{}>", self.render_type(*t, phase, local)),
+			Type::Map(t) => format!("Readonly<Record<string, {}>>", self.render_type(*t, phase, local)),
+			Type::MutMap(t) => format!("Record<string, {}>", self.render_type(*t, phase, local)),
+			Type::Set(t) => format!("ReadonlySet<{}>", self.render_type(*t, phase, local)),
+			Type::MutSet(t) => format!("Set<{}>", self.render_type(*t, phase, local)),
+			Type::Promise(t) => format!("Promise<{}>", self.render_type(*t, phase, local)),
+			Type::Union(members) => members.iter().map(|m| self.render_type(*m, phase, local)).join(" | "),
+			Type::Function(sig) => format!(
+				"(({}) => {})",
+				self.render_parameters(sig, sig.phase, local),
+				self.render_return_type(sig, local)
+			),
+			Type::Class(class) => {
+				if let Some(fqn) = &class.fqn {
+					// the SDK's inflight clients are declared as `I<Name>Client` next to the preflight class
+					if phase == Phase::Inflight && class.phase == Phase::Preflight {
+						if let Some((namespace, name)) = fqn.rsplit_once('.') {
+							return jsii_type_reference(&format!("{namespace}.I{name}Client"));
+						}
+					}
+					return jsii_type_reference(fqn);
+				}
+				if phase == Phase::Inflight {
+					return format!(
+						"InstanceType<ReturnType<typeof import(\"{}\")>>",
+						super::inflight_filename_of(&class.name.name, &self.preflight_file_of(&class.name.span.file_id))
+					);
+				}
+				self.user_type_reference(
+					&class.name,
+					class.generic_instance.as_ref().map(|i| &i.type_arguments),
+					phase,
+					local,
+				)
+			}
+			// JSII interfaces, structs and enums don't keep their fqn so we can't reference them, and user defined
+			// interfaces are only declared for preflight
+			Type::Interface(interface) if !is_wing_type(&interface.name) || phase == Phase::Inflight => "any".to_string(),
+			Type::Struct(s) if !is_wing_type(&s.name) => "any".to_string(),
+			Type::Enum(e) if !is_wing_type(&e.name) => "any".to_string(),
+			Type::Interface(interface) => self.user_type_reference(
+				&interface.name,
+				interface.generic_instance.as_ref().map(|i| &i.type_arguments),
+				phase,
+				local,
+			),
+			Type::Struct(s) => self.user_type_reference(
+				&s.name,
+				s.generic_instance.as_ref().map(|i| &i.type_arguments),
+				phase,
+				local,
+			),
+			Type::Enum(e) => self.user_type_reference(&e.name, None, phase, local),
+			Type::TypeParameter(p) => p.name.name.clone(),
+		}
+	}
+
+	/// Renders a reference to a type defined in Wing: by name if it's defined in the module being declared, and
+	/// otherwise as an import of the preflight module of the file it's defined in.
+	fn user_type_reference(
+		&self,
+		name: &Symbol,
+		type_arguments: Option<&Vec<TypeRef>>,
+		phase: Phase,
+		local: bool,
+	) -> String {
+		let type_arguments = match type_arguments {
+			Some(args) if !args.is_empty() => format!(
+				"<{}>",
+				args.iter().map(|t| self.render_type(*t, phase, local)).join(", ")
+			),
+			_ => String::new(),
+		};
+
+		if local && name.span.file_id == self.source_file {
+			return format!("{}{type_arguments}", name.name);
+		}
+
+		let preflight_file = self.preflight_file_of(&name.span.file_id);
+		format!("import(\"./{preflight_file}\").{}{type_arguments}", name.name)
+	}
+
+	/// Name of the preflight JS module emitted for a Wing file (given as a `WingSpan::file_id`)
+	fn preflight_file_of(&self, file_id: &str) -> String {
+		if file_id == self.source_file {
+			self.preflight_file.to_string()
+		} else {
+			preflight_file_name(Path::new(file_id), self.absolute_project_root)
+		}
+	}
+}
+
+/// Returns the name of the declarations file of the JS file `js_file`
+pub fn dts_file_name(js_file: &str) -> String {
+	format!("{}.d.ts", js_file.strip_suffix(".js").unwrap_or(js_file))
+}
+
+/// Renders a reference to a JSII type: `@winglang/sdk.cloud.Bucket` is rendered as `$stdlib.cloud.Bucket` and
+/// types of other assemblies are imported.
+fn jsii_type_reference(fqn: &str) -> String {
+	if let Some(path) = fqn.strip_prefix(&format!("{WINGSDK_ASSEMBLY_NAME}.")) {
+		return format!("{STDLIB}.{path}");
+	}
+
+	// the assembly name is the part before the first `.` (after the scope of scoped packages)
+	let scope_len = if fqn.starts_with('@') {
+		fqn.find('/').map_or(0, |idx| idx + 1)
+	} else {
+		0
+	};
+	match fqn[scope_len..].find('.') {
+		Some(idx) => format!(
+			"import(\"{}\").{}",
+			&fqn[..scope_len + idx],
+			&fqn[scope_len + idx + 1..]
+		),
+		None => "any".to_string(),
+	}
+}
+
+/// Returns true if the type named `name` is defined in a Wing file (and not imported from JSII)
+fn is_wing_type(name: &Symbol) -> bool {
+	Path::new(&name.span.file_id)
+		.extension()
+		.map_or(false, |ext| ext == "w")
+}
+
+/// Renders the name of a property, optional properties are marked with `?`
+fn property_name(name: &str, t: TypeRef) -> String {
+	if t.is_option() {
+		format!("{name}?")
+	} else {
+		name.to_string()
+	}
+}
+
+fn render_doc_comment(code: &mut CodeMaker, docs: &Docs) {
+	if let Some(summary) = &docs.summary {
+		code.line(format!("/** {} */", summary.replace("*/", "*\\/")));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::type_check::{FunctionParameter, Types};
+
+	fn dtsifier() -> DTSifier<'static> {
+		DTSifier::new("/project/main.w", "preflight.js", Path::new("/project"))
+	}
+
+	#[test]
+	fn test_render_type() {
+		let mut types = Types::new();
+		let dtsifier = dtsifier();
+		let render = |t| dtsifier.render_type(t, Phase::Preflight, true);
+
+		let optional_num = types.make_option(types.number());
+		assert_eq!(render(optional_num), "number | undefined");
+		let array = types.add_type(Type::Array(types.string()));
+		assert_eq!(render(array), "ReadonlyArray<string>");
+		let mut_map = types.add_type(Type::MutMap(types.bool()));
+		assert_eq!(render(mut_map), "Record<string, boolean>");
+		let union = types.make_union([types.string(), types.number()]);
+		assert_eq!(render(union), "string | number");
+		assert_eq!(render(types.duration()), "$stdlib.std.Duration");
+		assert_eq!(render(types.json()), "any");
+
+		let inflight_fn = types.add_type(Type::Function(FunctionSignature {
+			type_parameters: vec![],
+			this_type: None,
+			parameters: vec![
+				FunctionParameter {
+					name: "key".to_string(),
+					typeref: types.string(),
+					docs: Docs::default(),
+					variadic: false,
+				},
+				FunctionParameter {
+					name: "count".to_string(),
+					typeref: optional_num,
+					docs: Docs::default(),
+					variadic: false,
+				},
+			],
+			return_type: types.string(),
+			phase: Phase::Inflight,
+			js_override: None,
+			docs: Docs::default(),
+		}));
+		assert_eq!(
+			render(inflight_fn),
+			"((key: string, count?: number) => Promise<string>)"
+		);
+	}
+
+	#[test]
+	fn test_jsii_type_reference() {
+		assert_eq!(
+			jsii_type_reference("@winglang/sdk.cloud.Bucket"),
+			"$stdlib.cloud.Bucket"
+		);
+		assert_eq!(
+			jsii_type_reference("constructs.Construct"),
+			"import(\"constructs\").Construct"
+		);
+		assert_eq!(
+			jsii_type_reference("@cdktf/provider-aws.s3Bucket.S3Bucket"),
+			"import(\"@cdktf/provider-aws\").s3Bucket.S3Bucket"
+		);
+	}
+
+	#[test]
+	fn test_dts_file_name() {
+		assert_eq!(dts_file_name("preflight.js"), "preflight.d.ts");
+		assert_eq!(dts_file_name("./inflight.Foo.js"), "./inflight.Foo.d.ts");
+	}
+}
@@ -822,7 +822,7 @@ fn fails_on_preflight_static() {
 	assert_compile_fail!(
 		r#"
     class MyType {
-      static staticMethod(): str {}
+      static staticMethod(): str { return "hello"; }
     }
 
     test "test" {
@@ -837,7 +837,9 @@ fn reference_static_inflight() {
 	assert_compile_ok!(
 		r#"
     class MyType {
-      static inflight myStaticMethod(): str {}
+      static inflight myStaticMethod(): str {
+        return "hello";
+      }
     }
 
     test "test" {
@@ -1220,7 +1222,7 @@ fn capture_in_keyword_args() {
     let x = 1s;
     
     test "test" {
-      util.waitUntil((): bool => {}, interval: x);
+      util.waitUntil((): bool => { return true; }, interval: x);
     }
     "#
 	);
@@ -1391,8 +1393,9 @@ fn func_returns_func() {
 		r#"
     test "test" {
       (s: str): (): bool => {
-        (): bool => { 
+        return (): bool => {
           s;
+          return true;
         };
       };
     }
@@ -1771,6 +1774,7 @@ fn reassign_captured_variable() {
       class Inner {
         dang(): num {
           i = i + 1;
+          return i;
         }
       }
     }
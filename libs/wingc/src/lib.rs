@@ -10,16 +10,22 @@ extern crate lazy_static;
 use ast::{Scope, Stmt, Symbol, UtilityFunctions};
 use closure_transform::ClosureTransformer;
 use comp_ctx::set_custom_panic_hook;
-use diagnostic::{found_errors, report_diagnostic, Diagnostic};
+use diagnostic::{
+	found_errors, report_diagnostic, set_warnings_as_errors, Diagnostic, DiagnosticCode, DiagnosticSeverity,
+};
 use files::Files;
 use fold::Fold;
 use jsify::JSifier;
 use lifting::LiftTransform;
+use modules::{load_wing_modules, WingModule};
+use type_check::jsii_exporter::JsiiExporter;
 use type_check::jsii_importer::JsiiImportSpec;
 use type_check::symbol_env::StatementIdx;
 use type_check::{FunctionSignature, SymbolKind, Type};
 use type_check_assert::TypeCheckAssert;
 use wasm_util::{ptr_to_string, string_to_combined_ptr, WASM_RETURN_ERROR};
+use wingii::cache::AssemblyCache;
+use wingii::spec::SPEC_FILE_NAME;
 use wingii::type_system::TypeSystem;
 
 use crate::docs::Docs;
@@ -41,16 +47,19 @@ mod test_utils;
 
 pub mod ast;
 pub mod closure_transform;
-mod comp_ctx;
+pub mod comp_ctx;
 pub mod debug;
 pub mod diagnostic;
 mod docs;
-mod files;
+pub mod files;
 pub mod fold;
+pub mod formatter;
 pub mod jsify;
 mod lifting;
 pub mod lsp;
+pub mod modules;
 pub mod parser;
+pub mod source_map;
 
 pub mod type_check;
 mod type_check_assert;
@@ -78,6 +87,7 @@ const WINGSDK_BRINGABLE_MODULES: [&'static str; 6] = [
 ];
 
 const WINGSDK_DURATION: &'static str = "std.Duration";
+const WINGSDK_DATETIME: &'static str = "std.Datetime";
 const WINGSDK_MAP: &'static str = "std.Map";
 const WINGSDK_MUT_MAP: &'static str = "std.MutMap";
 const WINGSDK_ARRAY: &'static str = "std.Array";
@@ -148,8 +158,15 @@ pub unsafe extern "C" fn wingc_compile(ptr: u32, len: u32) -> u64 {
 	let source_file = Path::new(split[0]);
 	let output_dir = split.get(1).map(|s| Path::new(s));
 	let absolute_project_dir = split.get(2).map(|s| Path::new(s));
+	// Any remaining arguments are flags, using the same names as the `wingc` CLI
+	let flags = split.get(3..).unwrap_or_default();
+	set_warnings_as_errors(flags.contains(&"--warnings-as-errors"));
 
-	let results = compile(source_file, output_dir, absolute_project_dir);
+	let results = if flags.contains(&"--library") {
+		compile_library(source_file, output_dir, absolute_project_dir)
+	} else {
+		compile(source_file, output_dir, absolute_project_dir)
+	};
 	if results.is_err() {
 		WASM_RETURN_ERROR
 	} else {
@@ -158,16 +175,16 @@ pub unsafe extern "C" fn wingc_compile(ptr: u32, len: u32) -> u64 {
 }
 
 pub fn parse(source_path: &Path) -> (Files, Scope) {
-	let language = tree_sitter_wing::language();
-	let mut parser = tree_sitter::Parser::new();
-	parser.set_language(language).unwrap();
-
 	let source = match fs::read(&source_path) {
 		Ok(source) => source,
 		Err(err) => {
 			report_diagnostic(Diagnostic {
 				message: format!("Error reading source file: {}: {:?}", source_path.display(), err),
 				span: None,
+				severity: DiagnosticSeverity::Error,
+				code: DiagnosticCode::SourceFileNotFound,
+				annotations: vec![],
+				fixes: vec![],
 			});
 
 			// Set up a dummy scope to return
@@ -191,16 +208,54 @@ pub fn parse(source_path: &Path) -> (Files, Scope) {
 		}
 	}
 
-	let tree = match parser.parse(&source, None) {
+	let scope = parse_source(source_path, &source);
+	(files, scope)
+}
+
+/// Formats a Wing source file, returning its original and formatted source.
+/// Fails if the file can't be read or has syntax errors, which are reported as diagnostics.
+pub fn format_file(source_path: &Path) -> Result<(String, String), ()> {
+	let source = match fs::read_to_string(source_path) {
+		Ok(source) => source,
+		Err(err) => {
+			report_diagnostic(Diagnostic {
+				message: format!("Error reading source file: {}: {:?}", source_path.display(), err),
+				span: None,
+				severity: DiagnosticSeverity::Error,
+				code: DiagnosticCode::SourceFileNotFound,
+				annotations: vec![],
+				fixes: vec![],
+			});
+			return Err(());
+		}
+	};
+
+	match formatter::format_source(&source) {
+		Some(formatted) => Ok((source, formatted)),
+		None => {
+			// Parse the file to report its syntax errors
+			parse_source(source_path, source.as_bytes());
+			Err(())
+		}
+	}
+}
+
+/// Parses the given source text of a Wing file into its top scope
+pub(crate) fn parse_source(source_path: &Path, source: &[u8]) -> Scope {
+	let language = tree_sitter_wing::language();
+	let mut parser = tree_sitter::Parser::new();
+	parser.set_language(language).unwrap();
+
+	let tree = match parser.parse(source, None) {
 		Some(tree) => tree,
 		None => {
 			panic!("Failed parsing source file: {}", source_path.display());
 		}
 	};
 
-	let wing_parser = Parser::new(&source, source_path.to_str().unwrap().to_string());
+	let wing_parser = Parser::new(source, source_path.to_str().unwrap().to_string());
 
-	(files, wing_parser.wingit(&tree.root_node()))
+	wing_parser.wingit(&tree.root_node())
 }
 
 pub fn type_check(
@@ -219,11 +274,13 @@ pub fn type_check(
 	add_builtin(
 		UtilityFunctions::Log.to_string().as_str(),
 		Type::Function(FunctionSignature {
+			type_parameters: vec![],
 			this_type: None,
 			parameters: vec![FunctionParameter {
 				name: "message".into(),
 				typeref: types.string(),
 				docs: Docs::with_summary("The message to log"),
+				variadic: false,
 			}],
 			return_type: types.void(),
 			phase: Phase::Independent,
@@ -236,11 +293,13 @@ pub fn type_check(
 	add_builtin(
 		UtilityFunctions::Assert.to_string().as_str(),
 		Type::Function(FunctionSignature {
+			type_parameters: vec![],
 			this_type: None,
 			parameters: vec![FunctionParameter {
 				name: "condition".into(),
 				typeref: types.bool(),
 				docs: Docs::with_summary("The condition to assert"),
+				variadic: false,
 			}],
 			return_type: types.void(),
 			phase: Phase::Independent,
@@ -255,11 +314,13 @@ pub fn type_check(
 	add_builtin(
 		UtilityFunctions::Throw.to_string().as_str(),
 		Type::Function(FunctionSignature {
+			type_parameters: vec![],
 			this_type: None,
 			parameters: vec![FunctionParameter {
 				typeref: types.string(),
 				name: "message".into(),
 				docs: Docs::with_summary("The message to throw"),
+				variadic: false,
 			}],
 			return_type: types.void(),
 			phase: Phase::Independent,
@@ -272,11 +333,13 @@ pub fn type_check(
 	add_builtin(
 		UtilityFunctions::Panic.to_string().as_str(),
 		Type::Function(FunctionSignature {
+			type_parameters: vec![],
 			this_type: None,
 			parameters: vec![FunctionParameter {
 				typeref: types.string(),
 				name: "message".into(),
 				docs: Docs::with_summary("The message to panic with"),
+				variadic: false,
 			}],
 			return_type: types.void(),
 			phase: Phase::Independent,
@@ -293,6 +356,21 @@ pub fn type_check(
 	tc.type_check_scope(scope);
 }
 
+/// Type checks the given Wing modules (which must be in dependency order) and registers the types each of
+/// them defines so they can be brought by other files.
+pub(crate) fn type_check_wing_modules(modules: &mut Vec<WingModule>, types: &mut Types, jsii_types: &mut TypeSystem) {
+	for module in modules {
+		type_check(
+			&mut module.scope,
+			types,
+			&module.source_path,
+			jsii_types,
+			&mut module.jsii_imports,
+		);
+		types.add_wing_module(&module.source_path, &module.scope);
+	}
+}
+
 // TODO: refactor this (why is scope needed?) (move to separate module?)
 fn add_builtin(name: &str, typ: Type, scope: &mut Scope, types: &mut Types) {
 	let sym = Symbol::global(name);
@@ -309,15 +387,86 @@ fn add_builtin(name: &str, typ: Type, scope: &mut Scope, types: &mut Types) {
 		.expect("Failed to add builtin");
 }
 
+thread_local! {
+	static JSII_CACHE: RefCell<Option<AssemblyCache>> = RefCell::new(None);
+}
+
+/// Sets the cache JSII assemblies are loaded through by compilations started from now on (disabled by default)
+pub fn set_jsii_cache(cache: Option<AssemblyCache>) {
+	JSII_CACHE.with(|jsii_cache| *jsii_cache.borrow_mut() = cache);
+}
+
+/// Compiles a Wing source file (and the local Wing modules it brings) to JavaScript, emitting the
+/// resulting files into `out_dir` (defaults to `<file name>.out`).
 pub fn compile(
 	source_path: &Path,
 	out_dir: Option<&Path>,
 	absolute_project_root: Option<&Path>,
 ) -> Result<CompilerOutput, ()> {
+	compile_file(
+		source_path,
+		EmitOptions {
+			out_dir,
+			absolute_project_root,
+			library: false,
+			write_files: true,
+		},
+	)
+}
+
+/// Compiles a Wing library: like `compile`, but the entrypoint is emitted as a module which exports its types
+/// (instead of an app), along with a JSII assembly (`.jsii`) describing them. The name and version of the
+/// assembly are read from the `package.json` at the root of the project.
+pub fn compile_library(
+	source_path: &Path,
+	out_dir: Option<&Path>,
+	absolute_project_root: Option<&Path>,
+) -> Result<CompilerOutput, ()> {
+	compile_file(
+		source_path,
+		EmitOptions {
+			out_dir,
+			absolute_project_root,
+			library: true,
+			write_files: true,
+		},
+	)
+}
+
+/// Runs all the compilation phases on a Wing source file (and the local Wing modules it brings), reporting the
+/// same diagnostics as `compile`, without writing any files.
+pub fn check(source_path: &Path, absolute_project_root: Option<&Path>) -> Result<(), ()> {
+	compile_file(
+		source_path,
+		EmitOptions {
+			out_dir: None,
+			absolute_project_root,
+			library: false,
+			write_files: false,
+		},
+	)
+	.map(|_| ())
+}
+
+struct EmitOptions<'a> {
+	out_dir: Option<&'a Path>,
+	absolute_project_root: Option<&'a Path>,
+	/// Whether the source file is the entrypoint of a library rather than an app
+	library: bool,
+	/// Whether the compiled files are written to `out_dir`, otherwise they're only generated (to report diagnostics)
+	write_files: bool,
+}
+
+/// Runs the compiler on a source file.
+fn compile_file(source_path: &Path, emit: EmitOptions) -> Result<CompilerOutput, ()> {
 	if !source_path.exists() {
 		report_diagnostic(Diagnostic {
 			message: format!("Source file cannot be found: {}", source_path.display()),
 			span: None,
+			severity: DiagnosticSeverity::Error,
+			code: DiagnosticCode::SourceFileNotFound,
+			annotations: vec![],
+			fixes: vec![],
 		});
 		return Err(());
 	}
@@ -329,21 +478,32 @@ pub fn compile(
 				source_path.display()
 			),
 			span: None,
+			severity: DiagnosticSeverity::Error,
+			code: DiagnosticCode::SourceFileNotFound,
+			annotations: vec![],
+			fixes: vec![],
 		});
 		return Err(());
 	}
 
-	let file_name = source_path.file_name().unwrap().to_str().unwrap();
-	let default_out_dir = PathBuf::from(format!("{}.out", file_name));
-	let out_dir = out_dir.unwrap_or(default_out_dir.as_ref());
-
 	// -- PARSING PHASE --
-	let (files, scope) = parse(&source_path);
+	let (mut files, scope) = parse(&source_path);
+	let entry_file = scope.span.file_id.clone();
+
+	// Parse all the local Wing modules brought by the source file (in dependency order)
+	let dependencies = load_wing_modules(&source_path, &scope, &mut files);
 
 	// -- DESUGARING PHASE --
 
 	// Transform all inflight closures defined in preflight into single-method resources
 	let mut inflight_transformer = ClosureTransformer::new();
+	let mut dependencies = dependencies
+		.into_iter()
+		.map(|module| WingModule {
+			scope: Box::new(inflight_transformer.fold_scope(*module.scope)),
+			..module
+		})
+		.collect::<Vec<_>>();
 	let mut scope = inflight_transformer.fold_scope(scope);
 
 	// -- TYPECHECKING PHASE --
@@ -351,21 +511,33 @@ pub fn compile(
 	// Create universal types collection (need to keep this alive during entire compilation)
 	let mut types = Types::new();
 	let mut jsii_types = TypeSystem::new();
+	jsii_types.set_cache(JSII_CACHE.with(|jsii_cache| jsii_cache.borrow().clone()));
 
 	// Create a universal JSII import spec (need to keep this alive during entire compilation)
 	let mut jsii_imports = vec![];
 
+	// Type check the brought modules first so their types are available to the files that bring them
+	type_check_wing_modules(&mut dependencies, &mut types, &mut jsii_types);
+
 	// Type check everything and build typed symbol environment
 	type_check(&mut scope, &mut types, &source_path, &mut jsii_types, &mut jsii_imports);
 
 	// Validate the type checker didn't miss anything see `TypeCheckAssert` for details
 	let mut tc_assert = TypeCheckAssert::new(&types, found_errors());
+	for module in &dependencies {
+		tc_assert.check(&module.scope);
+	}
 	tc_assert.check(&scope);
 
+	let file_name = source_path.file_name().unwrap().to_str().unwrap();
+	let default_out_dir = PathBuf::from(format!("{}.out", file_name));
+	let out_dir = emit.out_dir.unwrap_or(default_out_dir.as_ref());
+
 	// -- JSIFICATION PHASE --
 
 	let app_name = source_path.file_stem().unwrap().to_str().unwrap();
-	let project_dir = absolute_project_root
+	let project_dir = emit
+		.absolute_project_root
 		.unwrap_or(source_path.parent().unwrap())
 		.to_path_buf();
 
@@ -374,15 +546,26 @@ pub fn compile(
 		report_diagnostic(Diagnostic {
 			message: format!("Project directory must be absolute: {}", project_dir.display()),
 			span: None,
+			severity: DiagnosticSeverity::Error,
+			code: DiagnosticCode::InvalidProjectRoot,
+			annotations: vec![],
+			fixes: vec![],
 		});
 		return Err(());
 	}
 
-	let mut jsifier = JSifier::new(&mut types, &files, app_name, &project_dir, true);
+	let mut jsifier = JSifier::new(&mut types, &files, app_name, &project_dir, &entry_file, true);
 
 	// -- LIFTING PHASE --
 
 	let mut lift = LiftTransform::new(&jsifier);
+	let dependencies = dependencies
+		.into_iter()
+		.map(|module| WingModule {
+			scope: Box::new(lift.fold_scope(*module.scope)),
+			..module
+		})
+		.collect::<Vec<_>>();
 	let scope = Box::new(lift.fold_scope(scope));
 
 	// bail out now (before jsification) if there are errors (no point in jsifying)
@@ -390,11 +573,40 @@ pub fn compile(
 		return Err(());
 	}
 
-	let files = jsifier.jsify(&scope);
+	let mut files = if emit.library {
+		jsifier.jsify_library(&scope)
+	} else {
+		jsifier.jsify(&scope)
+	};
+
+	// emit one JS module per brought Wing module
+	for module in &dependencies {
+		match files.add_files(jsifier.jsify_module(&module.source_path, &module.scope)) {
+			Ok(()) => {}
+			Err(err) => report_diagnostic(err.into()),
+		}
+	}
+
+	if emit.library {
+		if let Some(package_json) = read_package_manifest(&project_dir) {
+			let env = scope.env.borrow();
+			let exporter = JsiiExporter::new(jsifier.types, &jsii_types, &package_json, &scope.span.file_id);
+			let assembly = exporter.export(env.as_ref().unwrap());
+			match files.add_file(
+				SPEC_FILE_NAME,
+				serde_json::to_string_pretty(&assembly).expect("Failed to serialize JSII assembly"),
+			) {
+				Ok(()) => {}
+				Err(err) => report_diagnostic(err.into()),
+			}
+		}
+	}
 
-	match files.emit_files(out_dir) {
-		Ok(()) => {}
-		Err(err) => report_diagnostic(err.into()),
+	if emit.write_files {
+		match files.emit_files(out_dir) {
+			Ok(()) => {}
+			Err(err) => report_diagnostic(err.into()),
+		}
 	}
 
 	if found_errors() {
@@ -404,6 +616,31 @@ pub fn compile(
 	return Ok(CompilerOutput {});
 }
 
+/// Reads the `package.json` of a library, which must specify the name and version of its package
+fn read_package_manifest(project_dir: &Path) -> Option<serde_json::Value> {
+	let path = project_dir.join("package.json");
+	let manifest = fs::read_to_string(&path)
+		.map_err(|err| err.to_string())
+		.and_then(|content| serde_json::from_str::<serde_json::Value>(&content).map_err(|err| err.to_string()));
+	let message = match manifest {
+		Ok(manifest) if manifest["name"].is_string() && manifest["version"].is_string() => return Some(manifest),
+		Ok(_) => format!(
+			"Library manifest {} must specify the \"name\" and \"version\" of the package",
+			path.display()
+		),
+		Err(err) => format!("Unable to read library manifest {}: {}", path.display(), err),
+	};
+	report_diagnostic(Diagnostic {
+		message,
+		span: None,
+		severity: DiagnosticSeverity::Error,
+		code: DiagnosticCode::InvalidPackageManifest,
+		annotations: vec![],
+		fixes: vec![],
+	});
+	None
+}
+
 fn is_project_dir_absolute(project_dir: &PathBuf) -> bool {
 	if project_dir.starts_with("/") {
 		return true;
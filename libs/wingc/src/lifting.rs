@@ -1,12 +1,13 @@
 use crate::{
-	ast::{Class, Expr, ExprKind, FunctionBody, FunctionDefinition, Phase, Reference, UserDefinedType},
+	ast::{Class, ClassField, Expr, ExprKind, FunctionBody, FunctionDefinition, Phase, Reference, UserDefinedType},
 	comp_ctx::{CompilationContext, CompilationPhase},
-	diagnostic::{report_diagnostic, Diagnostic, WingSpan},
+	diagnostic::{report_diagnostic, Diagnostic, DiagnosticCode, DiagnosticSeverity, WingSpan},
 	files::Files,
 	fold::{self, Fold},
 	jsify::{JSifier, JSifyContext},
 	type_check::{
-		lifts::Lifts, resolve_user_defined_type, symbol_env::LookupResult, TypeRef, CLOSURE_CLASS_HANDLE_METHOD,
+		lifts::Lifts, resolve_user_defined_type, symbol_env::LookupResult, TypeRef, CLASS_INFLIGHT_INIT_NAME,
+		CLOSURE_CLASS_HANDLE_METHOD,
 	},
 	visit_context::VisitContext,
 };
@@ -58,6 +59,10 @@ impl<'a> LiftTransform<'a> {
 						message: format!(
 							"Cannot capture symbol \"{fullname}\" because it is shadowed by another symbol with the same name"
 						),
+						severity: DiagnosticSeverity::Error,
+						code: DiagnosticCode::ShadowedCapture,
+						annotations: vec![],
+						fixes: vec![],
 					});
 					return true;
 				}
@@ -135,6 +140,8 @@ impl<'a> LiftTransform<'a> {
 				phase: phase,
 				files: &mut Files::default(),
 				lifts: None,
+				dtsifier: None,
+				in_static_initializer: false,
 			},
 		)
 	}
@@ -214,6 +221,10 @@ impl<'a> Fold for LiftTransform<'a> {
 						expr_type.to_string()
 					),
 					span: Some(node.span.clone()),
+					severity: DiagnosticSeverity::Error,
+					code: DiagnosticCode::UnqualifiedLift,
+					annotations: vec![],
+					fixes: vec![],
 				});
 
 				return node;
@@ -264,6 +275,7 @@ impl<'a> Fold for LiftTransform<'a> {
 					body: FunctionBody::Statements(self.fold_scope(scope)),
 					signature: self.fold_function_signature(node.signature.clone()),
 					is_static: node.is_static,
+					access_modifier: node.access_modifier,
 					span: node.span.clone(),
 				};
 
@@ -282,6 +294,11 @@ impl<'a> Fold for LiftTransform<'a> {
 		if self.ctx.current_phase() == Phase::Inflight && node.phase == Phase::Inflight {
 			return Class {
 				name: self.fold_symbol(node.name),
+				type_parameters: node
+					.type_parameters
+					.into_iter()
+					.map(|p| self.fold_type_parameter(p))
+					.collect(),
 				fields: node
 					.fields
 					.into_iter()
@@ -326,6 +343,7 @@ impl<'a> Fold for LiftTransform<'a> {
 		let udt = UserDefinedType {
 			root: node.name.clone(),
 			fields: vec![],
+			type_arguments: vec![],
 			span: node.name.span.clone(),
 		};
 
@@ -355,6 +373,23 @@ impl<'a> Fold for LiftTransform<'a> {
 		result
 	}
 
+	fn fold_class_field(&mut self, node: ClassField) -> ClassField {
+		// the initializers of inflight static fields run when the inflight client of a preflight class is loaded, so
+		// anything they lift is attributed to the inflight initializer
+		if node.is_static && node.phase == Phase::Inflight && self.ctx.current_phase() == Phase::Preflight {
+			if let Some(env) = self.ctx.current_method_env().cloned() {
+				self
+					.ctx
+					.push_function_definition(&Some(CLASS_INFLIGHT_INIT_NAME.into()), &Phase::Inflight, env);
+				let result = fold::fold_class_field(self, node);
+				self.ctx.pop_function_definition();
+				return result;
+			}
+		}
+
+		fold::fold_class_field(self, node)
+	}
+
 	fn fold_scope(&mut self, node: crate::ast::Scope) -> crate::ast::Scope {
 		self.ctx.push_env(node.env.borrow().as_ref().unwrap().get_ref());
 		let result = fold::fold_scope(self, node);
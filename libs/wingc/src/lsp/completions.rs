@@ -5,16 +5,18 @@ use lsp_types::{
 };
 use std::cmp::max;
 use tree_sitter::{Node, Point};
+use wingii::type_system::TypeSystem;
 
-use crate::ast::{Expr, ExprKind, Phase, Scope, Symbol, TypeAnnotation, TypeAnnotationKind, UserDefinedType};
+use crate::ast::{Expr, ExprKind, Phase, Scope, Symbol, TypeAnnotation, TypeAnnotationKind};
 use crate::closure_transform::{CLOSURE_CLASS_PREFIX, PARENT_THIS_NAME};
 use crate::diagnostic::{WingLocation, WingSpan};
 use crate::docs::Documented;
 use crate::lsp::sync::{FILES, JSII_TYPES};
-use crate::type_check::symbol_env::{LookupResult, StatementIdx};
+use crate::type_check::jsii_importer::{import_namespace_on_demand, lookup_fqn_importing, lookup_nested_importing};
+use crate::type_check::symbol_env::{LookupResult, StatementIdx, SymbolEnv};
 use crate::type_check::{
-	fully_qualify_std_type, import_udt_from_jsii, resolve_user_defined_type, ClassLike, Namespace, Struct, SymbolKind,
-	Type, Types, UnsafeRef, VariableKind, CLASS_INFLIGHT_INIT_NAME, CLASS_INIT_NAME,
+	fully_qualify_std_type, ClassLike, Namespace, Struct, SymbolKind, Type, Types, UnsafeRef, VariableKind,
+	CLASS_INFLIGHT_INIT_NAME, CLASS_INIT_NAME,
 };
 use crate::visit::{visit_expr, visit_type_annotation, Visit};
 use crate::wasm_util::{ptr_to_string, string_to_combined_ptr, WASM_RETURN_ERROR};
@@ -35,15 +37,16 @@ pub unsafe extern "C" fn wingc_on_completion(ptr: u32, len: u32) -> u64 {
 }
 
 pub fn on_completion(params: lsp_types::CompletionParams) -> CompletionResponse {
-	let mut final_completions = FILES.with(|files| {
+	let mut final_completions = JSII_TYPES.with(|jsii_types| {
+		let jsii_types = &jsii_types.borrow();
+		FILES.with(|files| {
 		let mut files = files.borrow_mut();
 		let uri = params.text_document_position.text_document.uri;
 		let file_data = files.get_mut(&uri).expect("File must be open to get completions");
-		let types = &file_data.types;
+		// Types which aren't used by the file yet are imported when they're looked up for completions
+		let types = &mut file_data.types;
 		let root_ts_node = file_data.tree.root_node();
 		let root_scope = &file_data.scope;
-		let root_env = root_scope.env.borrow();
-		let root_env = root_env.as_ref().expect("The root scope must have an environment");
 		let file = uri.to_file_path().ok().expect("LSP only works on real filesystems");
 		let file_id = file.to_str().expect("File path must be valid utf8");
 
@@ -131,7 +134,7 @@ pub fn on_completion(params: lsp_types::CompletionParams) -> CompletionResponse
 						return vec![];
 					}
 
-					let mut completions = get_completions_from_type(&nearest_expr_type, types, Some(found_env.phase), true);
+					let mut completions = get_completions_from_type(&nearest_expr_type, types, jsii_types, found_env, Some(found_env.phase), true);
 					if nearest_expr_type.is_option() {
 						// check to see if we need to add a ? to the completion
 						let replace_node = if node_to_complete_kind == "." {
@@ -194,22 +197,26 @@ pub fn on_completion(params: lsp_types::CompletionParams) -> CompletionResponse
 
 			if let Some(nearest_type_annotation) = scope_visitor.nearest_type_annotation {
 				if let TypeAnnotationKind::UserDefined(udt) = &nearest_type_annotation.kind {
-					let type_lookup = resolve_user_defined_type(udt, found_env, scope_visitor.found_stmt_index.unwrap_or(0));
+					let mut nested_vec = vec![&udt.root];
+					nested_vec.extend(udt.fields.iter());
+					let type_lookup = lookup_nested_importing(
+						found_env,
+						&nested_vec,
+						scope_visitor.found_stmt_index,
+						types,
+						jsii_types,
+					);
 
-					let completions = if let Ok(type_lookup) = type_lookup {
-						get_completions_from_type(&type_lookup, types, Some(found_env.phase), false)
-					} else {
-						// this is probably a namespace, let's look it up
-						if let Some(namespace) = root_env
-							.lookup_nested_str(&udt.full_path_str(), scope_visitor.found_stmt_index)
-							.ok()
-							.and_then(|n| n.0.as_namespace_ref())
-						{
-							get_completions_from_namespace(&namespace, Some(found_env.phase))
-						} else {
-							// This is not a known type or namespace
-							vec![]
+					let completions = match type_lookup {
+						LookupResult::Found(SymbolKind::Type(t), _) => {
+							get_completions_from_type(t, types, jsii_types, found_env, Some(found_env.phase), false)
+						}
+						LookupResult::Found(SymbolKind::Namespace(namespace), _) => {
+							import_namespace_on_demand(*namespace, types, jsii_types);
+							get_completions_from_namespace(namespace, found_env, Some(found_env.phase))
 						}
+						// This is not a known type or namespace
+						_ => vec![],
 					};
 
 					return filter_completions(completions);
@@ -221,30 +228,23 @@ pub fn on_completion(params: lsp_types::CompletionParams) -> CompletionResponse
 			let reference_text =
 				fully_qualify_std_type(std::str::from_utf8(reference_bytes).expect("Reference must be valid utf8"));
 
-			if let Some((lookup_thing, _)) = found_env
-				.lookup_nested_str(&reference_text, scope_visitor.found_stmt_index)
-				.ok()
+			let nested_vec = reference_text.split('.').map(Symbol::global).collect_vec();
+			if let Some((lookup_thing, _)) = lookup_nested_importing(
+				found_env,
+				&nested_vec.iter().collect_vec(),
+				scope_visitor.found_stmt_index,
+				types,
+				jsii_types,
+			)
+			.ok()
 			{
 				let completions = match lookup_thing {
-					SymbolKind::Type(t) => get_completions_from_type(&t, types, Some(found_env.phase), false),
-					SymbolKind::Variable(v) => get_completions_from_type(&v.type_, types, Some(found_env.phase), false),
+					SymbolKind::Type(t) => get_completions_from_type(&t, types, jsii_types, found_env, Some(found_env.phase), false),
+					SymbolKind::Variable(v) => get_completions_from_type(&v.type_, types, jsii_types, found_env, Some(found_env.phase), false),
 					SymbolKind::Namespace(n) => {
 						// If the types in this namespace aren't loaded yet, load them now to get completions
-						if !n.loaded {
-							JSII_TYPES.with(|jsii_types| {
-								let mut jsii_types = jsii_types.borrow_mut();
-								let parts = reference_text.split(".").collect::<Vec<_>>();
-								// Dummy type representing the namespace to be loaded
-								let udt = UserDefinedType {
-									root: Symbol::global(parts[0].to_string()),
-									fields: parts[1..].iter().map(|s| Symbol::global(s.to_string())).collect(),
-									span: WingSpan::default(),
-								};
-								// Import all types in the namespace by trying to load the "dummy type"
-								import_udt_from_jsii(&mut file_data.types, &mut jsii_types, &udt, &file_data.jsii_imports);
-							});
-						}
-						get_completions_from_namespace(&n, Some(found_env.phase))
+						import_namespace_on_demand(*n, types, jsii_types);
+						get_completions_from_namespace(&n, found_env, Some(found_env.phase))
 					}
 				};
 
@@ -322,6 +322,7 @@ pub fn on_completion(params: lsp_types::CompletionParams) -> CompletionResponse
 
 		// fallback: no special completions, just get stuff from the current scope
 		get_current_scope_completions(&scope_visitor, &node_to_complete, preceding_text)
+	})
 	});
 
 	final_completions = final_completions
@@ -596,15 +597,17 @@ fn get_inner_struct_completions(struct_: &Struct, existing_fields: &Vec<String>)
 /// Gets accessible properties on a type as a list of CompletionItems
 fn get_completions_from_type(
 	type_: &UnsafeRef<Type>,
-	types: &Types,
+	types: &mut Types,
+	jsii_types: &TypeSystem,
+	current_env: &SymbolEnv,
 	current_phase: Option<Phase>,
 	is_instance: bool,
 ) -> Vec<CompletionItem> {
 	let type_ = &**type_.maybe_unwrap_option();
 	match type_ {
-		Type::Class(c) => get_completions_from_class(c, current_phase, is_instance),
-		Type::Interface(i) => get_completions_from_class(i, current_phase, is_instance),
-		Type::Struct(s) => get_completions_from_class(s, current_phase, is_instance),
+		Type::Class(c) => get_completions_from_class(c, current_env, current_phase, is_instance),
+		Type::Interface(i) => get_completions_from_class(i, current_env, current_phase, is_instance),
+		Type::Struct(s) => get_completions_from_class(s, current_env, current_phase, is_instance),
 		Type::Enum(enum_) => {
 			let variants = &enum_.values;
 			variants
@@ -617,11 +620,16 @@ fn get_completions_from_type(
 				})
 				.collect()
 		}
-		Type::Optional(t) => get_completions_from_type(t, types, current_phase, is_instance),
-		Type::Void | Type::Function(_) | Type::Anything | Type::Unresolved => vec![],
+		Type::Optional(t) => get_completions_from_type(t, types, jsii_types, current_env, current_phase, is_instance),
+		// The members of a type parameter are the members of its bound
+		Type::TypeParameter(p) => p.bound.map_or(vec![], |bound| {
+			get_completions_from_type(&bound, types, jsii_types, current_env, current_phase, is_instance)
+		}),
+		Type::Void | Type::Function(_) | Type::Promise(_) | Type::Union(_) | Type::Anything | Type::Unresolved => vec![],
 		Type::Number
 		| Type::String
 		| Type::Duration
+		| Type::Datetime
 		| Type::Boolean
 		| Type::Json
 		| Type::MutJson
@@ -640,6 +648,7 @@ fn get_completions_from_type(
 			type_name = match type_name {
 				"str" => "String",
 				"duration" => "Duration",
+				"datetime" => "Datetime",
 				"bool" => "Boolean",
 				"num" => "Number",
 				_ => type_name,
@@ -648,8 +657,9 @@ fn get_completions_from_type(
 			let final_type_name = final_type_name.as_str();
 
 			let fqn = format!("{WINGSDK_ASSEMBLY_NAME}.{final_type_name}");
-			if let LookupResult::Found(std_type, _) = types.libraries.lookup_nested_str(fqn.as_str(), None) {
-				return get_completions_from_type(&std_type.as_type().expect("is type"), types, current_phase, is_instance);
+			if let LookupResult::Found(std_type, _) = lookup_fqn_importing(&fqn, types, jsii_types) {
+				let std_type = std_type.as_type().expect("is type");
+				return get_completions_from_type(&std_type, types, jsii_types, current_env, current_phase, is_instance);
 			} else {
 				vec![]
 			}
@@ -659,6 +669,7 @@ fn get_completions_from_type(
 
 fn get_completions_from_namespace(
 	namespace: &UnsafeRef<Namespace>,
+	current_env: &SymbolEnv,
 	current_phase: Option<Phase>,
 ) -> Vec<CompletionItem> {
 	// If a namespace has a class named "Util", then its members can be accessed directly from
@@ -668,7 +679,7 @@ fn get_completions_from_namespace(
 			SymbolKind::Type(typeref) => {
 				let util_class = typeref.as_class();
 				if let Some(util_class) = util_class {
-					get_completions_from_class(util_class, current_phase, false)
+					get_completions_from_class(util_class, current_env, current_phase, false)
 				} else {
 					vec![]
 				}
@@ -692,6 +703,7 @@ fn get_completions_from_namespace(
 /// Gets accessible properties on a class as a list of CompletionItems
 fn get_completions_from_class(
 	class: &impl ClassLike,
+	current_env: &SymbolEnv,
 	current_phase: Option<Phase>,
 	is_instance: bool,
 ) -> Vec<CompletionItem> {
@@ -713,6 +725,11 @@ fn get_completions_from_class(
 				return None;
 			}
 
+			// hide private and protected members that can't be accessed from the current scope
+			if !current_env.can_access_member(variable.access_modifier, &symbol_data.2.env) {
+				return None;
+			}
+
 			// See `Phase::can_call_to` for phase access rules
 			if let Some(current_phase) = current_phase {
 				if variable.type_.maybe_unwrap_option().as_function_sig().is_some()
@@ -759,22 +776,26 @@ fn format_symbol_kind_as_completion(name: &str, symbol_kind: &SymbolKind) -> Opt
 				| Type::MutMap(_)
 				| Type::Set(_)
 				| Type::MutSet(_)
+				| Type::Promise(_)
 				| Type::Class(_) => CompletionItemKind::CLASS,
 				Type::Anything
 				| Type::Number
 				| Type::String
 				| Type::Duration
+				| Type::Datetime
 				| Type::Boolean
 				| Type::Void
 				| Type::Json
 				| Type::MutJson
 				| Type::Nil
 				| Type::Unresolved
-				| Type::Optional(_) => CompletionItemKind::CONSTANT,
+				| Type::Optional(_)
+				| Type::Union(_) => CompletionItemKind::CONSTANT,
 				Type::Function(_) => CompletionItemKind::FUNCTION,
 				Type::Struct(_) => CompletionItemKind::STRUCT,
 				Type::Enum(_) => CompletionItemKind::ENUM,
 				Type::Interface(_) => CompletionItemKind::INTERFACE,
+				Type::TypeParameter(_) => CompletionItemKind::TYPE_PARAMETER,
 			}),
 			..Default::default()
 		},
@@ -1026,6 +1047,23 @@ Resource.
 		assert!(static_method_call.iter().filter(|c| c.label == "hello").count() == 1)
 	);
 
+	test_completion_list!(
+		hide_private_static_methods,
+		r#"
+class Resource {
+	static hello() {}
+	private static secret() {}
+	protected static shared() {}
+}
+
+Resource. 
+       //^
+"#,
+		assert!(hide_private_static_methods.iter().filter(|c| c.label == "hello").count() == 1)
+
+		assert!(hide_private_static_methods.iter().all(|c| c.label != "secret" && c.label != "shared"))
+	);
+
 	test_completion_list!(
 		only_show_symbols_in_scope,
 		r#"
@@ -0,0 +1,89 @@
+use lsp_types::{PublishDiagnosticsParams, TextDocumentIdentifier};
+use serde::Deserialize;
+
+use crate::diagnostic::get_diagnostics;
+use crate::lsp::sync::FILES;
+use crate::wasm_util::{ptr_to_string, string_to_combined_ptr, WASM_RETURN_ERROR};
+
+/// Parameters of `wingc_on_diagnostics`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticsParams {
+	pub text_document: TextDocumentIdentifier,
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn wingc_on_diagnostics(ptr: u32, len: u32) -> u64 {
+	let parse_string = ptr_to_string(ptr, len);
+	if let Ok(parsed) = serde_json::from_str(&parse_string) {
+		let diagnostics = on_diagnostics(parsed);
+		let result = serde_json::to_string(&diagnostics).expect("Failed to serialize PublishDiagnostics params");
+
+		string_to_combined_ptr(result)
+	} else {
+		eprintln!("Failed to parse 'onDiagnostics' text document: {}", parse_string);
+		WASM_RETURN_ERROR
+	}
+}
+
+/// Returns the diagnostics of the last compilation of an open document, ready to be published to the client
+/// in a `textDocument/publishDiagnostics` notification.
+///
+/// Only diagnostics in the document itself (or without a location) are included, diagnostics in the Wing
+/// modules it brings are published for those files when they are opened.
+pub fn on_diagnostics(params: DiagnosticsParams) -> PublishDiagnosticsParams {
+	let uri = params.text_document.uri;
+	let file_id = uri.to_file_path().ok().map(|path| path.to_string_lossy().to_string());
+
+	let diagnostics = FILES.with(|files| {
+		// If the compiler panicked while compiling the document it won't have any data, but the panic is still
+		// reported as a diagnostic of the last compilation
+		files
+			.borrow()
+			.get(&uri)
+			.map(|file_data| file_data.diagnostics.clone())
+			.unwrap_or_else(get_diagnostics)
+	});
+
+	PublishDiagnosticsParams {
+		uri,
+		diagnostics: diagnostics
+			.into_iter()
+			.filter(|diagnostic| match &diagnostic.span {
+				Some(span) => Some(&span.file_id) == file_id.as_ref(),
+				None => true,
+			})
+			.map(|diagnostic| diagnostic.into())
+			.collect(),
+		version: None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::lsp::diagnostics::*;
+	use crate::lsp::sync::test_utils::*;
+	use lsp_types::*;
+
+	#[test]
+	fn publishes_document_diagnostics() {
+		let position = load_file_with_contents(
+			r#"
+let x: num = "hello";
+//^
+"#,
+		);
+
+		let published = on_diagnostics(DiagnosticsParams {
+			text_document: position.text_document.clone(),
+		});
+
+		assert_eq!(published.uri, position.text_document.uri);
+		assert_eq!(published.diagnostics.len(), 1);
+		let diagnostic = &published.diagnostics[0];
+		assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::ERROR));
+		assert_eq!(diagnostic.source, Some("wing".to_string()));
+		assert_eq!(diagnostic.code, Some(NumberOrString::String("E2002".to_string())));
+		assert_eq!(diagnostic.range.start, Position { line: 1, character: 13 });
+	}
+}
@@ -0,0 +1,177 @@
+use lsp_types::{DocumentFormattingParams, DocumentRangeFormattingParams, Position, Range, TextEdit, Url};
+use similar::{DiffTag, TextDiff};
+
+use crate::formatter::format;
+use crate::lsp::sync::FILES;
+use crate::wasm_util::{ptr_to_string, string_to_combined_ptr, WASM_RETURN_ERROR};
+
+#[no_mangle]
+pub unsafe extern "C" fn wingc_on_formatting(ptr: u32, len: u32) -> u64 {
+	let parse_string = ptr_to_string(ptr, len);
+	if let Ok(parsed) = serde_json::from_str(&parse_string) {
+		let edits = on_formatting(parsed);
+		let result = serde_json::to_string(&edits).expect("Failed to serialize Formatting response");
+
+		string_to_combined_ptr(result)
+	} else {
+		eprintln!("Failed to parse 'onFormatting' text document: {}", parse_string);
+		WASM_RETURN_ERROR
+	}
+}
+
+/// Formats a whole document. The formatter is opinionated, so the client's formatting options are ignored.
+pub fn on_formatting(params: DocumentFormattingParams) -> Vec<TextEdit> {
+	format_document(&params.text_document.uri, None)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn wingc_on_range_formatting(ptr: u32, len: u32) -> u64 {
+	let parse_string = ptr_to_string(ptr, len);
+	if let Ok(parsed) = serde_json::from_str(&parse_string) {
+		let edits = on_range_formatting(parsed);
+		let result = serde_json::to_string(&edits).expect("Failed to serialize RangeFormatting response");
+
+		string_to_combined_ptr(result)
+	} else {
+		eprintln!("Failed to parse 'onRangeFormatting' text document: {}", parse_string);
+		WASM_RETURN_ERROR
+	}
+}
+
+/// Formats the lines of a document within a range. The whole document is formatted, but only the changes to
+/// these lines are returned.
+pub fn on_range_formatting(params: DocumentRangeFormattingParams) -> Vec<TextEdit> {
+	format_document(&params.text_document.uri, Some(params.range))
+}
+
+/// Returns the edits that format an open document, limited to the lines of `range` if given.
+/// Documents with syntax errors aren't formatted.
+fn format_document(uri: &Url, range: Option<Range>) -> Vec<TextEdit> {
+	FILES.with(|files| {
+		let files = files.borrow();
+		let Some(file_data) = files.get(uri) else {
+			return vec![];
+		};
+		let Some(formatted) = format(&file_data.contents, &file_data.tree) else {
+			return vec![];
+		};
+
+		// Replace only the lines that changed, so the client keeps the cursor position and markers on the rest
+		let diff = TextDiff::from_lines(file_data.contents.as_str(), formatted.as_str());
+		let old_lines = diff.old_slices();
+		let new_lines = diff.new_slices();
+		diff
+			.ops()
+			.iter()
+			.filter(|op| op.tag() != DiffTag::Equal)
+			.filter(|op| match range {
+				Some(range) => {
+					let lines = op.old_range();
+					// Lines that are only inserted are attributed to the line they're inserted before
+					lines.start <= range.end.line as usize && lines.end.max(lines.start + 1) > range.start.line as usize
+				}
+				None => true,
+			})
+			.map(|op| {
+				let lines = op.old_range();
+				let end = if lines.end < old_lines.len() {
+					Position::new(lines.end as u32, 0)
+				} else {
+					end_position(&file_data.contents)
+				};
+				TextEdit {
+					range: Range::new(Position::new(lines.start as u32, 0), end),
+					new_text: new_lines[op.new_range()].concat(),
+				}
+			})
+			.collect()
+	})
+}
+
+/// Returns the position at the end of a text
+fn end_position(text: &str) -> Position {
+	let line = text.matches('\n').count();
+	let last_line = text.rsplit('\n').next().unwrap_or_default();
+	Position::new(line as u32, last_line.encode_utf16().count() as u32)
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::lsp::formatting::*;
+	use crate::lsp::sync::test_utils::*;
+	use lsp_types::*;
+
+	fn formatting_params(uri: Url) -> DocumentFormattingParams {
+		DocumentFormattingParams {
+			text_document: TextDocumentIdentifier { uri },
+			options: FormattingOptions {
+				tab_size: 2,
+				insert_spaces: true,
+				..Default::default()
+			},
+			work_done_progress_params: Default::default(),
+		}
+	}
+
+	#[test]
+	fn formats_changed_lines() {
+		let position = load_file_with_contents(
+			r#"let x = 1;
+let y   =  x;
+//^
+let z = y;"#,
+		);
+
+		let edits = on_formatting(formatting_params(position.text_document.uri));
+
+		assert_eq!(
+			edits,
+			vec![
+				TextEdit {
+					range: Range::new(Position::new(1, 0), Position::new(2, 0)),
+					new_text: "let y = x;\n".to_string(),
+				},
+				TextEdit {
+					range: Range::new(Position::new(3, 0), Position::new(3, 10)),
+					new_text: "let z = y;\n".to_string(),
+				},
+			]
+		);
+	}
+
+	#[test]
+	fn range_formatting_only_changes_range() {
+		let position = load_file_with_contents(
+			r#"let x  = 1;
+//^
+let y   =  x;
+"#,
+		);
+
+		let edits = on_range_formatting(DocumentRangeFormattingParams {
+			text_document: position.text_document.clone(),
+			range: Range::new(Position::new(2, 0), Position::new(2, 5)),
+			options: formatting_params(position.text_document.uri.clone()).options,
+			work_done_progress_params: Default::default(),
+		});
+
+		assert_eq!(
+			edits,
+			vec![TextEdit {
+				range: Range::new(Position::new(2, 0), Position::new(3, 0)),
+				new_text: "let y = x;\n".to_string(),
+			}]
+		);
+	}
+
+	#[test]
+	fn does_not_format_syntax_errors() {
+		let position = load_file_with_contents(
+			r#"let x  = ;
+//^
+"#,
+		);
+
+		assert!(on_formatting(formatting_params(position.text_document.uri)).is_empty());
+	}
+}
@@ -4,17 +4,19 @@ use crate::ast::{
 };
 use crate::diagnostic::WingSpan;
 use crate::docs::Documented;
-use crate::lsp::sync::FILES;
+use crate::lsp::sync::{FILES, JSII_TYPES};
 use crate::type_check::symbol_env::LookupResult;
 use crate::type_check::{ClassLike, Type, Types, CLASS_INFLIGHT_INIT_NAME, CLASS_INIT_NAME};
 use crate::visit::{self, Visit};
 use crate::wasm_util::WASM_RETURN_ERROR;
 use crate::wasm_util::{ptr_to_string, string_to_combined_ptr};
 use lsp_types::{Hover, HoverContents, MarkupContent, MarkupKind, Position};
+use wingii::type_system::TypeSystem;
 
 pub struct HoverVisitor<'a> {
 	position: Position,
-	types: &'a Types,
+	types: &'a mut Types,
+	jsii_types: &'a TypeSystem,
 	current_scope: &'a Scope,
 	current_expr: Option<&'a Expr>,
 	current_statement_index: usize,
@@ -22,9 +24,10 @@ pub struct HoverVisitor<'a> {
 }
 
 impl<'a> HoverVisitor<'a> {
-	pub fn new(position: Position, scope: &'a Scope, types: &'a Types) -> Self {
+	pub fn new(position: Position, scope: &'a Scope, types: &'a mut Types, jsii_types: &'a TypeSystem) -> Self {
 		Self {
 			types,
+			jsii_types,
 			position,
 			current_scope: scope,
 			current_expr: None,
@@ -82,7 +85,13 @@ impl<'a> HoverVisitor<'a> {
 		if property.span.contains(&self.position) {
 			let new_span = self.current_expr.unwrap().span.clone();
 			match &**obj_type.maybe_unwrap_option() {
-				Type::Optional(_) | Type::Anything | Type::Void | Type::Nil | Type::Unresolved => {}
+				Type::Optional(_)
+				| Type::Anything
+				| Type::Void
+				| Type::Nil
+				| Type::Promise(_)
+				| Type::Union(_)
+				| Type::Unresolved => {}
 
 				Type::Array(_)
 				| Type::MutArray(_)
@@ -95,8 +104,9 @@ impl<'a> HoverVisitor<'a> {
 				| Type::Number
 				| Type::String
 				| Type::Duration
+				| Type::Datetime
 				| Type::Boolean => {
-					if let Some((std_type, ..)) = self.types.get_std_class(&obj_type.to_string()) {
+					if let Some((std_type, ..)) = self.types.get_std_class(&obj_type.to_string(), self.jsii_types) {
 						if let Some(c) = std_type.as_type() {
 							if let Some(c) = c.as_class() {
 								self.found = Some((new_span, docs_from_classlike_property(c, property)));
@@ -120,6 +130,16 @@ impl<'a> HoverVisitor<'a> {
 				Type::Struct(c) => {
 					self.found = Some((new_span, docs_from_classlike_property(c, property)));
 				}
+				Type::TypeParameter(p) => {
+					// The members of a type parameter are the members of its bound
+					let docs = match p.bound.as_deref() {
+						Some(Type::Class(c)) => docs_from_classlike_property(c, property),
+						Some(Type::Interface(c)) => docs_from_classlike_property(c, property),
+						Some(Type::Struct(c)) => docs_from_classlike_property(c, property),
+						_ => None,
+					};
+					self.found = Some((new_span, docs));
+				}
 			}
 		}
 	}
@@ -294,6 +314,9 @@ impl<'a> Visit<'a> for HoverVisitor<'a> {
 				}
 
 				v.visit_type_annotation(&field.member_type);
+				if let Some(initializer) = &field.initializer {
+					v.visit_expr(initializer);
+				}
 			}
 
 			for method in &node.methods {
@@ -351,6 +374,7 @@ impl<'a> Visit<'a> for HoverVisitor<'a> {
 			}
 			Reference::InstanceMember { object, property, .. } => self.visit_reference_with_member(object, property),
 			Reference::TypeMember { typeobject, property } => self.visit_reference_with_member(&typeobject, property),
+			Reference::ElementAccess { .. } => {}
 		}
 
 		visit::visit_reference(self, node);
@@ -374,35 +398,40 @@ pub unsafe extern "C" fn wingc_on_hover(ptr: u32, len: u32) -> u64 {
 	}
 }
 pub fn on_hover(params: lsp_types::HoverParams) -> Option<Hover> {
-	FILES.with(|files| {
-		let files = files.borrow();
-		let file_data = files.get(&params.text_document_position_params.text_document.uri.clone());
-		let file_data = file_data.expect(
-			format!(
-				"Compiled data not found for \"{}\"",
-				params.text_document_position_params.text_document.uri
-			)
-			.as_str(),
-		);
-
-		let root_scope = &file_data.scope;
-
-		let mut hover_visitor = HoverVisitor::new(
-			params.text_document_position_params.position,
-			&root_scope,
-			&file_data.types,
-		);
-		if let Some((span, Some(docs))) = hover_visitor.visit() {
-			Some(Hover {
-				contents: HoverContents::Markup(MarkupContent {
-					kind: MarkupKind::Markdown,
-					value: docs,
-				}),
-				range: Some(span.clone().into()),
-			})
-		} else {
-			None
-		}
+	JSII_TYPES.with(|jsii_types| {
+		FILES.with(|files| {
+			let mut files = files.borrow_mut();
+			let file_data = files.get_mut(&params.text_document_position_params.text_document.uri.clone());
+			let file_data = file_data.expect(
+				format!(
+					"Compiled data not found for \"{}\"",
+					params.text_document_position_params.text_document.uri
+				)
+				.as_str(),
+			);
+
+			let root_scope = &file_data.scope;
+
+			// Types of the standard library which the file doesn't use yet are imported when hovering over them
+			let jsii_types = jsii_types.borrow();
+			let mut hover_visitor = HoverVisitor::new(
+				params.text_document_position_params.position,
+				&root_scope,
+				&mut file_data.types,
+				&jsii_types,
+			);
+			if let Some((span, Some(docs))) = hover_visitor.visit() {
+				Some(Hover {
+					contents: HoverContents::Markup(MarkupContent {
+						kind: MarkupKind::Markdown,
+						value: docs,
+					}),
+					range: Some(span.clone().into()),
+				})
+			} else {
+				None
+			}
+		})
 	})
 }
 
@@ -1,6 +1,10 @@
 mod completions;
+mod diagnostics;
 mod document_symbols;
+mod formatting;
 mod goto_definition;
 mod hover;
+mod references;
+pub mod server;
 mod signature;
 mod sync;
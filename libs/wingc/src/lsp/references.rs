@@ -0,0 +1,670 @@
+use std::collections::{HashMap, HashSet};
+
+use lsp_types::{
+	Location, Position, PrepareRenameResponse, ReferenceParams, RenameParams, TextDocumentPositionParams, TextEdit, Url,
+	WorkspaceEdit,
+};
+
+use crate::ast::{
+	Class, Expr, ExprKind, FunctionParameter, Interface, Phase, Reference, Scope, Stmt, StmtKind, Symbol, UserDefinedType,
+};
+use crate::diagnostic::WingSpan;
+use crate::lsp::sync::{FileData, FILES};
+use crate::type_check::symbol_env::{LookupResult, SymbolEnv};
+use crate::type_check::{ClassLike, Type, TypeRef, Types, CLASS_INFLIGHT_INIT_NAME, CLASS_INIT_NAME};
+use crate::visit::{self, Visit};
+use crate::wasm_util::{ptr_to_string, string_to_combined_ptr, WASM_RETURN_ERROR};
+
+/// Collects every occurrence of a user declared symbol, paired with the span of its declaration.
+///
+/// Symbols are resolved through the symbol environments created by the type checker, so shadowed
+/// variables and members with the same name (in different classes, structs, etc.) are told apart.
+pub struct ReferenceVisitor<'a> {
+	types: &'a Types,
+	current_scope: Option<&'a Scope>,
+	current_statement_index: usize,
+	/// Spans of the declarations found in the visited scopes
+	declarations: HashSet<WingSpan>,
+	/// Pairs of (occurrence, declaration). Declarations are included as occurrences of themselves.
+	references: Vec<(WingSpan, WingSpan)>,
+	/// Pairs of member declarations which are the same symbol: a member of a class or an interface and the member
+	/// it overrides, implements or redeclares in a type it inherits from
+	linked_declarations: Vec<(WingSpan, WingSpan)>,
+}
+
+impl<'a> ReferenceVisitor<'a> {
+	pub fn new(types: &'a Types) -> Self {
+		Self {
+			types,
+			current_scope: None,
+			current_statement_index: 0,
+			declarations: HashSet::new(),
+			references: vec![],
+			linked_declarations: vec![],
+		}
+	}
+
+	/// Returns the occurrences found in all the visited scopes. Occurrences that resolve to something
+	/// which wasn't declared in one of them (e.g. symbols created by the compiler) are left out.
+	/// Occurrences of linked members are paired with the declaration of the member they all inherit from.
+	pub fn into_references(self) -> Vec<(WingSpan, WingSpan)> {
+		fn root<'s>(inherited_from: &'s HashMap<WingSpan, WingSpan>, mut declaration: &'s WingSpan) -> &'s WingSpan {
+			while let Some(inherited) = inherited_from.get(declaration) {
+				declaration = inherited;
+			}
+			declaration
+		}
+
+		let declarations = self.declarations;
+		let mut inherited_from = HashMap::new();
+		for (member, inherited) in self.linked_declarations {
+			if !declarations.contains(&member) || !declarations.contains(&inherited) {
+				continue;
+			}
+			let (member, inherited) = (
+				root(&inherited_from, &member).clone(),
+				root(&inherited_from, &inherited).clone(),
+			);
+			if member != inherited {
+				inherited_from.insert(member, inherited);
+			}
+		}
+
+		self
+			.references
+			.into_iter()
+			.filter(|(_, declaration)| declarations.contains(declaration))
+			.map(|(span, declaration)| {
+				let declaration = root(&inherited_from, &declaration).clone();
+				(span, declaration)
+			})
+			.collect()
+	}
+
+	fn declare(&mut self, symbol: &Symbol) {
+		if symbol.span.file_id.is_empty() {
+			return;
+		}
+		self.declarations.insert(symbol.span.clone());
+		self.references.push((symbol.span.clone(), symbol.span.clone()));
+	}
+
+	fn reference(&mut self, symbol: &Symbol, declaration: Option<WingSpan>) {
+		if symbol.span.file_id.is_empty() {
+			return;
+		}
+		if let Some(declaration) = declaration {
+			self.references.push((symbol.span.clone(), declaration));
+		}
+	}
+
+	/// Looks up a (possibly nested) symbol from the current statement and returns the span of its declaration
+	fn lookup_declaration(&self, path: &[&Symbol]) -> Option<WingSpan> {
+		let env = self.current_scope?.env.borrow();
+		let env = env.as_ref()?;
+		if let LookupResult::Found(kind, _) = env.lookup_nested(path, Some(self.current_statement_index)) {
+			kind.definition_span()
+		} else {
+			None
+		}
+	}
+
+	/// Returns the span of the declaration of a member of the given type
+	fn member_declaration(&self, type_: Option<TypeRef>, property: &Symbol) -> Option<WingSpan> {
+		let type_ = type_?;
+		match &**type_.maybe_unwrap_option() {
+			Type::Class(c) => env_declaration(c.get_env(), property),
+			Type::Interface(i) => env_declaration(i.get_env(), property),
+			Type::Struct(s) => env_declaration(s.get_env(), property),
+			Type::Enum(e) => e
+				.values
+				.get(property.name.as_str())
+				.map(|value| value.span.clone())
+				.filter(|span| !span.file_id.is_empty()),
+			_ => None,
+		}
+	}
+
+	/// Links the members declared by a class or an interface with the members of the same name in the types it
+	/// inherits from, so renaming either of them renames both
+	fn link_inherited_members<'b>(&mut self, type_name: &Symbol, members: impl Iterator<Item = &'b Symbol>) {
+		let Some(scope) = self.current_scope else {
+			return;
+		};
+		let env = scope.env.borrow();
+		let Some(type_) = env.as_ref().and_then(|env| env.lookup(type_name, None)?.as_type()) else {
+			return;
+		};
+		let supertypes = supertypes(type_);
+		for member in members {
+			let mut inherited = vec![];
+			for supertype in &supertypes {
+				self.inherited_declarations(*supertype, member, &mut inherited);
+			}
+			for declaration in inherited {
+				self.linked_declarations.push((member.span.clone(), declaration));
+			}
+		}
+	}
+
+	/// Collects the declarations of a member in a type, or in the types it inherits from if it doesn't declare it
+	fn inherited_declarations(&self, type_: TypeRef, member: &Symbol, declarations: &mut Vec<WingSpan>) {
+		if let Some(declaration) = self.member_declaration(Some(type_), member) {
+			declarations.push(declaration);
+			return;
+		}
+		for supertype in supertypes(type_) {
+			self.inherited_declarations(supertype, member, declarations);
+		}
+	}
+
+	fn visit_named_args(&mut self, type_: Option<TypeRef>, named_args: impl Iterator<Item = &'a Symbol>) {
+		let Some(type_) = type_ else {
+			return;
+		};
+		let Some(structy) = type_.get_function_struct_arg() else {
+			return;
+		};
+		for arg_name in named_args {
+			let declaration = env_declaration(structy.get_env(), arg_name);
+			self.reference(arg_name, declaration);
+		}
+	}
+}
+
+fn env_declaration(env: &SymbolEnv, property: &Symbol) -> Option<WingSpan> {
+	env.lookup(property, None)?.definition_span()
+}
+
+/// The parent class and implemented interfaces of a class, or the interfaces extended by an interface
+fn supertypes(type_: TypeRef) -> Vec<TypeRef> {
+	match &*type_ {
+		Type::Class(c) => c.parent.iter().chain(c.implements.iter()).copied().collect(),
+		Type::Interface(i) => i.extends.clone(),
+		_ => vec![],
+	}
+}
+
+impl<'a> Visit<'a> for ReferenceVisitor<'a> {
+	fn visit_scope(&mut self, node: &'a Scope) {
+		let last_scope = self.current_scope;
+		self.current_scope = Some(node);
+		visit::visit_scope(self, node);
+		self.current_scope = last_scope;
+	}
+
+	fn visit_stmt(&mut self, node: &'a Stmt) {
+		let last_statement_index = self.current_statement_index;
+		self.current_statement_index = node.idx;
+
+		match &node.kind {
+			StmtKind::Let { var_name, .. } => self.declare(var_name),
+			StmtKind::ForLoop { iterator, .. } => self.declare(iterator),
+			StmtKind::IfLet { var_name, .. } => self.declare(var_name),
+			StmtKind::Struct { name, fields, .. } => {
+				self.declare(name);
+				for field in fields {
+					self.declare(&field.name);
+				}
+			}
+			StmtKind::Enum { name, values } => {
+				self.declare(name);
+				for value in values {
+					self.declare(value);
+				}
+			}
+			StmtKind::TryCatch {
+				catch_block: Some(catch_block),
+				..
+			} => {
+				if let Some(exception_var) = &catch_block.exception_var {
+					self.declare(exception_var);
+				}
+			}
+			_ => {}
+		}
+
+		visit::visit_stmt(self, node);
+		self.current_statement_index = last_statement_index;
+	}
+
+	fn visit_class(&mut self, node: &'a Class) {
+		self.declare(&node.name);
+		for field in &node.fields {
+			self.declare(&field.name);
+		}
+		for (method_name, _) in &node.methods {
+			self.declare(method_name);
+		}
+		let members = node.fields.iter().map(|f| &f.name);
+		self.link_inherited_members(&node.name, members.chain(node.methods.iter().map(|(name, _)| name)));
+
+		visit::visit_class(self, node);
+	}
+
+	fn visit_interface(&mut self, node: &'a Interface) {
+		self.declare(&node.name);
+		for field in &node.fields {
+			self.declare(&field.name);
+		}
+		for (method_name, _) in &node.methods {
+			self.declare(method_name);
+		}
+		let members = node.fields.iter().map(|f| &f.name);
+		self.link_inherited_members(&node.name, members.chain(node.methods.iter().map(|(name, _)| name)));
+
+		visit::visit_interface(self, node);
+	}
+
+	fn visit_function_parameter(&mut self, node: &'a FunctionParameter) {
+		self.declare(&node.name);
+
+		visit::visit_function_parameter(self, node);
+	}
+
+	fn visit_user_defined_type(&mut self, node: &'a UserDefinedType) {
+		// Each part of the path may refer to a different declaration (e.g. a brought module and a class in it)
+		let path = std::iter::once(&node.root)
+			.chain(node.fields.iter())
+			.collect::<Vec<_>>();
+		for i in 0..path.len() {
+			let declaration = self.lookup_declaration(&path[..=i]);
+			self.reference(path[i], declaration);
+		}
+	}
+
+	fn visit_reference(&mut self, node: &'a Reference) {
+		match node {
+			Reference::Identifier(symbol) => {
+				let declaration = self.lookup_declaration(&[symbol]);
+				self.reference(symbol, declaration);
+			}
+			Reference::InstanceMember { object, property, .. } => {
+				let declaration = self.member_declaration(self.types.try_get_expr_type(object), property);
+				self.reference(property, declaration);
+			}
+			Reference::TypeMember { typeobject, property } => {
+				let declaration = self.member_declaration(self.types.try_get_expr_type(typeobject), property);
+				self.reference(property, declaration);
+			}
+			Reference::TypeReference(_) | Reference::ElementAccess { .. } => {}
+		}
+
+		visit::visit_reference(self, node);
+	}
+
+	fn visit_expr(&mut self, node: &'a Expr) {
+		match &node.kind {
+			ExprKind::StructLiteral { fields, .. } => {
+				for field_name in fields.keys() {
+					let declaration = self.member_declaration(self.types.try_get_expr_type(node), field_name);
+					self.reference(field_name, declaration);
+				}
+			}
+			ExprKind::Call { callee, arg_list } => {
+				self.visit_named_args(self.types.try_get_expr_type(callee), arg_list.named_args.keys());
+			}
+			ExprKind::New(new_expr) => {
+				// Keyword arguments of a `new` expression are the fields of the initializer's struct argument
+				let initializer = self.types.try_get_expr_type(node).and_then(|class_type| {
+					let init_name = match self.types.get_expr_phase(node) {
+						Some(Phase::Inflight) => CLASS_INFLIGHT_INIT_NAME,
+						_ => CLASS_INIT_NAME,
+					};
+					Some(class_type.as_class()?.get_method(&Symbol::global(init_name))?.type_)
+				});
+				self.visit_named_args(initializer, new_expr.arg_list.named_args.keys());
+			}
+			_ => {}
+		}
+
+		visit::visit_expr(self, node);
+	}
+}
+
+/// Finds the symbol at the given position of a file and returns its span along with the spans of all the
+/// occurrences of the same symbol in the file and in the modules it brings (including the declaration).
+fn find_references(file_data: &FileData, position: &Position) -> Option<(WingSpan, Vec<WingSpan>)> {
+	let mut visitor = ReferenceVisitor::new(&file_data.types);
+	visitor.visit_scope(&file_data.scope);
+	for module in &file_data.dependencies {
+		visitor.visit_scope(&module.scope);
+	}
+	let references = visitor.into_references();
+
+	let file_id = &file_data.scope.span.file_id;
+	let (symbol_span, declaration) = references
+		.iter()
+		.find(|(span, _)| &span.file_id == file_id && span.contains(position))?
+		.clone();
+
+	let mut occurrences = references
+		.into_iter()
+		.filter(|(_, other_declaration)| other_declaration == &declaration)
+		.map(|(span, _)| span)
+		.collect::<Vec<_>>();
+	occurrences.sort();
+	occurrences.dedup();
+	occurrences.retain(|span| span != &declaration);
+	occurrences.insert(0, declaration);
+
+	Some((symbol_span, occurrences))
+}
+
+fn span_to_location(span: WingSpan) -> Option<Location> {
+	let uri = Url::from_file_path(&span.file_id).ok()?;
+	Some(Location {
+		uri,
+		range: span.into(),
+	})
+}
+
+/// Returns true if `name` can be used as a Wing identifier
+fn is_valid_identifier(name: &str) -> bool {
+	let mut chars = name.chars();
+	match chars.next() {
+		Some(c) if c.is_ascii_alphabetic() || c == '_' => chars.all(|c| c.is_ascii_alphanumeric() || c == '_'),
+		_ => false,
+	}
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn wingc_on_references(ptr: u32, len: u32) -> u64 {
+	let parse_string = ptr_to_string(ptr, len);
+	if let Ok(parsed) = serde_json::from_str(&parse_string) {
+		let locations = on_references(parsed);
+		let result = serde_json::to_string(&locations).expect("Failed to serialize References response");
+
+		string_to_combined_ptr(result)
+	} else {
+		eprintln!("Failed to parse 'onReferences' text document: {}", parse_string);
+		WASM_RETURN_ERROR
+	}
+}
+pub fn on_references(params: ReferenceParams) -> Vec<Location> {
+	FILES.with(|files| {
+		let files = files.borrow();
+		let uri = params.text_document_position.text_document.uri;
+		let file_data = files.get(&uri).expect("File must be open to find references");
+
+		let Some((_, occurrences)) = find_references(file_data, &params.text_document_position.position) else {
+			return vec![];
+		};
+
+		// The declaration is always the first occurrence
+		let skip = if params.context.include_declaration { 0 } else { 1 };
+		occurrences
+			.into_iter()
+			.skip(skip)
+			.filter_map(span_to_location)
+			.collect()
+	})
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn wingc_on_prepare_rename(ptr: u32, len: u32) -> u64 {
+	let parse_string = ptr_to_string(ptr, len);
+	if let Ok(parsed) = serde_json::from_str(&parse_string) {
+		let response = on_prepare_rename(parsed);
+		let result = serde_json::to_string(&response).expect("Failed to serialize PrepareRename response");
+
+		string_to_combined_ptr(result)
+	} else {
+		eprintln!("Failed to parse 'onPrepareRename' text document: {}", parse_string);
+		WASM_RETURN_ERROR
+	}
+}
+pub fn on_prepare_rename(params: TextDocumentPositionParams) -> Option<PrepareRenameResponse> {
+	FILES.with(|files| {
+		let files = files.borrow();
+		let file_data = files
+			.get(&params.text_document.uri)
+			.expect("File must be open to rename");
+
+		// Only symbols declared in Wing source can be renamed (and not the ones imported from JSII)
+		let (symbol_span, _) = find_references(file_data, &params.position)?;
+		Some(PrepareRenameResponse::Range(symbol_span.into()))
+	})
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn wingc_on_rename(ptr: u32, len: u32) -> u64 {
+	let parse_string = ptr_to_string(ptr, len);
+	if let Ok(parsed) = serde_json::from_str(&parse_string) {
+		let edit = on_rename(parsed);
+		let result = serde_json::to_string(&edit).expect("Failed to serialize Rename response");
+
+		string_to_combined_ptr(result)
+	} else {
+		eprintln!("Failed to parse 'onRename' text document: {}", parse_string);
+		WASM_RETURN_ERROR
+	}
+}
+pub fn on_rename(params: RenameParams) -> Option<WorkspaceEdit> {
+	if !is_valid_identifier(&params.new_name) {
+		return None;
+	}
+
+	FILES.with(|files| {
+		let files = files.borrow();
+		let uri = params.text_document_position.text_document.uri;
+		let file_data = files.get(&uri).expect("File must be open to rename");
+
+		let (_, occurrences) = find_references(file_data, &params.text_document_position.position)?;
+
+		let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+		for location in occurrences.into_iter().filter_map(span_to_location) {
+			changes.entry(location.uri).or_default().push(TextEdit {
+				range: location.range,
+				new_text: params.new_name.clone(),
+			});
+		}
+
+		Some(WorkspaceEdit {
+			changes: Some(changes),
+			..Default::default()
+		})
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::lsp::references::*;
+	use crate::lsp::sync::test_utils::*;
+	use lsp_types::*;
+
+	fn find(text_document_position: TextDocumentPositionParams, include_declaration: bool) -> Vec<Range> {
+		on_references(ReferenceParams {
+			text_document_position,
+			work_done_progress_params: Default::default(),
+			partial_result_params: Default::default(),
+			context: ReferenceContext { include_declaration },
+		})
+		.into_iter()
+		.map(|location| location.range)
+		.collect()
+	}
+
+	fn range(start_line: u32, start_character: u32, end_character: u32) -> Range {
+		Range {
+			start: Position {
+				line: start_line,
+				character: start_character,
+			},
+			end: Position {
+				line: start_line,
+				character: end_character,
+			},
+		}
+	}
+
+	#[test]
+	fn variable_references() {
+		let position = load_file_with_contents(
+			r#"
+let x = 1;
+let y = x + 2;
+      //^
+if true {
+  let x = "shadowed";
+  log(x);
+}
+log("${x}");
+"#,
+		);
+
+		assert_eq!(
+			find(position.clone(), true),
+			vec![range(1, 4, 5), range(2, 8, 9), range(8, 7, 8)]
+		);
+		assert_eq!(find(position, false), vec![range(2, 8, 9), range(8, 7, 8)]);
+	}
+
+	#[test]
+	fn struct_field_references() {
+		let position = load_file_with_contents(
+			r#"
+struct Options {
+  name: str;
+}
+class Foo {
+  init(opts: Options) {
+    log(opts.name);
+  }
+}
+let o = Options { name: "a" };
+new Foo(name: "b");
+      //^
+"#,
+		);
+
+		assert_eq!(
+			find(position, true),
+			vec![range(2, 2, 6), range(6, 13, 17), range(9, 18, 22), range(10, 8, 12)]
+		);
+	}
+
+	#[test]
+	fn class_member_references() {
+		let position = load_file_with_contents(
+			r#"
+class Foo {
+  count: num;
+  init() {
+    this.count = 0;
+  }
+  bump() {
+    log("${this.count}");
+  }
+}
+let foo = new Foo();
+log("${foo.count}");
+         //^
+"#,
+		);
+
+		assert_eq!(
+			find(position, true),
+			vec![range(2, 2, 7), range(4, 9, 14), range(7, 16, 21), range(11, 11, 16)]
+		);
+	}
+
+	#[test]
+	fn overridden_method_references() {
+		let position = load_file_with_contents(
+			r#"
+class Base {
+  describe(): str {
+    return "base";
+  }
+}
+class Derived extends Base {
+  describe(): str {
+    return "derived";
+  }
+}
+let b = new Base();
+let d = new Derived();
+log(b.describe());
+log(d.describe());
+    //^
+"#,
+		);
+
+		assert_eq!(
+			find(position, true),
+			vec![range(2, 2, 10), range(7, 2, 10), range(13, 6, 14), range(14, 6, 14)]
+		);
+	}
+
+	#[test]
+	fn rename_implemented_interface_method() {
+		let position = load_file_with_contents(
+			r#"
+interface IShape {
+  area(): num;
+//^
+}
+interface ISquare extends IShape {}
+class Square impl ISquare {
+  area(): num {
+    return 4;
+  }
+}
+let s: IShape = new Square();
+log("${s.area()}");
+log("${new Square().area()}");
+"#,
+		);
+
+		let edit = on_rename(RenameParams {
+			text_document_position: position.clone(),
+			new_name: "size".to_string(),
+			work_done_progress_params: Default::default(),
+		})
+		.expect("Expected a rename edit");
+		let edits = edit.changes.unwrap().remove(&position.text_document.uri).unwrap();
+		assert_eq!(
+			edits.into_iter().map(|e| e.range).collect::<Vec<_>>(),
+			vec![range(2, 2, 6), range(7, 2, 6), range(12, 9, 13), range(13, 20, 24)]
+		);
+	}
+
+	#[test]
+	fn rename_enum_value() {
+		let position = load_file_with_contents(
+			r#"
+enum Color { RED, BLUE }
+let c = Color.RED;
+            //^
+"#,
+		);
+
+		let prepared = on_prepare_rename(position.clone());
+		assert_eq!(prepared, Some(PrepareRenameResponse::Range(range(2, 14, 17))));
+
+		let edit = on_rename(RenameParams {
+			text_document_position: position.clone(),
+			new_name: "GREEN".to_string(),
+			work_done_progress_params: Default::default(),
+		})
+		.expect("Expected a rename edit");
+		let edits = edit.changes.unwrap().remove(&position.text_document.uri).unwrap();
+		assert_eq!(
+			edits.into_iter().map(|e| e.range).collect::<Vec<_>>(),
+			vec![range(1, 13, 16), range(2, 14, 17)]
+		);
+	}
+
+	#[test]
+	fn cannot_rename_std_symbols() {
+		let position = load_file_with_contents(
+			r#"
+log("hello");
+//^
+"#,
+		);
+
+		assert_eq!(on_prepare_rename(position), None);
+	}
+}
@@ -0,0 +1,214 @@
+use std::io::{self, BufRead, Write};
+use std::panic::{self, AssertUnwindSafe};
+
+use lsp_types::{
+	CompletionOptions, HoverProviderCapability, InitializeResult, OneOf, RenameOptions, ServerCapabilities, ServerInfo,
+	SignatureHelpOptions, TextDocumentIdentifier, TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+
+use crate::lsp::completions::on_completion;
+use crate::lsp::diagnostics::{on_diagnostics, DiagnosticsParams};
+use crate::lsp::document_symbols::on_document_symbols;
+use crate::lsp::formatting::{on_formatting, on_range_formatting};
+use crate::lsp::goto_definition::on_goto_definition;
+use crate::lsp::hover::on_hover;
+use crate::lsp::references::{on_prepare_rename, on_references, on_rename};
+use crate::lsp::signature::on_signature_help;
+use crate::lsp::sync::{on_document_did_change, on_document_did_open};
+
+// JSON-RPC error codes, see https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#errorCodes
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+
+/// Runs a language server over stdin/stdout until the client sends an `exit` notification (or closes stdin).
+///
+/// This is the native counterpart of the language server in `apps/wing` which hosts the WASM build of wingc.
+/// Requests are handled synchronously, one at a time, by the same handlers used by the WASM exports.
+pub fn run_server() -> io::Result<()> {
+	let stdin = io::stdin();
+	let mut input = stdin.lock();
+	let stdout = io::stdout();
+	let mut output = stdout.lock();
+
+	while let Some(message) = read_message(&mut input)? {
+		let method = message["method"].as_str().unwrap_or_default().to_string();
+		let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+		// Messages without an id are notifications, which don't get a response
+		let Some(id) = message.get("id").cloned() else {
+			match method.as_str() {
+				"exit" => return Ok(()),
+				"textDocument/didOpen" => handle_document_update(&mut output, params, on_document_did_open)?,
+				"textDocument/didChange" => handle_document_update(&mut output, params, on_document_did_change)?,
+				// Other notifications (`initialized`, `$/cancelRequest`, etc.) are ignored
+				_ => {}
+			}
+			continue;
+		};
+
+		let result = match method.as_str() {
+			"initialize" => Ok(serde_json::to_value(initialize_result()).unwrap()),
+			"shutdown" => Ok(Value::Null),
+			"textDocument/completion" => handle_request(params, on_completion),
+			"textDocument/signatureHelp" => handle_request(params, on_signature_help),
+			"textDocument/definition" => handle_request(params, on_goto_definition),
+			"textDocument/documentSymbol" => handle_request(params, on_document_symbols),
+			"textDocument/hover" => handle_request(params, on_hover),
+			"textDocument/references" => handle_request(params, on_references),
+			"textDocument/prepareRename" => handle_request(params, on_prepare_rename),
+			"textDocument/rename" => handle_request(params, on_rename),
+			"textDocument/formatting" => handle_request(params, on_formatting),
+			"textDocument/rangeFormatting" => handle_request(params, on_range_formatting),
+			_ => Err((METHOD_NOT_FOUND, format!("Unsupported method: {}", method))),
+		};
+
+		let response = match result {
+			Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+			Err((code, message)) => json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } }),
+		};
+		write_message(&mut output, &response)?;
+	}
+
+	Ok(())
+}
+
+fn initialize_result() -> InitializeResult {
+	InitializeResult {
+		capabilities: ServerCapabilities {
+			text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::INCREMENTAL)),
+			completion_provider: Some(CompletionOptions {
+				trigger_characters: Some(vec![".".to_string(), ":".to_string()]),
+				..Default::default()
+			}),
+			signature_help_provider: Some(SignatureHelpOptions {
+				trigger_characters: Some(vec!["(".to_string(), ",".to_string(), ")".to_string()]),
+				..Default::default()
+			}),
+			hover_provider: Some(HoverProviderCapability::Simple(true)),
+			document_symbol_provider: Some(OneOf::Left(true)),
+			definition_provider: Some(OneOf::Left(true)),
+			references_provider: Some(OneOf::Left(true)),
+			rename_provider: Some(OneOf::Right(RenameOptions {
+				prepare_provider: Some(true),
+				work_done_progress_options: Default::default(),
+			})),
+			document_formatting_provider: Some(OneOf::Left(true)),
+			document_range_formatting_provider: Some(OneOf::Left(true)),
+			..Default::default()
+		},
+		server_info: Some(ServerInfo {
+			name: "wingc".to_string(),
+			version: Some(env!("CARGO_PKG_VERSION").to_string()),
+		}),
+	}
+}
+
+/// Deserializes the params of a request and runs its handler. Panics in the handler are turned into
+/// an error response so a single bad request doesn't bring down the server.
+fn handle_request<P: DeserializeOwned, R: Serialize>(
+	params: Value,
+	handler: fn(P) -> R,
+) -> Result<Value, (i64, String)> {
+	let params = serde_json::from_value(params).map_err(|err| (INVALID_PARAMS, err.to_string()))?;
+	let result = panic::catch_unwind(AssertUnwindSafe(|| handler(params))).map_err(|_| {
+		(
+			INTERNAL_ERROR,
+			"Wing language server crashed while handling the request".to_string(),
+		)
+	})?;
+	Ok(serde_json::to_value(result).unwrap())
+}
+
+/// Handles a notification that updates the contents of a document, then publishes the diagnostics
+/// found while compiling it.
+fn handle_document_update<P: DeserializeOwned>(
+	output: &mut impl Write,
+	params: Value,
+	handler: fn(P),
+) -> io::Result<()> {
+	let Ok(uri) = serde_json::from_value::<Url>(params["textDocument"]["uri"].clone()) else {
+		return Ok(());
+	};
+	let Ok(params) = serde_json::from_value(params) else {
+		return Ok(());
+	};
+
+	// If the compiler panics, the custom panic hook reports it as a diagnostic which is published below
+	let _ = panic::catch_unwind(AssertUnwindSafe(|| handler(params)));
+
+	let notification = json!({
+		"jsonrpc": "2.0",
+		"method": "textDocument/publishDiagnostics",
+		"params": on_diagnostics(DiagnosticsParams {
+			text_document: TextDocumentIdentifier { uri },
+		}),
+	});
+	write_message(output, &notification)
+}
+
+/// Reads a single JSON-RPC message (a `Content-Length` header followed by a JSON body).
+/// Returns `None` when the input is closed.
+fn read_message(input: &mut impl BufRead) -> io::Result<Option<Value>> {
+	let mut content_length = None;
+	loop {
+		let mut header = String::new();
+		if input.read_line(&mut header)? == 0 {
+			return Ok(None);
+		}
+
+		let header = header.trim_end();
+		if header.is_empty() {
+			break;
+		}
+
+		if let Some((name, value)) = header.split_once(':') {
+			if name.eq_ignore_ascii_case("Content-Length") {
+				content_length = value.trim().parse::<usize>().ok();
+			}
+		}
+	}
+
+	let Some(content_length) = content_length else {
+		return Err(io::Error::new(
+			io::ErrorKind::InvalidData,
+			"Missing Content-Length header",
+		));
+	};
+
+	let mut body = vec![0; content_length];
+	input.read_exact(&mut body)?;
+	serde_json::from_slice(&body)
+		.map(Some)
+		.map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+fn write_message(output: &mut impl Write, message: &Value) -> io::Result<()> {
+	let body = message.to_string();
+	write!(output, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+	output.flush()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn read_and_write_messages() {
+		let message = json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} });
+		let mut buffer = vec![];
+		write_message(&mut buffer, &message).unwrap();
+
+		let mut input = io::Cursor::new(buffer);
+		assert_eq!(read_message(&mut input).unwrap(), Some(message));
+		assert_eq!(read_message(&mut input).unwrap(), None);
+	}
+
+	#[test]
+	fn read_message_without_content_length() {
+		let mut input = io::Cursor::new(b"Content-Type: application/json\r\n\r\n{}".to_vec());
+		assert!(read_message(&mut input).is_err());
+	}
+}
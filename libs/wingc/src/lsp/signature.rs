@@ -90,6 +90,9 @@ pub fn on_signature_help(params: lsp_types::SignatureHelpParams) -> Option<Signa
 			.map(|p| {
 				if p.0 == sig.parameters.len() - 1 && p.1.typeref.maybe_unwrap_option().is_struct() {
 					format!("...{}", p.1.name)
+				} else if p.1.variadic {
+					// any number of arguments can be passed in place of a variadic parameter
+					format!("...{}: {}", p.1.name, p.1.typeref)
 				} else {
 					format!("{}: {}", p.1.name, p.1.typeref)
 				}
@@ -281,4 +284,12 @@ bring cloud;
 let bucket = new cloud.Bucket( );
                             //^"#,
 	);
+
+	test_signature!(
+		variadic_arg_active,
+		r#"
+let join = (sep: str, ...parts: Array<str>): str => { return sep; };
+join(",", "a", "b", )
+                 //^"#,
+	);
 }
@@ -1,18 +1,19 @@
-use lsp_types::{DidChangeTextDocumentParams, DidOpenTextDocumentParams, Url};
+use lsp_types::{DidChangeTextDocumentParams, DidOpenTextDocumentParams, Position, Url};
 use wingii::type_system::TypeSystem;
 
 use std::path::Path;
 use std::{cell::RefCell, collections::HashMap};
-use tree_sitter::Tree;
+use tree_sitter::{InputEdit, Point, Tree};
 
 use crate::closure_transform::ClosureTransformer;
 use crate::diagnostic::{get_diagnostics, reset_diagnostics, Diagnostic};
 use crate::files::Files;
 use crate::fold::Fold;
 use crate::jsify::JSifier;
+use crate::modules::{load_wing_modules, WingModule};
 use crate::parser::Parser;
 use crate::type_check;
-use crate::type_check::jsii_importer::JsiiImportSpec;
+use crate::type_check_wing_modules;
 use crate::{ast::Scope, type_check::Types, wasm_util::ptr_to_string};
 
 /// The result of running wingc on a file
@@ -27,9 +28,8 @@ pub struct FileData {
 	pub scope: Box<Scope>,
 	/// The universal type collection for the scope. This is saved to ensure references live long enough.
 	pub types: Types,
-	/// The JSII imports for the file. This is saved so we can load JSII types (for autotocompletion for example)
-	/// which don't exist explicitly in the source.
-	pub jsii_imports: Vec<JsiiImportSpec>,
+	/// The local Wing modules brought by the file. These are saved since the file's types reference them.
+	pub dependencies: Vec<WingModule>,
 }
 
 thread_local! {
@@ -56,7 +56,13 @@ pub fn on_document_did_open(params: DidOpenTextDocumentParams) {
 			let uri_path = uri.to_file_path().unwrap();
 			let path = uri_path.to_str().unwrap();
 
-			let result = partial_compile(path, params.text_document.text.as_bytes(), &mut jsii_types.borrow_mut());
+			let result = partial_compile(
+				path,
+				params.text_document.text.as_bytes(),
+				None,
+				None,
+				&mut jsii_types.borrow_mut(),
+			);
 			files.borrow_mut().insert(uri, result);
 		});
 	});
@@ -78,9 +84,38 @@ pub fn on_document_did_change(params: DidChangeTextDocumentParams) {
 			let uri_path = uri.to_file_path().unwrap();
 			let path = uri_path.to_str().unwrap();
 
+			let Some(previous) = files.borrow_mut().remove(&uri) else {
+				// Without the previous contents only a change of the full document can be applied
+				let full_change = params.content_changes.into_iter().last().filter(|c| c.range.is_none());
+				if let Some(change) = full_change {
+					let result = partial_compile(path, change.text.as_bytes(), None, None, &mut jsii_types.borrow_mut());
+					files.borrow_mut().insert(uri, result);
+				}
+				return;
+			};
+
+			// Apply the changes to the previous contents and tree, so the tree can be reused when re-parsing
+			let mut contents = previous.contents;
+			let mut tree = previous.tree;
+			for change in params.content_changes {
+				let edit = match change.range {
+					Some(range) => apply_ranged_change(&mut contents, range.start, range.end, &change.text),
+					None => apply_full_change(&mut contents, change.text),
+				};
+				tree.edit(&edit);
+			}
+
+			// Reuse the JSII libraries imported into the previous types, dropping the rest of the previous compilation
+			let mut types = previous.types;
+			types.reset_compilation();
+			drop(previous.scope);
+			drop(previous.dependencies);
+
 			let result = partial_compile(
 				path,
-				params.content_changes[0].text.as_bytes(),
+				contents.as_bytes(),
+				Some(&tree),
+				Some(types),
 				&mut jsii_types.borrow_mut(),
 			);
 			files.borrow_mut().insert(uri, result);
@@ -88,18 +123,111 @@ pub fn on_document_did_change(params: DidChangeTextDocumentParams) {
 	})
 }
 
+/// Replaces the text between two LSP positions with `text`. Returns the corresponding tree-sitter edit.
+fn apply_ranged_change(contents: &mut String, start: Position, end: Position, text: &str) -> InputEdit {
+	let start_byte = byte_offset(contents, start);
+	let old_end_byte = byte_offset(contents, end).max(start_byte);
+	let start_position = byte_to_point(contents, start_byte);
+	let old_end_position = byte_to_point(contents, old_end_byte);
+
+	contents.replace_range(start_byte..old_end_byte, text);
+
+	let new_end_byte = start_byte + text.len();
+	InputEdit {
+		start_byte,
+		old_end_byte,
+		new_end_byte,
+		start_position,
+		old_end_position,
+		new_end_position: byte_to_point(contents, new_end_byte),
+	}
+}
+
+/// Replaces the whole contents with `text`. Returns a tree-sitter edit covering the text between the common prefix
+/// and suffix of the old and new contents, so the tree can be reused even when the client sends the full document.
+fn apply_full_change(contents: &mut String, text: String) -> InputEdit {
+	let start_byte = contents
+		.bytes()
+		.zip(text.bytes())
+		.take_while(|(old, new)| old == new)
+		.count();
+	let max_suffix = contents.len().min(text.len()) - start_byte;
+	let suffix_len = contents
+		.bytes()
+		.rev()
+		.zip(text.bytes().rev())
+		.take(max_suffix)
+		.take_while(|(old, new)| old == new)
+		.count();
+
+	let old_end_byte = contents.len() - suffix_len;
+	let new_end_byte = text.len() - suffix_len;
+	let start_position = byte_to_point(contents, start_byte);
+	let old_end_position = byte_to_point(contents, old_end_byte);
+
+	*contents = text;
+
+	InputEdit {
+		start_byte,
+		old_end_byte,
+		new_end_byte,
+		start_position,
+		old_end_position,
+		new_end_position: byte_to_point(contents, new_end_byte),
+	}
+}
+
+/// Converts an LSP position (where the character offset is in UTF-16 code units) to a byte offset in `text`
+fn byte_offset(text: &str, position: Position) -> usize {
+	let line_start: usize = text
+		.split_inclusive('\n')
+		.take(position.line as usize)
+		.map(str::len)
+		.sum();
+	let line = text[line_start..].split('\n').next().unwrap_or_default();
+
+	let mut utf16_offset = 0;
+	for (byte_idx, c) in line.char_indices() {
+		if utf16_offset >= position.character as usize {
+			return line_start + byte_idx;
+		}
+		utf16_offset += c.len_utf16();
+	}
+	line_start + line.len()
+}
+
+/// Converts a byte offset in `text` to a tree-sitter point (where the column is in bytes)
+fn byte_to_point(text: &str, byte_offset: usize) -> Point {
+	let before = &text.as_bytes()[..byte_offset];
+	let row = before.iter().filter(|&&b| b == b'\n').count();
+	let line_start = before.iter().rposition(|&b| b == b'\n').map_or(0, |idx| idx + 1);
+	Point {
+		row,
+		column: byte_offset - line_start,
+	}
+}
+
 /// Runs several phases of the wing compile on a file, including: parsing, type checking, and capturing
-fn partial_compile(source_file: &str, text: &[u8], jsii_types: &mut TypeSystem) -> FileData {
+///
+/// `old_tree` is the tree of the previous version of the file (already edited to match `text`) and `types` are the
+/// types of its previous compilation, reset with `Types::reset_compilation`. Both are reused if provided.
+fn partial_compile(
+	source_file: &str,
+	text: &[u8],
+	old_tree: Option<&Tree>,
+	types: Option<Types>,
+	jsii_types: &mut TypeSystem,
+) -> FileData {
 	// Reset diagnostics before new compilation (`partial_compile` can be called multiple)
 	reset_diagnostics();
 
-	let mut types = type_check::Types::new();
+	let mut types = types.unwrap_or_else(type_check::Types::new);
 
 	let language = tree_sitter_wing::language();
 	let mut parser = tree_sitter::Parser::new();
 	parser.set_language(language).unwrap();
 
-	let tree = match parser.parse(text, None) {
+	let tree = match parser.parse(text, old_tree) {
 		Some(tree) => tree,
 		None => {
 			panic!("Failed parsing source file: {}", source_file);
@@ -120,10 +248,20 @@ fn partial_compile(source_file: &str, text: &[u8], jsii_types: &mut TypeSystem)
 		}
 	}
 
+	// Parse all the local Wing modules brought by the file (in dependency order)
+	let dependencies = load_wing_modules(Path::new(source_file), &scope, &mut files);
+
 	// -- DESUGARING PHASE --
 
 	// Transform all inflight closures defined in preflight into single-method resources
 	let mut inflight_transformer = ClosureTransformer::new();
+	let mut dependencies = dependencies
+		.into_iter()
+		.map(|module| WingModule {
+			scope: Box::new(inflight_transformer.fold_scope(*module.scope)),
+			..module
+		})
+		.collect::<Vec<_>>();
 	// Note: The scope is intentionally boxed here to force heap allocation
 	// Otherwise, the scope will be moved during type checking and we'll be left with dangling references elsewhere
 	let mut scope = Box::new(inflight_transformer.fold_scope(scope));
@@ -131,6 +269,8 @@ fn partial_compile(source_file: &str, text: &[u8], jsii_types: &mut TypeSystem)
 	// -- TYPECHECKING PHASE --
 	let mut jsii_imports = vec![];
 
+	type_check_wing_modules(&mut dependencies, &mut types, jsii_types);
+
 	type_check(
 		&mut scope,
 		&mut types,
@@ -146,7 +286,7 @@ fn partial_compile(source_file: &str, text: &[u8], jsii_types: &mut TypeSystem)
 	let app_name = source_path.file_stem().expect("Empty filename").to_str().unwrap();
 	let project_dir = source_path.parent().expect("Empty filename");
 
-	let mut jsifier = JSifier::new(&mut types, &files, app_name, &project_dir, true);
+	let mut jsifier = JSifier::new(&mut types, &files, app_name, &project_dir, source_file, true);
 	jsifier.jsify(&scope);
 
 	return FileData {
@@ -155,7 +295,7 @@ fn partial_compile(source_file: &str, text: &[u8], jsii_types: &mut TypeSystem)
 		diagnostics: get_diagnostics(),
 		scope,
 		types,
-		jsii_imports,
+		dependencies,
 	};
 }
 
@@ -235,3 +375,110 @@ pub mod test_utils {
 		};
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use lsp_types::*;
+
+	use super::*;
+	use crate::lsp::sync::test_utils::load_file_with_contents;
+
+	#[test]
+	fn ranged_changes() {
+		let mut contents = "let x = 1;\nlet y = \"😀\";\n".to_string();
+
+		// Positions are in UTF-16 code units, so the emoji counts as two characters
+		let edit = apply_ranged_change(
+			&mut contents,
+			Position { line: 1, character: 11 },
+			Position { line: 1, character: 12 },
+			"!\n;",
+		);
+		assert_eq!(contents, "let x = 1;\nlet y = \"😀!\n;;\n");
+		assert_eq!(edit.start_byte, 24);
+		assert_eq!(edit.old_end_byte, 25);
+		assert_eq!(edit.new_end_byte, 27);
+		assert_eq!(edit.start_position, Point { row: 1, column: 13 });
+		assert_eq!(edit.old_end_position, Point { row: 1, column: 14 });
+		assert_eq!(edit.new_end_position, Point { row: 2, column: 1 });
+	}
+
+	#[test]
+	fn full_changes() {
+		let mut contents = "let x = 1;\nlet y = 2;\n".to_string();
+
+		let edit = apply_full_change(&mut contents, "let x = 1;\nlet z = 3;\nlet y = 2;\n".to_string());
+		assert_eq!(contents, "let x = 1;\nlet z = 3;\nlet y = 2;\n");
+		assert_eq!(edit.start_byte, 15);
+		assert_eq!(edit.old_end_byte, 15);
+		assert_eq!(edit.new_end_byte, 26);
+		assert_eq!(edit.start_position, Point { row: 1, column: 4 });
+		assert_eq!(edit.new_end_position, Point { row: 2, column: 4 });
+
+		// Identical contents result in an empty edit
+		let same_contents = contents.clone();
+		let edit = apply_full_change(&mut contents, same_contents);
+		assert_eq!(edit.start_byte, edit.old_end_byte);
+		assert_eq!(edit.start_byte, edit.new_end_byte);
+	}
+
+	#[test]
+	fn incremental_document_changes() {
+		let doc = load_file_with_contents("let x = 1;\n");
+
+		on_document_did_change(DidChangeTextDocumentParams {
+			text_document: VersionedTextDocumentIdentifier {
+				uri: doc.text_document.uri.clone(),
+				version: 1,
+			},
+			content_changes: vec![TextDocumentContentChangeEvent {
+				range: Some(Range {
+					start: Position { line: 0, character: 8 },
+					end: Position { line: 0, character: 9 },
+				}),
+				range_length: None,
+				text: "\"hello\"".to_string(),
+			}],
+		});
+
+		FILES.with(|files| {
+			let files = files.borrow();
+			let file = files.get(&doc.text_document.uri).unwrap();
+			assert_eq!(file.contents, "let x = \"hello\";\n");
+			assert!(!file.tree.root_node().has_error());
+			assert!(file.diagnostics.is_empty());
+		});
+	}
+
+	#[test]
+	fn document_changes_reuse_jsii_imports() {
+		let doc = load_file_with_contents("bring cloud;\nclass Foo {}\nlet b = new cloud.Bucket();\n");
+
+		for (version, name) in ["Bar", "Baz"].iter().enumerate() {
+			on_document_did_change(DidChangeTextDocumentParams {
+				text_document: VersionedTextDocumentIdentifier {
+					uri: doc.text_document.uri.clone(),
+					version: version as i32 + 1,
+				},
+				content_changes: vec![TextDocumentContentChangeEvent {
+					range: Some(Range {
+						start: Position { line: 1, character: 6 },
+						end: Position { line: 1, character: 9 },
+					}),
+					range_length: None,
+					text: name.to_string(),
+				}],
+			});
+		}
+
+		FILES.with(|files| {
+			let files = files.borrow();
+			let file = files.get(&doc.text_document.uri).unwrap();
+			assert_eq!(
+				file.contents,
+				"bring cloud;\nclass Baz {}\nlet b = new cloud.Bucket();\n"
+			);
+			assert!(file.diagnostics.is_empty());
+		});
+	}
+}
@@ -0,0 +1,510 @@
+//! The `wingc` command line interface.
+//!
+//! This is a native alternative to the WASM build of wingc (which is hosted by the Wing CLI), useful
+//! for running the compiler from CI pipelines and editors without a Node.js/WASI host.
+
+use std::{
+	env, fs,
+	panic::{self, AssertUnwindSafe},
+	path::{Path, PathBuf},
+	process,
+};
+
+use wingc::{
+	check,
+	comp_ctx::set_custom_panic_hook,
+	compile, compile_library,
+	diagnostic::{found_errors, get_diagnostics, set_warnings_as_errors, Diagnostic, DiagnosticCode, DiagnosticSeverity},
+	files::Files,
+	format_file,
+	lsp::server::run_server,
+	parse, set_jsii_cache,
+};
+use wingii::cache::AssemblyCache;
+
+const USAGE: &str = "Usage: wingc <command> [options]
+
+Commands:
+  compile <file>  Compile a Wing source file to JavaScript
+  check <file>    Check a Wing source file for errors (like compile) without writing any files
+  parse <file>    Parse a Wing source file
+  fmt <file>      Format a Wing source file in place
+  lsp             Start a language server that communicates over stdio
+  explain <code>  Print a detailed explanation of a diagnostic code (e.g. E2003)
+
+Options:
+  --out-dir <dir>        Directory to emit the compiled files to (compile only, defaults to <file>.out)
+  --project-root <dir>   Root directory of the project (compile and check only, defaults to the source file's
+                         directory)
+  --library              Compile the file as a library: emit a JSII assembly (.jsii) of its public types instead
+                         of an app, using the name and version in <project root>/package.json (compile only)
+  --dump-ast             Print the parsed AST to stdout (parse only)
+  --warnings-as-errors   Treat warnings as errors, failing the compilation (compile and check only)
+  --check                Don't write the formatted file, fail if it isn't formatted (fmt only)
+  --error-format <fmt>   Format of the diagnostics printed to stderr: \"human\" (default) or \"json\" (one JSON
+                         object per line, with the code, severity, span, related spans and suggested fixes)
+  -h, --help             Print this help message
+
+Exit codes:
+  0  Success
+  1  Compilation failed (diagnostics are printed to stderr), or the file isn't formatted (fmt --check)
+  2  Invalid command line arguments";
+
+const EXIT_COMPILATION_FAILED: i32 = 1;
+const EXIT_INVALID_ARGS: i32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ErrorFormat {
+	Human,
+	Json,
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+	Compile {
+		source_file: PathBuf,
+		out_dir: Option<PathBuf>,
+		project_root: Option<PathBuf>,
+		library: bool,
+		warnings_as_errors: bool,
+		error_format: ErrorFormat,
+	},
+	Check {
+		source_file: PathBuf,
+		project_root: Option<PathBuf>,
+		warnings_as_errors: bool,
+		error_format: ErrorFormat,
+	},
+	Parse {
+		source_file: PathBuf,
+		dump_ast: bool,
+		error_format: ErrorFormat,
+	},
+	Fmt {
+		source_file: PathBuf,
+		check: bool,
+		error_format: ErrorFormat,
+	},
+	Explain {
+		code: DiagnosticCode,
+	},
+	Lsp,
+	Help,
+}
+
+fn main() {
+	let command = match parse_args(env::args().skip(1)) {
+		Ok(command) => command,
+		Err(message) => {
+			eprintln!("error: {}\n\n{}", message, USAGE);
+			process::exit(EXIT_INVALID_ARGS);
+		}
+	};
+
+	// Report compiler panics as diagnostics (same as the WASM build does in `wingc_init`)
+	set_custom_panic_hook();
+
+	let succeeded = match command {
+		Command::Help => {
+			println!("{}", USAGE);
+			true
+		}
+		Command::Explain { code } => {
+			println!("{}: {}", code, code.explanation());
+			true
+		}
+		Command::Lsp => match run_server() {
+			Ok(()) => true,
+			Err(err) => {
+				eprintln!("error: language server failed: {}", err);
+				false
+			}
+		},
+		Command::Compile {
+			source_file,
+			out_dir,
+			project_root,
+			library,
+			warnings_as_errors,
+			error_format,
+		} => {
+			set_warnings_as_errors(warnings_as_errors);
+			set_jsii_cache(AssemblyCache::from_env());
+
+			// The compiler requires an absolute project root, so resolve it relative to the working directory
+			let project_root = project_root
+				.or_else(|| source_file.parent().map(Path::to_path_buf))
+				.map(|dir| absolute_path(&dir));
+			run_compiler(error_format, || {
+				if library {
+					compile_library(&source_file, out_dir.as_deref(), project_root.as_deref()).is_ok()
+				} else {
+					compile(&source_file, out_dir.as_deref(), project_root.as_deref()).is_ok()
+				}
+			})
+		}
+		Command::Check {
+			source_file,
+			project_root,
+			warnings_as_errors,
+			error_format,
+		} => {
+			set_warnings_as_errors(warnings_as_errors);
+			set_jsii_cache(AssemblyCache::from_env());
+
+			let project_root = project_root
+				.or_else(|| source_file.parent().map(Path::to_path_buf))
+				.map(|dir| absolute_path(&dir));
+			run_compiler(error_format, || check(&source_file, project_root.as_deref()).is_ok())
+		}
+		Command::Parse {
+			source_file,
+			dump_ast,
+			error_format,
+		} => run_compiler(error_format, || {
+			let (_, scope) = parse(&source_file);
+			if dump_ast {
+				println!("{:#?}", scope);
+			}
+			!found_errors()
+		}),
+		Command::Fmt {
+			source_file,
+			check,
+			error_format,
+		} => run_compiler(error_format, || {
+			let Ok((source, formatted)) = format_file(&source_file) else {
+				return false;
+			};
+			if source == formatted {
+				return true;
+			}
+			if check {
+				eprintln!("{} is not formatted", source_file.display());
+				return false;
+			}
+			match fs::write(&source_file, formatted) {
+				Ok(()) => true,
+				Err(err) => {
+					eprintln!("error: failed to write {}: {}", source_file.display(), err);
+					false
+				}
+			}
+		}),
+	};
+
+	if !succeeded {
+		process::exit(EXIT_COMPILATION_FAILED);
+	}
+}
+
+/// Runs a compiler entry point and prints all the diagnostics it reported to stderr.
+/// Returns whether the compilation succeeded.
+fn run_compiler(error_format: ErrorFormat, f: impl FnOnce() -> bool) -> bool {
+	// A panic is reported as a diagnostic by the custom panic hook, so it's printed along with the rest
+	let succeeded = panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(false);
+
+	let mut diagnostics = get_diagnostics();
+	diagnostics.sort();
+
+	if error_format == ErrorFormat::Json {
+		for diagnostic in &diagnostics {
+			eprintln!("{}", serde_json::to_string(diagnostic).unwrap());
+		}
+		return succeeded;
+	}
+
+	let sources = load_sources(&diagnostics);
+	let colors = colored::control::SHOULD_COLORIZE.should_colorize();
+	for diagnostic in &diagnostics {
+		eprintln!("{}", diagnostic.render(&sources, colors));
+	}
+
+	let errors = diagnostics
+		.iter()
+		.filter(|d| d.severity == DiagnosticSeverity::Error)
+		.count();
+	// Commands can also fail without errors (e.g. `fmt --check` on an unformatted file)
+	if !succeeded && errors > 0 {
+		eprintln!("Compilation failed with {} errors", errors);
+	}
+	succeeded
+}
+
+/// Reads the source files referenced by the diagnostics, so they can be rendered with the source lines they refer to
+fn load_sources(diagnostics: &[Diagnostic]) -> Files {
+	let mut sources = Files::new();
+	let spans = diagnostics
+		.iter()
+		.flat_map(|d| d.span.iter().chain(d.annotations.iter().map(|a| &a.span)));
+	for span in spans {
+		if sources.get_file(&span.file_id).is_some() {
+			continue;
+		}
+		if let Ok(source) = fs::read_to_string(&span.file_id) {
+			sources
+				.add_file(&span.file_id, source)
+				.expect("Source file was already added");
+		}
+	}
+	sources
+}
+
+fn absolute_path(path: &Path) -> PathBuf {
+	if path.is_absolute() {
+		path.to_path_buf()
+	} else {
+		env::current_dir()
+			.expect("Unable to determine the current directory")
+			.join(path)
+	}
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+	let Some(command_name) = args.next() else {
+		return Err("missing command".to_string());
+	};
+
+	let mut source_file = None;
+	let mut out_dir = None;
+	let mut project_root = None;
+	let mut library = false;
+	let mut dump_ast = false;
+	let mut warnings_as_errors = false;
+	let mut check = false;
+	let mut error_format = None;
+
+	while let Some(arg) = args.next() {
+		// Options with a value can also be passed as `--option=value`
+		let (arg, inline_value) = match arg.split_once('=') {
+			Some((option, value)) if option.starts_with("--") => (option.to_string(), Some(value.to_string())),
+			_ => (arg, None),
+		};
+		let mut value = || match &inline_value {
+			Some(value) => Ok(value.clone()),
+			None => expect_value(&mut args, &arg),
+		};
+
+		match arg.as_str() {
+			"-h" | "--help" => return Ok(Command::Help),
+			"--out-dir" => out_dir = Some(PathBuf::from(value()?)),
+			"--project-root" => project_root = Some(PathBuf::from(value()?)),
+			"--error-format" => {
+				error_format = Some(match value()?.as_str() {
+					"human" => ErrorFormat::Human,
+					"json" => ErrorFormat::Json,
+					other => {
+						return Err(format!(
+							"unknown error format \"{}\" (expected \"human\" or \"json\")",
+							other
+						))
+					}
+				})
+			}
+			"--library" => library = true,
+			"--dump-ast" => dump_ast = true,
+			"--warnings-as-errors" => warnings_as_errors = true,
+			"--check" => check = true,
+			_ if arg.starts_with('-') => return Err(format!("unknown option \"{}\"", arg)),
+			_ if source_file.is_none() => source_file = Some(PathBuf::from(arg)),
+			_ => return Err(format!("unexpected argument \"{}\"", arg)),
+		}
+	}
+
+	let check_option = |is_set: bool, option: &str| {
+		if is_set {
+			Err(format!(
+				"option \"{}\" is not supported by the \"{}\" command",
+				option, command_name
+			))
+		} else {
+			Ok(())
+		}
+	};
+	let require_source_file = |source_file: Option<PathBuf>| {
+		source_file.ok_or_else(|| format!("missing source file for the \"{}\" command", command_name))
+	};
+
+	match command_name.as_str() {
+		"-h" | "--help" | "help" => Ok(Command::Help),
+		"compile" => {
+			check_option(dump_ast, "--dump-ast")?;
+			check_option(check, "--check")?;
+			Ok(Command::Compile {
+				source_file: require_source_file(source_file)?,
+				out_dir,
+				project_root,
+				library,
+				warnings_as_errors,
+				error_format: error_format.unwrap_or(ErrorFormat::Human),
+			})
+		}
+		"check" => {
+			check_option(out_dir.is_some(), "--out-dir")?;
+			check_option(library, "--library")?;
+			check_option(dump_ast, "--dump-ast")?;
+			check_option(check, "--check")?;
+			Ok(Command::Check {
+				source_file: require_source_file(source_file)?,
+				project_root,
+				warnings_as_errors,
+				error_format: error_format.unwrap_or(ErrorFormat::Human),
+			})
+		}
+		"parse" => {
+			check_option(out_dir.is_some(), "--out-dir")?;
+			check_option(project_root.is_some(), "--project-root")?;
+			check_option(library, "--library")?;
+			check_option(warnings_as_errors, "--warnings-as-errors")?;
+			check_option(check, "--check")?;
+			Ok(Command::Parse {
+				source_file: require_source_file(source_file)?,
+				dump_ast,
+				error_format: error_format.unwrap_or(ErrorFormat::Human),
+			})
+		}
+		"fmt" => {
+			check_option(out_dir.is_some(), "--out-dir")?;
+			check_option(project_root.is_some(), "--project-root")?;
+			check_option(library, "--library")?;
+			check_option(dump_ast, "--dump-ast")?;
+			check_option(warnings_as_errors, "--warnings-as-errors")?;
+			Ok(Command::Fmt {
+				source_file: require_source_file(source_file)?,
+				check,
+				error_format: error_format.unwrap_or(ErrorFormat::Human),
+			})
+		}
+		"explain" => {
+			check_option(out_dir.is_some(), "--out-dir")?;
+			check_option(project_root.is_some(), "--project-root")?;
+			check_option(library, "--library")?;
+			check_option(dump_ast, "--dump-ast")?;
+			check_option(warnings_as_errors, "--warnings-as-errors")?;
+			check_option(check, "--check")?;
+			check_option(error_format.is_some(), "--error-format")?;
+			let Some(code) = source_file else {
+				return Err("missing diagnostic code for the \"explain\" command".to_string());
+			};
+			let code = code.to_string_lossy();
+			match DiagnosticCode::from_code(&code) {
+				Some(code) => Ok(Command::Explain { code }),
+				None => Err(format!("unknown diagnostic code \"{}\"", code)),
+			}
+		}
+		"lsp" => {
+			check_option(out_dir.is_some(), "--out-dir")?;
+			check_option(project_root.is_some(), "--project-root")?;
+			check_option(library, "--library")?;
+			check_option(dump_ast, "--dump-ast")?;
+			check_option(warnings_as_errors, "--warnings-as-errors")?;
+			check_option(check, "--check")?;
+			check_option(error_format.is_some(), "--error-format")?;
+			if let Some(source_file) = source_file {
+				return Err(format!("unexpected argument \"{}\"", source_file.display()));
+			}
+			Ok(Command::Lsp)
+		}
+		_ => Err(format!("unknown command \"{}\"", command_name)),
+	}
+}
+
+fn expect_value(args: &mut impl Iterator<Item = String>, option: &str) -> Result<String, String> {
+	args
+		.next()
+		.filter(|value| !value.starts_with('-'))
+		.ok_or_else(|| format!("option \"{}\" requires a value", option))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn parse(args: &[&str]) -> Result<Command, String> {
+		parse_args(args.iter().map(|arg| arg.to_string()))
+	}
+
+	#[test]
+	fn parse_compile_args() {
+		assert_eq!(
+			parse(&["compile", "main.w", "--out-dir", "target", "--project-root", "/project"]),
+			Ok(Command::Compile {
+				source_file: PathBuf::from("main.w"),
+				out_dir: Some(PathBuf::from("target")),
+				project_root: Some(PathBuf::from("/project")),
+				library: false,
+				warnings_as_errors: false,
+				error_format: ErrorFormat::Human,
+			})
+		);
+		assert_eq!(
+			parse(&["compile", "--library", "lib.w"]),
+			Ok(Command::Compile {
+				source_file: PathBuf::from("lib.w"),
+				out_dir: None,
+				project_root: None,
+				library: true,
+				warnings_as_errors: false,
+				error_format: ErrorFormat::Human,
+			})
+		);
+		assert_eq!(
+			parse(&["check", "--warnings-as-errors", "main.w", "--error-format=json"]),
+			Ok(Command::Check {
+				source_file: PathBuf::from("main.w"),
+				project_root: None,
+				warnings_as_errors: true,
+				error_format: ErrorFormat::Json,
+			})
+		);
+		assert_eq!(
+			parse(&["check", "main.w", "--project-root=/project"]),
+			Ok(Command::Check {
+				source_file: PathBuf::from("main.w"),
+				project_root: Some(PathBuf::from("/project")),
+				warnings_as_errors: false,
+				error_format: ErrorFormat::Human,
+			})
+		);
+		assert_eq!(
+			parse(&["parse", "--dump-ast", "main.w", "--error-format", "human"]),
+			Ok(Command::Parse {
+				source_file: PathBuf::from("main.w"),
+				dump_ast: true,
+				error_format: ErrorFormat::Human,
+			})
+		);
+		assert_eq!(
+			parse(&["explain", "e2003"]),
+			Ok(Command::Explain {
+				code: DiagnosticCode::UnknownSymbol
+			})
+		);
+		assert_eq!(
+			parse(&["fmt", "--check", "main.w"]),
+			Ok(Command::Fmt {
+				source_file: PathBuf::from("main.w"),
+				check: true,
+				error_format: ErrorFormat::Human,
+			})
+		);
+		assert_eq!(parse(&["lsp"]), Ok(Command::Lsp));
+		assert_eq!(parse(&["check", "main.w", "--help"]), Ok(Command::Help));
+	}
+
+	#[test]
+	fn parse_invalid_args() {
+		assert!(parse(&[]).is_err());
+		assert!(parse(&["build", "main.w"]).is_err());
+		assert!(parse(&["compile"]).is_err());
+		assert!(parse(&["compile", "a.w", "b.w"]).is_err());
+		assert!(parse(&["compile", "main.w", "--out-dir"]).is_err());
+		assert!(parse(&["check", "main.w", "--out-dir", "target"]).is_err());
+		assert!(parse(&["lsp", "main.w"]).is_err());
+		assert!(parse(&["compile", "main.w", "--check"]).is_err());
+		assert!(parse(&["check", "main.w", "--library"]).is_err());
+		assert!(parse(&["check", "main.w", "--error-format=xml"]).is_err());
+		assert!(parse(&["explain"]).is_err());
+		assert!(parse(&["explain", "E9999"]).is_err());
+	}
+}
@@ -0,0 +1,286 @@
+use std::{
+	collections::HashSet,
+	fs,
+	path::{Component, Path, PathBuf},
+};
+
+use itertools::Itertools;
+
+use crate::{
+	ast::{Scope, StmtKind, Symbol},
+	diagnostic::{report_diagnostic, Diagnostic, DiagnosticCode, DiagnosticSeverity, WingSpan},
+	files::Files,
+	parse_source,
+	type_check::jsii_importer::JsiiImportSpec,
+};
+
+const WING_FILE_EXTENSION: &str = ".w";
+
+/// A local Wing source file that was brought (directly or transitively) by the file being compiled.
+pub struct WingModule {
+	/// The normalized path of the module's source file
+	pub source_path: PathBuf,
+	/// The top scope of the module. Boxed so the scope's environment has a stable address after type checking.
+	pub scope: Box<Scope>,
+	/// The JSII imports of the module (each file has its own bring statements)
+	pub jsii_imports: Vec<JsiiImportSpec>,
+}
+
+/// Returns true if the module name of a `bring` statement refers to a local Wing source file
+/// (e.g. `bring "./other.w" as other;`).
+pub fn is_wing_module(module_name: &Symbol) -> bool {
+	let name = &module_name.name;
+	name.starts_with('"') && name.ends_with(&format!("{WING_FILE_EXTENSION}\""))
+}
+
+/// Resolves the quoted module name of a `bring` statement relative to the directory of the file
+/// that contains it.
+pub fn resolve_module_path(importing_file: &Path, module_name: &Symbol) -> PathBuf {
+	let relative_path = module_name.name.trim_matches('"');
+	let base_dir = importing_file.parent().unwrap_or(Path::new(""));
+	normalize_path(&base_dir.join(relative_path))
+}
+
+/// Name of the JS file emitted for a brought Wing module, derived from its path relative to the project root
+/// (e.g. `lib/util.w` => `preflight.lib.util.js`). Dots in directory and file names are escaped (as `%2E`), so
+/// different paths never map to the same name (e.g. `lib/util.w` and `lib.util.w`, or `../lib/util.w` for modules
+/// outside of the project root).
+pub fn preflight_file_name(source_path: &Path, project_root: &Path) -> String {
+	let relative_path = relative_path(&normalize_path(source_path), &normalize_path(project_root));
+	let segments = relative_path
+		.with_extension("")
+		.components()
+		.filter_map(|c| match c {
+			Component::Normal(s) => Some(escape_file_name_segment(&s.to_string_lossy())),
+			Component::ParentDir => Some(escape_file_name_segment("..")),
+			_ => None,
+		})
+		.collect_vec();
+	format!("preflight.{}.js", segments.join("."))
+}
+
+fn escape_file_name_segment(segment: &str) -> String {
+	segment.replace('%', "%25").replace('.', "%2E")
+}
+
+/// Lexically computes the path of `path` relative to the directory `base` (e.g. `/a/c.w` relative to `/a/b`
+/// is `../c.w`). Both paths are expected to be normalized.
+fn relative_path(path: &Path, base: &Path) -> PathBuf {
+	let path_components = path.components().collect_vec();
+	let base_components = base.components().collect_vec();
+	let common = path_components
+		.iter()
+		.zip(base_components.iter())
+		.take_while(|(a, b)| a == b)
+		.count();
+	base_components[common..]
+		.iter()
+		.map(|_| Component::ParentDir)
+		.chain(path_components[common..].iter().copied())
+		.collect()
+}
+
+/// Lexically normalizes a path by resolving `.` and `..` components. We don't use `fs::canonicalize` since it
+/// isn't available when running under WASI and it would also resolve symlinks.
+pub fn normalize_path(path: &Path) -> PathBuf {
+	let mut result = PathBuf::new();
+	for component in path.components() {
+		match component {
+			Component::CurDir => {}
+			Component::ParentDir => {
+				if !result.pop() {
+					result.push("..");
+				}
+			}
+			c => result.push(c),
+		}
+	}
+	result
+}
+
+/// Parses all the local Wing modules brought by the entry file (transitively).
+///
+/// The modules are returned in dependency order, so each module appears after all the modules it brings.
+/// The entry file itself is not included. Every parsed source is added to `files`.
+pub fn load_wing_modules(entry_path: &Path, entry_scope: &Scope, files: &mut Files) -> Vec<WingModule> {
+	let mut loader = ModuleLoader {
+		files,
+		modules: vec![],
+		visited: HashSet::new(),
+		stack: vec![normalize_path(entry_path)],
+	};
+	loader.load_brought_modules(entry_path, entry_scope);
+	loader.modules
+}
+
+struct ModuleLoader<'a> {
+	files: &'a mut Files,
+	/// Modules that were fully loaded (including their dependencies), in dependency order
+	modules: Vec<WingModule>,
+	visited: HashSet<PathBuf>,
+	/// The chain of modules currently being loaded, used to detect cyclic brings
+	stack: Vec<PathBuf>,
+}
+
+impl<'a> ModuleLoader<'a> {
+	fn load_brought_modules(&mut self, source_path: &Path, scope: &Scope) {
+		for stmt in &scope.statements {
+			if let StmtKind::Bring { module_name, .. } = &stmt.kind {
+				if is_wing_module(module_name) {
+					self.load_module(resolve_module_path(source_path, module_name), &stmt.span);
+				}
+			}
+		}
+	}
+
+	fn load_module(&mut self, source_path: PathBuf, bring_span: &WingSpan) {
+		if let Some(pos) = self.stack.iter().position(|p| p == &source_path) {
+			let cycle = self.stack[pos..]
+				.iter()
+				.chain(std::iter::once(&source_path))
+				.map(|p| p.display().to_string())
+				.join(" -> ");
+			report_diagnostic(Diagnostic {
+				message: format!("Cyclic bring of \"{}\": {}", source_path.display(), cycle),
+				span: Some(bring_span.clone()),
+				severity: DiagnosticSeverity::Error,
+				code: DiagnosticCode::CyclicBring,
+				annotations: vec![],
+				fixes: vec![],
+			});
+			return;
+		}
+
+		if self.visited.contains(&source_path) {
+			return;
+		}
+		self.visited.insert(source_path.clone());
+
+		let source = match fs::read(&source_path) {
+			Ok(source) => source,
+			Err(err) => {
+				report_diagnostic(Diagnostic {
+					message: format!("Cannot find Wing module \"{}\": {}", source_path.display(), err),
+					span: Some(bring_span.clone()),
+					severity: DiagnosticSeverity::Error,
+					code: DiagnosticCode::ModuleNotFound,
+					annotations: vec![],
+					fixes: vec![],
+				});
+				return;
+			}
+		};
+
+		match self.files.add_file(
+			&source_path,
+			String::from_utf8(source.clone()).expect("Invalid UTF-8 sequence"),
+		) {
+			Ok(()) => {}
+			Err(err) => report_diagnostic(err.into()),
+		}
+
+		let scope = parse_source(&source_path, &source);
+		if !validate_module_statements(&scope) {
+			report_diagnostic(Diagnostic {
+				message: format!(
+					"Brought Wing module \"{}\" has top-level statements that are not type definitions",
+					source_path.display()
+				),
+				span: Some(bring_span.clone()),
+				severity: DiagnosticSeverity::Error,
+				code: DiagnosticCode::InvalidModuleStatement,
+				annotations: vec![],
+				fixes: vec![],
+			});
+		}
+
+		self.stack.push(source_path.clone());
+		self.load_brought_modules(&source_path, &scope);
+		self.stack.pop();
+
+		self.modules.push(WingModule {
+			source_path,
+			scope: Box::new(scope),
+			jsii_imports: vec![],
+		});
+	}
+}
+
+/// Brought modules are only allowed to declare types (and bring other modules), since there is no
+/// construct scope to run preflight code in. Returns `false` if the module has other statements.
+fn validate_module_statements(scope: &Scope) -> bool {
+	let mut valid = true;
+	for stmt in &scope.statements {
+		match stmt.kind {
+			StmtKind::Bring { .. }
+			| StmtKind::Class(_)
+			| StmtKind::Interface(_)
+			| StmtKind::Struct { .. }
+			| StmtKind::Enum { .. } => {}
+			_ => {
+				valid = false;
+				report_diagnostic(Diagnostic {
+					message: "Brought Wing modules can only contain classes, interfaces, structs, enums and bring statements"
+						.to_string(),
+					span: Some(stmt.span.clone()),
+					severity: DiagnosticSeverity::Error,
+					code: DiagnosticCode::InvalidModuleStatement,
+					annotations: vec![],
+					fixes: vec![],
+				});
+			}
+		}
+	}
+	valid
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_is_wing_module() {
+		assert!(is_wing_module(&Symbol::global("\"./other.w\"")));
+		assert!(is_wing_module(&Symbol::global("\"../lib/util.w\"")));
+		assert!(!is_wing_module(&Symbol::global("\"cdktf\"")));
+		assert!(!is_wing_module(&Symbol::global("cloud")));
+	}
+
+	#[test]
+	fn test_resolve_module_path() {
+		let importing_file = Path::new("/project/src/main.w");
+		assert_eq!(
+			resolve_module_path(importing_file, &Symbol::global("\"./other.w\"")),
+			PathBuf::from("/project/src/other.w")
+		);
+		assert_eq!(
+			resolve_module_path(importing_file, &Symbol::global("\"../lib/./util.w\"")),
+			PathBuf::from("/project/lib/util.w")
+		);
+	}
+
+	#[test]
+	fn test_preflight_file_name() {
+		let root = Path::new("/project");
+		assert_eq!(
+			preflight_file_name(Path::new("/project/other.w"), root),
+			"preflight.other.js"
+		);
+		assert_eq!(
+			preflight_file_name(Path::new("/project/lib/util.w"), root),
+			"preflight.lib.util.js"
+		);
+		assert_eq!(
+			preflight_file_name(Path::new("/project/lib.util.w"), root),
+			"preflight.lib%2Eutil.js"
+		);
+		assert_eq!(
+			preflight_file_name(Path::new("/lib/util.w"), root),
+			"preflight.%2E%2E.lib.util.js"
+		);
+		assert_eq!(
+			preflight_file_name(Path::new("/project/../project/lib/./util.w"), root),
+			"preflight.lib.util.js"
+		);
+	}
+}
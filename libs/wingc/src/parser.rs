@@ -7,13 +7,15 @@ use tree_sitter::Node;
 use tree_sitter_traversal::{traverse, Order};
 
 use crate::ast::{
-	ArgList, BinaryOperator, CatchBlock, Class, ClassField, ElifBlock, Expr, ExprKind, FunctionBody, FunctionDefinition,
-	FunctionParameter, FunctionSignature, Interface, InterpolatedString, InterpolatedStringPart, Literal, NewExpr, Phase,
-	Reference, Scope, Stmt, StmtKind, StructField, Symbol, TypeAnnotation, TypeAnnotationKind, UnaryOperator,
-	UserDefinedType,
+	AccessModifier, ArgList, BinaryOperator, CatchBlock, Class, ClassField, ElifBlock, Expr, ExprKind, FunctionBody,
+	FunctionDefinition, FunctionParameter, FunctionSignature, Interface, InterfaceField, InterpolatedString,
+	InterpolatedStringPart, Literal, MatchArm, MatchExprArm, NewExpr, Phase, Reference, Scope, Stmt, StmtKind,
+	StructField, Symbol, TypeAnnotation, TypeAnnotationKind, TypeParameter, UnaryOperator, UserDefinedType,
 };
 use crate::comp_ctx::{CompilationContext, CompilationPhase};
-use crate::diagnostic::{report_diagnostic, Diagnostic, DiagnosticResult, WingSpan};
+use crate::diagnostic::{
+	report_diagnostic, Diagnostic, DiagnosticCode, DiagnosticFix, DiagnosticResult, DiagnosticSeverity, WingSpan,
+};
 use crate::type_check::{CLASS_INFLIGHT_INIT_NAME, CLASS_INIT_NAME};
 use crate::{dbg_panic, WINGSDK_STD_MODULE, WINGSDK_TEST_CLASS_NAME};
 
@@ -32,13 +34,9 @@ pub struct Parser<'a> {
 // k=grammar, v=optional_message, example: ("generic", "targed impl: 1.0.0")
 static UNIMPLEMENTED_GRAMMARS: phf::Map<&'static str, &'static str> = phf_map! {
 	"any" => "see https://github.com/winglang/wing/issues/434",
-	"Promise" => "see https://github.com/winglang/wing/issues/529",
 	"preflight_closure" => "see https://github.com/winglang/wing/issues/474",
 	"pure_closure" => "see https://github.com/winglang/wing/issues/474",
 	"storage_modifier" => "see https://github.com/winglang/wing/issues/107",
-	"access_modifier" => "see https://github.com/winglang/wing/issues/108",
-	"await_expression" => "see https://github.com/winglang/wing/issues/116",
-	"defer_expression" => "see https://github.com/winglang/wing/issues/116",
 	"=>" => "see https://github.com/winglang/wing/issues/474",
 };
 
@@ -112,6 +110,8 @@ static RESERVED_WORDS: phf::Set<&'static str> = phf_set! {
 	// wing-specific reserved words
 	"struct",
 	"as",
+	"is",
+	"match",
 	"nil",
 	"test",
 	"inflight",
@@ -122,6 +122,7 @@ static RESERVED_WORDS: phf::Set<&'static str> = phf_set! {
 	"num",
 	"str",
 	"duration",
+	"datetime",
 	"bool",
 	"Json",
 	"MutJson",
@@ -174,14 +175,26 @@ impl<'s> Parser<'s> {
 		let diag = Diagnostic {
 			message: message.to_string(),
 			span: Some(span),
+			severity: DiagnosticSeverity::Error,
+			code: DiagnosticCode::SyntaxError,
+			annotations: vec![],
+			fixes: vec![],
 		};
 		report_diagnostic(diag);
 	}
 
 	fn add_error(&self, message: impl ToString, node: &Node) {
+		self.add_error_with_code(DiagnosticCode::SyntaxError, message, node);
+	}
+
+	fn add_error_with_code(&self, code: DiagnosticCode, message: impl ToString, node: &Node) {
 		let diag = Diagnostic {
 			message: message.to_string(),
 			span: Some(self.node_span(node)),
+			severity: DiagnosticSeverity::Error,
+			code,
+			annotations: vec![],
+			fixes: vec![],
 		};
 		report_diagnostic(diag);
 
@@ -205,13 +218,15 @@ impl<'s> Parser<'s> {
 		node: &Node,
 	) -> DiagnosticResult<T> {
 		if let Some(entry) = UNIMPLEMENTED_GRAMMARS.get(&grammar_element) {
-			self.with_error(
+			self.add_error_with_code(
+				DiagnosticCode::UnsupportedFeature,
 				format!(
 					"{} \"{}\" is not supported yet {}",
 					grammar_context, grammar_element, entry
 				),
 				node,
-			)?
+			);
+			Err(())
 		} else {
 			self.with_error(format!("Unexpected {} \"{}\"", grammar_context, grammar_element), node)?
 		}
@@ -301,7 +316,6 @@ impl<'s> Parser<'s> {
 		WingSpan {
 			start: node_range.start_point.into(),
 			end: node_range.end_point.into(),
-			// TODO: Implement multi-file support
 			file_id: self.source_name.to_string(),
 		}
 	}
@@ -338,6 +352,7 @@ impl<'s> Parser<'s> {
 			"block" => StmtKind::Scope(self.build_scope(statement_node, phase)),
 			"if_statement" => self.build_if_statement(statement_node, phase)?,
 			"if_let_statement" => self.build_if_let_statement(statement_node, phase)?,
+			"match_statement" => self.build_match_statement(statement_node, phase)?,
 			"for_in_loop" => self.build_for_statement(statement_node, phase)?,
 			"while_statement" => self.build_while_statement(statement_node, phase)?,
 			"break_statement" => self.build_break_statement(statement_node)?,
@@ -498,6 +513,31 @@ impl<'s> Parser<'s> {
 		})
 	}
 
+	fn build_match_statement(&self, statement_node: &Node, phase: Phase) -> DiagnosticResult<StmtKind> {
+		let value = self.build_expression(&self.get_child_field(statement_node, "value")?, phase)?;
+		let mut arms = vec![];
+		let mut cursor = statement_node.walk();
+		for arm_node in statement_node.children_by_field_name("arm", &mut cursor) {
+			let mut patterns = vec![];
+			let mut arm_cursor = arm_node.walk();
+			for pattern_node in arm_node.children_by_field_name("pattern", &mut arm_cursor) {
+				patterns.push(self.build_expression(&pattern_node, phase)?);
+			}
+			arms.push(MatchArm {
+				patterns,
+				statements: self.build_scope(&self.get_child_field(&arm_node, "block")?, phase),
+			});
+		}
+		let else_statements = statement_node
+			.child_by_field_name("else_block")
+			.map(|else_block| self.build_scope(&else_block, phase));
+		Ok(StmtKind::Match {
+			value,
+			arms,
+			else_statements,
+		})
+	}
+
 	fn build_assignment_statement(&self, statement_node: &Node, phase: Phase) -> DiagnosticResult<StmtKind> {
 		let reference = self.build_reference(&statement_node.child_by_field_name("name").unwrap(), phase)?;
 		if let ExprKind::Reference(r) = reference.kind {
@@ -515,6 +555,7 @@ impl<'s> Parser<'s> {
 
 	fn build_struct_definition_statement(&self, statement_node: &Node, phase: Phase) -> DiagnosticResult<StmtKind> {
 		let name = self.check_reserved_symbol(&self.get_child_field(&statement_node, "name")?)?;
+		let type_parameters = self.build_type_parameters(statement_node, phase)?;
 
 		let mut cursor = statement_node.walk();
 		let mut members = vec![];
@@ -547,6 +588,7 @@ impl<'s> Parser<'s> {
 
 		Ok(StmtKind::Struct {
 			name,
+			type_parameters,
 			extends,
 			fields: members,
 		})
@@ -620,6 +662,25 @@ impl<'s> Parser<'s> {
 		let mut initializer = None;
 		let mut inflight_initializer = None;
 		let name = self.check_reserved_symbol(&statement_node.child_by_field_name("name").unwrap())?;
+		let type_parameters = self.build_type_parameters(statement_node, class_phase)?;
+		// The initializers of a generic class return the class instantiated with its own type parameters
+		let class_udt = UserDefinedType {
+			root: name.clone(),
+			fields: vec![],
+			type_arguments: type_parameters
+				.iter()
+				.map(|p| TypeAnnotation {
+					kind: TypeAnnotationKind::UserDefined(UserDefinedType {
+						root: p.name.clone(),
+						fields: vec![],
+						type_arguments: vec![],
+						span: p.name.span.clone(),
+					}),
+					span: p.name.span.clone(),
+				})
+				.collect(),
+			span: name.span.clone(),
+		};
 		for class_element in statement_node
 			.child_by_field_name("implementation")
 			.unwrap()
@@ -648,13 +709,6 @@ impl<'s> Parser<'s> {
 				}
 				"class_field" => {
 					let is_static = class_element.child_by_field_name("static").is_some();
-					if is_static {
-						report_diagnostic(Diagnostic {
-							message: "Static class fields not supported yet, see https://github.com/winglang/wing/issues/1668"
-								.to_string(),
-							span: Some(self.node_span(&class_element)),
-						});
-					}
 
 					// if there is no "phase_modifier", then inherit from the class phase
 					// currently "phase_modifier" can only be "inflight".
@@ -663,12 +717,39 @@ impl<'s> Parser<'s> {
 						Some(_) => Phase::Inflight,
 					};
 
+					let name = self.node_symbol(&class_element.child_by_field_name("name").unwrap())?;
+
+					// static fields are initialized inline, instance fields are initialized in the class initializers
+					let initializer = match (class_element.child_by_field_name("initializer"), is_static) {
+						(Some(initializer_node), true) => self.build_expression(&initializer_node, phase).ok(),
+						(None, true) => {
+							self.add_error(
+								format!("Static field \"{}\" must be initialized", name.name),
+								&class_element,
+							);
+							None
+						}
+						(Some(initializer_node), false) => {
+							self.add_error(
+								format!(
+									"Field \"{}\" is not static and must be initialized in the class initializer",
+									name.name
+								),
+								&initializer_node,
+							);
+							None
+						}
+						(None, false) => None,
+					};
+
 					fields.push(ClassField {
-						name: self.node_symbol(&class_element.child_by_field_name("name").unwrap())?,
+						name,
 						member_type: self.build_type_annotation(&class_element.child_by_field_name("type").unwrap(), phase)?,
 						reassignable: class_element.child_by_field_name("reassignable").is_some(),
 						is_static,
 						phase,
+						access_modifier: self.build_access_modifier(class_element.child_by_field_name("access_modifier")),
+						initializer,
 					})
 				}
 				"initializer" => {
@@ -700,11 +781,7 @@ impl<'s> Parser<'s> {
 					}
 
 					let init_return_type = Box::new(TypeAnnotation {
-						kind: TypeAnnotationKind::UserDefined(UserDefinedType {
-							root: name.clone(),
-							fields: vec![],
-							span: name.span.clone(),
-						}),
+						kind: TypeAnnotationKind::UserDefined(class_udt.clone()),
 						span: self.node_span(&class_element),
 					});
 
@@ -715,11 +792,13 @@ impl<'s> Parser<'s> {
 								self.build_scope(&class_element.child_by_field_name("block").unwrap(), Phase::Inflight),
 							),
 							signature: FunctionSignature {
+								type_parameters: vec![],
 								parameters,
 								return_type: init_return_type,
 								phase: Phase::Inflight,
 							},
 							is_static: false,
+							access_modifier: AccessModifier::Public,
 							span: self.node_span(&class_element),
 						})
 					} else {
@@ -729,7 +808,9 @@ impl<'s> Parser<'s> {
 								self.build_scope(&class_element.child_by_field_name("block").unwrap(), Phase::Preflight),
 							),
 							is_static: false,
+							access_modifier: AccessModifier::Public,
 							signature: FunctionSignature {
+								type_parameters: vec![],
 								parameters,
 								return_type: init_return_type,
 								phase: Phase::Preflight,
@@ -764,12 +845,12 @@ impl<'s> Parser<'s> {
 			None => FunctionDefinition {
 				name: Some(CLASS_INIT_NAME.into()),
 				signature: FunctionSignature {
+					type_parameters: vec![],
 					parameters: vec![],
 					return_type: Box::new(TypeAnnotation {
 						kind: TypeAnnotationKind::UserDefined(UserDefinedType {
-							root: name.clone(),
-							fields: vec![],
 							span: WingSpan::default(),
+							..class_udt.clone()
 						}),
 						span: WingSpan::default(),
 					}),
@@ -777,6 +858,7 @@ impl<'s> Parser<'s> {
 				},
 				body: FunctionBody::Statements(Scope::new(vec![], WingSpan::default())),
 				is_static: false,
+				access_modifier: AccessModifier::Public,
 				span: WingSpan::default(),
 			},
 		};
@@ -788,12 +870,12 @@ impl<'s> Parser<'s> {
 			None => FunctionDefinition {
 				name: Some(CLASS_INFLIGHT_INIT_NAME.into()),
 				signature: FunctionSignature {
+					type_parameters: vec![],
 					parameters: vec![],
 					return_type: Box::new(TypeAnnotation {
 						kind: TypeAnnotationKind::UserDefined(UserDefinedType {
-							root: name.clone(),
-							fields: vec![],
 							span: WingSpan::default(),
+							..class_udt.clone()
 						}),
 						span: WingSpan::default(),
 					}),
@@ -801,6 +883,7 @@ impl<'s> Parser<'s> {
 				},
 				body: FunctionBody::Statements(Scope::new(vec![], WingSpan::default())),
 				is_static: false,
+				access_modifier: AccessModifier::Public,
 				span: WingSpan::default(),
 			},
 		};
@@ -853,6 +936,7 @@ impl<'s> Parser<'s> {
 
 		Ok(StmtKind::Class(Class {
 			name,
+			type_parameters,
 			fields,
 			methods,
 			parent,
@@ -866,8 +950,10 @@ impl<'s> Parser<'s> {
 	fn build_interface_statement(&self, statement_node: &Node, phase: Phase) -> DiagnosticResult<StmtKind> {
 		let mut cursor = statement_node.walk();
 		let mut extends = vec![];
+		let mut fields = vec![];
 		let mut methods = vec![];
 		let name = self.check_reserved_symbol(&statement_node.child_by_field_name("name").unwrap())?;
+		let type_parameters = self.build_type_parameters(statement_node, phase)?;
 
 		for interface_element in statement_node
 			.child_by_field_name("implementation")
@@ -892,9 +978,35 @@ impl<'s> Parser<'s> {
 					}
 				}
 				"class_field" => {
-					self
-						.with_error::<Node>("Properties are not supported in interfaces", &interface_element)
-						.err();
+					if let Some(static_node) = interface_element.child_by_field_name("static") {
+						self.add_error("Static members are not allowed in interfaces", &static_node);
+					}
+					if let Some(access_modifier_node) = interface_element.child_by_field_name("access_modifier") {
+						self.add_error(
+							"Access modifiers are not allowed in interfaces, all members are public",
+							&access_modifier_node,
+						);
+					}
+					if let Some(initializer_node) = interface_element.child_by_field_name("initializer") {
+						self.add_error("Interface properties cannot be initialized", &initializer_node);
+					}
+
+					// if there is no "phase_modifier", then inherit from the interface phase
+					let field_phase = match interface_element.child_by_field_name("phase_modifier") {
+						None => phase,
+						Some(_) => Phase::Inflight,
+					};
+					let name = self.node_symbol(&interface_element.child_by_field_name("name").unwrap());
+					let member_type =
+						self.build_type_annotation(&interface_element.child_by_field_name("type").unwrap(), field_phase);
+					if let (Ok(name), Ok(member_type)) = (name, member_type) {
+						fields.push(InterfaceField {
+							name,
+							member_type,
+							reassignable: interface_element.child_by_field_name("reassignable").is_some(),
+							phase: field_phase,
+						});
+					}
 				}
 				"ERROR" => {
 					self
@@ -924,13 +1036,19 @@ impl<'s> Parser<'s> {
 			if let Ok(TypeAnnotation {
 				kind: TypeAnnotationKind::UserDefined(interface_type),
 				..
-			}) = self.build_udt_annotation(&extend)
+			}) = self.build_udt_annotation(&extend, phase)
 			{
 				extends.push(interface_type);
 			}
 		}
 
-		Ok(StmtKind::Interface(Interface { name, methods, extends }))
+		Ok(StmtKind::Interface(Interface {
+			name,
+			type_parameters,
+			fields,
+			methods,
+			extends,
+		}))
 	}
 
 	fn build_interface_method(
@@ -945,6 +1063,12 @@ impl<'s> Parser<'s> {
 	}
 
 	fn build_function_signature(&self, func_sig_node: &Node, phase: Phase) -> DiagnosticResult<FunctionSignature> {
+		if let Some(async_node) = func_sig_node.child_by_field_name("async") {
+			self.add_error(
+				"Functions cannot be declared \"async\", preflight functions are synchronous and inflight functions are always asynchronous (use \"defer\" to call a function without waiting for it)",
+				&async_node,
+			);
+		}
 		let parameters = self.build_parameter_list(&func_sig_node.child_by_field_name("parameter_list").unwrap(), phase)?;
 		let return_type = if let Some(rt) = func_sig_node.child_by_field_name("type") {
 			self.build_type_annotation(&rt, phase)?
@@ -956,6 +1080,7 @@ impl<'s> Parser<'s> {
 		};
 
 		Ok(FunctionSignature {
+			type_parameters: self.build_type_parameters(func_sig_node, phase)?,
 			parameters,
 			return_type: Box::new(return_type),
 			phase,
@@ -987,34 +1112,88 @@ impl<'s> Parser<'s> {
 			body: statements,
 			signature,
 			is_static,
+			access_modifier: self.build_access_modifier(func_def_node.child_by_field_name("access_modifier")),
 			span: self.node_span(func_def_node),
 		})
 	}
 
+	/// Members without an explicit access modifier are public.
+	fn build_access_modifier(&self, access_modifier_node: Option<Node>) -> AccessModifier {
+		match access_modifier_node.map(|n| self.node_text(&n)) {
+			Some("private") => AccessModifier::Private,
+			Some("protected") => AccessModifier::Protected,
+			_ => AccessModifier::Public,
+		}
+	}
+
 	/// Builds a vector of all parameters defined in `parameter_list_node`.
 	///
 	/// # Returns
 	/// A vector of tuples for each parameter in the list. The tuples are the name, type and a bool letting
 	/// us know whether the parameter is reassignable or not respectively.
 	fn build_parameter_list(&self, parameter_list_node: &Node, phase: Phase) -> DiagnosticResult<Vec<FunctionParameter>> {
-		let mut res = vec![];
+		let mut res: Vec<FunctionParameter> = vec![];
 		let mut cursor = parameter_list_node.walk();
 		for parameter_definition_node in parameter_list_node.named_children(&mut cursor) {
 			if parameter_definition_node.is_extra() {
 				continue;
 			}
 
+			if let Some(variadic) = res.last().filter(|p| p.variadic) {
+				self.add_error(
+					format!("Variadic parameter \"{}\" must be the last parameter", variadic.name),
+					&parameter_definition_node,
+				);
+			}
+
 			res.push(FunctionParameter {
 				name: self.check_reserved_symbol(&parameter_definition_node.child_by_field_name("name").unwrap())?,
 				type_annotation: self
 					.build_type_annotation(&parameter_definition_node.child_by_field_name("type").unwrap(), phase)?,
 				reassignable: parameter_definition_node.child_by_field_name("reassignable").is_some(),
+				variadic: parameter_definition_node.child_by_field_name("variadic").is_some(),
 			});
 		}
 
 		Ok(res)
 	}
-	fn build_udt(&self, type_node: &Node) -> DiagnosticResult<UserDefinedType> {
+	fn build_type_parameters(&self, decl_node: &Node, phase: Phase) -> DiagnosticResult<Vec<TypeParameter>> {
+		let Some(type_parameters_node) = decl_node.child_by_field_name("type_parameters") else {
+			return Ok(vec![]);
+		};
+
+		let mut cursor = type_parameters_node.walk();
+		let mut type_parameters = vec![];
+		for type_parameter_node in type_parameters_node.children_by_field_name("parameter", &mut cursor) {
+			let bound = if let Some(bound_node) = type_parameter_node.child_by_field_name("bound") {
+				Some(self.build_udt(&bound_node, phase)?)
+			} else {
+				None
+			};
+			type_parameters.push(TypeParameter {
+				name: self.check_reserved_symbol(&type_parameter_node.child_by_field_name("name").unwrap())?,
+				bound,
+			});
+		}
+
+		Ok(type_parameters)
+	}
+
+	fn build_type_arguments(&self, type_node: &Node, phase: Phase) -> DiagnosticResult<Vec<TypeAnnotation>> {
+		let Some(type_arguments_node) = type_node.child_by_field_name("type_arguments") else {
+			return Ok(vec![]);
+		};
+
+		let mut cursor = type_arguments_node.walk();
+		let mut type_arguments = vec![];
+		for type_argument_node in type_arguments_node.children_by_field_name("argument", &mut cursor) {
+			type_arguments.push(self.build_type_annotation(&type_argument_node, phase)?);
+		}
+
+		Ok(type_arguments)
+	}
+
+	fn build_udt(&self, type_node: &Node, phase: Phase) -> DiagnosticResult<UserDefinedType> {
 		match type_node.kind() {
 			"custom_type" => {
 				// check if last node is a "."
@@ -1034,6 +1213,7 @@ impl<'s> Parser<'s> {
 						.children_by_field_name("fields", &mut cursor)
 						.map(|n| self.node_symbol(&n).unwrap())
 						.collect(),
+					type_arguments: self.build_type_arguments(type_node, phase)?,
 					span: self.node_span(&type_node),
 				};
 
@@ -1050,6 +1230,7 @@ impl<'s> Parser<'s> {
 								name: builtin.to_string(),
 								span: self.node_span(&type_node),
 							}],
+							type_arguments: vec![],
 							span: self.node_span(&type_node),
 						};
 						Ok(udt)
@@ -1080,6 +1261,10 @@ impl<'s> Parser<'s> {
 					kind: TypeAnnotationKind::Duration,
 					span,
 				}),
+				"datetime" => Ok(TypeAnnotation {
+					kind: TypeAnnotationKind::Datetime,
+					span,
+				}),
 				"void" => Ok(TypeAnnotation {
 					kind: TypeAnnotationKind::Void,
 					span,
@@ -1096,7 +1281,23 @@ impl<'s> Parser<'s> {
 					span,
 				})
 			}
-			"custom_type" => Ok(self.build_udt_annotation(&type_node)?),
+			"custom_type" => Ok(self.build_udt_annotation(&type_node, phase)?),
+			"union_type" => {
+				let mut types = vec![];
+				let mut cursor = type_node.walk();
+				for member_node in type_node.children_by_field_name("types", &mut cursor) {
+					let member = self.build_type_annotation(&member_node, phase)?;
+					// `a | b | c` is parsed as `(a | b) | c`, so we flatten nested unions
+					match member.kind {
+						TypeAnnotationKind::Union(member_types) => types.extend(member_types),
+						_ => types.push(member),
+					}
+				}
+				Ok(TypeAnnotation {
+					kind: TypeAnnotationKind::Union(types),
+					span,
+				})
+			}
 			"function_type" => {
 				let param_type_list_node = type_node.child_by_field_name("parameter_types").unwrap();
 				let mut cursor = param_type_list_node.walk();
@@ -1109,12 +1310,14 @@ impl<'s> Parser<'s> {
 						name: "".into(),
 						type_annotation: t,
 						reassignable: false,
+						variadic: false,
 					})
 				}
 
 				match type_node.child_by_field_name("return_type") {
 					Some(return_type) => Ok(TypeAnnotation {
 						kind: TypeAnnotationKind::Function(FunctionSignature {
+							type_parameters: vec![],
 							parameters,
 							return_type: Box::new(self.build_type_annotation(&return_type, phase)?),
 							phase: if type_node.child_by_field_name("inflight").is_some() {
@@ -1170,6 +1373,10 @@ impl<'s> Parser<'s> {
 						kind: TypeAnnotationKind::MutSet(Box::new(self.build_type_annotation(&element_type, phase)?)),
 						span,
 					}),
+					"Promise" => Ok(TypeAnnotation {
+						kind: TypeAnnotationKind::Promise(Box::new(self.build_type_annotation(&element_type, phase)?)),
+						span,
+					}),
 					"ERROR" => self.with_error("Expected builtin container type", type_node)?,
 					other => self.report_unimplemented_grammar(other, "builtin container type", type_node),
 				}
@@ -1194,6 +1401,7 @@ impl<'s> Parser<'s> {
 							UserDefinedType {
 								root: Symbol::global(WINGSDK_STD_MODULE),
 								fields: vec![self.node_symbol(&object_expr)?],
+								type_arguments: vec![],
 								span: self.node_span(&object_expr),
 							}
 							.to_expression(),
@@ -1230,7 +1438,7 @@ impl<'s> Parser<'s> {
 		}
 	}
 
-	fn build_udt_annotation(&self, nested_node: &Node) -> DiagnosticResult<TypeAnnotation> {
+	fn build_udt_annotation(&self, nested_node: &Node, phase: Phase) -> DiagnosticResult<TypeAnnotation> {
 		// check if last node is a "."
 		let last_child = nested_node
 			.child(nested_node.child_count() - 1)
@@ -1247,6 +1455,7 @@ impl<'s> Parser<'s> {
 				.children_by_field_name("fields", &mut cursor)
 				.map(|n| self.node_symbol(&n).unwrap())
 				.collect(),
+			type_arguments: self.build_type_arguments(nested_node, phase)?,
 			span: self.node_span(&nested_node),
 		});
 		Ok(TypeAnnotation {
@@ -1264,9 +1473,13 @@ impl<'s> Parser<'s> {
 				actual_node_span,
 			)),
 			"nested_identifier" => Ok(self.build_nested_identifier(&actual_node, phase)?),
-			"structured_access_expression" => {
-				self.report_unimplemented_grammar("structured_access_expression", "reference", &actual_node)
-			}
+			"structured_access_expression" => Ok(Expr::new(
+				ExprKind::Reference(Reference::ElementAccess {
+					object: Box::new(self.build_expression(&self.get_child_field(&actual_node, "object")?, phase)?),
+					index: Box::new(self.build_expression(&self.get_child_field(&actual_node, "index")?, phase)?),
+				}),
+				actual_node_span,
+			)),
 			other => self.with_error(format!("Expected reference, got {other}"), &actual_node),
 		}
 	}
@@ -1319,7 +1532,7 @@ impl<'s> Parser<'s> {
 		let expression_node = &self.check_error(*exp_node, "expression")?;
 		match expression_node.kind() {
 			"new_expression" => {
-				let class_udt = self.build_udt(&expression_node.child_by_field_name("class").unwrap())?;
+				let class_udt = self.build_udt(&expression_node.child_by_field_name("class").unwrap(), phase)?;
 				let class_udt_exp = Expr::new(
 					ExprKind::Reference(Reference::TypeReference(class_udt)),
 					expression_span.clone(),
@@ -1391,6 +1604,18 @@ impl<'s> Parser<'s> {
 				},
 				expression_span,
 			)),
+			"await_expression" => Ok(Expr::new(
+				ExprKind::Await(Box::new(
+					self.build_expression(&expression_node.named_child(0).unwrap(), phase)?,
+				)),
+				expression_span,
+			)),
+			"defer_expression" => Ok(Expr::new(
+				ExprKind::Defer(Box::new(
+					self.build_expression(&expression_node.named_child(0).unwrap(), phase)?,
+				)),
+				expression_span,
+			)),
 			"string" => {
 				if expression_node.named_child_count() == 0 {
 					Ok(Expr::new(
@@ -1639,6 +1864,42 @@ impl<'s> Parser<'s> {
 					expression_span,
 				))
 			}
+			"type_test" => Ok(Expr::new(
+				ExprKind::TypeTest {
+					expr: Box::new(self.build_expression(&self.get_child_field(expression_node, "expression")?, phase)?),
+					type_: self.build_type_annotation(&self.get_child_field(expression_node, "type")?, phase)?,
+				},
+				expression_span,
+			)),
+			"match_expression" => {
+				let value = self.build_expression(&self.get_child_field(expression_node, "value")?, phase)?;
+				let mut arms = vec![];
+				let mut cursor = expression_node.walk();
+				for arm_node in expression_node.children_by_field_name("arm", &mut cursor) {
+					let mut patterns = vec![];
+					let mut arm_cursor = arm_node.walk();
+					for pattern_node in arm_node.children_by_field_name("pattern", &mut arm_cursor) {
+						patterns.push(self.build_expression(&pattern_node, phase)?);
+					}
+					arms.push(MatchExprArm {
+						patterns,
+						value: self.build_expression(&self.get_child_field(&arm_node, "value")?, phase)?,
+					});
+				}
+				let else_value = if let Some(else_node) = expression_node.child_by_field_name("else_value") {
+					Some(Box::new(self.build_expression(&else_node, phase)?))
+				} else {
+					None
+				};
+				Ok(Expr::new(
+					ExprKind::Match {
+						value: Box::new(value),
+						arms,
+						else_value,
+					},
+					expression_span,
+				))
+			}
 			"compiler_dbg_panic" => {
 				// Handle the debug panic expression (during parsing)
 				dbg_panic!();
@@ -1731,45 +1992,67 @@ impl<'s> Parser<'s> {
 		let iter = traverse(root.walk(), Order::Pre);
 		for node in iter {
 			if node.kind() == "AUTOMATIC_SEMICOLON" {
-				let target_node = Self::last_non_extra(node);
-				let diag = Diagnostic {
-					message: "Expected ';'".to_string(),
-					span: Some(WingSpan {
-						start: target_node.end_position().into(),
-						end: target_node.end_position().into(),
-						file_id: self.source_name.clone(),
-					}),
-				};
-				report_diagnostic(diag);
+				self.report_missing_token(";", true, &node);
 			} else if node.kind() == "AUTOMATIC_BLOCK" {
-				self.add_error("Expected block".to_string(), &Self::last_non_extra(node));
+				self.add_error_with_code(
+					DiagnosticCode::MissingToken,
+					"Expected block".to_string(),
+					&Self::last_non_extra(node),
+				);
 			} else if !self.error_nodes.borrow().contains(&node.id()) {
 				if node.is_error() {
 					if node.named_child_count() == 0 {
-						self.add_error(String::from("Unknown parser error"), &node);
+						self.add_error_with_code(
+							DiagnosticCode::UnexpectedToken,
+							String::from("Unknown parser error"),
+							&node,
+						);
 					} else {
 						let mut cursor = node.walk();
 						let children = node.named_children(&mut cursor);
 						for child in children {
-							self.add_error(format!("Unexpected '{}'", child.kind()), &child);
+							self.add_error_with_code(
+								DiagnosticCode::UnexpectedToken,
+								format!("Unexpected '{}'", child.kind()),
+								&child,
+							);
 						}
 					}
 				} else if node.is_missing() {
-					let target_node = Self::last_non_extra(node);
-					let diag = Diagnostic {
-						message: format!("Expected '{}'", node.kind()),
-						span: Some(WingSpan {
-							start: target_node.end_position().into(),
-							end: target_node.end_position().into(),
-							file_id: self.source_name.clone(),
-						}),
-					};
-					report_diagnostic(diag);
+					// Only anonymous nodes (e.g. `)`) are tokens we can suggest inserting, named nodes (e.g. an identifier) are not
+					self.report_missing_token(node.kind(), !node.is_named(), &node);
 				}
 			}
 		}
 	}
 
+	/// Reports a token missing after `node`, with a fix that inserts it if `can_insert` is set
+	fn report_missing_token(&self, token: &str, can_insert: bool, node: &Node) {
+		let target_node = Self::last_non_extra(*node);
+		let span = WingSpan {
+			start: target_node.end_position().into(),
+			end: target_node.end_position().into(),
+			file_id: self.source_name.clone(),
+		};
+		let fixes = if can_insert {
+			vec![DiagnosticFix {
+				message: format!("Insert '{}'", token),
+				span: span.clone(),
+				replacement: token.to_string(),
+			}]
+		} else {
+			vec![]
+		};
+		report_diagnostic(Diagnostic {
+			message: format!("Expected '{}'", token),
+			span: Some(span),
+			severity: DiagnosticSeverity::Error,
+			code: DiagnosticCode::MissingToken,
+			annotations: vec![],
+			fixes,
+		});
+	}
+
 	fn build_super_constructor_statement(&self, statement_node: &Node, phase: Phase, idx: usize) -> Result<StmtKind, ()> {
 		// Calls to super constructor can only occur in specific scenario:
 		// 1. We are in a derived class' constructor
@@ -1845,6 +2128,7 @@ impl<'s> Parser<'s> {
 				name: None,
 				body: FunctionBody::Statements(statements),
 				signature: FunctionSignature {
+					type_parameters: vec![],
 					parameters: vec![],
 					return_type: Box::new(TypeAnnotation {
 						kind: TypeAnnotationKind::Void,
@@ -1853,6 +2137,7 @@ impl<'s> Parser<'s> {
 					phase: Phase::Inflight,
 				},
 				is_static: true,
+				access_modifier: AccessModifier::Public,
 				span: statements_span.clone(),
 			}),
 			statements_span.clone(),
@@ -1865,6 +2150,7 @@ impl<'s> Parser<'s> {
 					ExprKind::Reference(Reference::TypeReference(UserDefinedType {
 						root: Symbol::global(WINGSDK_STD_MODULE),
 						fields: vec![Symbol::global(WINGSDK_TEST_CLASS_NAME)],
+						type_arguments: vec![],
 						span: type_span.clone(),
 					})),
 					type_span.clone(),
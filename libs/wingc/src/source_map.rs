@@ -0,0 +1,243 @@
+use std::path::{Component, Path, PathBuf};
+
+use serde_json::json;
+
+use crate::{diagnostic::WingSpan, modules::normalize_path};
+
+const BASE64_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// A position in a generated file which originates from a position in a source file
+#[derive(Debug, Clone, PartialEq)]
+struct Mapping {
+	generated_line: u32,
+	generated_col: u32,
+	/// Index of the source file in `SourceMap::sources`
+	source: usize,
+	source_line: u32,
+	source_col: u32,
+}
+
+/// Maps the lines of a generated JS file back to the Wing source code they were generated from, so stack
+/// traces and debuggers can point at Wing code. Rendered in the source map v3 format
+/// (https://sourcemaps.info/spec.html).
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SourceMap {
+	/// The source files the mappings point to (as `WingSpan::file_id`s)
+	sources: Vec<String>,
+	/// Sorted by generated position
+	mappings: Vec<Mapping>,
+}
+
+impl SourceMap {
+	/// Maps a position in the generated file to the start of the given span. Mappings must be added in the order
+	/// of their generated positions.
+	pub fn add_mapping(&mut self, generated_line: u32, generated_col: u32, span: &WingSpan) {
+		if let Some(last) = self.mappings.last() {
+			assert!(
+				(last.generated_line, last.generated_col) < (generated_line, generated_col),
+				"Source map mappings must be added in order"
+			);
+		}
+
+		let source = match self.sources.iter().position(|s| *s == span.file_id) {
+			Some(idx) => idx,
+			None => {
+				self.sources.push(span.file_id.clone());
+				self.sources.len() - 1
+			}
+		};
+
+		self.mappings.push(Mapping {
+			generated_line,
+			generated_col,
+			source,
+			source_line: span.start.line,
+			source_col: span.start.col,
+		});
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.mappings.is_empty()
+	}
+
+	/// Renders the source map of the generated file `file` as JSON. The paths of the source files are made
+	/// relative to `map_dir`, the directory the source map is written to.
+	pub fn to_json(&self, file: &str, map_dir: &Path) -> String {
+		let sources = self
+			.sources
+			.iter()
+			.map(|source| {
+				relative_path(map_dir, Path::new(source))
+					.to_string_lossy()
+					.replace('\\', "/")
+			})
+			.collect::<Vec<_>>();
+
+		json!({
+			"version": 3,
+			"file": file,
+			"sources": sources,
+			"names": [],
+			"mappings": self.encode_mappings(),
+		})
+		.to_string()
+	}
+
+	/// Encodes the mappings as a string of base64 VLQ segments: generated lines are separated by `;`, segments
+	/// within a line by `,`, and each field of a segment is relative to the same field of the previous segment
+	/// (the generated column is only relative within a line).
+	fn encode_mappings(&self) -> String {
+		let mut result = String::new();
+		let mut line = 0;
+		let mut line_has_segments = false;
+		let mut prev_generated_col = 0;
+		let mut prev_source = 0;
+		let mut prev_source_line = 0;
+		let mut prev_source_col = 0;
+
+		for mapping in &self.mappings {
+			while line < mapping.generated_line {
+				result.push(';');
+				line += 1;
+				line_has_segments = false;
+				prev_generated_col = 0;
+			}
+			if line_has_segments {
+				result.push(',');
+			}
+
+			encode_vlq(mapping.generated_col as i64 - prev_generated_col, &mut result);
+			encode_vlq(mapping.source as i64 - prev_source, &mut result);
+			encode_vlq(mapping.source_line as i64 - prev_source_line, &mut result);
+			encode_vlq(mapping.source_col as i64 - prev_source_col, &mut result);
+
+			line_has_segments = true;
+			prev_generated_col = mapping.generated_col as i64;
+			prev_source = mapping.source as i64;
+			prev_source_line = mapping.source_line as i64;
+			prev_source_col = mapping.source_col as i64;
+		}
+
+		result
+	}
+}
+
+/// Appends the base64 VLQ encoding of `value`: the sign is stored in the lowest bit, followed by groups of 5
+/// bits (least significant first) where the 6th bit marks that another group follows.
+fn encode_vlq(value: i64, out: &mut String) {
+	let mut vlq = if value < 0 { (-value << 1) | 1 } else { value << 1 };
+	loop {
+		let mut digit = vlq & 0b11111;
+		vlq >>= 5;
+		if vlq > 0 {
+			digit |= 0b100000;
+		}
+		out.push(BASE64_CHARS[digit as usize] as char);
+		if vlq == 0 {
+			break;
+		}
+	}
+}
+
+/// Returns the path of `path` relative to the directory `base`. Relative paths are resolved against the
+/// current working directory (if it's available).
+fn relative_path(base: &Path, path: &Path) -> PathBuf {
+	let base = absolute_path(base);
+	let path = absolute_path(path);
+
+	let base_components = base.components().collect::<Vec<_>>();
+	let path_components = path.components().collect::<Vec<_>>();
+	let common = base_components
+		.iter()
+		.zip(path_components.iter())
+		.take_while(|(a, b)| a == b)
+		.count();
+
+	// No common root (e.g. different drives on Windows), so the path can't be made relative
+	if common == 0 || !matches!(base_components[0], Component::RootDir | Component::Prefix(_)) {
+		return path;
+	}
+
+	let mut result = PathBuf::new();
+	for _ in common..base_components.len() {
+		result.push("..");
+	}
+	for component in &path_components[common..] {
+		result.push(component);
+	}
+	result
+}
+
+fn absolute_path(path: &Path) -> PathBuf {
+	if path.is_absolute() {
+		return normalize_path(path);
+	}
+	match std::env::current_dir() {
+		Ok(cwd) => normalize_path(&cwd.join(path)),
+		Err(_) => normalize_path(path),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::diagnostic::WingLocation;
+
+	fn span(file_id: &str, line: u32, col: u32) -> WingSpan {
+		WingSpan {
+			start: WingLocation { line, col },
+			end: WingLocation { line, col: col + 1 },
+			file_id: file_id.to_string(),
+		}
+	}
+
+	#[test]
+	fn test_encode_vlq() {
+		let encode = |value| {
+			let mut result = String::new();
+			encode_vlq(value, &mut result);
+			result
+		};
+		assert_eq!(encode(0), "A");
+		assert_eq!(encode(1), "C");
+		assert_eq!(encode(-1), "D");
+		assert_eq!(encode(15), "e");
+		assert_eq!(encode(16), "gB");
+		assert_eq!(encode(-17), "jB");
+		assert_eq!(encode(1000), "w+B");
+	}
+
+	#[test]
+	fn test_encode_mappings() {
+		let mut map = SourceMap::default();
+		map.add_mapping(0, 0, &span("/project/main.w", 0, 0));
+		map.add_mapping(0, 10, &span("/project/main.w", 0, 4));
+		map.add_mapping(2, 2, &span("/project/other.w", 3, 2));
+		map.add_mapping(3, 2, &span("/project/main.w", 1, 0));
+		assert_eq!(map.encode_mappings(), "AAAA,UAAI;;ECGF;EDFF");
+	}
+
+	#[test]
+	fn test_to_json() {
+		let mut map = SourceMap::default();
+		map.add_mapping(1, 2, &span("/project/src/main.w", 0, 0));
+		let json: serde_json::Value =
+			serde_json::from_str(&map.to_json("preflight.js", Path::new("/project/target"))).unwrap();
+		assert_eq!(json["version"], 3);
+		assert_eq!(json["file"], "preflight.js");
+		assert_eq!(json["sources"], json!(["../src/main.w"]));
+		assert_eq!(json["mappings"], ";EAAA");
+	}
+
+	#[test]
+	fn test_relative_path() {
+		assert_eq!(
+			relative_path(Path::new("/project/target/main.tfaws"), Path::new("/project/main.w")),
+			PathBuf::from("../../main.w")
+		);
+		assert_eq!(
+			relative_path(Path::new("/project"), Path::new("/project/./lib/util.w")),
+			PathBuf::from("lib/util.w")
+		);
+	}
+}
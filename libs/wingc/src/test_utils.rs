@@ -85,6 +85,8 @@ fn compile_code(code: &str) -> String {
 			let files = files
 				.filter(|f| f.is_ok())
 				.map(|f| f.unwrap().path())
+				// source maps contain paths relative to the temporary directories
+				.filter(|f| f.extension().map_or(true, |ext| ext != "map"))
 				.sorted_by_key(|f| f.as_os_str().to_string_lossy().to_string())
 				.collect::<Vec<_>>();
 
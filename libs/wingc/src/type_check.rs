@@ -1,36 +1,43 @@
 mod class_fields_init;
+mod control_flow;
+mod generics;
+pub(crate) mod jsii_exporter;
 pub(crate) mod jsii_importer;
 pub mod lifts;
 pub mod symbol_env;
 
 use crate::ast::{self, ClassField, FunctionDefinition, NewExpr, TypeAnnotationKind};
 use crate::ast::{
-	ArgList, BinaryOperator, Class as AstClass, Expr, ExprKind, FunctionBody, FunctionParameter as AstFunctionParameter,
-	Interface as AstInterface, InterpolatedStringPart, Literal, Phase, Reference, Scope, Spanned, Stmt, StmtKind, Symbol,
-	TypeAnnotation, UnaryOperator, UserDefinedType,
+	AccessModifier, ArgList, BinaryOperator, Class as AstClass, Expr, ExprKind, FunctionBody,
+	FunctionParameter as AstFunctionParameter, Interface as AstInterface, InterpolatedStringPart, Literal, Phase,
+	Reference, Scope, Spanned, Stmt, StmtKind, Symbol, TypeAnnotation, UnaryOperator, UserDefinedType,
 };
 use crate::comp_ctx::{CompilationContext, CompilationPhase};
-use crate::diagnostic::{report_diagnostic, Diagnostic, TypeError, WingSpan};
+use crate::diagnostic::{
+	report_diagnostic, Diagnostic, DiagnosticAnnotation, DiagnosticCode, DiagnosticSeverity, TypeError, WingSpan,
+};
 use crate::docs::Docs;
+use crate::modules::{is_wing_module, resolve_module_path};
 use crate::{
-	dbg_panic, debug, WINGSDK_ARRAY, WINGSDK_ASSEMBLY_NAME, WINGSDK_BRINGABLE_MODULES, WINGSDK_DURATION, WINGSDK_JSON,
-	WINGSDK_MAP, WINGSDK_MUT_ARRAY, WINGSDK_MUT_JSON, WINGSDK_MUT_MAP, WINGSDK_MUT_SET, WINGSDK_RESOURCE, WINGSDK_SET,
-	WINGSDK_STD_MODULE, WINGSDK_STRING,
+	dbg_panic, debug, WINGSDK_ARRAY, WINGSDK_ASSEMBLY_NAME, WINGSDK_BRINGABLE_MODULES, WINGSDK_DATETIME,
+	WINGSDK_DURATION, WINGSDK_JSON, WINGSDK_MAP, WINGSDK_MUT_ARRAY, WINGSDK_MUT_JSON, WINGSDK_MUT_MAP, WINGSDK_MUT_SET,
+	WINGSDK_RESOURCE, WINGSDK_SET, WINGSDK_STD_MODULE, WINGSDK_STRING,
 };
 use derivative::Derivative;
 use indexmap::{IndexMap, IndexSet};
 use itertools::{izip, Itertools};
-use jsii_importer::JsiiImporter;
+use jsii_importer::{import_nested_on_demand, lookup_fqn_importing, lookup_nested_importing, JsiiImporter};
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Display};
 use std::iter::FilterMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use symbol_env::{StatementIdx, SymbolEnv};
 use wingii::fqn::FQN;
 use wingii::type_system::TypeSystem;
 
-use self::class_fields_init::VisitClassInit;
+use self::class_fields_init::{VisitClassInit, VisitStaticFieldInit};
+use self::generics::write_generic_name;
 use self::jsii_importer::JsiiImportSpec;
 use self::lifts::Lifts;
 use self::symbol_env::{LookupResult, SymbolEnvIter, SymbolEnvRef};
@@ -110,6 +117,8 @@ pub struct VariableInfo {
 	pub phase: Phase,
 	/// The kind of variable
 	pub kind: VariableKind,
+	/// Who can access this variable (only meaningful for class members)
+	pub access_modifier: AccessModifier,
 
 	pub docs: Option<Docs>,
 }
@@ -121,6 +130,7 @@ impl SymbolKind {
 		reassignable: bool,
 		is_static: bool,
 		phase: Phase,
+		access_modifier: AccessModifier,
 		docs: Option<Docs>,
 	) -> Self {
 		SymbolKind::Variable(VariableInfo {
@@ -133,6 +143,7 @@ impl SymbolKind {
 			} else {
 				VariableKind::InstanceMember
 			},
+			access_modifier,
 			docs,
 		})
 	}
@@ -144,6 +155,7 @@ impl SymbolKind {
 			reassignable,
 			phase,
 			kind: VariableKind::Free,
+			access_modifier: AccessModifier::Public,
 			docs: None,
 		})
 	}
@@ -182,6 +194,27 @@ impl SymbolKind {
 			_ => None,
 		}
 	}
+
+	/// The span of the symbol's definition in Wing source code (`None` for builtins and JSII imported symbols)
+	pub fn definition_span(&self) -> Option<WingSpan> {
+		let span = match self {
+			SymbolKind::Variable(v) => &v.name.span,
+			SymbolKind::Type(t) => match &**t {
+				Type::Class(c) => &c.name.span,
+				Type::Interface(i) => &i.name.span,
+				Type::Struct(s) => &s.name.span,
+				Type::Enum(e) => &e.name.span,
+				Type::TypeParameter(p) => &p.name.span,
+				_ => return None,
+			},
+			SymbolKind::Namespace(_) => return None,
+		};
+		if span.file_id.is_empty() {
+			None
+		} else {
+			Some(span.clone())
+		}
+	}
 }
 
 #[derive(Debug)]
@@ -190,6 +223,7 @@ pub enum Type {
 	Number,
 	String,
 	Duration,
+	Datetime,
 	Boolean,
 	Void,
 	Json,
@@ -203,11 +237,15 @@ pub enum Type {
 	MutMap(TypeRef),
 	Set(TypeRef),
 	MutSet(TypeRef),
+	Promise(TypeRef),
+	/// A value of any of the given types, see `Types::make_union`
+	Union(Vec<TypeRef>),
 	Function(FunctionSignature),
 	Class(Class),
 	Interface(Interface),
 	Struct(Struct),
 	Enum(Enum),
+	TypeParameter(TypeParameter),
 }
 
 pub const CLASS_INIT_NAME: &'static str = "init";
@@ -228,6 +266,29 @@ pub struct Namespace {
 	// the types after initial compilation.
 	#[derivative(Debug = "ignore")]
 	pub loaded: bool,
+
+	// The fully qualified name of the JSII assembly or submodule this namespace represents (e.g.
+	// `@winglang/sdk.cloud`). Types of JSII namespaces are imported the first time they're referenced
+	// (see `jsii_importer::lookup_nested_importing`).
+	pub jsii_fqn: Option<String>,
+
+	// Symbols which were looked up in this JSII namespace but don't exist in it, so importing them isn't
+	// attempted again.
+	#[derivative(Debug = "ignore")]
+	pub missing_jsii_symbols: HashSet<String>,
+}
+
+impl Namespace {
+	/// Returns the fully qualified name of `symbol` if it should be imported from JSII before looking it up in
+	/// this namespace: this is a JSII namespace which isn't fully loaded and `symbol` wasn't imported (or found
+	/// missing) yet.
+	pub fn unimported_jsii_symbol(&self, symbol: &Symbol) -> Option<String> {
+		if self.loaded || self.missing_jsii_symbols.contains(&symbol.name) || self.env.lookup(symbol, None).is_some() {
+			return None;
+		}
+		let jsii_fqn = self.jsii_fqn.as_ref()?;
+		Some(format!("{}.{}", jsii_fqn, symbol.name))
+	}
 }
 
 pub type NamespaceRef = UnsafeRef<Namespace>;
@@ -249,6 +310,7 @@ pub struct Class {
 	pub fqn: Option<String>,
 	pub is_abstract: bool,
 	pub type_parameters: Option<Vec<TypeRef>>,
+	pub generic_instance: Option<GenericInstance>,
 	pub phase: Phase,
 	pub docs: Docs,
 	pub lifts: Option<Lifts>,
@@ -272,6 +334,15 @@ impl Class {
 			.find(|(name, type_)| name == CLOSURE_CLASS_HANDLE_METHOD && type_.is_inflight_function())
 			.map(|(_, t)| t)
 	}
+
+	/// Returns the type parameters of a user-defined generic class. JSII generics (the builtin collections) are
+	/// excluded, they are instantiated by `hydrate_class_type_arguments` instead.
+	pub fn generic_type_parameters(&self) -> Option<&Vec<TypeRef>> {
+		if self.fqn.is_some() {
+			return None;
+		}
+		self.type_parameters.as_ref()
+	}
 }
 
 #[derive(Derivative)]
@@ -282,6 +353,8 @@ pub struct Interface {
 	pub extends: Vec<TypeRef>, // Must be a Type::Interface type
 	#[derivative(Debug = "ignore")]
 	pub env: SymbolEnv,
+	pub type_parameters: Option<Vec<TypeRef>>,
+	pub generic_instance: Option<GenericInstance>,
 }
 
 impl Interface {
@@ -306,10 +379,20 @@ impl Display for Interface {
 			if method.phase == Phase::Inflight {
 				write!(f, "{}", method.type_) // show signature of inflight closure
 			} else {
-				write!(f, "{}", self.name.name)
+				write_generic_name(
+					f,
+					&self.name,
+					self.type_parameters.as_ref(),
+					self.generic_instance.as_ref(),
+				)
 			}
 		} else {
-			write!(f, "{}", self.name.name)
+			write_generic_name(
+				f,
+				&self.name,
+				self.type_parameters.as_ref(),
+				self.generic_instance.as_ref(),
+			)
 		}
 	}
 }
@@ -392,6 +475,8 @@ pub struct Struct {
 	pub extends: Vec<TypeRef>, // Must be a Type::Struct type
 	#[derivative(Debug = "ignore")]
 	pub env: SymbolEnv,
+	pub type_parameters: Option<Vec<TypeRef>>,
+	pub generic_instance: Option<GenericInstance>,
 }
 
 #[derive(Debug)]
@@ -407,6 +492,23 @@ pub struct EnumInstance {
 	pub enum_value: Symbol,
 }
 
+/// A type parameter of a generic class, interface, struct or function (e.g. `T` in `class Cache<T>`)
+#[derive(Debug)]
+pub struct TypeParameter {
+	pub name: Symbol,
+	/// The type that type arguments must be assignable to (e.g. `IFoo` in `T extends IFoo`)
+	pub bound: Option<TypeRef>,
+}
+
+/// The generic type a class, interface or struct was instantiated from (e.g. `Cache` and `[str]` for `Cache<str>`)
+#[derive(Derivative, Clone)]
+#[derivative(Debug)]
+pub struct GenericInstance {
+	#[derivative(Debug = "ignore")]
+	pub definition: TypeRef,
+	pub type_arguments: Vec<TypeRef>,
+}
+
 trait Subtype {
 	/// Returns true if `self` is a subtype of `other`.
 	///
@@ -431,6 +533,44 @@ trait Subtype {
 	}
 }
 
+/// Returns the type of the elements of a value of type `t` which can be accessed with `[]` and whether they
+/// can be assigned, or `None` if values of type `t` can't be indexed
+fn element_access_info(t: TypeRef) -> Option<(TypeRef, bool)> {
+	match &*t {
+		Type::Array(element) => Some((*element, false)),
+		Type::MutArray(element) => Some((*element, true)),
+		Type::Map(element) => Some((*element, false)),
+		Type::MutMap(element) => Some((*element, true)),
+		Type::Json => Some((t, false)),
+		Type::MutJson => Some((t, true)),
+		Type::Anything | Type::Unresolved => Some((t, true)),
+		_ => None,
+	}
+}
+
+/// Returns true if a value of type `t` can be tested for at runtime with `is`
+fn is_testable_type(t: TypeRef) -> bool {
+	match &*t {
+		Type::Number | Type::String | Type::Boolean | Type::Duration | Type::Datetime | Type::Class(_) => true,
+		Type::Union(types) => types.iter().all(|t| is_testable_type(*t)),
+		_ => false,
+	}
+}
+
+/// Returns true if some value of type `t` may also be of type `tested`
+fn can_be_of_type(t: TypeRef, tested: TypeRef) -> bool {
+	let members = |t: TypeRef| match &*t {
+		Type::Union(types) => types.clone(),
+		_ => vec![t],
+	};
+	let tested_members = members(tested);
+	members(*t.maybe_unwrap_option()).iter().any(|m| {
+		tested_members
+			.iter()
+			.any(|tm| m.is_subtype_of(tm) || tm.is_subtype_of(m))
+	})
+}
+
 impl Subtype for Phase {
 	fn is_subtype_of(&self, other: &Self) -> bool {
 		// We model phase subtyping as if the independent phase is an
@@ -463,6 +603,19 @@ impl Subtype for Type {
 				// TODO: Hack to make anything's compatible with all other types, specifically useful for handling core.Inflight handlers
 				true
 			}
+			(Self::TypeParameter(l0), _) => {
+				// A type parameter is only known to be a subtype of its bound (and of itself, which is checked above)
+				if let Self::Optional(r0) = other {
+					let r: &Type = r0;
+					if self.is_subtype_of(r) {
+						return true;
+					}
+				}
+				l0.bound.map_or(false, |bound| {
+					let bound: &Type = &bound;
+					bound.is_subtype_of(other)
+				})
+			}
 			(Self::Function(l0), Self::Interface(r0)) => {
 				// TODO: Hack to make functions compatible with interfaces
 				// Remove this after https://github.com/winglang/wing/issues/1448
@@ -507,6 +660,11 @@ impl Subtype for Type {
 				let rparams = r0.parameters.iter();
 
 				for (l, r) in lparams.zip(rparams) {
+					// a variadic parameter receives its arguments one by one, so it can only match another variadic parameter
+					if l.variadic != r.variadic {
+						return false;
+					}
+
 					// parameter types are contravariant, which means even if Cat is a subtype of Animal,
 					// (Cat) => void is not a subtype of (Animal) => void
 					// but (Animal) => void is a subtype of (Cat) => void
@@ -653,10 +811,30 @@ impl Subtype for Type {
 				let r: &Type = r0;
 				l.is_subtype_of(r)
 			}
+			(Self::Promise(l0), Self::Promise(r0)) => {
+				// A Promise type is a subtype of another Promise type if the value type is a subtype of the other value type
+				let l: &Type = l0;
+				let r: &Type = r0;
+				l.is_subtype_of(r)
+			}
 			(Self::Enum(e0), Self::Enum(e1)) => {
 				// An enum type is a subtype of another Enum type only if they are the exact same
 				e0.name == e1.name
 			}
+			(Self::Union(l0), _) => {
+				// A union is a subtype of another type if all of its members are
+				l0.iter().all(|l| {
+					let l: &Type = l;
+					l.is_subtype_of(other)
+				})
+			}
+			(_, Self::Union(r0)) => {
+				// A type is a subtype of a union if it's a subtype of one of its members
+				r0.iter().any(|r| {
+					let r: &Type = r;
+					self.is_subtype_of(r)
+				})
+			}
 			(Self::Optional(l0), Self::Optional(r0)) => {
 				// An Optional type is a subtype of another Optional type if the value type is a subtype of the other value type
 				let l: &Type = l0;
@@ -677,6 +855,7 @@ impl Subtype for Type {
 			(Self::String, Self::String) => true,
 			(Self::Boolean, Self::Boolean) => true,
 			(Self::Duration, Self::Duration) => true,
+			(Self::Datetime, Self::Datetime) => true,
 			(Self::Void, Self::Void) => true,
 			_ => false,
 		}
@@ -688,10 +867,15 @@ pub struct FunctionParameter {
 	pub name: String,
 	pub typeref: TypeRef,
 	pub docs: Docs,
+	/// Whether this is a variadic parameter (e.g. `...rest: Array<T>`), which collects all the remaining
+	/// positional arguments into an array. Only the last parameter of a function can be variadic.
+	pub variadic: bool,
 }
 
 #[derive(Clone, Debug)]
 pub struct FunctionSignature {
+	/// The type parameters of a generic function, these are inferred from the arguments of each call
+	pub type_parameters: Vec<TypeRef>,
 	/// The type of "this" inside the function, if any. This should be None for
 	/// static or anonymous functions.
 	pub this_type: Option<TypeRef>,
@@ -718,11 +902,26 @@ impl FunctionSignature {
 			.iter()
 			.rev()
 			// TODO - as a hack we treat `anything` arguments like optionals so that () => {} can be a subtype of (any) => {}
-			.take_while(|arg| arg.typeref.is_option() || arg.typeref.is_struct() || arg.typeref.is_anything())
+			.take_while(|arg| arg.variadic || arg.typeref.is_option() || arg.typeref.is_struct() || arg.typeref.is_anything())
 			.count();
 
 		self.parameters.len() - num_optionals
 	}
+
+	/// Returns true if the last parameter of this function is variadic.
+	pub fn is_variadic(&self) -> bool {
+		self.parameters.last().map_or(false, |p| p.variadic)
+	}
+
+	/// Returns the type expected for the positional argument at the given index. Arguments passed in place of a
+	/// variadic parameter are expected to be of its element type.
+	pub fn arg_type_at(&self, index: usize) -> Option<TypeRef> {
+		if self.is_variadic() && index >= self.parameters.len() - 1 {
+			let variadic = self.parameters.last().unwrap();
+			return Some(variadic.typeref.collection_item_type().unwrap_or(variadic.typeref));
+		}
+		self.parameters.get(index).map(|p| p.typeref)
+	}
 }
 
 impl PartialEq for FunctionSignature {
@@ -731,7 +930,7 @@ impl PartialEq for FunctionSignature {
 			.parameters
 			.iter()
 			.zip(other.parameters.iter())
-			.all(|(x, y)| x.typeref.is_same_type_as(&y.typeref))
+			.all(|(x, y)| x.typeref.is_same_type_as(&y.typeref) && x.variadic == y.variadic)
 			&& self.return_type.is_same_type_as(&other.return_type)
 			&& self.phase == other.phase
 	}
@@ -752,7 +951,12 @@ impl Display for Class {
 		if let Some(closure) = self.get_closure_method() {
 			std::fmt::Display::fmt(&closure, f)
 		} else {
-			write!(f, "{}", self.name.name)
+			write_generic_name(
+				f,
+				&self.name,
+				self.generic_type_parameters(),
+				self.generic_instance.as_ref(),
+			)
 		}
 	}
 }
@@ -764,25 +968,35 @@ impl Display for Type {
 			Type::Number => write!(f, "num"),
 			Type::String => write!(f, "str"),
 			Type::Duration => write!(f, "duration"),
+			Type::Datetime => write!(f, "datetime"),
 			Type::Boolean => write!(f, "bool"),
 			Type::Void => write!(f, "void"),
 			Type::Json => write!(f, "Json"),
 			Type::MutJson => write!(f, "MutJson"),
 			Type::Nil => write!(f, "nil"),
 			Type::Unresolved => write!(f, "unresolved"),
-			Type::Optional(v) => write!(f, "{}?", v),
+			Type::Optional(v) => {
+				if matches!(**v, Type::Union(_)) {
+					write!(f, "({})?", v)
+				} else {
+					write!(f, "{}?", v)
+				}
+			}
 			Type::Function(sig) => write!(f, "{}", sig),
 			Type::Class(class) => write!(f, "{}", class),
 
 			Type::Interface(iface) => write!(f, "{}", iface),
-			Type::Struct(s) => write!(f, "{}", s.name.name),
+			Type::Struct(s) => write_generic_name(f, &s.name, s.type_parameters.as_ref(), s.generic_instance.as_ref()),
 			Type::Array(v) => write!(f, "Array<{}>", v),
 			Type::MutArray(v) => write!(f, "MutArray<{}>", v),
 			Type::Map(v) => write!(f, "Map<{}>", v),
 			Type::MutMap(v) => write!(f, "MutMap<{}>", v),
 			Type::Set(v) => write!(f, "Set<{}>", v),
 			Type::MutSet(v) => write!(f, "MutSet<{}>", v),
+			Type::Promise(v) => write!(f, "Promise<{}>", v),
+			Type::Union(types) => write!(f, "{}", types.iter().join(" | ")),
 			Type::Enum(s) => write!(f, "{}", s.name.name),
+			Type::TypeParameter(p) => write!(f, "{}", p.name.name),
 		}
 	}
 }
@@ -798,17 +1012,24 @@ impl Display for FunctionSignature {
 			.parameters
 			.iter()
 			.map(|a| {
+				let variadic = if a.variadic { "..." } else { "" };
 				if a.name.is_empty() {
-					format!("{}", a.typeref)
+					format!("{}{}", variadic, a.typeref)
 				} else {
-					format!("{}: {}", a.name, a.typeref)
+					format!("{}{}: {}", variadic, a.name, a.typeref)
 				}
 			})
 			.collect::<Vec<String>>()
 			.join(", ");
 
+		let type_params_str = if self.type_parameters.is_empty() {
+			String::new()
+		} else {
+			format!("<{}>", self.type_parameters.iter().join(", "))
+		};
+
 		let ret_type_str = self.return_type.to_string();
-		write!(f, "{phase_str}({params_str}): {ret_type_str}")
+		write!(f, "{phase_str}{type_params_str}({params_str}): {ret_type_str}")
 	}
 }
 
@@ -865,6 +1086,39 @@ impl TypeRef {
 		}
 	}
 
+	pub fn as_type_parameter(&self) -> Option<&TypeParameter> {
+		if let Type::TypeParameter(ref p) = **self {
+			Some(p)
+		} else {
+			None
+		}
+	}
+
+	/// Returns the type parameters of a user-defined generic class, interface or struct
+	pub fn generic_type_parameters(&self) -> Option<&Vec<TypeRef>> {
+		match **self {
+			Type::Class(ref c) => c.generic_type_parameters(),
+			Type::Interface(ref i) => i.type_parameters.as_ref(),
+			Type::Struct(ref s) => s.type_parameters.as_ref(),
+			_ => None,
+		}
+	}
+
+	/// Returns the generic type and type arguments this class, interface or struct was instantiated from
+	pub fn generic_instance(&self) -> Option<&GenericInstance> {
+		match **self {
+			Type::Class(ref c) => c.generic_instance.as_ref(),
+			Type::Interface(ref i) => i.generic_instance.as_ref(),
+			Type::Struct(ref s) => s.generic_instance.as_ref(),
+			_ => None,
+		}
+	}
+
+	/// Returns the generic type this type was instantiated from, or the type itself if it isn't a generic instance
+	pub fn generic_definition(&self) -> TypeRef {
+		self.generic_instance().map_or(*self, |instance| instance.definition)
+	}
+
 	pub fn maybe_unwrap_option(&self) -> &Self {
 		if let Type::Optional(ref t) = **self {
 			t
@@ -929,6 +1183,10 @@ impl TypeRef {
 		matches!(**self, Type::String)
 	}
 
+	pub fn is_datetime(&self) -> bool {
+		matches!(**self, Type::Datetime)
+	}
+
 	pub fn is_struct(&self) -> bool {
 		matches!(**self, Type::Struct(_))
 	}
@@ -977,6 +1235,14 @@ impl TypeRef {
 		}
 	}
 
+	/// Returns the value type of a promise, or None if the type is not a promise.
+	pub fn as_promise(&self) -> Option<TypeRef> {
+		match **self {
+			Type::Promise(t) => Some(t),
+			_ => None,
+		}
+	}
+
 	pub fn is_mutable_collection(&self) -> bool {
 		matches!(**self, Type::MutArray(_) | Type::MutMap(_) | Type::MutSet(_))
 	}
@@ -995,6 +1261,7 @@ impl TypeRef {
 			Type::Number => true,
 			Type::String => true,
 			Type::Duration => true,
+			Type::Datetime => true,
 			Type::Boolean => true,
 			Type::Json => true,
 			Type::Nil => true,
@@ -1011,9 +1278,13 @@ impl TypeRef {
 			Type::MutMap(v) => v.is_capturable(),
 			Type::MutSet(v) => v.is_capturable(),
 			Type::Function(sig) => sig.phase == Phase::Inflight,
+			// promises only exist inflight
+			Type::Promise(_) => false,
+			Type::Union(types) => types.iter().all(|t| t.is_capturable()),
 
 			// only preflight classes can be captured
 			Type::Class(c) => c.phase == Phase::Preflight,
+			Type::TypeParameter(p) => p.bound.map_or(true, |bound| bound.is_capturable()),
 		}
 	}
 
@@ -1028,6 +1299,7 @@ impl TypeRef {
 			Type::Map(v) => v.is_mutable(),
 			Type::Set(v) => v.is_mutable(),
 			Type::Optional(v) => v.is_mutable(),
+			Type::Union(types) => types.iter().any(|t| t.is_mutable()),
 			_ => false,
 		}
 	}
@@ -1053,6 +1325,7 @@ impl TypeRef {
 			Type::Json | Type::MutJson => true,
 			Type::Array(v) => v.is_json_legal_value(),
 			Type::Optional(v) => v.is_json_legal_value(),
+			Type::Union(ref types) => types.iter().all(|t| t.is_json_legal_value()),
 			_ => false,
 		}
 	}
@@ -1087,11 +1360,19 @@ pub struct Types {
 	// Note: we need the box so reallocations of the vec while growing won't change the addresses of the types since they are referenced from the TypeRef struct
 	types: Vec<Box<Type>>,
 	namespaces: Vec<Box<Namespace>>,
+	/// Types and namespaces imported from JSII assemblies. These only reference each other and the builtin types, so
+	/// unlike the other types they can be reused by later compilations (see `reset_compilation`)
+	imported_types: Vec<Box<Type>>,
+	imported_namespaces: Vec<Box<Namespace>>,
+	/// Whether a JSII assembly is being imported, so added types and namespaces go to `imported_types` and
+	/// `imported_namespaces`
+	importing_jsii: bool,
 	pub libraries: SymbolEnv,
 	numeric_idx: usize,
 	string_idx: usize,
 	bool_idx: usize,
 	duration_idx: usize,
+	datetime_idx: usize,
 	anything_idx: usize,
 	void_idx: usize,
 	json_idx: usize,
@@ -1102,6 +1383,15 @@ pub struct Types {
 	type_for_expr: Vec<Option<ResolvedExpression>>,
 
 	resource_base_type: Option<TypeRef>,
+
+	/// Exported types of the local Wing modules, keyed by the normalized path of each module
+	wing_modules: HashMap<PathBuf, NamespaceRef>,
+
+	/// Environments that aren't owned by any scope of the AST (e.g. the type parameters of generic declarations or
+	/// the initializers of static fields). They live as long as the types, so they can be parent environments of scopes
+	detached_envs: Vec<Box<SymbolEnv>>,
+	/// Instances of generic types, so each instantiation with the same type arguments resolves to the same type
+	generic_instances: Vec<TypeRef>,
 }
 
 impl Types {
@@ -1115,6 +1405,8 @@ impl Types {
 		let bool_idx = types.len() - 1;
 		types.push(Box::new(Type::Duration));
 		let duration_idx = types.len() - 1;
+		types.push(Box::new(Type::Datetime));
+		let datetime_idx = types.len() - 1;
 		types.push(Box::new(Type::Anything));
 		let anything_idx = types.len() - 1;
 		types.push(Box::new(Type::Void));
@@ -1136,11 +1428,15 @@ impl Types {
 		Self {
 			types,
 			namespaces: Vec::new(),
+			imported_types: Vec::new(),
+			imported_namespaces: Vec::new(),
+			importing_jsii: false,
 			libraries,
 			numeric_idx,
 			string_idx,
 			bool_idx,
 			duration_idx,
+			datetime_idx,
 			anything_idx,
 			void_idx,
 			json_idx,
@@ -1149,6 +1445,9 @@ impl Types {
 			err_idx,
 			type_for_expr: Vec::new(),
 			resource_base_type: None,
+			wing_modules: HashMap::new(),
+			detached_envs: Vec::new(),
+			generic_instances: Vec::new(),
 		}
 	}
 
@@ -1172,6 +1471,10 @@ impl Types {
 		self.get_typeref(self.duration_idx)
 	}
 
+	pub fn datetime(&self) -> TypeRef {
+		self.get_typeref(self.datetime_idx)
+	}
+
 	pub fn anything(&self) -> TypeRef {
 		self.get_typeref(self.anything_idx)
 	}
@@ -1185,8 +1488,103 @@ impl Types {
 	}
 
 	pub fn add_type(&mut self, t: Type) -> TypeRef {
-		self.types.push(Box::new(t));
-		self.get_typeref(self.types.len() - 1)
+		let types = if self.importing_jsii {
+			&mut self.imported_types
+		} else {
+			&mut self.types
+		};
+		types.push(Box::new(t));
+		UnsafeRef::<Type>(&**types.last().unwrap() as *const Type)
+	}
+
+	/// Creates a union of the given types. Nested unions are flattened, duplicate members are removed and
+	/// optional members make the whole union optional (`str | num?` is the same as `(str | num)?`).
+	/// A union of a single type is the type itself.
+	pub fn make_union(&mut self, types: impl IntoIterator<Item = TypeRef>) -> TypeRef {
+		fn add_member(members: &mut Vec<TypeRef>, is_optional: &mut bool, t: TypeRef) {
+			match &*t {
+				Type::Optional(v) => {
+					*is_optional = true;
+					add_member(members, is_optional, *v);
+				}
+				Type::Nil => *is_optional = true,
+				Type::Union(types) => {
+					for t in types {
+						add_member(members, is_optional, *t);
+					}
+				}
+				_ => {
+					if !members.iter().any(|m| m.is_same_type_as(&t)) {
+						members.push(t);
+					}
+				}
+			}
+		}
+
+		let mut members = vec![];
+		let mut is_optional = false;
+		for t in types {
+			add_member(&mut members, &mut is_optional, t);
+		}
+
+		if members.iter().any(|m| m.is_anything()) {
+			return self.anything();
+		}
+
+		let union = match members.len() {
+			0 => return self.nil(),
+			1 => members[0],
+			_ => self.add_type(Type::Union(members)),
+		};
+		if is_optional {
+			self.add_type(Type::Optional(union))
+		} else {
+			union
+		}
+	}
+
+	/// Returns the type of a value of type `t` which is known to be of type `tested` (e.g. `str | num` narrowed
+	/// by `is str` is `str`).
+	pub fn narrow_to(&mut self, t: TypeRef, tested: TypeRef) -> TypeRef {
+		let t = *t.maybe_unwrap_option();
+		if t.is_anything() {
+			return tested;
+		}
+		let members = match &*t {
+			Type::Union(members) => members.clone(),
+			_ => vec![t],
+		};
+		let matching = members.into_iter().filter(|m| m.is_subtype_of(&tested)).collect_vec();
+		if matching.is_empty() {
+			tested
+		} else {
+			self.make_union(matching)
+		}
+	}
+
+	/// Returns the type of a value of type `t` which is known *not* to be of type `tested` (e.g. `str | num`
+	/// narrowed by `!(is str)` is `num`).
+	pub fn narrow_excluding(&mut self, t: TypeRef, tested: TypeRef) -> TypeRef {
+		let Type::Union(members) = &**t.maybe_unwrap_option() else {
+			return t;
+		};
+		let remaining = members
+			.iter()
+			.filter(|m| !m.is_subtype_of(&tested))
+			.copied()
+			.collect_vec();
+		let union = self.make_union(remaining);
+		if t.is_option() && !union.is_option() {
+			self.add_type(Type::Optional(union))
+		} else {
+			union
+		}
+	}
+
+	/// Adds an environment that isn't owned by a scope of the AST and returns a reference to it
+	pub fn add_detached_env(&mut self, env: SymbolEnv) -> SymbolEnvRef {
+		self.detached_envs.push(Box::new(env));
+		self.detached_envs.last().unwrap().get_ref()
 	}
 
 	/// Get the optional version of a given type.
@@ -1226,23 +1624,66 @@ impl Types {
 	}
 
 	pub fn add_namespace(&mut self, n: Namespace) -> NamespaceRef {
-		self.namespaces.push(Box::new(n));
-		self.get_namespaceref(self.namespaces.len() - 1)
+		let namespaces = if self.importing_jsii {
+			&mut self.imported_namespaces
+		} else {
+			&mut self.namespaces
+		};
+		namespaces.push(Box::new(n));
+		UnsafeRef::<Namespace>(&**namespaces.last().unwrap() as *const Namespace)
+	}
+
+	/// Drops everything added by a compilation except for the types and namespaces imported from JSII assemblies, so the
+	/// types can be reused by another compilation without importing the assemblies again. The dropped types may reference
+	/// the AST of their compilation, so this must be called before the AST is dropped and the types are reused.
+	pub fn reset_compilation(&mut self) {
+		// The builtin types are added first, the error type being the last of them
+		self.types.truncate(self.err_idx + 1);
+		self.namespaces.clear();
+		self.type_for_expr.clear();
+		self.wing_modules.clear();
+		self.detached_envs.clear();
+		self.generic_instances.clear();
+	}
+
+	/// Registers the types defined at the top level of a type checked local Wing module as a namespace,
+	/// so the module can be brought by other files.
+	pub fn add_wing_module(&mut self, source_path: &Path, scope: &Scope) {
+		let mut exports = SymbolEnv::new(None, self.void(), false, false, Phase::Preflight, 0);
+		let env = scope.env.borrow();
+		let env = env
+			.as_ref()
+			.expect("Module should be type checked before it's registered");
+		for (name, (_, kind)) in env.symbol_map.iter() {
+			if let SymbolKind::Type(t) = kind {
+				exports
+					.define(&Symbol::global(name), SymbolKind::Type(*t), StatementIdx::Top)
+					.expect("Failed to export type from module");
+			}
+		}
+
+		let name = source_path.file_stem().unwrap().to_string_lossy().to_string();
+		let ns = self.add_namespace(Namespace {
+			name,
+			env: exports,
+			loaded: true,
+			jsii_fqn: None,
+			missing_jsii_symbols: HashSet::new(),
+		});
+		self.wing_modules.insert(source_path.to_path_buf(), ns);
 	}
 
-	fn get_namespaceref(&self, idx: usize) -> NamespaceRef {
-		let t = &self.namespaces[idx];
-		UnsafeRef::<Namespace>(&**t as *const Namespace)
+	/// Returns the namespace with the exported types of a local Wing module, if it was registered.
+	pub fn get_wing_module(&self, source_path: &Path) -> Option<NamespaceRef> {
+		self.wing_modules.get(source_path).copied()
 	}
 
-	fn resource_base_type(&mut self) -> TypeRef {
+	fn resource_base_type(&mut self, jsii_types: &TypeSystem) -> TypeRef {
 		// cache the resource base type ref
 		if self.resource_base_type.is_none() {
 			let resource_fqn = format!("{}.{}", WINGSDK_ASSEMBLY_NAME, WINGSDK_RESOURCE);
 			self.resource_base_type = Some(
-				self
-					.libraries
-					.lookup_nested_str(&resource_fqn, None)
+				lookup_fqn_importing(&resource_fqn, self, jsii_types)
 					.unwrap()
 					.0
 					.as_type()
@@ -1293,12 +1734,16 @@ impl Types {
 	/// https://github.com/winglang/wing/issues/1780
 	///
 	/// Note: This Doesn't handle generics (i.e. this keeps the `T1`)
-	pub fn get_std_class(&self, type_: &str) -> Option<(&SymbolKind, symbol_env::SymbolLookupInfo)> {
+	pub fn get_std_class(
+		&mut self,
+		type_: &str,
+		jsii_types: &TypeSystem,
+	) -> Option<(&SymbolKind, symbol_env::SymbolLookupInfo)> {
 		let type_name = fully_qualify_std_type(type_);
 
 		let fqn = format!("{WINGSDK_ASSEMBLY_NAME}.{type_name}");
 
-		self.libraries.lookup_nested_str(fqn.as_str(), None).ok()
+		lookup_fqn_importing(&fqn, self, jsii_types).ok()
 	}
 }
 
@@ -1332,6 +1777,11 @@ pub struct TypeChecker<'a> {
 
 	/// Index of the current statement being type checked within the current scope
 	statement_idx: usize,
+
+	/// Function bodies which must return a value of the given type, along with the span of the return type
+	/// annotation. These are checked once the body and all of its inner scopes were type checked, since whether
+	/// a `match` statement covers all cases depends on the types of its patterns.
+	pending_return_checks: HashMap<*const Scope, (WingSpan, TypeRef)>,
 }
 
 impl<'a> TypeChecker<'a> {
@@ -1350,6 +1800,7 @@ impl<'a> TypeChecker<'a> {
 			in_json: 0,
 			is_in_mut_json: false,
 			statement_idx: 0,
+			pending_return_checks: HashMap::new(),
 		}
 	}
 
@@ -1363,19 +1814,39 @@ impl<'a> TypeChecker<'a> {
 		);
 	}
 
-	fn spanned_error_with_var<S: Into<String>>(&self, spanned: &impl Spanned, message: S) -> (VariableInfo, Phase) {
+	fn spanned_error_with_var<S: Into<String>>(
+		&self,
+		spanned: &impl Spanned,
+		code: DiagnosticCode,
+		message: S,
+	) -> (VariableInfo, Phase) {
+		self.spanned_error_with_code(spanned, code, message);
+		(self.make_error_variable_info(), Phase::Independent)
+	}
+
+	fn spanned_error<S: Into<String>>(&self, spanned: &impl Spanned, message: S) {
+		self.spanned_error_with_code(spanned, DiagnosticCode::TypeError, message);
+	}
+
+	fn spanned_error_with_code<S: Into<String>>(&self, spanned: &impl Spanned, code: DiagnosticCode, message: S) {
 		report_diagnostic(Diagnostic {
 			message: message.into(),
 			span: Some(spanned.span()),
+			severity: DiagnosticSeverity::Error,
+			code,
+			annotations: vec![],
+			fixes: vec![],
 		});
-
-		(self.make_error_variable_info(), Phase::Independent)
 	}
 
-	fn spanned_error<S: Into<String>>(&self, spanned: &impl Spanned, message: S) {
+	fn spanned_warning<S: Into<String>>(&self, spanned: &impl Spanned, code: DiagnosticCode, message: S) {
 		report_diagnostic(Diagnostic {
 			message: message.into(),
 			span: Some(spanned.span()),
+			severity: DiagnosticSeverity::Warning,
+			code,
+			annotations: vec![],
+			fixes: vec![],
 		});
 	}
 
@@ -1383,14 +1854,27 @@ impl<'a> TypeChecker<'a> {
 		report_diagnostic(Diagnostic {
 			message: message.into(),
 			span: None,
+			severity: DiagnosticSeverity::Error,
+			code: DiagnosticCode::TypeError,
+			annotations: vec![],
+			fixes: vec![],
 		});
 	}
 
 	fn type_error(&self, type_error: TypeError) -> TypeRef {
-		let TypeError { message, span } = type_error;
+		let TypeError {
+			message,
+			span,
+			code,
+			annotations,
+		} = type_error;
 		report_diagnostic(Diagnostic {
 			message,
 			span: Some(span),
+			severity: DiagnosticSeverity::Error,
+			code,
+			annotations,
+			fixes: vec![],
 		});
 
 		self.types.error()
@@ -1403,6 +1887,7 @@ impl<'a> TypeChecker<'a> {
 			reassignable: false,
 			phase: Phase::Independent,
 			kind: VariableKind::Error,
+			access_modifier: AccessModifier::Public,
 			docs: None,
 		}
 	}
@@ -1415,6 +1900,32 @@ impl<'a> TypeChecker<'a> {
 		(t, phase)
 	}
 
+	/// Type checks an expression whose type is known from its context (e.g. from the type annotation of the variable
+	/// it's assigned to). Array literals without an explicit type take the expected type, so their items can be of any
+	/// subtype of the expected element type (e.g. `let values: Array<str | num> = ["a", 1];`).
+	fn type_check_exp_with_expected_type(
+		&mut self,
+		exp: &Expr,
+		env: &SymbolEnv,
+		expected_type: TypeRef,
+	) -> (TypeRef, Phase) {
+		let ExprKind::ArrayLiteral { type_: None, items } = &exp.kind else {
+			return self.type_check_exp(exp, env);
+		};
+		let container_type = *expected_type.maybe_unwrap_option();
+		let Type::Array(element_type) = *container_type else {
+			return self.type_check_exp(exp, env);
+		};
+
+		CompilationContext::set(CompilationPhase::TypeChecking, &exp.span);
+		for item in items {
+			let (t, _) = self.type_check_exp(item, env);
+			self.check_json_serializable_or_validate_type(t, element_type, item);
+		}
+		self.types.assign_type_to_expr(exp, container_type, env.phase);
+		(container_type, env.phase)
+	}
+
 	/// Helper function for type_check_exp. This is needed because we want to be able to `return`
 	/// and break early, while still setting the evaluated type on the expression.
 	///
@@ -1449,13 +1960,18 @@ impl<'a> TypeChecker<'a> {
 					BinaryOperator::AddOrConcat => {
 						if ltype.is_subtype_of(&self.types.number()) && rtype.is_subtype_of(&self.types.number()) {
 							(self.types.number(), Phase::Independent)
+						} else if ltype.is_datetime() {
+							// A duration can be added to a datetime
+							self.validate_type(rtype, self.types.duration(), right);
+							(self.types.datetime(), Phase::Independent)
 						} else if ltype.is_subtype_of(&self.types.string()) && rtype.is_subtype_of(&self.types.string()) {
 							(self.types.string(), Phase::Independent)
 						} else {
 							// If any of the types are unresolved (error) then don't report this assuming the error has already been reported
 							if !ltype.is_unresolved() && !rtype.is_unresolved() {
-								self.spanned_error(
+								self.spanned_error_with_code(
 									exp,
+									DiagnosticCode::TypeMismatch,
 									format!(
 										"Binary operator '+' cannot be applied to operands of type '{}' and '{}'; only ({}, {}) and ({}, {}) are supported",
 										ltype, rtype, self.types.number(), self.types.number(), self.types.string(), self.types.string(),
@@ -1465,6 +1981,11 @@ impl<'a> TypeChecker<'a> {
 							self.resolved_error()
 						}
 					}
+					// A duration can be subtracted from a datetime
+					BinaryOperator::Sub if ltype.is_datetime() => {
+						self.validate_type(rtype, self.types.duration(), right);
+						(self.types.datetime(), Phase::Independent)
+					}
 					BinaryOperator::Sub
 					| BinaryOperator::Mul
 					| BinaryOperator::Div
@@ -1483,14 +2004,24 @@ impl<'a> TypeChecker<'a> {
 					| BinaryOperator::LessOrEqual
 					| BinaryOperator::Greater
 					| BinaryOperator::GreaterOrEqual => {
-						self.validate_type(ltype, self.types.number(), left);
-						self.validate_type(rtype, self.types.number(), right);
+						// Datetimes are compared with each other, everything else is compared as numbers
+						let operand_type = if ltype.is_datetime() {
+							self.types.datetime()
+						} else {
+							self.types.number()
+						};
+						self.validate_type(ltype, operand_type, left);
+						self.validate_type(rtype, operand_type, right);
 						(self.types.bool(), Phase::Independent)
 					}
 					BinaryOperator::UnwrapOr => {
 						// Left argument must be an optional type
 						if !ltype.is_option() {
-							self.spanned_error(left, format!("Expected optional type, found \"{}\"", ltype));
+							self.spanned_error_with_code(
+								left,
+								DiagnosticCode::TypeMismatch,
+								format!("Expected optional type, found \"{}\"", ltype),
+							);
 							(ltype, ltype_phase)
 						} else {
 							// Right argument must be a subtype of the inner type of the left argument
@@ -1509,7 +2040,11 @@ impl<'a> TypeChecker<'a> {
 					UnaryOperator::Minus => (self.validate_type(type_, self.types.number(), unary_exp), phase),
 					UnaryOperator::OptionalTest => {
 						if !type_.is_option() {
-							self.spanned_error(unary_exp, format!("Expected optional type, found \"{}\"", type_));
+							self.spanned_error_with_code(
+								unary_exp,
+								DiagnosticCode::TypeMismatch,
+								format!("Expected optional type, found \"{}\"", type_),
+							);
 						}
 						(self.types.bool(), phase)
 					}
@@ -1529,6 +2064,12 @@ impl<'a> TypeChecker<'a> {
 			}
 			ExprKind::Reference(_ref) => {
 				let (vi, phase) = self.resolve_reference(_ref, env);
+				// Looking up a key which doesn't exist in a map results in `nil`
+				if let Reference::ElementAccess { object, .. } = _ref {
+					if matches!(*self.types.get_expr_type(object), Type::Map(_) | Type::MutMap(_)) {
+						return (self.types.make_option(vi.type_), phase);
+					}
+				}
 				(vi.type_, phase)
 			}
 			ExprKind::New(new_expr) => {
@@ -1545,23 +2086,47 @@ impl<'a> TypeChecker<'a> {
 				let arg_list_types = self.type_check_arg_list(arg_list, env);
 
 				let ExprKind::Reference(ref r) = class.kind else {
-					self.spanned_error(exp,"Must be a reference to a class");
+					self.spanned_error_with_code(exp, DiagnosticCode::TypeMismatch, "Must be a reference to a class");
 					return (self.types.error(), Phase::Independent);
 				};
 
-				let Reference::TypeReference(_) = r else {
-					self.spanned_error(exp,"Must be a type reference to a class");
+				let Reference::TypeReference(class_udt) = r else {
+					self.spanned_error_with_code(exp, DiagnosticCode::TypeMismatch, "Must be a type reference to a class");
 					return (self.types.error(), Phase::Independent);
 				};
 
+				let init_method_name = if env.phase == Phase::Preflight {
+					CLASS_INIT_NAME
+				} else {
+					CLASS_INFLIGHT_INIT_NAME
+				};
+
+				// Infer the type arguments of a generic class from the arguments passed to its initializer
+				let class_type = if class_udt.type_arguments.is_empty() && class_type.generic_type_parameters().is_some() {
+					let init_sig = class_type
+						.as_class()
+						.and_then(|c| c.get_method(&init_method_name.into()))
+						.and_then(|init| init.type_.as_function_sig().cloned());
+					let param_and_arg_types = arg_list_types
+						.pos_args
+						.iter()
+						.enumerate()
+						.filter_map(|(i, arg)| Some((init_sig.as_ref()?.arg_type_at(i)?, *arg)))
+						.collect_vec();
+					self.infer_generic_type(class_type, &param_and_arg_types, exp)
+				} else {
+					class_type
+				};
+
 				// Lookup the class's type in the env
 				let (class_env, class_symbol) = match *class_type {
 					Type::Class(ref class) => {
 						if class.phase == Phase::Independent || env.phase == class.phase {
 							(&class.env, &class.name)
 						} else {
-							self.spanned_error(
+							self.spanned_error_with_code(
 								exp,
+								DiagnosticCode::PhaseViolation,
 								format!(
 									"Cannot create {} class \"{}\" in {} phase",
 									class.phase, class.name, env.phase
@@ -1575,15 +2140,17 @@ impl<'a> TypeChecker<'a> {
 					// If type is error, we assume the error was already reported and evauate the new expression to error as well
 					Type::Unresolved => return self.resolved_error(),
 					Type::Struct(_) => {
-						self.spanned_error(
+						self.spanned_error_with_code(
 							class,
+							DiagnosticCode::TypeMismatch,
 							format!("Cannot instantiate type \"{}\" because it is a struct and not a class. Use struct instantiation instead.", class_type),
 						);
 						return self.resolved_error();
 					}
 					_ => {
-						self.spanned_error(
+						self.spanned_error_with_code(
 							class,
+							DiagnosticCode::TypeMismatch,
 							format!("Cannot instantiate type \"{}\" because it is not a class", class_type),
 						);
 						return self.resolved_error();
@@ -1591,12 +2158,6 @@ impl<'a> TypeChecker<'a> {
 				};
 
 				// Type check args against constructor
-				let init_method_name = if env.phase == Phase::Preflight {
-					CLASS_INIT_NAME
-				} else {
-					CLASS_INFLIGHT_INIT_NAME
-				};
-
 				let lookup_res = class_env.lookup_ext(&init_method_name.into(), None);
 				let constructor_type = if let LookupResult::Found(k, _) = lookup_res {
 					k.as_variable().expect("Expected constructor to be a variable").type_
@@ -1647,8 +2208,9 @@ impl<'a> TypeChecker<'a> {
 					// Verify the object scope is an actually resource
 					if let Some(obj_scope_type) = obj_scope_type {
 						if !obj_scope_type.is_preflight_class() {
-							self.spanned_error(
+							self.spanned_error_with_code(
 								exp,
+								DiagnosticCode::TypeMismatch,
 								format!(
 									"Expected scope to be a preflight object, instead found \"{}\"",
 									obj_scope_type
@@ -1674,10 +2236,18 @@ impl<'a> TypeChecker<'a> {
 				} else {
 					// This is an inflight class, make sure the object scope and id are not set
 					if let Some(obj_scope) = obj_scope {
-						self.spanned_error(obj_scope, "Inflight classes cannot have a scope");
+						self.spanned_error_with_code(
+							obj_scope,
+							DiagnosticCode::PhaseViolation,
+							"Inflight classes cannot have a scope",
+						);
 					}
 					if let Some(obj_id) = obj_id {
-						self.spanned_error(obj_id, "Inflight classes cannot have an id");
+						self.spanned_error_with_code(
+							obj_id,
+							DiagnosticCode::PhaseViolation,
+							"Inflight classes cannot have an id",
+						);
 					}
 				}
 
@@ -1710,26 +2280,37 @@ impl<'a> TypeChecker<'a> {
 					let handle_type = if let Some(method) = lookup_res {
 						method.type_
 					} else {
-						self.spanned_error(callee, "Expected a function or method");
+						self.spanned_error_with_code(callee, DiagnosticCode::TypeMismatch, "Expected a function or method");
 						return self.resolved_error();
 					};
 					if let Some(sig_type) = handle_type.as_function_sig() {
 						sig_type.clone()
 					} else {
-						self.spanned_error(callee, "Expected a function or method");
+						self.spanned_error_with_code(callee, DiagnosticCode::TypeMismatch, "Expected a function or method");
 						return self.resolved_error();
 					}
 				} else {
-					self.spanned_error(
+					self.spanned_error_with_code(
 						callee,
+						DiagnosticCode::TypeMismatch,
 						format!("Expected a function or method, found \"{}\"", func_type),
 					);
 					return self.resolved_error();
 				};
 
+				// Infer the type arguments of a generic function from the arguments it's called with
+				let func_sig = if func_sig.type_parameters.is_empty() {
+					func_sig
+				} else if let Some(func_sig) = self.instantiate_generic_function(&func_sig, &arg_list_types.pos_args, exp) {
+					func_sig
+				} else {
+					return self.resolved_error();
+				};
+
 				if !env.phase.can_call_to(&func_sig.phase) {
-					self.spanned_error(
+					self.spanned_error_with_code(
 						exp,
+						DiagnosticCode::PhaseViolation,
 						format!("Cannot call into {} phase while {}", func_sig.phase, env.phase),
 					);
 				}
@@ -1793,7 +2374,11 @@ impl<'a> TypeChecker<'a> {
 					Type::Array(t) => t,
 					Type::MutArray(t) => t,
 					_ => {
-						self.spanned_error(exp, format!("Expected \"Array\" type, found \"{}\"", container_type));
+						self.spanned_error_with_code(
+							exp,
+							DiagnosticCode::TypeMismatch,
+							format!("Expected \"Array\" type, found \"{}\"", container_type),
+						);
 						self.types.error()
 					}
 				};
@@ -1808,7 +2393,13 @@ impl<'a> TypeChecker<'a> {
 			}
 			ExprKind::StructLiteral { type_, fields } => {
 				// Find this struct's type in the environment
-				let struct_type = self.resolve_type_annotation(type_, env);
+				let struct_type = match &type_.kind {
+					// The type arguments of a generic struct can be inferred from its fields
+					TypeAnnotationKind::UserDefined(udt) if udt.type_arguments.is_empty() => self
+						.resolve_user_defined_type(udt, env, self.statement_idx)
+						.unwrap_or_else(|e| self.type_error(e)),
+					_ => self.resolve_type_annotation(type_, env),
+				};
 
 				// Type check each of the struct's fields
 				let field_types: IndexMap<Symbol, TypeRef> = fields
@@ -1819,6 +2410,20 @@ impl<'a> TypeChecker<'a> {
 					})
 					.collect();
 
+				// Infer the type arguments of a generic struct from the values of its fields
+				let struct_type = if struct_type.generic_type_parameters().is_some() && struct_type.is_struct() {
+					let param_and_arg_types = field_types
+						.iter()
+						.filter_map(|(name, t)| {
+							let field = struct_type.as_struct()?.env.lookup(name, None)?.as_variable()?;
+							Some((field.type_, *t))
+						})
+						.collect_vec();
+					self.infer_generic_type(struct_type, &param_and_arg_types, exp)
+				} else {
+					struct_type
+				};
+
 				// If we don't have type information for the struct we don't need to validate the fields
 				if struct_type.is_anything() || struct_type.is_unresolved() {
 					return (struct_type, env.phase);
@@ -1851,7 +2456,11 @@ impl<'a> TypeChecker<'a> {
 				// Verify that no unexpected fields are present
 				for (name, _t) in field_types.iter() {
 					if st.env.lookup(name, Some(self.statement_idx)).is_none() {
-						self.spanned_error(exp, format!("\"{}\" is not a field of \"{}\"", name.name, st.name.name));
+						self.spanned_error_with_code(
+							exp,
+							DiagnosticCode::UnknownSymbol,
+							format!("\"{}\" is not a field of \"{}\"", name.name, st.name.name),
+						);
 					}
 				}
 
@@ -1884,8 +2493,9 @@ impl<'a> TypeChecker<'a> {
 					match *t {
 						Type::Json => {
 							if self.is_in_mut_json {
-								self.spanned_error(
+								self.spanned_error_with_code(
 									v,
+									DiagnosticCode::TypeMismatch,
 									"Cannot assign type: \"Json\" to a \"MutJson\" field (hint: try using Json.deepMutCopy())"
 										.to_string(),
 								)
@@ -1893,8 +2503,9 @@ impl<'a> TypeChecker<'a> {
 						}
 						Type::MutJson => {
 							if !self.is_in_mut_json {
-								self.spanned_error(
+								self.spanned_error_with_code(
 									v,
+									DiagnosticCode::TypeMismatch,
 									"Cannot assign type: \"MutJson\" to a \"Json\" field (hint: try using Json.deepCopy())".to_string(),
 								)
 							}
@@ -1903,8 +2514,9 @@ impl<'a> TypeChecker<'a> {
 					};
 
           if !t.is_json_legal_value() {
-            self.spanned_error(
+            self.spanned_error_with_code(
               v,
+              DiagnosticCode::TypeMismatch,
               format!(
                 "Expected \"Json\" elements to be Json values (https://www.json.org/json-en.html), but got \"{}\" which is not a Json value",
                 t
@@ -1931,7 +2543,11 @@ impl<'a> TypeChecker<'a> {
 					Type::Map(t) => t,
 					Type::MutMap(t) => t,
 					_ => {
-						self.spanned_error(exp, format!("Expected \"Map\" type, found \"{}\"", container_type));
+						self.spanned_error_with_code(
+							exp,
+							DiagnosticCode::TypeMismatch,
+							format!("Expected \"Map\" type, found \"{}\"", container_type),
+						);
 						self.types.error()
 					}
 				};
@@ -1960,7 +2576,11 @@ impl<'a> TypeChecker<'a> {
 					Type::Set(t) => t,
 					Type::MutSet(t) => t,
 					_ => {
-						self.spanned_error(exp, format!("Expected \"Set\" type, found \"{}\"", container_type));
+						self.spanned_error_with_code(
+							exp,
+							DiagnosticCode::TypeMismatch,
+							format!("Expected \"Set\" type, found \"{}\"", container_type),
+						);
 						self.types.error()
 					}
 				};
@@ -1974,6 +2594,88 @@ impl<'a> TypeChecker<'a> {
 				(container_type, env.phase)
 			}
 			ExprKind::FunctionClosure(func_def) => self.type_check_closure(func_def, env),
+			ExprKind::Await(inner) => {
+				let (inner_type, _) = self.type_check_exp(inner, env);
+				if env.phase != Phase::Inflight {
+					self.spanned_error_with_code(
+						exp,
+						DiagnosticCode::PhaseViolation,
+						"\"await\" can only be used in inflight code",
+					);
+				}
+
+				let awaited_type = match *inner_type {
+					Type::Promise(t) => t,
+					// awaiting an array of promises waits for all of them
+					Type::Array(item) | Type::MutArray(item) if item.as_promise().is_some() => {
+						let t = item.as_promise().unwrap();
+						self.types.add_type(Type::Array(t))
+					}
+					_ => {
+						if !inner_type.is_unresolved() {
+							self.spanned_error_with_code(
+								inner,
+								DiagnosticCode::TypeMismatch,
+								format!(
+									"Expected a promise or an array of promises, but got \"{}\" instead",
+									inner_type
+								),
+							);
+						}
+						self.types.error()
+					}
+				};
+				(awaited_type, Phase::Inflight)
+			}
+			ExprKind::Defer(inner) => {
+				let (inner_type, _) = self.type_check_exp(inner, env);
+				if env.phase != Phase::Inflight {
+					self.spanned_error_with_code(
+						exp,
+						DiagnosticCode::PhaseViolation,
+						"\"defer\" can only be used in inflight code",
+					);
+				}
+
+				// deferring a promise results in the same promise
+				if inner_type.as_promise().is_some() {
+					(inner_type, Phase::Inflight)
+				} else {
+					(self.types.add_type(Type::Promise(inner_type)), Phase::Inflight)
+				}
+			}
+			ExprKind::TypeTest { expr: inner, type_ } => {
+				let (_, _, phase) = self.type_check_type_test(exp, inner, type_, env);
+				(self.types.bool(), phase)
+			}
+			ExprKind::Match {
+				value,
+				arms,
+				else_value,
+			} => {
+				let (value_type, _) = self.type_check_exp(value, env);
+
+				let mut matched: Vec<String> = vec![];
+				let mut arm_types = vec![];
+				for arm in arms {
+					self.type_check_match_patterns(&arm.patterns, value_type, &mut matched, env);
+					arm_types.push(self.type_check_exp(&arm.value, env).0);
+				}
+				if let Some(else_value) = else_value {
+					arm_types.push(self.type_check_exp(else_value, env).0);
+				}
+
+				let patterns = arms.iter().flat_map(|arm| arm.patterns.iter());
+				let missing = control_flow::missing_match_patterns(self.types, value, patterns);
+				self.check_match_exhaustiveness(value, value_type, missing, else_value.as_ref().map(|e| &e.span));
+
+				// The match evaluates to the value of one of its arms
+				if arm_types.iter().any(|t| t.is_unresolved()) {
+					(self.types.error(), env.phase)
+				} else {
+					(self.types.make_union(arm_types), env.phase)
+				}
+			}
 			ExprKind::CompilerDebugPanic => {
 				// Handle the debug panic expression (during type-checking)
 				dbg_panic!();
@@ -1981,6 +2683,8 @@ impl<'a> TypeChecker<'a> {
 					self.type_error(TypeError {
 						message: "Panic expression".to_string(),
 						span: exp.span.clone(),
+						code: DiagnosticCode::TypeError,
+						annotations: vec![],
 					}),
 					env.phase,
 				)
@@ -1988,26 +2692,95 @@ impl<'a> TypeChecker<'a> {
 		}
 	}
 
-	fn resolved_error(&mut self) -> (UnsafeRef<Type>, Phase) {
-		(self.types.error(), Phase::Independent)
+	/// Type checks the patterns of a `match` arm against the type of the matched value, warning about patterns
+	/// already in `matched` and adding the rest to it. Returns whether the arm matches `nil`.
+	fn type_check_match_patterns(
+		&mut self,
+		patterns: &[Expr],
+		value_type: TypeRef,
+		matched: &mut Vec<String>,
+		env: &SymbolEnv,
+	) -> bool {
+		let mut matches_nil = false;
+		for pattern in patterns {
+			let (pattern_type, _) = self.type_check_exp(pattern, env);
+			let Some(key) = control_flow::match_pattern_key(self.types, pattern) else {
+				self.spanned_error(pattern, "Match patterns must be literals, \"nil\" or enum values");
+				continue;
+			};
+			self.validate_type(pattern_type, value_type, pattern);
+			matches_nil |= key == "nil";
+			if matched.contains(&key) {
+				self.spanned_warning(
+					pattern,
+					DiagnosticCode::UnreachableCode,
+					format!("Pattern \"{}\" is already matched by a previous arm", key),
+				);
+			} else {
+				matched.push(key);
+			}
+		}
+		matches_nil
 	}
 
-	fn type_check_arg_list_against_function_sig(
+	/// Reports a `match` without an `else` arm which doesn't cover all values, or an `else` arm which is unreachable
+	/// (see `control_flow::missing_match_patterns`)
+	fn check_match_exhaustiveness(
 		&mut self,
-		arg_list: &ArgList,
-		func_sig: &FunctionSignature,
-		exp: &impl Spanned,
-		arg_list_types: ArgListTypes,
-	) -> Option<UnsafeRef<Type>> {
-		// Verify arity
-		let pos_args_count = arg_list.pos_args.len();
-		let min_args = func_sig.min_parameters();
+		value: &Expr,
+		value_type: TypeRef,
+		missing: Option<Vec<String>>,
+		else_span: Option<&WingSpan>,
+	) {
+		if let Some(else_span) = else_span {
+			if missing.map_or(false, |missing| missing.is_empty()) {
+				self.spanned_warning(
+					else_span,
+					DiagnosticCode::UnreachableCode,
+					"Unreachable \"else\" arm, all values are already matched",
+				);
+			}
+			return;
+		}
+
+		match missing {
+			None => self.spanned_error_with_code(
+				value,
+				DiagnosticCode::NonExhaustiveMatch,
+				format!(
+					"Match over a value of type \"{}\" is not exhaustive, add an \"else\" arm",
+					value_type
+				),
+			),
+			Some(missing) if !missing.is_empty() => self.spanned_error_with_code(
+				value,
+				DiagnosticCode::NonExhaustiveMatch,
+				format!("Match is not exhaustive, missing: {}", missing.join(", ")),
+			),
+			Some(_) => {}
+		}
+	}
+
+	fn resolved_error(&mut self) -> (UnsafeRef<Type>, Phase) {
+		(self.types.error(), Phase::Independent)
+	}
+
+	fn type_check_arg_list_against_function_sig(
+		&mut self,
+		arg_list: &ArgList,
+		func_sig: &FunctionSignature,
+		exp: &impl Spanned,
+		arg_list_types: ArgListTypes,
+	) -> Option<UnsafeRef<Type>> {
+		// Verify arity
+		let pos_args_count = arg_list.pos_args.len();
+		let min_args = func_sig.min_parameters();
 		if pos_args_count < min_args {
 			let err_text = format!(
 				"Expected {} positional argument(s) but got {}",
 				min_args, pos_args_count
 			);
-			self.spanned_error(exp, err_text);
+			self.spanned_error_with_code(exp, DiagnosticCode::InvalidArguments, err_text);
 			return Some(self.types.error());
 		}
 
@@ -2015,8 +2788,9 @@ impl<'a> TypeChecker<'a> {
 			let last_arg = match func_sig.parameters.last() {
 				Some(arg) => arg.typeref.maybe_unwrap_option(),
 				None => {
-					self.spanned_error(
+					self.spanned_error_with_code(
 						exp,
+						DiagnosticCode::InvalidArguments,
 						format!("Expected 0 named arguments for func at {}", exp.span().to_string()),
 					);
 					return Some(self.types.error());
@@ -2024,7 +2798,7 @@ impl<'a> TypeChecker<'a> {
 			};
 
 			if !last_arg.is_struct() {
-				self.spanned_error(exp, "No named arguments expected");
+				self.spanned_error_with_code(exp, DiagnosticCode::InvalidArguments, "No named arguments expected");
 				return Some(self.types.error());
 			}
 
@@ -2037,12 +2811,23 @@ impl<'a> TypeChecker<'a> {
 			.parameters
 			.iter()
 			.rev()
-			.take_while(|arg| arg.typeref.is_option())
+			.take_while(|arg| arg.variadic || arg.typeref.is_option())
 			.count();
 
 		// Verify arity
 		let arg_count = arg_list.pos_args.len() + (if arg_list.named_args.is_empty() { 0 } else { 1 });
 		let min_args = func_sig.parameters.len() - num_optionals;
+		if func_sig.is_variadic() {
+			// Verify passed positional arguments match the function's parameter types, including each of the
+			// arguments passed to the variadic parameter
+			for (i, (arg_expr, arg_type)) in arg_list.pos_args.iter().zip(arg_list_types.pos_args.iter()).enumerate() {
+				if let Some(param_type) = func_sig.arg_type_at(i) {
+					self.validate_type(*arg_type, param_type, arg_expr);
+				}
+			}
+			return None;
+		}
+
 		let max_args = func_sig.parameters.len();
 		if arg_count < min_args || arg_count > max_args {
 			let err_text = if min_args == max_args {
@@ -2053,7 +2838,7 @@ impl<'a> TypeChecker<'a> {
 					min_args, max_args, arg_count
 				)
 			};
-			self.spanned_error(exp, err_text);
+			self.spanned_error_with_code(exp, DiagnosticCode::InvalidArguments, err_text);
 		}
 		let params = func_sig
 			.parameters
@@ -2067,16 +2852,89 @@ impl<'a> TypeChecker<'a> {
 		None
 	}
 
+	/// Type checks a type test expression (`inner is type_`) and returns the type of the tested value, the tested
+	/// type and the phase of the expression.
+	fn type_check_type_test(
+		&mut self,
+		exp: &Expr,
+		inner: &Expr,
+		type_: &TypeAnnotation,
+		env: &SymbolEnv,
+	) -> (TypeRef, TypeRef, Phase) {
+		let (inner_type, phase) = self.type_check_exp(inner, env);
+		let tested_type = self.resolve_type_annotation(type_, env);
+		if tested_type.is_unresolved() || inner_type.is_unresolved() {
+			return (inner_type, tested_type, phase);
+		}
+
+		if !is_testable_type(tested_type) {
+			self.spanned_error(
+				type_,
+				format!(
+					"Cannot test whether a value is of type \"{}\" at runtime, only primitive types, \"duration\", \"datetime\" and classes can be tested",
+					tested_type
+				),
+			);
+		} else if env.phase == Phase::Inflight && tested_type.as_class().is_some() {
+			self.spanned_error_with_code(
+				type_,
+				DiagnosticCode::PhaseViolation,
+				format!(
+					"Cannot test whether a value is of class \"{}\" in inflight code",
+					tested_type
+				),
+			);
+		} else if !inner_type.is_anything() && !inner_type.is_json() && !can_be_of_type(inner_type, tested_type) {
+			self.spanned_error(
+				exp,
+				format!(
+					"Expression of type \"{}\" can never be of type \"{}\"",
+					inner_type, tested_type
+				),
+			);
+		}
+		(inner_type, tested_type, phase)
+	}
+
+	/// Type checks the condition of an `if` or `elif` statement. If the condition tests the type of a variable
+	/// which can't be reassigned (`x is T`), returns the variable's name, its type and the tested type so that
+	/// the variable can be narrowed within the statement's blocks.
+	fn type_check_condition(&mut self, condition: &Expr, env: &SymbolEnv) -> Option<(String, TypeRef, TypeRef)> {
+		let ExprKind::TypeTest { expr: inner, type_ } = &condition.kind else {
+			let (cond_type, _) = self.type_check_exp(condition, env);
+			self.validate_type(cond_type, self.types.bool(), condition);
+			return None;
+		};
+
+		CompilationContext::set(CompilationPhase::TypeChecking, &condition.span);
+		let (inner_type, tested_type, phase) = self.type_check_type_test(condition, inner, type_, env);
+		self.types.assign_type_to_expr(condition, self.types.bool(), phase);
+
+		let ExprKind::Reference(Reference::Identifier(symbol)) = &inner.kind else {
+			return None;
+		};
+		let var = env.lookup(symbol, Some(self.statement_idx))?.as_variable()?;
+		if var.reassignable || inner_type.is_unresolved() || tested_type.is_unresolved() {
+			return None;
+		}
+		Some((symbol.name.clone(), inner_type, tested_type))
+	}
+
 	fn type_check_closure(&mut self, func_def: &ast::FunctionDefinition, env: &SymbolEnv) -> (UnsafeRef<Type>, Phase) {
-		// TODO: make sure this function returns on all control paths when there's a return type (can be done by recursively traversing the statements and making sure there's a "return" statements in all control paths)
-		// https://github.com/winglang/wing/issues/457
 		// Create a type_checker function signature from the AST function definition
 		let function_type = self.resolve_type_annotation(&func_def.signature.to_type_annotation(), env);
 		let sig = function_type.as_function_sig().unwrap();
 
+		// The type parameters of a generic function can be referenced from its body
+		let parent_env = if sig.type_parameters.is_empty() {
+			env.get_ref()
+		} else {
+			self.make_type_parameters_env(&sig.type_parameters, env)
+		};
+
 		// Create an environment for the function
 		let mut function_env = SymbolEnv::new(
-			Some(env.get_ref()),
+			Some(parent_env),
 			sig.return_type,
 			false,
 			true,
@@ -2087,6 +2945,9 @@ impl<'a> TypeChecker<'a> {
 
 		// Type check the function body
 		if let FunctionBody::Statements(scope) = &func_def.body {
+			self
+				.pending_return_checks
+				.insert(scope, (func_def.signature.return_type.span(), sig.return_type));
 			scope.set_env(function_env);
 
 			self.inner_scopes.push(scope);
@@ -2108,7 +2969,11 @@ impl<'a> TypeChecker<'a> {
 		let expected_struct = if let Some(expected_struct) = expected_type.as_struct() {
 			expected_struct
 		} else {
-			self.spanned_error(value, "Named arguments provided for non-struct argument");
+			self.spanned_error_with_code(
+				value,
+				DiagnosticCode::InvalidArguments,
+				"Named arguments provided for non-struct argument",
+			);
 			return;
 		};
 
@@ -2124,7 +2989,11 @@ impl<'a> TypeChecker<'a> {
 					.type_;
 				field_map.insert(k.name.clone(), (k, field_type));
 			} else {
-				self.spanned_error(value, format!("\"{}\" is not a field of \"{}\"", k.name, expected_type));
+				self.spanned_error_with_code(
+					value,
+					DiagnosticCode::InvalidArguments,
+					format!("\"{}\" is not a field of \"{}\"", k.name, expected_type),
+				);
 			}
 		}
 
@@ -2142,8 +3011,9 @@ impl<'a> TypeChecker<'a> {
 				let t = object_types.get(*symb).unwrap();
 				self.validate_type(*t, *expected_field_type, provided_exp);
 			} else if !v.is_option() {
-				self.spanned_error(
+				self.spanned_error_with_code(
 					value,
+					DiagnosticCode::InvalidArguments,
 					format!(
 						"Missing required field \"{}\" from \"{}\"",
 						k, expected_struct.name.name
@@ -2165,8 +3035,9 @@ impl<'a> TypeChecker<'a> {
 		}
 
 		if !actual_type.is_json_legal_value() {
-			self.spanned_error(
+			self.spanned_error_with_code(
 				exp,
+				DiagnosticCode::TypeMismatch,
 				format!(
 					"Expected \"Json\" elements to be Json values (https://www.json.org/json-en.html), but got \"{}\" which is not a Json value",
 					actual_type
@@ -2239,6 +3110,10 @@ impl<'a> TypeChecker<'a> {
 		report_diagnostic(Diagnostic {
 			message,
 			span: Some(span.span()),
+			severity: DiagnosticSeverity::Error,
+			code: DiagnosticCode::TypeMismatch,
+			annotations: vec![],
+			fixes: vec![],
 		});
 
 		// Evaluate to one of the expected types
@@ -2255,6 +3130,13 @@ impl<'a> TypeChecker<'a> {
 		for inner_scope in inner_scopes {
 			self.type_check_scope(unsafe { &*inner_scope });
 		}
+
+		for unreachable in control_flow::find_unreachable_stmts(self.types, scope) {
+			self.spanned_warning(unreachable, DiagnosticCode::UnreachableCode, "Unreachable code");
+		}
+		if let Some((return_type_span, return_type)) = self.pending_return_checks.remove(&(scope as *const Scope)) {
+			self.check_all_paths_return(scope, &return_type_span, return_type);
+		}
 	}
 
 	fn resolve_type_annotation(&mut self, annotation: &TypeAnnotation, env: &SymbolEnv) -> TypeRef {
@@ -2263,6 +3145,7 @@ impl<'a> TypeChecker<'a> {
 			TypeAnnotationKind::String => self.types.string(),
 			TypeAnnotationKind::Bool => self.types.bool(),
 			TypeAnnotationKind::Duration => self.types.duration(),
+			TypeAnnotationKind::Datetime => self.types.datetime(),
 			TypeAnnotationKind::Void => self.types.void(),
 			TypeAnnotationKind::Json => self.types.json(),
 			TypeAnnotationKind::MutJson => self.types.mut_json(),
@@ -2271,18 +3154,39 @@ impl<'a> TypeChecker<'a> {
 				self.types.add_type(Type::Optional(value_type))
 			}
 			TypeAnnotationKind::Function(ast_sig) => {
+				// The type parameters of a generic function can be referenced from its signature
+				let type_parameters = self.resolve_type_parameters(&ast_sig.type_parameters, env, self.statement_idx);
+				let sig_env = if type_parameters.is_empty() {
+					env.get_ref()
+				} else {
+					self.make_type_parameters_env(&type_parameters, env)
+				};
+
 				let mut parameters = vec![];
 				for p in ast_sig.parameters.iter() {
+					let typeref = self.resolve_type_annotation(&p.type_annotation, &sig_env);
+					if p.variadic && !matches!(*typeref, Type::Array(_) | Type::Unresolved) {
+						self.spanned_error_with_code(
+							&p.type_annotation,
+							DiagnosticCode::TypeMismatch,
+							format!(
+								"Variadic parameter \"{}\" must be of type \"Array<T>\", but got \"{}\" instead",
+								p.name, typeref
+							),
+						);
+					}
 					parameters.push(FunctionParameter {
 						name: p.name.name.clone(),
-						typeref: self.resolve_type_annotation(&p.type_annotation, env),
+						typeref,
 						docs: Docs::default(),
+						variadic: p.variadic,
 					});
 				}
 				let sig = FunctionSignature {
+					type_parameters,
 					this_type: None,
 					parameters,
-					return_type: self.resolve_type_annotation(ast_sig.return_type.as_ref(), env),
+					return_type: self.resolve_type_annotation(ast_sig.return_type.as_ref(), &sig_env),
 					phase: ast_sig.phase,
 					js_override: None,
 					docs: Docs::default(),
@@ -2290,9 +3194,12 @@ impl<'a> TypeChecker<'a> {
 				// TODO: avoid creating a new type for each function_sig resolution
 				self.types.add_type(Type::Function(sig))
 			}
-			TypeAnnotationKind::UserDefined(user_defined_type) => self
-				.resolve_user_defined_type(user_defined_type, env, self.statement_idx)
-				.unwrap_or_else(|e| self.type_error(e)),
+			TypeAnnotationKind::UserDefined(user_defined_type) => {
+				let t = self
+					.resolve_user_defined_type(user_defined_type, env, self.statement_idx)
+					.unwrap_or_else(|e| self.type_error(e));
+				self.validate_generic_type_has_arguments(t, user_defined_type)
+			}
 			TypeAnnotationKind::Array(v) => {
 				let value_type = self.resolve_type_annotation(v, env);
 				// TODO: avoid creating a new type for each array resolution
@@ -2323,6 +3230,14 @@ impl<'a> TypeChecker<'a> {
 				// TODO: avoid creating a new type for each map resolution
 				self.types.add_type(Type::MutMap(value_type))
 			}
+			TypeAnnotationKind::Promise(v) => {
+				let value_type = self.resolve_type_annotation(v, env);
+				self.types.add_type(Type::Promise(value_type))
+			}
+			TypeAnnotationKind::Union(types) => {
+				let types = types.iter().map(|t| self.resolve_type_annotation(t, env)).collect_vec();
+				self.types.make_union(types)
+			}
 		}
 	}
 
@@ -2365,7 +3280,10 @@ impl<'a> TypeChecker<'a> {
 				type_,
 			} => {
 				let explicit_type = type_.as_ref().map(|t| self.resolve_type_annotation(t, env));
-				let (inferred_type, _) = self.type_check_exp(initial_value, env);
+				let (inferred_type, _) = match explicit_type {
+					Some(explicit_type) => self.type_check_exp_with_expected_type(initial_value, env, explicit_type),
+					None => self.type_check_exp(initial_value, env),
+				};
 				if inferred_type.is_void() {
 					self.spanned_error(
 						var_name,
@@ -2373,8 +3291,9 @@ impl<'a> TypeChecker<'a> {
 					);
 				}
 				if explicit_type.is_none() && inferred_type.is_nil() {
-					self.spanned_error(
+					self.spanned_error_with_code(
 						initial_value,
+						DiagnosticCode::TypeMismatch,
 						"Cannot assign nil value to variables without explicit optional type",
 					);
 				}
@@ -2468,6 +3387,10 @@ impl<'a> TypeChecker<'a> {
 					report_diagnostic(Diagnostic {
 						message: format!("Expected type to be optional, but got \"{}\" instead", cond_type),
 						span: Some(value.span()),
+						severity: DiagnosticSeverity::Error,
+						code: DiagnosticCode::TypeMismatch,
+						annotations: vec![],
+						fixes: vec![],
 					});
 				}
 
@@ -2512,43 +3435,82 @@ impl<'a> TypeChecker<'a> {
 				elif_statements,
 				else_statements,
 			} => {
-				let (cond_type, _) = self.type_check_exp(condition, env);
-				self.validate_type(cond_type, self.types.bool(), condition);
+				// The types of the variables tested by previous conditions, excluding the tested types (e.g. in
+				// `if x is str {} else {}`, `x` is known not to be a `str` inside the `else` block)
+				let mut excluded: Vec<(String, TypeRef)> = vec![];
+
+				let branches = std::iter::once((condition, statements))
+					.chain(elif_statements.iter().map(|elif| (&elif.condition, &elif.statements)));
+				for (condition, scope) in branches {
+					let narrowing = self.type_check_condition(condition, env);
+
+					let mut scope_env = SymbolEnv::new(Some(env.get_ref()), env.return_type, false, false, env.phase, stmt.idx);
+					for (name, t) in &excluded {
+						scope_env.narrow(name, *t);
+					}
+					if let Some((name, var_type, tested_type)) = narrowing {
+						let var_type = excluded.iter().find(|(n, _)| *n == name).map_or(var_type, |(_, t)| *t);
+						scope_env.narrow(&name, self.types.narrow_to(var_type, tested_type));
 
-				statements.set_env(SymbolEnv::new(
-					Some(env.get_ref()),
-					env.return_type,
-					false,
-					false,
-					env.phase,
-					stmt.idx,
-				));
-				self.inner_scopes.push(statements);
+						let remaining_type = self.types.narrow_excluding(var_type, tested_type);
+						excluded.retain(|(n, _)| *n != name);
+						excluded.push((name, remaining_type));
+					}
+					scope.set_env(scope_env);
+					self.inner_scopes.push(scope);
+				}
 
-				for elif_scope in elif_statements {
-					let (cond_type, _) = self.type_check_exp(&elif_scope.condition, env);
-					self.validate_type(cond_type, self.types.bool(), condition);
+				if let Some(else_scope) = else_statements {
+					let mut else_env = SymbolEnv::new(Some(env.get_ref()), env.return_type, false, false, env.phase, stmt.idx);
+					for (name, t) in &excluded {
+						else_env.narrow(name, *t);
+					}
+					else_scope.set_env(else_env);
+					self.inner_scopes.push(else_scope);
+				}
+			}
+			StmtKind::Match {
+				value,
+				arms,
+				else_statements,
+			} => {
+				let (value_type, _) = self.type_check_exp(value, env);
+
+				// An optional variable which can't be reassigned is known not to be `nil` in arms which don't match `nil`
+				let narrowed_var = match &value.kind {
+					ExprKind::Reference(Reference::Identifier(symbol)) if value_type.is_option() => env
+						.lookup(symbol, Some(self.statement_idx))
+						.and_then(|kind| kind.as_variable())
+						.filter(|var| !var.reassignable)
+						.map(|_| symbol.name.clone()),
+					_ => None,
+				};
 
-					(&elif_scope.statements).set_env(SymbolEnv::new(
-						Some(env.get_ref()),
-						env.return_type,
-						false,
-						false,
-						env.phase,
-						stmt.idx,
-					));
-					self.inner_scopes.push(&elif_scope.statements);
+				let mut matched: Vec<String> = vec![];
+				for arm in arms {
+					let arm_matches_nil = self.type_check_match_patterns(&arm.patterns, value_type, &mut matched, env);
+
+					let mut arm_env = SymbolEnv::new(Some(env.get_ref()), env.return_type, false, false, env.phase, stmt.idx);
+					if let Some(name) = &narrowed_var {
+						if !arm_matches_nil {
+							arm_env.narrow(name, *value_type.maybe_unwrap_option());
+						}
+					}
+					arm.statements.set_env(arm_env);
+					self.inner_scopes.push(&arm.statements);
 				}
 
+				let patterns = arms.iter().flat_map(|arm| arm.patterns.iter());
+				let missing = control_flow::missing_match_patterns(self.types, value, patterns);
+				self.check_match_exhaustiveness(value, value_type, missing, else_statements.as_ref().map(|s| &s.span));
 				if let Some(else_scope) = else_statements {
-					else_scope.set_env(SymbolEnv::new(
-						Some(env.get_ref()),
-						env.return_type,
-						false,
-						false,
-						env.phase,
-						stmt.idx,
-					));
+					let mut else_env = SymbolEnv::new(Some(env.get_ref()), env.return_type, false, false, env.phase, stmt.idx);
+					if let Some(name) = &narrowed_var {
+						if matched.iter().any(|key| key == "nil") {
+							else_env.narrow(name, *value_type.maybe_unwrap_option());
+						}
+					}
+					else_scope.set_env(else_env);
 					self.inner_scopes.push(else_scope);
 				}
 			}
@@ -2559,6 +3521,34 @@ impl<'a> TypeChecker<'a> {
 				let (exp_type, _) = self.type_check_exp(value, env);
 				let (var_type, var_phase) = self.type_check_exp(variable, env);
 
+				if let ExprKind::Reference(Reference::ElementAccess { object, .. }) = &variable.kind {
+					let object_type = self.types.get_expr_type(object);
+					let Some((element_type, mutable)) = element_access_info(object_type) else {
+						// An error was already reported when type checking the reference
+						return;
+					};
+					if !mutable {
+						self.spanned_error_with_code(
+							variable,
+							DiagnosticCode::NotReassignable,
+							format!(
+								"Cannot assign to an element of \"{}\", only elements of mutable arrays, maps and Json values can be assigned",
+								object_type
+							),
+						);
+					} else if self.types.get_expr_phase(object) == Some(Phase::Preflight) && env.phase == Phase::Inflight {
+						// The element's phase is inflight when the index is, so check the phase of the indexed object itself
+						self.spanned_error_with_code(
+							stmt,
+							DiagnosticCode::PhaseViolation,
+							"Preflight values cannot be modified from inflight".to_string(),
+						);
+					}
+					// Validate against the element type since a map lookup is optional while `nil` can't be stored in a map
+					self.validate_type(exp_type, element_type, value);
+					return;
+				}
+
 				// TODO: we need to verify that if this variable is defined in a parent environment (i.e.
 				// being captured) it cannot be reassigned: https://github.com/winglang/wing/issues/3069
 
@@ -2566,9 +3556,17 @@ impl<'a> TypeChecker<'a> {
 					let (var, _) = self.resolve_reference(&r, env);
 
 					if !var_type.is_unresolved() && !var.reassignable {
-						self.spanned_error(variable, "Variable is not reassignable".to_string());
+						self.spanned_error_with_code(
+							variable,
+							DiagnosticCode::NotReassignable,
+							"Variable is not reassignable".to_string(),
+						);
 					} else if var_phase == Phase::Preflight && env.phase == Phase::Inflight {
-						self.spanned_error(stmt, "Variable cannot be reassigned from inflight".to_string());
+						self.spanned_error_with_code(
+							stmt,
+							DiagnosticCode::PhaseViolation,
+							"Variable cannot be reassigned from inflight".to_string(),
+						);
 					}
 				}
 
@@ -2587,7 +3585,11 @@ impl<'a> TypeChecker<'a> {
 				// alias is the symbol we are giving to the imported library or namespace
 				let alias: &Symbol;
 
-				if module_name.name.starts_with('"') && module_name.name.ends_with('"') {
+				if is_wing_module(module_name) {
+					// case 0: bring "./file.w" as identifier;
+					self.bring_wing_module(env, stmt, module_name, identifier);
+					return;
+				} else if module_name.name.starts_with('"') && module_name.name.ends_with('"') {
 					// case 1: bring "library_name" as identifier;
 					if identifier.is_none() {
 						self.spanned_error(
@@ -2615,7 +3617,11 @@ impl<'a> TypeChecker<'a> {
 						self.spanned_error(stmt, format!("Redundant bring of \"{}\"", WINGSDK_STD_MODULE));
 						return;
 					} else {
-						self.spanned_error(stmt, format!("\"{}\" is not a built-in module", module_name.name));
+						self.spanned_error_with_code(
+							stmt,
+							DiagnosticCode::UnknownSymbol,
+							format!("\"{}\" is not a built-in module", module_name.name),
+						);
 						return;
 					}
 				};
@@ -2639,14 +3645,19 @@ impl<'a> TypeChecker<'a> {
 					if !env.return_type.is_void() {
 						self.validate_type(return_type, env.return_type, return_expression);
 					} else if env.is_in_function() {
-						self.spanned_error(stmt, "Unexpected return value from void function");
+						self.spanned_error_with_code(
+							stmt,
+							DiagnosticCode::TypeMismatch,
+							"Unexpected return value from void function",
+						);
 					} else {
 						self.spanned_error(stmt, "Return statement outside of function cannot return a value");
 					}
 				} else {
 					if !env.return_type.is_void() {
-						self.spanned_error(
+						self.spanned_error_with_code(
 							stmt,
+							DiagnosticCode::TypeMismatch,
 							format!("Expected return statement to return type {}", env.return_type),
 						);
 					}
@@ -2654,6 +3665,7 @@ impl<'a> TypeChecker<'a> {
 			}
 			StmtKind::Class(AstClass {
 				name,
+				type_parameters,
 				fields,
 				methods,
 				parent,
@@ -2665,10 +3677,22 @@ impl<'a> TypeChecker<'a> {
 				// preflight classes cannot be declared inside an inflight scope
 				// (the other way is okay)
 				if env.phase == Phase::Inflight && *phase == Phase::Preflight {
-					self.spanned_error(stmt, format!("Cannot declare a {} class in {} scope", phase, env.phase));
+					self.spanned_error_with_code(
+						stmt,
+						DiagnosticCode::PhaseViolation,
+						format!("Cannot declare a {} class in {} scope", phase, env.phase),
+					);
 				}
+				// The type parameters of a generic class can be referenced from its declaration and members
+				let type_params = self.resolve_type_parameters(type_parameters, env, stmt.idx);
+				let mut decl_env = if type_params.is_empty() {
+					env.get_ref()
+				} else {
+					self.make_type_parameters_env(&type_params, env)
+				};
+
 				// Verify parent is a known class and get their env
-				let (parent_class, parent_class_env) = self.extract_parent_class(parent.as_ref(), *phase, name, env);
+				let (parent_class, parent_class_env) = self.extract_parent_class(parent.as_ref(), *phase, name, &mut decl_env);
 
 				// Create environment representing this class, for now it'll be empty just so we can support referencing ourselves from the class definition.
 				let dummy_env = SymbolEnv::new(None, self.types.void(), false, false, env.phase, stmt.idx);
@@ -2677,12 +3701,17 @@ impl<'a> TypeChecker<'a> {
 					.iter()
 					.filter_map(|i| {
 						let t = self
-							.resolve_user_defined_type(i, env, stmt.idx)
+							.resolve_user_defined_type(i, &decl_env, stmt.idx)
 							.unwrap_or_else(|e| self.type_error(e));
+						let t = self.validate_generic_type_has_arguments(t, i);
 						if t.as_interface().is_some() {
 							Some(t)
 						} else {
-							self.spanned_error(i, format!("Expected an interface, instead found type \"{}\"", t));
+							self.spanned_error_with_code(
+								i,
+								DiagnosticCode::TypeMismatch,
+								format!("Expected an interface, instead found type \"{}\"", t),
+							);
 							None
 						}
 					})
@@ -2697,7 +3726,12 @@ impl<'a> TypeChecker<'a> {
 					implements: impl_interfaces.clone(),
 					is_abstract: false,
 					phase: *phase,
-					type_parameters: None, // TODO no way to have generic args in wing yet
+					type_parameters: if type_params.is_empty() {
+						None
+					} else {
+						Some(type_params)
+					},
+					generic_instance: None,
 					docs: Docs::default(),
 					std_construct_args: *phase == Phase::Preflight,
 					lifts: None,
@@ -2715,7 +3749,7 @@ impl<'a> TypeChecker<'a> {
 
 				// Add fields to the class env
 				for field in fields.iter() {
-					let field_type = self.resolve_type_annotation(&field.member_type, env);
+					let field_type = self.resolve_type_annotation(&field.member_type, &decl_env);
 					match class_env.define(
 						&field.name,
 						SymbolKind::make_member_variable(
@@ -2724,6 +3758,7 @@ impl<'a> TypeChecker<'a> {
 							field.reassignable,
 							field.is_static,
 							field.phase,
+							field.access_modifier,
 							None,
 						),
 						StatementIdx::Top,
@@ -2739,10 +3774,11 @@ impl<'a> TypeChecker<'a> {
 				for (method_name, method_def) in methods.iter() {
 					self.add_method_to_class_env(
 						&method_def.signature,
-						env,
+						&mut decl_env,
 						if method_def.is_static { None } else { Some(class_type) },
 						&mut class_env,
 						method_name,
+						method_def.access_modifier,
 					);
 				}
 
@@ -2752,7 +3788,14 @@ impl<'a> TypeChecker<'a> {
 					span: initializer.span.clone(),
 				};
 
-				self.add_method_to_class_env(&initializer.signature, env, None, &mut class_env, &init_symb);
+				self.add_method_to_class_env(
+					&initializer.signature,
+					&mut decl_env,
+					None,
+					&mut class_env,
+					&init_symb,
+					AccessModifier::Public,
+				);
 
 				let inflight_init_symb = Symbol {
 					name: CLASS_INFLIGHT_INIT_NAME.into(),
@@ -2762,16 +3805,25 @@ impl<'a> TypeChecker<'a> {
 				// Add the inflight initializer to the class env
 				self.add_method_to_class_env(
 					&inflight_initializer.signature,
-					env,
+					&mut decl_env,
 					Some(class_type),
 					&mut class_env,
 					&inflight_init_symb,
+					AccessModifier::Public,
 				);
 
 				// Replace the dummy class environment with the real one before type checking the methods
 				class_type.as_class_mut().unwrap().env = class_env;
+				self.types.update_generic_instances(class_type);
 				let class_env = &class_type.as_class().unwrap().env;
 
+				let members = fields
+					.iter()
+					.map(|field| (&field.name, field.access_modifier))
+					.chain(methods.iter().map(|(name, method)| (name, method.access_modifier)))
+					.collect_vec();
+				self.validate_member_overrides(&members, parent_class);
+
 				if let FunctionBody::Statements(scope) = &inflight_initializer.body {
 					self.check_class_field_initialization(&scope, fields, Phase::Inflight);
 					self.type_check_super_constructor_against_parent_initializer(
@@ -2783,7 +3835,7 @@ impl<'a> TypeChecker<'a> {
 				};
 
 				// Type check constructor
-				self.type_check_method(class_env, &init_symb, env, stmt.idx, initializer, class_type);
+				self.type_check_method(class_env, &init_symb, &decl_env, stmt.idx, initializer, class_type);
 
 				// Verify if all fields of a class/resource are initialized in the initializer.
 				let init_statements = match &initializer.body {
@@ -2804,7 +3856,7 @@ impl<'a> TypeChecker<'a> {
 				self.type_check_method(
 					class_env,
 					&inflight_init_symb,
-					env,
+					&decl_env,
 					stmt.idx,
 					inflight_initializer,
 					class_type,
@@ -2815,7 +3867,49 @@ impl<'a> TypeChecker<'a> {
 
 				// Type check methods
 				for (method_name, method_def) in methods.iter() {
-					self.type_check_method(class_env, method_name, env, stmt.idx, method_def, class_type);
+					self.type_check_method(class_env, method_name, &decl_env, stmt.idx, method_def, class_type);
+				}
+
+				// Type check the initializers of static fields, they are evaluated in the scope the class is declared in
+				for field in fields.iter() {
+					let Some(initializer) = &field.initializer else {
+						continue;
+					};
+					let field_env = self.types.add_detached_env(SymbolEnv::new(
+						Some(decl_env),
+						self.types.void(),
+						false,
+						false,
+						field.phase,
+						stmt.idx,
+					));
+					let (initializer_type, _) = self.type_check_exp(initializer, &field_env);
+					if let Some(field_var) = class_env.lookup(&field.name, None).and_then(|s| s.as_variable()) {
+						self.validate_type(initializer_type, field_var.type_, initializer);
+					}
+
+					let mut visit_init = VisitStaticFieldInit::new(&self.types);
+					visit_init.analyze_expr(initializer);
+					for span in visit_init.inflight_calls {
+						self.spanned_error_with_code(
+							&span,
+							DiagnosticCode::PhaseViolation,
+							format!(
+								"Cannot call inflight functions in the initializer of static field \"{}\"",
+								field.name
+							),
+						);
+					}
+					for span in visit_init.awaits {
+						self.spanned_error_with_code(
+							&span,
+							DiagnosticCode::PhaseViolation,
+							format!(
+								"Cannot use \"await\" in the initializer of static field \"{}\"",
+								field.name
+							),
+						);
+					}
 				}
 
 				// Check that the class satisfies all of its interfaces
@@ -2834,8 +3928,9 @@ impl<'a> TypeChecker<'a> {
 							let class_method_type = symbol.as_variable().expect("Expected method to be a variable").type_;
 							self.validate_type(class_method_type, method_type, name);
 						} else {
-							self.spanned_error(
+							self.spanned_error_with_code(
 								name,
+								DiagnosticCode::MissingImplementation,
 								format!(
 									"Class \"{}\" does not implement method \"{}\" of interface \"{}\"",
 									name.name, method_name, interface_type.name.name
@@ -2847,11 +3942,30 @@ impl<'a> TypeChecker<'a> {
 					// Check all fields are implemented
 					for (field_name, field_type) in interface_type.fields(true) {
 						if let Some(symbol) = class_env.lookup(&field_name.as_str().into(), None) {
-							let class_field_type = symbol.as_variable().expect("Expected field to be a variable").type_;
-							self.validate_type(class_field_type, field_type, name);
+							let class_field = symbol.as_variable().expect("Expected field to be a variable");
+							let interface_field = interface_type
+								.get_field(&field_name.as_str().into())
+								.expect("Expected interface field to exist");
+							self.validate_interface_field_implementation(name, &class_field, &interface_field, &interface_type.name);
+							if interface_field.reassignable {
+								// Reassignable fields can be both read and written through the interface, so their types must match
+								if !class_field.type_.is_same_type_as(&field_type) {
+									self.spanned_error_with_code(
+										&class_field.name,
+										DiagnosticCode::MissingImplementation,
+										format!(
+											"Reassignable field \"{}\" must have type \"{}\" to implement interface \"{}\", but has type \"{}\"",
+											field_name, field_type, interface_type.name.name, class_field.type_
+										),
+									);
+								}
+							} else {
+								self.validate_type(class_field.type_, field_type, &class_field.name);
+							}
 						} else {
-							self.spanned_error(
+							self.spanned_error_with_code(
 								name,
+								DiagnosticCode::MissingImplementation,
 								format!(
 									"Class \"{}\" does not implement field \"{}\" of interface \"{}\"",
 									name.name, field_name, interface_type.name.name
@@ -2861,7 +3975,21 @@ impl<'a> TypeChecker<'a> {
 					}
 				}
 			}
-			StmtKind::Interface(AstInterface { name, methods, extends }) => {
+			StmtKind::Interface(AstInterface {
+				name,
+				type_parameters,
+				fields,
+				methods,
+				extends,
+			}) => {
+				// The type parameters of a generic interface can be referenced from its declaration and members
+				let type_params = self.resolve_type_parameters(type_parameters, env, stmt.idx);
+				let decl_env = if type_params.is_empty() {
+					env.get_ref()
+				} else {
+					self.make_type_parameters_env(&type_params, env)
+				};
+
 				// Create environment representing this interface, for now it'll be empty just so we can support referencing ourselves from the interface definition.
 				let dummy_env = SymbolEnv::new(None, self.types.void(), false, false, env.phase, stmt.idx);
 
@@ -2869,14 +3997,19 @@ impl<'a> TypeChecker<'a> {
 					.iter()
 					.filter_map(|i| {
 						let t = self
-							.resolve_user_defined_type(i, env, stmt.idx)
+							.resolve_user_defined_type(i, &decl_env, stmt.idx)
 							.unwrap_or_else(|e| self.type_error(e));
+						let t = self.validate_generic_type_has_arguments(t, i);
 						if t.as_interface().is_some() {
 							Some(t)
 						} else {
 							// The type checker resolves non-existing definitions to `any`, so we avoid duplicate errors by checking for that here
 							if !t.is_unresolved() {
-								self.spanned_error(i, format!("Expected an interface, instead found type \"{}\"", t));
+								self.spanned_error_with_code(
+									i,
+									DiagnosticCode::TypeMismatch,
+									format!("Expected an interface, instead found type \"{}\"", t),
+								);
 							}
 							None
 						}
@@ -2889,6 +4022,12 @@ impl<'a> TypeChecker<'a> {
 					docs: Docs::default(),
 					env: dummy_env,
 					extends: extend_interfaces.clone(),
+					type_parameters: if type_params.is_empty() {
+						None
+					} else {
+						Some(type_params)
+					},
+					generic_instance: None,
 				};
 				let mut interface_type = self.types.add_type(Type::Interface(interface_spec));
 				match env.define(name, SymbolKind::Type(interface_type), StatementIdx::Top) {
@@ -2901,9 +4040,32 @@ impl<'a> TypeChecker<'a> {
 				// Create the real interface environment to be filled with the interface AST types
 				let mut interface_env = SymbolEnv::new(None, self.types.void(), false, false, env.phase, stmt.idx);
 
+				// Add fields to the interface env
+				for field in fields.iter() {
+					let field_type = self.resolve_type_annotation(&field.member_type, &decl_env);
+					match interface_env.define(
+						&field.name,
+						SymbolKind::make_member_variable(
+							field.name.clone(),
+							field_type,
+							field.reassignable,
+							false,
+							field.phase,
+							AccessModifier::Public,
+							None,
+						),
+						StatementIdx::Top,
+					) {
+						Err(type_error) => {
+							self.type_error(type_error);
+						}
+						_ => {}
+					};
+				}
+
 				// Add methods to the interface env
 				for (method_name, sig) in methods.iter() {
-					let mut method_type = self.resolve_type_annotation(&sig.to_type_annotation(), env);
+					let mut method_type = self.resolve_type_annotation(&sig.to_type_annotation(), &decl_env);
 					// use the interface type as the function's "this" type
 					if let Type::Function(ref mut f) = *method_type {
 						f.this_type = Some(interface_type);
@@ -2913,7 +4075,15 @@ impl<'a> TypeChecker<'a> {
 
 					match interface_env.define(
 						method_name,
-						SymbolKind::make_member_variable(method_name.clone(), method_type, false, false, sig.phase, None),
+						SymbolKind::make_member_variable(
+							method_name.clone(),
+							method_type,
+							false,
+							false,
+							sig.phase,
+							AccessModifier::Public,
+							None,
+						),
 						StatementIdx::Top,
 					) {
 						Err(type_error) => {
@@ -2930,24 +4100,46 @@ impl<'a> TypeChecker<'a> {
 
 				// Replace the dummy interface environment with the real one before type checking the methods
 				interface_type.as_mut_interface().unwrap().env = interface_env;
+				self.types.update_generic_instances(interface_type);
 			}
-			StmtKind::Struct { name, extends, fields } => {
+			StmtKind::Struct {
+				name,
+				type_parameters,
+				extends,
+				fields,
+			} => {
 				// Note: structs don't have a parent environment, instead they flatten their parent's members into the struct's env.
 				//   If we encounter an existing member with the same name and type we skip it, if the types are different we
 				//   fail type checking.
 
+				// The type parameters of a generic struct can be referenced from its declaration and fields
+				let type_params = self.resolve_type_parameters(type_parameters, env, stmt.idx);
+				let decl_env = if type_params.is_empty() {
+					env.get_ref()
+				} else {
+					self.make_type_parameters_env(&type_params, env)
+				};
+
 				// Create an environment for the struct
 				let mut struct_env = SymbolEnv::new(None, self.types.void(), false, false, env.phase, stmt.idx);
 
 				// Add fields to the struct env
 				for field in fields.iter() {
-					let field_type = self.resolve_type_annotation(&field.member_type, env);
+					let field_type = self.resolve_type_annotation(&field.member_type, &decl_env);
 					if field_type.is_mutable() {
 						self.spanned_error(&field.name, "Struct fields must have immutable types");
 					}
 					match struct_env.define(
 						&field.name,
-						SymbolKind::make_member_variable(field.name.clone(), field_type, false, false, Phase::Independent, None),
+						SymbolKind::make_member_variable(
+							field.name.clone(),
+							field_type,
+							false,
+							false,
+							Phase::Independent,
+							AccessModifier::Public,
+							None,
+						),
 						StatementIdx::Top,
 					) {
 						Err(type_error) => {
@@ -2962,12 +4154,17 @@ impl<'a> TypeChecker<'a> {
 					.iter()
 					.filter_map(|ext| {
 						let t = self
-							.resolve_user_defined_type(ext, env, stmt.idx)
+							.resolve_user_defined_type(ext, &decl_env, stmt.idx)
 							.unwrap_or_else(|e| self.type_error(e));
+						let t = self.validate_generic_type_has_arguments(t, ext);
 						if t.as_struct().is_some() {
 							Some(t)
 						} else {
-							self.spanned_error(ext, format!("Expected a struct, found type \"{}\"", t));
+							self.spanned_error_with_code(
+								ext,
+								DiagnosticCode::TypeMismatch,
+								format!("Expected a struct, found type \"{}\"", t),
+							);
 							None
 						}
 					})
@@ -2983,6 +4180,12 @@ impl<'a> TypeChecker<'a> {
 						extends: extends_types,
 						env: struct_env,
 						docs: Docs::default(),
+						type_parameters: if type_params.is_empty() {
+							None
+						} else {
+							Some(type_params)
+						},
+						generic_instance: None,
 					}))),
 					StatementIdx::Top,
 				) {
@@ -3123,6 +4326,58 @@ impl<'a> TypeChecker<'a> {
 		};
 	}
 
+	/// Validates that a class field can implement a field of an interface the class implements: it must be a public,
+	/// non-static field with the same phase, and reassignable if the interface field is reassignable.
+	fn validate_interface_field_implementation(
+		&mut self,
+		class_name: &Symbol,
+		class_field: &VariableInfo,
+		interface_field: &VariableInfo,
+		interface_name: &Symbol,
+	) {
+		let field_name = &class_field.name;
+		if matches!(class_field.kind, VariableKind::StaticMember) {
+			self.spanned_error_with_code(
+				field_name,
+				DiagnosticCode::MissingImplementation,
+				format!(
+					"Field \"{}\" of class \"{}\" cannot be static to implement interface \"{}\"",
+					field_name.name, class_name.name, interface_name.name
+				),
+			);
+		}
+		if class_field.access_modifier != AccessModifier::Public {
+			self.spanned_error_with_code(
+				field_name,
+				DiagnosticCode::MissingImplementation,
+				format!(
+					"Field \"{}\" of class \"{}\" must be public to implement interface \"{}\"",
+					field_name.name, class_name.name, interface_name.name
+				),
+			);
+		}
+		if class_field.phase != interface_field.phase {
+			self.spanned_error_with_code(
+				field_name,
+				DiagnosticCode::MissingImplementation,
+				format!(
+					"Field \"{}\" of class \"{}\" is {} but interface \"{}\" declares it {}",
+					field_name.name, class_name.name, class_field.phase, interface_name.name, interface_field.phase
+				),
+			);
+		}
+		if interface_field.reassignable && !class_field.reassignable {
+			self.spanned_error_with_code(
+				field_name,
+				DiagnosticCode::MissingImplementation,
+				format!(
+					"Field \"{}\" of class \"{}\" must be reassignable to implement interface \"{}\"",
+					field_name.name, class_name.name, interface_name.name
+				),
+			);
+		}
+	}
+
 	/// Validate if the fields of a class are initialized in the constructor (init) according to the given phase.
 	/// For example, if the phase is preflight, then all non-static preflight fields must be initialized
 	/// and if the phase is inflight, then all non-static inflight fields must be initialized.
@@ -3132,7 +4387,6 @@ impl<'a> TypeChecker<'a> {
 	/// * `scope` - The constructor scope (init)
 	/// * `fields` - All fields of a class
 	/// * `phase` - initializer phase
-	///
 	fn check_class_field_initialization(&mut self, scope: &Scope, fields: &[ClassField], phase: Phase) {
 		let mut visit_init = VisitClassInit::default();
 		visit_init.analyze_statements(&scope.statements);
@@ -3179,8 +4433,6 @@ impl<'a> TypeChecker<'a> {
 		method_def: &FunctionDefinition,
 		class_type: UnsafeRef<Type>,
 	) {
-		// TODO: make sure this function returns on all control paths when there's a return type (can be done by recursively traversing the statements and making sure there's a "return" statements in all control paths)
-		// https://github.com/winglang/wing/issues/457
 		// Lookup the method in the class_env
 		let method_type = class_env
 			.lookup(&method_name, None)
@@ -3193,16 +4445,24 @@ impl<'a> TypeChecker<'a> {
 			.as_function_sig()
 			.expect("Expected method type to be a function signature");
 
+		// The type parameters of a generic method can be referenced from its body
+		let parent_env = if method_sig.type_parameters.is_empty() {
+			parent_env.get_ref()
+		} else {
+			self.make_type_parameters_env(&method_sig.type_parameters, parent_env)
+		};
+
 		// Create method environment and prime it with args
 		let is_init = method_name.name == CLASS_INIT_NAME || method_name.name == CLASS_INFLIGHT_INIT_NAME;
 		let mut method_env = SymbolEnv::new(
-			Some(parent_env.get_ref()),
+			Some(parent_env),
 			method_sig.return_type,
 			is_init,
 			true,
 			method_sig.phase,
 			statement_idx,
 		);
+		method_env.enclosing_class = Some(class_type);
 		// Prime the method environment with `this`
 		if !method_def.is_static || is_init {
 			method_env
@@ -3219,11 +4479,30 @@ impl<'a> TypeChecker<'a> {
 		self.add_arguments_to_env(&method_def.signature.parameters, method_sig, &mut method_env);
 
 		if let FunctionBody::Statements(scope) = &method_def.body {
+			// Initializers implicitly return the new instance, so there's no return value to check for
+			if !is_init {
+				self
+					.pending_return_checks
+					.insert(scope, (method_def.signature.return_type.span(), method_sig.return_type));
+			}
 			scope.set_env(method_env);
 			self.inner_scopes.push(scope);
 		}
 	}
 
+	/// Reports an error if a function with a non-void return type can reach the end of its body
+	/// without returning a value
+	fn check_all_paths_return(&self, body: &Scope, return_type_span: &WingSpan, return_type: TypeRef) {
+		if return_type.is_void() || control_flow::scope_always_exits(self.types, body) {
+			return;
+		}
+		self.spanned_error_with_code(
+			return_type_span,
+			DiagnosticCode::MissingReturn,
+			format!("Not all paths return a value of type \"{}\"", return_type),
+		);
+	}
+
 	fn add_method_to_class_env(
 		&mut self,
 		method_sig: &ast::FunctionSignature,
@@ -3231,6 +4510,7 @@ impl<'a> TypeChecker<'a> {
 		instance_type: Option<TypeRef>,
 		class_env: &mut SymbolEnv,
 		method_name: &Symbol,
+		access_modifier: AccessModifier,
 	) {
 		let mut method_type = self.resolve_type_annotation(&method_sig.to_type_annotation(), env);
 		// use the class type as the function's "this" type (or None if static)
@@ -3247,6 +4527,7 @@ impl<'a> TypeChecker<'a> {
 				false,
 				instance_type.is_none(),
 				method_sig.phase,
+				access_modifier,
 				None,
 			),
 			StatementIdx::Top,
@@ -3258,6 +4539,38 @@ impl<'a> TypeChecker<'a> {
 		};
 	}
 
+	fn bring_wing_module(&mut self, env: &mut SymbolEnv, stmt: &Stmt, module_name: &Symbol, identifier: &Option<Symbol>) {
+		let Some(alias) = identifier else {
+			self.spanned_error(
+				stmt,
+				format!(
+					"bring {} must be assigned to an identifier (e.g. bring \"./foo.w\" as foo)",
+					module_name.name
+				),
+			);
+			return;
+		};
+
+		if !env.is_root() {
+			self.spanned_error(stmt, "Wing modules can only be brought at the top level of a file");
+			return;
+		}
+
+		let module_path = resolve_module_path(self.source_path, module_name);
+		// If the module wasn't registered then loading it already failed (e.g. it doesn't exist or it's part
+		// of a cycle) and a diagnostic was reported by the module loader
+		let Some(ns) = self.types.get_wing_module(&module_path) else {
+			return;
+		};
+
+		match env.define(alias, SymbolKind::Namespace(ns), StatementIdx::Index(stmt.idx)) {
+			Ok(_) => {}
+			Err(type_error) => {
+				self.type_error(type_error);
+			}
+		}
+	}
+
 	fn add_module_to_env(
 		&mut self,
 		env: &mut SymbolEnv,
@@ -3283,8 +4596,9 @@ impl<'a> TypeChecker<'a> {
 				let assembly_name = match self.jsii_types.load_module(manifest_root.as_str()) {
 					Ok(name) => name,
 					Err(type_error) => {
-						self.spanned_error(
+						self.spanned_error_with_code(
 							&stmt.map(|s| s.span.clone()).unwrap_or_default(),
+							DiagnosticCode::ModuleNotFound,
 							format!(
 								"Cannot locate Wing standard library from \"{}\": {}",
 								manifest_root, type_error
@@ -3300,8 +4614,9 @@ impl<'a> TypeChecker<'a> {
 				let assembly_name = match self.jsii_types.load_dep(library_name.as_str(), source_dir) {
 					Ok(name) => name,
 					Err(type_error) => {
-						self.spanned_error(
+						self.spanned_error_with_code(
 							&stmt.map(|s| s.span.clone()).unwrap_or_default(),
+							DiagnosticCode::ModuleNotFound,
 							format!(
 								"Cannot find module \"{}\" in source directory: {}",
 								library_name, type_error
@@ -3334,20 +4649,15 @@ impl<'a> TypeChecker<'a> {
 			.lookup(&jsii.alias.name.as_str().into(), Some(jsii.import_statement_idx))
 			.is_some()
 		{
-			self.spanned_error(alias, format!("\"{}\" is already defined", alias.name));
+			self.spanned_error_with_code(
+				alias,
+				DiagnosticCode::DuplicateSymbol,
+				format!("\"{}\" is already defined", alias.name),
+			);
 		} else {
+			// Only the namespaces of the module are set up here, the types within them are imported when they're
+			// referenced (see `jsii_importer::lookup_nested_importing`)
 			let mut importer = JsiiImporter::new(&jsii, self.types, self.jsii_types);
-
-			// If we're importing from the the wing sdk, eagerly import all the types within it
-			// The wing sdk is special because it's currently the only jsii module we import with a specific target namespace
-			if jsii.assembly_name == WINGSDK_ASSEMBLY_NAME {
-				importer.deep_import_submodule_to_env(if jsii.namespace_filter.is_empty() {
-					None
-				} else {
-					Some(jsii.namespace_filter.join("."))
-				});
-			}
-
 			importer.import_root_types();
 			importer.import_submodules_to_env(env);
 		}
@@ -3395,7 +4705,12 @@ impl<'a> TypeChecker<'a> {
 		original_fqn: &str,
 		type_params: Vec<TypeRef>,
 	) -> TypeRef {
-		let original_type = env.lookup_nested_str(original_fqn, None).unwrap().0.as_type().unwrap();
+		let original_type = self
+			.lookup_nested_importing(env, original_fqn, None)
+			.unwrap()
+			.0
+			.as_type()
+			.unwrap();
 		let original_type_class = original_type.as_class().unwrap();
 		let original_type_params = if let Some(tp) = original_type_class.type_parameters.as_ref() {
 			tp
@@ -3438,6 +4753,7 @@ impl<'a> TypeChecker<'a> {
 			implements: original_type_class.implements.clone(),
 			is_abstract: original_type_class.is_abstract,
 			type_parameters: Some(type_params),
+			generic_instance: None,
 			phase: original_type_class.phase,
 			docs: original_type_class.docs.clone(),
 			std_construct_args: original_type_class.std_construct_args,
@@ -3458,6 +4774,7 @@ impl<'a> TypeChecker<'a> {
 					reassignable,
 					phase: flight,
 					kind,
+					access_modifier,
 					docs: _,
 				}) => {
 					// Replace type params in function signatures
@@ -3477,10 +4794,12 @@ impl<'a> TypeChecker<'a> {
 								name: param.name.clone(),
 								docs: param.docs.clone(),
 								typeref: self.get_concrete_type_for_generic(param.typeref, &types_map),
+								variadic: param.variadic,
 							})
 							.collect();
 
 						let new_sig = FunctionSignature {
+							type_parameters: vec![],
 							this_type: new_this_type,
 							parameters: new_params,
 							return_type: new_return_type,
@@ -3499,6 +4818,7 @@ impl<'a> TypeChecker<'a> {
 								*reassignable,
 								matches!(kind, VariableKind::StaticMember),
 								*flight,
+								*access_modifier,
 								None,
 							),
 							StatementIdx::Top,
@@ -3520,6 +4840,7 @@ impl<'a> TypeChecker<'a> {
 								*reassignable,
 								matches!(kind, VariableKind::StaticMember),
 								*flight,
+								*access_modifier,
 								None,
 							),
 							StatementIdx::Top,
@@ -3592,6 +4913,10 @@ impl<'a> TypeChecker<'a> {
 				name: "Duration".to_string(),
 				span: symbol.span.clone(),
 			}),
+			"datetime" => Some(Symbol {
+				name: "Datetime".to_string(),
+				span: symbol.span.clone(),
+			}),
 			"str" => Some(Symbol {
 				name: "String".to_string(),
 				span: symbol.span.clone(),
@@ -3641,6 +4966,7 @@ impl<'a> TypeChecker<'a> {
 				Reference::TypeReference(type_) => {
 					return Some(type_.clone());
 				}
+				Reference::ElementAccess { .. } => return None,
 				Reference::TypeMember { typeobject, property } => {
 					path.push(property.clone());
 					current_reference = match &typeobject.kind {
@@ -3662,6 +4988,7 @@ impl<'a> TypeChecker<'a> {
 		Some(UserDefinedType {
 			root,
 			fields: path,
+			type_arguments: vec![],
 			span: WingSpan { start, end, file_id },
 		})
 	}
@@ -3683,7 +5010,7 @@ impl<'a> TypeChecker<'a> {
 		// then resolve a class named "Util" within it. This will basically be equivalent to the
 		// `foo.Bar.baz()` case (where `baz()`) is a static method of class `Bar`.
 		if base_udt.fields.is_empty() {
-			let result = env.lookup_nested_str(&base_udt.full_path_str(), Some(self.statement_idx));
+			let result = self.lookup_nested_importing(env, &base_udt.full_path_str(), Some(self.statement_idx));
 			if let LookupResult::Found(symbol_kind, _) = result {
 				if let SymbolKind::Namespace(_) = symbol_kind {
 					let mut new_udt = base_udt.clone();
@@ -3711,12 +5038,17 @@ impl<'a> TypeChecker<'a> {
 			Reference::Identifier(symbol) => {
 				let lookup_res = env.lookup_ext(symbol, Some(self.statement_idx));
 				if let LookupResult::Found(var, _) = lookup_res {
-					if let Some(var) = var.as_variable() {
+					if let Some(mut var) = var.as_variable() {
+						// The variable might be known to have a more specific type in this scope (e.g. inside `if x is str {}`)
+						if let Some(narrowed_type) = env.lookup_narrowed_type(&symbol.name) {
+							var.type_ = narrowed_type;
+						}
 						let phase = var.phase;
 						(var, phase)
 					} else {
 						self.spanned_error_with_var(
 							symbol,
+							DiagnosticCode::TypeError,
 							format!("Expected identifier \"{symbol}\" to be a variable, but it's a {var}",),
 						)
 					}
@@ -3810,10 +5142,60 @@ impl<'a> TypeChecker<'a> {
 
 				(property_variable, property_phase)
 			}
+			Reference::ElementAccess { object, index } => {
+				let (object_type, object_phase) = self.type_check_exp(object, env);
+				let (index_type, index_phase) = self.type_check_exp(index, env);
+
+				let index_expected_type = match *object_type {
+					Type::Array(_) | Type::MutArray(_) => Some(self.types.number()),
+					Type::Map(_) | Type::MutMap(_) => Some(self.types.string()),
+					Type::Json | Type::MutJson => {
+						let (number, string) = (self.types.number(), self.types.string());
+						Some(self.types.make_union(vec![number, string]))
+					}
+					_ => None,
+				};
+				if let Some(index_expected_type) = index_expected_type {
+					self.validate_type(index_type, index_expected_type, index);
+				}
+
+				let Some((element_type, reassignable)) = element_access_info(object_type) else {
+					return self.spanned_error_with_var(
+						object,
+						DiagnosticCode::TypeError,
+						format!(
+							"Type \"{}\" cannot be indexed, only arrays, maps and Json values can",
+							object_type
+						),
+					);
+				};
+
+				// The element can only be resolved preflight if the index is known preflight as well
+				let phase = if index_phase == Phase::Inflight {
+					Phase::Inflight
+				} else {
+					object_phase
+				};
+				(
+					VariableInfo {
+						name: Symbol {
+							name: reference.to_string(),
+							span: index.span.clone(),
+						},
+						type_: element_type,
+						reassignable,
+						phase,
+						kind: VariableKind::InstanceMember,
+						access_modifier: AccessModifier::Public,
+						docs: None,
+					},
+					phase,
+				)
+			}
 			Reference::TypeReference(udt) => {
 				let result = self.resolve_user_defined_type(udt, env, self.statement_idx);
 				let t = match result {
-					Err(e) => return self.spanned_error_with_var(udt, e.message),
+					Err(e) => return self.spanned_error_with_var(udt, e.code, e.message),
 					Ok(t) => t,
 				};
 
@@ -3830,6 +5212,7 @@ impl<'a> TypeChecker<'a> {
 						reassignable: false,
 						phase,
 						kind: VariableKind::Type,
+						access_modifier: AccessModifier::Public,
 						docs: None,
 					},
 					phase,
@@ -3839,11 +5222,11 @@ impl<'a> TypeChecker<'a> {
 				let (type_, _) = self.type_check_exp(typeobject, env);
 
 				let ExprKind::Reference(typeref) = &typeobject.kind else {
-					return self.spanned_error_with_var(typeobject, "Expecting a reference");
+					return self.spanned_error_with_var(typeobject, DiagnosticCode::TypeError, "Expecting a reference");
 				};
 
 				let Reference::TypeReference(_) = typeref else {
-					return self.spanned_error_with_var(typeobject, "Expecting a reference to a type");
+					return self.spanned_error_with_var(typeobject, DiagnosticCode::TypeError, "Expecting a reference to a type");
 				};
 
 				match *type_ {
@@ -3856,6 +5239,7 @@ impl<'a> TypeChecker<'a> {
 									type_,
 									reassignable: false,
 									phase: Phase::Independent,
+									access_modifier: AccessModifier::Public,
 									docs: None,
 								},
 								Phase::Independent,
@@ -3863,6 +5247,7 @@ impl<'a> TypeChecker<'a> {
 						} else {
 							self.spanned_error_with_var(
 								property,
+								DiagnosticCode::UnknownSymbol,
 								format!("Enum \"{}\" does not contain value \"{}\"", type_, property.name),
 							)
 						}
@@ -3870,10 +5255,13 @@ impl<'a> TypeChecker<'a> {
 					Type::Class(ref c) => match c.env.lookup(&property, None) {
 						Some(SymbolKind::Variable(v)) => {
 							if let VariableKind::StaticMember = v.kind {
+								self.validate_member_access(c, property, env);
+								self.validate_member_not_deprecated(property, v);
 								(v.clone(), v.phase)
 							} else {
 								self.spanned_error_with_var(
 									property,
+									DiagnosticCode::TypeError,
 									format!(
 										"Class \"{}\" contains a member \"{}\" but it is not static",
 										type_, property.name
@@ -3883,10 +5271,15 @@ impl<'a> TypeChecker<'a> {
 						}
 						_ => self.spanned_error_with_var(
 							property,
+							DiagnosticCode::UnknownSymbol,
 							format!("No member \"{}\" in class \"{}\"", property.name, type_),
 						),
 					},
-					_ => self.spanned_error_with_var(property, format!("\"{}\" not a valid reference", reference)),
+					_ => self.spanned_error_with_var(
+						property,
+						DiagnosticCode::TypeError,
+						format!("\"{}\" not a valid reference", reference),
+					),
 				}
 			}
 		}
@@ -3902,7 +5295,10 @@ impl<'a> TypeChecker<'a> {
 	) -> VariableInfo {
 		match *instance_type {
 			Type::Optional(t) => self.resolve_variable_from_instance_type(t, property, env, _object),
-			Type::Class(ref class) => self.get_property_from_class_like(class, property),
+			Type::Class(ref class) => {
+				self.validate_member_access(class, property, env);
+				self.get_property_from_class_like(class, property)
+			}
 			Type::Interface(ref interface) => self.get_property_from_class_like(interface, property),
 			Type::Anything => VariableInfo {
 				name: property.clone(),
@@ -3910,6 +5306,7 @@ impl<'a> TypeChecker<'a> {
 				reassignable: false,
 				phase: env.phase,
 				kind: VariableKind::InstanceMember,
+				access_modifier: AccessModifier::Public,
 				docs: None,
 			},
 
@@ -3938,54 +5335,75 @@ impl<'a> TypeChecker<'a> {
 				let new_class = self.hydrate_class_type_arguments(env, WINGSDK_MUT_MAP, vec![t]);
 				self.get_property_from_class_like(new_class.as_class().unwrap(), property)
 			}
-			Type::Json => self.get_property_from_class_like(
-				env
-					.lookup_nested_str(WINGSDK_JSON, None)
+			Type::Json => {
+				let json = self
+					.lookup_nested_importing(env, WINGSDK_JSON, None)
 					.unwrap()
 					.0
 					.as_type()
-					.unwrap()
-					.as_class()
-					.unwrap(),
-				property,
-			),
-			Type::MutJson => self.get_property_from_class_like(
-				env
-					.lookup_nested_str(WINGSDK_MUT_JSON, None)
+					.unwrap();
+				self.get_property_from_class_like(json.as_class().unwrap(), property)
+			}
+			Type::MutJson => {
+				let mut_json = self
+					.lookup_nested_importing(env, WINGSDK_MUT_JSON, None)
 					.unwrap()
 					.0
 					.as_type()
-					.unwrap()
-					.as_class()
-					.unwrap(),
-				property,
-			),
-			Type::String => self.get_property_from_class_like(
-				env
-					.lookup_nested_str(WINGSDK_STRING, None)
+					.unwrap();
+				self.get_property_from_class_like(mut_json.as_class().unwrap(), property)
+			}
+			Type::String => {
+				let string = self
+					.lookup_nested_importing(env, WINGSDK_STRING, None)
 					.unwrap()
 					.0
 					.as_type()
-					.unwrap()
-					.as_class()
-					.unwrap(),
-				property,
-			),
-			Type::Duration => self.get_property_from_class_like(
-				env
-					.lookup_nested_str(WINGSDK_DURATION, None)
+					.unwrap();
+				self.get_property_from_class_like(string.as_class().unwrap(), property)
+			}
+			Type::Duration => {
+				let duration = self
+					.lookup_nested_importing(env, WINGSDK_DURATION, None)
 					.unwrap()
 					.0
 					.as_type()
+					.unwrap();
+				self.get_property_from_class_like(duration.as_class().unwrap(), property)
+			}
+			Type::Datetime => {
+				let datetime = self
+					.lookup_nested_importing(env, WINGSDK_DATETIME, None)
 					.unwrap()
-					.as_class()
-					.unwrap(),
-				property,
-			),
+					.0
+					.as_type()
+					.unwrap();
+				self.get_property_from_class_like(datetime.as_class().unwrap(), property)
+			}
 			Type::Struct(ref s) => self.get_property_from_class_like(s, property),
+			// The members of a type parameter are the members of its bound
+			Type::TypeParameter(TypeParameter { bound: Some(bound), .. }) => {
+				self.resolve_variable_from_instance_type(bound, property, env, _object)
+			}
+			Type::Union(_) => {
+				self
+					.spanned_error_with_var(
+						property,
+						DiagnosticCode::TypeError,
+						format!(
+							"Property \"{}\" cannot be accessed on a value of union type \"{}\" (use \"is\" to narrow its type first)",
+							property.name, instance_type
+						),
+					)
+					.0
+			}
 			_ => {
 				self
-					.spanned_error_with_var(property, "Property not found".to_string())
+					.spanned_error_with_var(
+						property,
+						DiagnosticCode::UnknownSymbol,
+						"Property not found".to_string(),
+					)
 					.0
 			}
 		}
@@ -4000,10 +5418,12 @@ impl<'a> TypeChecker<'a> {
 				self
 					.spanned_error_with_var(
 						property,
+						DiagnosticCode::TypeError,
 						format!("Cannot access static property \"{property}\" from instance"),
 					)
 					.0
 			} else {
+				self.validate_member_not_deprecated(property, &var);
 				var
 			}
 		} else {
@@ -4012,6 +5432,113 @@ impl<'a> TypeChecker<'a> {
 		}
 	}
 
+	/// Reports a warning if a member which is marked as deprecated in its docs is used
+	fn validate_member_not_deprecated(&self, property: &Symbol, member: &VariableInfo) {
+		let Some(deprecated) = member.docs.as_ref().and_then(|docs| docs.deprecated.as_ref()) else {
+			return;
+		};
+
+		report_diagnostic(Diagnostic {
+			message: if deprecated.is_empty() {
+				format!("\"{}\" is deprecated", property.name)
+			} else {
+				format!("\"{}\" is deprecated: {}", property.name, deprecated)
+			},
+			span: Some(property.span()),
+			severity: DiagnosticSeverity::Warning,
+			code: DiagnosticCode::DeprecatedMember,
+			annotations: vec![],
+			fixes: vec![],
+		});
+	}
+
+	/// Reports an error if a private or protected member of a class is accessed from outside of
+	/// the class (or its subclasses in the case of protected members)
+	fn validate_member_access(&mut self, class: &Class, property: &Symbol, env: &SymbolEnv) {
+		let LookupResult::Found(SymbolKind::Variable(member), lookup_info) = class.env.lookup_ext(property, None) else {
+			return;
+		};
+
+		if !env.can_access_member(member.access_modifier, &lookup_info.env) {
+			report_diagnostic(Diagnostic {
+				message: format!(
+					"Cannot access {} member \"{}\" of class \"{}\"",
+					member.access_modifier, property.name, class.name
+				),
+				span: Some(property.span()),
+				severity: DiagnosticSeverity::Error,
+				code: DiagnosticCode::InaccessibleMember,
+				annotations: vec![DiagnosticAnnotation {
+					message: format!("\"{}\" is declared {} here", property.name, member.access_modifier),
+					span: member.name.span.clone(),
+				}],
+				fixes: vec![],
+			});
+		}
+	}
+
+	/// Reports an error if a class member overrides a private member of the parent class, or overrides a member with
+	/// a more restrictive access modifier (e.g. a public method with a protected one)
+	fn validate_member_overrides(&mut self, members: &[(&Symbol, AccessModifier)], parent_class: Option<TypeRef>) {
+		let Some(parent) = parent_class.as_ref().and_then(|p| p.as_class()) else {
+			return;
+		};
+		let visibility = |access_modifier: AccessModifier| match access_modifier {
+			AccessModifier::Private => 0,
+			AccessModifier::Protected => 1,
+			AccessModifier::Public => 2,
+		};
+
+		for (name, access_modifier) in members {
+			let Some(parent_member) = parent.env.lookup(name, None).and_then(|kind| kind.as_variable()) else {
+				continue;
+			};
+			let message = if parent_member.access_modifier == AccessModifier::Private {
+				format!(
+					"Cannot override private member \"{}\" of class \"{}\"",
+					name.name, parent.name
+				)
+			} else if visibility(*access_modifier) < visibility(parent_member.access_modifier) {
+				format!(
+					"Cannot override {} member \"{}\" of class \"{}\" with a {} member",
+					parent_member.access_modifier, name.name, parent.name, access_modifier
+				)
+			} else {
+				continue;
+			};
+
+			report_diagnostic(Diagnostic {
+				message,
+				span: Some(name.span.clone()),
+				severity: DiagnosticSeverity::Error,
+				code: DiagnosticCode::InvalidInheritance,
+				annotations: vec![DiagnosticAnnotation {
+					message: format!("\"{}\" is declared {} here", name.name, parent_member.access_modifier),
+					span: parent_member.name.span.clone(),
+				}],
+				fixes: vec![],
+			});
+		}
+	}
+
+	/// Looks up `nested_str` (e.g. `std.Json`) in `env`, first importing the JSII types it references which weren't
+	/// imported yet (see `jsii_importer::lookup_nested_importing`)
+	fn lookup_nested_importing<'b>(
+		&mut self,
+		env: &'b SymbolEnv,
+		nested_str: &str,
+		statement_idx: Option<usize>,
+	) -> LookupResult<'b> {
+		let nested_vec = nested_str.split('.').map(Symbol::global).collect_vec();
+		lookup_nested_importing(
+			env,
+			&nested_vec.iter().collect_vec(),
+			statement_idx,
+			self.types,
+			self.jsii_types,
+		)
+	}
+
 	/// Resolves a user defined type (e.g. `Foo.Bar.Baz`) to a type reference
 	/// If needed, this method can also resolve types from jsii libraries that have yet to be imported
 	fn resolve_user_defined_type(
@@ -4021,18 +5548,46 @@ impl<'a> TypeChecker<'a> {
 		statement_idx: usize,
 	) -> Result<TypeRef, TypeError> {
 		// Attempt to resolve the type from the current environment
-		let res = resolve_user_defined_type(user_defined_type, env, statement_idx);
-		if res.is_ok() {
-			return res;
-		}
+		let mut nested_vec = vec![&user_defined_type.root];
+		nested_vec.extend(user_defined_type.fields.iter());
+		import_nested_on_demand(env, &nested_vec, self.types, self.jsii_types);
+		let mut res = resolve_user_defined_type(user_defined_type, env, statement_idx);
 
 		// If the type is not found, attempt to import it from a jsii library
-		if import_udt_from_jsii(self.types, self.jsii_types, user_defined_type, &self.jsii_imports) {
-			return resolve_user_defined_type(user_defined_type, env, statement_idx);
+		if res.is_err() && import_udt_from_jsii(self.types, self.jsii_types, user_defined_type, &self.jsii_imports) {
+			res = resolve_user_defined_type(user_defined_type, env, statement_idx);
 		}
 
-		// If the type is still not found, return the original error
-		res
+		// Instantiate generic types with their type arguments (e.g. `Cache<str>`)
+		if user_defined_type.type_arguments.is_empty() {
+			return res;
+		}
+		let t = res?;
+		let type_arguments = user_defined_type
+			.type_arguments
+			.iter()
+			.map(|a| self.resolve_type_annotation(a, env))
+			.collect_vec();
+		Ok(self.instantiate_generic_type(t, type_arguments, user_defined_type))
+	}
+
+	/// Reports an error if a generic type is referenced without type arguments where they can't be inferred
+	fn validate_generic_type_has_arguments(&mut self, t: TypeRef, user_defined_type: &UserDefinedType) -> TypeRef {
+		match t.generic_type_parameters() {
+			Some(type_parameters) if user_defined_type.type_arguments.is_empty() => {
+				self.spanned_error_with_code(
+					user_defined_type,
+					DiagnosticCode::InvalidTypeArguments,
+					format!(
+						"Generic type \"{}\" requires type arguments ({})",
+						user_defined_type,
+						type_parameters.iter().join(", ")
+					),
+				);
+				self.types.error()
+			}
+			_ => t,
+		}
 	}
 
 	fn extract_parent_class(
@@ -4042,10 +5597,10 @@ impl<'a> TypeChecker<'a> {
 		name: &Symbol,
 		env: &mut SymbolEnv,
 	) -> (Option<TypeRef>, Option<SymbolEnvRef>) {
-		let Some(parent_expr) = parent_expr else  {
+		let Some(parent_expr) = parent_expr else {
 			if phase == Phase::Preflight {
 				// if this is a preflight and we don't have a parent, then we implicitly set it to `std.Resource`
-				let t = self.types.resource_base_type();
+				let t = self.types.resource_base_type(self.jsii_types);
 				let env = t.as_preflight_class().unwrap().env.get_ref();
 				return (Some(t), Some(env));
 			} else {
@@ -4064,7 +5619,17 @@ impl<'a> TypeChecker<'a> {
 		let parent_udt = parent_expr.as_type_reference().unwrap();
 
 		if &parent_udt.root == name && parent_udt.fields.is_empty() {
-			self.spanned_error(parent_udt, "Class cannot extend itself".to_string());
+			self.spanned_error_with_code(
+				parent_udt,
+				DiagnosticCode::InvalidInheritance,
+				"Class cannot extend itself".to_string(),
+			);
+			self.types.assign_type_to_expr(parent_expr, self.types.error(), phase);
+			return (None, None);
+		}
+
+		let parent_type = self.validate_generic_type_has_arguments(parent_type, parent_udt);
+		if parent_type.is_unresolved() {
 			self.types.assign_type_to_expr(parent_expr, self.types.error(), phase);
 			return (None, None);
 		}
@@ -4079,6 +5644,10 @@ impl<'a> TypeChecker<'a> {
 						name, phase, parent_class.phase, parent_class.name
 					),
 					span: Some(parent_expr.span.clone()),
+					severity: DiagnosticSeverity::Error,
+					code: DiagnosticCode::InvalidInheritance,
+					annotations: vec![],
+					fixes: vec![],
 				});
 				self.types.assign_type_to_expr(parent_expr, self.types.error(), phase);
 				(None, None)
@@ -4087,6 +5656,10 @@ impl<'a> TypeChecker<'a> {
 			report_diagnostic(Diagnostic {
 				message: format!("Expected \"{}\" to be a class", parent_udt),
 				span: Some(parent_expr.span.clone()),
+				severity: DiagnosticSeverity::Error,
+				code: DiagnosticCode::InvalidInheritance,
+				annotations: vec![],
+				fixes: vec![],
 			});
 			self.types.assign_type_to_expr(parent_expr, self.types.error(), phase);
 			(None, None)
@@ -4110,14 +5683,16 @@ fn add_parent_members_to_struct_env(
 					name.name, parent_type
 				),
 				span: name.span.clone(),
+				code: DiagnosticCode::InvalidInheritance,
+				annotations: vec![],
 			});
 		};
 		// Add each member of current parent to the struct's environment (if it wasn't already added by a previous parent)
 		for (parent_member_name, parent_member, _) in parent_struct.env.iter(true) {
-			let member_type = parent_member
+			let parent_member = parent_member
 				.as_variable()
-				.expect("Expected struct member to be a variable")
-				.type_;
+				.expect("Expected struct member to be a variable");
+			let member_type = parent_member.type_;
 			if let Some(existing_type) = struct_env.lookup(&parent_member_name.as_str().into(), None) {
 				let existing_type = existing_type
 					.as_variable()
@@ -4130,16 +5705,27 @@ fn add_parent_members_to_struct_env(
 							"Struct \"{}\" extends \"{}\" which introduces a conflicting member \"{}\" ({} != {})",
 							name, parent_type, parent_member_name, member_type, member_type
 						),
+						code: DiagnosticCode::InvalidInheritance,
+						annotations: vec![],
 					});
 				}
 			} else {
+				// Keep the span of the parent's declaration so the inherited member can be traced back to it
 				let sym = Symbol {
 					name: parent_member_name,
-					span: name.span.clone(),
+					span: parent_member.name.span.clone(),
 				};
 				struct_env.define(
 					&sym,
-					SymbolKind::make_member_variable(sym.clone(), member_type, false, false, struct_env.phase, None),
+					SymbolKind::make_member_variable(
+						sym.clone(),
+						member_type,
+						false,
+						false,
+						struct_env.phase,
+						AccessModifier::Public,
+						None,
+					),
 					StatementIdx::Top,
 				)?;
 			}
@@ -4165,14 +5751,16 @@ fn add_parent_members_to_iface_env(
 					name.name, parent_type
 				),
 				span: name.span.clone(),
+				code: DiagnosticCode::InvalidInheritance,
+				annotations: vec![],
 			});
 		};
 		// Add each member of current parent to the interface's environment (if it wasn't already added by a previous parent)
 		for (parent_member_name, parent_member, _) in parent_iface.env.iter(true) {
-			let member_type = parent_member
+			let parent_member = parent_member
 				.as_variable()
-				.expect("Expected interface member to be a variable")
-				.type_;
+				.expect("Expected interface member to be a variable");
+			let member_type = parent_member.type_;
 			if let Some(existing_type) = iface_env.lookup(&parent_member_name.as_str().into(), None) {
 				let existing_type = existing_type
 					.as_variable()
@@ -4185,16 +5773,22 @@ fn add_parent_members_to_iface_env(
 							"Interface \"{}\" extends \"{}\" but has a conflicting member \"{}\" ({} != {})",
 							name, parent_type, parent_member_name, member_type, member_type
 						),
+						code: DiagnosticCode::InvalidInheritance,
+						annotations: vec![],
 					});
 				}
 			} else {
 				let sym = Symbol {
 					name: parent_member_name,
-					span: name.span.clone(),
+					span: parent_member.name.span.clone(),
 				};
+				// Inherited members keep the phase and reassignability they were declared with
 				iface_env.define(
 					&sym,
-					SymbolKind::make_member_variable(sym.clone(), member_type, false, true, iface_env.phase, None),
+					SymbolKind::Variable(VariableInfo {
+						name: sym.clone(),
+						..parent_member.clone()
+					}),
 					StatementIdx::Top,
 				)?;
 			}
@@ -4219,7 +5813,12 @@ where
 		),
 		LookupResult::Found(..) => panic!("Expected a lookup error, but found a successful lookup"),
 	};
-	TypeError { message, span }
+	TypeError {
+		message,
+		span,
+		code: DiagnosticCode::UnknownSymbol,
+		annotations: vec![],
+	}
 }
 
 /// Resolves a user defined type (e.g. `Foo.Bar.Baz`) to a type reference
@@ -4242,6 +5841,8 @@ pub fn resolve_user_defined_type(
 			Err(TypeError {
 				message: format!("Expected \"{}\" to be a type but it's a {symb_kind}", symb.name),
 				span: symb.span.clone(),
+				code: DiagnosticCode::TypeError,
+				annotations: vec![],
 			})
 		}
 	} else {
@@ -4292,6 +5893,7 @@ pub fn fully_qualify_std_type(type_: &str) -> std::string::String {
 	let type_name = match type_name {
 		"str" => "String",
 		"duration" => "Duration",
+		"datetime" => "Datetime",
 		"bool" => "Boolean",
 		"num" => "Number",
 		_ => type_name,
@@ -4299,7 +5901,7 @@ pub fn fully_qualify_std_type(type_: &str) -> std::string::String {
 
 	match type_name {
 		"Json" | "MutJson" | "MutArray" | "MutMap" | "MutSet" | "Array" | "Map" | "Set" | "String" | "Duration"
-		| "Boolean" | "Number" => format!("{WINGSDK_STD_MODULE}.{type_name}"),
+		| "Datetime" | "Boolean" | "Number" => format!("{WINGSDK_STD_MODULE}.{type_name}"),
 		_ => type_name.to_string(),
 	}
 }
@@ -4330,6 +5932,7 @@ mod tests {
 
 	fn make_function(params: Vec<FunctionParameter>, ret: TypeRef, phase: Phase) -> Type {
 		Type::Function(FunctionSignature {
+			type_parameters: vec![],
 			this_type: None,
 			parameters: params,
 			return_type: ret,
@@ -4354,6 +5957,30 @@ mod tests {
 		assert!(opt_string.is_subtype_of(&opt_string));
 	}
 
+	#[test]
+	fn union_subtyping() {
+		let string = UnsafeRef::<Type>(&Type::String as *const Type);
+		let num = UnsafeRef::<Type>(&Type::Number as *const Type);
+		let boolean = UnsafeRef::<Type>(&Type::Boolean as *const Type);
+		let str_or_num_type = Type::Union(vec![string, num]);
+		let str_or_num = UnsafeRef::<Type>(&str_or_num_type as *const Type);
+		let str_or_num_or_bool_type = Type::Union(vec![string, num, boolean]);
+		let str_or_num_or_bool = UnsafeRef::<Type>(&str_or_num_or_bool_type as *const Type);
+
+		// each member is a subtype of the union (but not vice versa)
+		assert!(string.is_subtype_of(&str_or_num));
+		assert!(num.is_subtype_of(&str_or_num));
+		assert!(!boolean.is_subtype_of(&str_or_num));
+		assert!(!str_or_num.is_subtype_of(&string));
+
+		// a union is a subtype of another union if all of its members are
+		assert!(str_or_num.is_subtype_of(&str_or_num_or_bool));
+		assert!(!str_or_num_or_bool.is_subtype_of(&str_or_num));
+
+		// subtyping is reflexive
+		assert!(str_or_num.is_subtype_of(&str_or_num));
+	}
+
 	#[test]
 	fn function_subtyping_across_phases() {
 		let void = UnsafeRef::<Type>(&Type::Void as *const Type);
@@ -4379,6 +6006,7 @@ mod tests {
 				typeref: num,
 				docs: Docs::default(),
 				name: "p1".into(),
+				variadic: false,
 			}],
 			void,
 			Phase::Inflight,
@@ -4388,6 +6016,7 @@ mod tests {
 				typeref: string,
 				docs: Docs::default(),
 				name: "p1".into(),
+				variadic: false,
 			}],
 			void,
 			Phase::Inflight,
@@ -4426,6 +6055,7 @@ mod tests {
 				typeref: string,
 				docs: Docs::default(),
 				name: "p1".into(),
+				variadic: false,
 			}],
 			void,
 			Phase::Inflight,
@@ -4435,6 +6065,7 @@ mod tests {
 				typeref: opt_string,
 				docs: Docs::default(),
 				name: "p1".into(),
+				variadic: false,
 			}],
 			void,
 			Phase::Inflight,
@@ -1,8 +1,11 @@
 use crate::{
-	ast::{ExprKind, Reference, Stmt, StmtKind, Symbol},
+	ast::{Expr, ExprKind, FunctionDefinition, Phase, Reference, Stmt, StmtKind, Symbol},
+	diagnostic::WingSpan,
 	visit::{self, Visit},
 };
 
+use super::Types;
+
 /// Determine a list of all fields that are initialized in a class constructor.
 #[derive(Default)]
 pub struct VisitClassInit {
@@ -22,14 +25,17 @@ impl Visit<'_> for VisitClassInit {
 		match &node.kind {
 			StmtKind::Assignment { variable, value: _ } => match &variable.kind {
 				ExprKind::Reference(r) => match r {
+					// Only assignments to `this.<field>` initialize fields (e.g. `MyClass.field = ...` assigns a static field)
 					Reference::InstanceMember {
 						property,
-						object: _,
+						object,
 						optional_accessor: _,
-					} => self.fields.push(Symbol {
-						name: property.name.clone(),
-						span: property.span.clone(),
-					}),
+					} if matches!(&object.kind, ExprKind::Reference(Reference::Identifier(o)) if o.name == "this") => {
+						self.fields.push(Symbol {
+							name: property.name.clone(),
+							span: property.span.clone(),
+						})
+					}
 					_ => (),
 				},
 				_ => (),
@@ -39,3 +45,49 @@ impl Visit<'_> for VisitClassInit {
 		visit::visit_stmt(self, node);
 	}
 }
+
+/// Find the calls to inflight functions in the initializer of a static field. Static fields are initialized
+/// synchronously when the class is defined, so these calls can't be awaited.
+pub struct VisitStaticFieldInit<'a> {
+	types: &'a Types,
+	pub inflight_calls: Vec<WingSpan>,
+	pub awaits: Vec<WingSpan>,
+}
+
+impl<'a> VisitStaticFieldInit<'a> {
+	pub fn new(types: &'a Types) -> Self {
+		Self {
+			types,
+			inflight_calls: vec![],
+			awaits: vec![],
+		}
+	}
+
+	pub fn analyze_expr(&mut self, expr: &Expr) {
+		self.visit_expr(expr);
+	}
+}
+
+impl<'a> Visit<'_> for VisitStaticFieldInit<'a> {
+	fn visit_expr(&mut self, node: &Expr) {
+		match &node.kind {
+			ExprKind::Call { callee, arg_list: _ } => {
+				if let Some(callee_type) = self.types.try_get_expr_type(callee) {
+					if let Some(sig) = callee_type.maybe_unwrap_option().as_function_sig() {
+						if sig.phase == Phase::Inflight {
+							self.inflight_calls.push(node.span.clone());
+						}
+					}
+				}
+			}
+			ExprKind::Await(_) => self.awaits.push(node.span.clone()),
+			// deferred expressions run asynchronously, so they may call inflight functions
+			ExprKind::Defer(_) => return,
+			_ => {}
+		}
+		visit::visit_expr(self, node);
+	}
+
+	// closures defined in the initializer are called later, so they may call inflight functions
+	fn visit_function_definition(&mut self, _: &FunctionDefinition) {}
+}
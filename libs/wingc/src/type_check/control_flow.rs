@@ -0,0 +1,223 @@
+use std::collections::HashSet;
+
+use crate::{
+	ast::{Expr, ExprKind, Literal, Reference, Scope, Stmt, StmtKind, UtilityFunctions},
+	diagnostic::WingSpan,
+};
+
+use super::{SymbolKind, Type, Types};
+
+/// Returns true if control can never reach the end of the given scope, i.e. every path through
+/// it ends with a `return`, a `throw` or a `panic` (or an infinite loop).
+pub fn scope_always_exits(types: &Types, scope: &Scope) -> bool {
+	scope
+		.statements
+		.iter()
+		.any(|stmt| stmt_always_exits(types, scope, stmt))
+}
+
+/// Returns true if the expression is a call to the builtin `throw` or `panic` function. The callee is
+/// resolved in the scope's environment so user defined functions that shadow the builtins aren't
+/// mistaken for them. Must be called after the scope was type checked.
+fn calls_exiting_builtin(scope: &Scope, expr: &Expr) -> bool {
+	let ExprKind::Call { callee, .. } = &expr.kind else {
+		return false;
+	};
+	let ExprKind::Reference(Reference::Identifier(symbol)) = &callee.kind else {
+		return false;
+	};
+	if symbol.name != UtilityFunctions::Throw.to_string() && symbol.name != UtilityFunctions::Panic.to_string() {
+		return false;
+	}
+	let env = scope.env.borrow();
+	let Some(env) = env.as_ref() else {
+		return false;
+	};
+	// Builtins are defined in the root environment with a global (default) span
+	match env.lookup(symbol, None) {
+		Some(SymbolKind::Variable(var)) => var.name.span == WingSpan::default(),
+		_ => false,
+	}
+}
+
+/// Returns the statements in the scope that can never be executed because a preceding
+/// statement always exits the enclosing function.
+pub fn find_unreachable_stmts<'a>(types: &Types, scope: &'a Scope) -> &'a [Stmt] {
+	match scope
+		.statements
+		.iter()
+		.position(|stmt| stmt_always_exits(types, scope, stmt))
+	{
+		Some(exit_idx) => &scope.statements[exit_idx + 1..],
+		None => &[],
+	}
+}
+
+fn stmt_always_exits(types: &Types, scope: &Scope, stmt: &Stmt) -> bool {
+	match &stmt.kind {
+		StmtKind::Return(_) => true,
+		StmtKind::Expression(expr) => calls_exiting_builtin(scope, expr),
+		StmtKind::Scope(scope) => scope_always_exits(types, scope),
+		StmtKind::If {
+			statements,
+			elif_statements,
+			else_statements,
+			..
+		} => {
+			let Some(else_statements) = else_statements else {
+				return false;
+			};
+			scope_always_exits(types, statements)
+				&& elif_statements
+					.iter()
+					.all(|elif| scope_always_exits(types, &elif.statements))
+				&& scope_always_exits(types, else_statements)
+		}
+		StmtKind::IfLet {
+			statements,
+			else_statements,
+			..
+		} => {
+			let Some(else_statements) = else_statements else {
+				return false;
+			};
+			scope_always_exits(types, statements) && scope_always_exits(types, else_statements)
+		}
+		StmtKind::Match {
+			value,
+			arms,
+			else_statements,
+		} => {
+			let covered = match else_statements {
+				Some(else_statements) => scope_always_exits(types, else_statements),
+				None => missing_match_patterns(types, value, arms.iter().flat_map(|arm| arm.patterns.iter()))
+					.map_or(false, |missing| missing.is_empty()),
+			};
+			covered && arms.iter().all(|arm| scope_always_exits(types, &arm.statements))
+		}
+		StmtKind::TryCatch {
+			try_statements,
+			catch_block,
+			finally_statements,
+		} => {
+			if let Some(finally_statements) = finally_statements {
+				if scope_always_exits(types, finally_statements) {
+					return true;
+				}
+			}
+			scope_always_exits(types, try_statements)
+				&& catch_block
+					.as_ref()
+					.map_or(true, |catch_block| scope_always_exits(types, &catch_block.statements))
+		}
+		// A `while true` loop can only be left through a `break` (or by exiting the function)
+		StmtKind::While { condition, statements } => {
+			matches!(condition.kind, ExprKind::Literal(Literal::Boolean(true))) && !scope_has_break(statements)
+		}
+		StmtKind::ForLoop { .. }
+		| StmtKind::Break
+		| StmtKind::Continue
+		| StmtKind::Bring { .. }
+		| StmtKind::SuperConstructor { .. }
+		| StmtKind::Let { .. }
+		| StmtKind::Assignment { .. }
+		| StmtKind::Class(_)
+		| StmtKind::Interface(_)
+		| StmtKind::Struct { .. }
+		| StmtKind::Enum { .. }
+		| StmtKind::CompilerDebugEnv => false,
+	}
+}
+
+/// Returns true if the scope contains a `break` that applies to the loop owning the scope
+/// (breaks inside nested loops are ignored).
+fn scope_has_break(scope: &Scope) -> bool {
+	scope.statements.iter().any(|stmt| match &stmt.kind {
+		StmtKind::Break => true,
+		StmtKind::Scope(scope) => scope_has_break(scope),
+		StmtKind::If {
+			statements,
+			elif_statements,
+			else_statements,
+			..
+		} => {
+			scope_has_break(statements)
+				|| elif_statements.iter().any(|elif| scope_has_break(&elif.statements))
+				|| else_statements.as_ref().map_or(false, scope_has_break)
+		}
+		StmtKind::IfLet {
+			statements,
+			else_statements,
+			..
+		} => scope_has_break(statements) || else_statements.as_ref().map_or(false, scope_has_break),
+		StmtKind::Match {
+			arms, else_statements, ..
+		} => {
+			arms.iter().any(|arm| scope_has_break(&arm.statements)) || else_statements.as_ref().map_or(false, scope_has_break)
+		}
+		StmtKind::TryCatch {
+			try_statements,
+			catch_block,
+			finally_statements,
+		} => {
+			scope_has_break(try_statements)
+				|| catch_block
+					.as_ref()
+					.map_or(false, |catch_block| scope_has_break(&catch_block.statements))
+				|| finally_statements.as_ref().map_or(false, scope_has_break)
+		}
+		_ => false,
+	})
+}
+
+/// Returns a key identifying the value matched by a `match` pattern (e.g. `Color.RED`, `true`, `"a"` or `nil`),
+/// or `None` if the expression isn't a valid pattern. Must be called after the pattern was type checked.
+pub fn match_pattern_key(types: &Types, pattern: &Expr) -> Option<String> {
+	match &pattern.kind {
+		ExprKind::Literal(Literal::Nil) => Some("nil".to_string()),
+		ExprKind::Literal(Literal::Boolean(b)) => Some(b.to_string()),
+		ExprKind::Literal(Literal::Number(n)) => Some(n.to_string()),
+		ExprKind::Literal(Literal::String(s)) => Some(s.clone()),
+		ExprKind::Reference(Reference::TypeMember { property, .. }) => {
+			let enum_type = types.try_get_expr_type(pattern)?;
+			let Type::Enum(e) = &*enum_type else {
+				return None;
+			};
+			Some(format!("{}.{}", e.name.name, property.name))
+		}
+		_ => None,
+	}
+}
+
+/// Returns the values a `match` without an `else` arm doesn't cover (e.g. `Color.BLUE` or `nil`), or
+/// `None` if its subject has a type whose values can only be covered by an `else` arm (e.g. `str` or `num`).
+/// Must be called after the subject and the patterns of all arms were type checked.
+pub fn missing_match_patterns<'a>(
+	types: &Types,
+	value: &Expr,
+	patterns: impl Iterator<Item = &'a Expr>,
+) -> Option<Vec<String>> {
+	let value_type = types.try_get_expr_type(value)?;
+	if value_type.is_unresolved() {
+		return Some(vec![]);
+	}
+
+	let matched = patterns
+		.filter_map(|pattern| match_pattern_key(types, pattern))
+		.collect::<HashSet<_>>();
+
+	let all_values = match &**value_type.maybe_unwrap_option() {
+		Type::Enum(e) => e.values.iter().map(|v| format!("{}.{}", e.name.name, v.name)).collect(),
+		Type::Boolean => vec!["true".to_string(), "false".to_string()],
+		Type::Nil => vec![],
+		_ => return None,
+	};
+	let mut missing = all_values
+		.into_iter()
+		.filter(|key| !matched.contains(key))
+		.collect::<Vec<_>>();
+	if (value_type.is_option() || value_type.is_nil()) && !matched.contains("nil") {
+		missing.push("nil".to_string());
+	}
+	Some(missing)
+}
@@ -0,0 +1,627 @@
+use itertools::Itertools;
+
+use crate::ast::{self, Phase, Spanned, Symbol};
+use crate::diagnostic::DiagnosticCode;
+
+use super::symbol_env::{StatementIdx, SymbolEnv, SymbolEnvRef};
+use super::{
+	Class, FunctionParameter, FunctionSignature, GenericInstance, Interface, Struct, Subtype, SymbolKind, Type,
+	TypeChecker, TypeParameter, TypeRef, Types, VariableInfo,
+};
+
+/// Maps the type parameters of a generic declaration to the types they are instantiated with
+pub(super) struct TypeArgumentMap(Vec<(TypeRef, TypeRef)>);
+
+impl TypeArgumentMap {
+	pub fn new() -> Self {
+		Self(vec![])
+	}
+
+	pub fn from_pairs(type_parameters: &[TypeRef], type_arguments: &[TypeRef]) -> Self {
+		Self(
+			type_parameters
+				.iter()
+				.copied()
+				.zip(type_arguments.iter().copied())
+				.collect(),
+		)
+	}
+
+	pub fn get(&self, type_parameter: TypeRef) -> Option<TypeRef> {
+		self
+			.0
+			.iter()
+			.find(|(p, _)| is_same_ref(*p, type_parameter))
+			.map(|(_, arg)| *arg)
+	}
+
+	fn insert(&mut self, type_parameter: TypeRef, type_argument: TypeRef) {
+		if let Some(entry) = self.0.iter_mut().find(|(p, _)| is_same_ref(*p, type_parameter)) {
+			entry.1 = type_argument;
+		} else {
+			self.0.push((type_parameter, type_argument));
+		}
+	}
+}
+
+fn is_same_ref(a: TypeRef, b: TypeRef) -> bool {
+	a.0 == b.0
+}
+
+/// Writes the name of a generic definition with its type parameters (e.g. `Cache<T>`), or of a generic instance
+/// with its type arguments (e.g. `Cache<str>`)
+pub(super) fn write_generic_name(
+	f: &mut std::fmt::Formatter<'_>,
+	name: &Symbol,
+	type_parameters: Option<&Vec<TypeRef>>,
+	generic_instance: Option<&GenericInstance>,
+) -> std::fmt::Result {
+	if let Some(instance) = generic_instance {
+		write!(f, "{}<{}>", name.name, instance.type_arguments.iter().join(", "))
+	} else if let Some(params) = type_parameters.filter(|params| !params.is_empty()) {
+		write!(f, "{}<{}>", name.name, params.iter().join(", "))
+	} else {
+		write!(f, "{}", name.name)
+	}
+}
+
+/// Infers the type arguments of a generic function by matching the type of a parameter with the type of the
+/// argument passed to it. For example, passing an `Array<str>` to a parameter of type `Array<T>` infers `T` to be `str`.
+/// If a type parameter is matched more than once, the inferred type is widened to the most general argument.
+pub(super) fn infer_type_arguments(
+	param: TypeRef,
+	arg: TypeRef,
+	type_parameters: &[TypeRef],
+	inferred: &mut TypeArgumentMap,
+) {
+	if arg.is_unresolved() {
+		return;
+	}
+
+	if type_parameters.iter().any(|p| is_same_ref(*p, param)) {
+		// `nil` doesn't tell us anything about the type argument
+		if arg.is_nil() {
+			return;
+		}
+		match inferred.get(param) {
+			Some(existing) if !existing.is_subtype_of(&arg) => {}
+			_ => inferred.insert(param, arg),
+		}
+		return;
+	}
+
+	match (&*param, &*arg) {
+		(Type::Optional(p), Type::Optional(a)) => infer_type_arguments(*p, *a, type_parameters, inferred),
+		(Type::Optional(p), _) => infer_type_arguments(*p, arg, type_parameters, inferred),
+		(Type::Array(p), Type::Array(a))
+		| (Type::MutArray(p), Type::MutArray(a))
+		| (Type::Map(p), Type::Map(a))
+		| (Type::MutMap(p), Type::MutMap(a))
+		| (Type::Set(p), Type::Set(a))
+		| (Type::MutSet(p), Type::MutSet(a))
+		| (Type::Promise(p), Type::Promise(a)) => infer_type_arguments(*p, *a, type_parameters, inferred),
+		(Type::Union(p), _) => {
+			// If the argument matches one of the members as is, it doesn't tell us anything about the type arguments
+			if p.iter().any(|p| arg.is_subtype_of(p)) {
+				return;
+			}
+			for p in p.iter() {
+				infer_type_arguments(*p, arg, type_parameters, inferred);
+			}
+		}
+		(Type::Function(p), Type::Function(a)) => {
+			for (p, a) in p.parameters.iter().zip(a.parameters.iter()) {
+				infer_type_arguments(p.typeref, a.typeref, type_parameters, inferred);
+			}
+			infer_type_arguments(p.return_type, a.return_type, type_parameters, inferred);
+		}
+		(Type::Class(_) | Type::Interface(_) | Type::Struct(_), _) => {
+			let Some(param_instance) = param.generic_instance() else {
+				return;
+			};
+			if let Some(arg_type_arguments) = find_generic_ancestor(arg, param_instance.definition) {
+				for (p, a) in param_instance.type_arguments.iter().zip(arg_type_arguments.iter()) {
+					infer_type_arguments(*p, *a, type_parameters, inferred);
+				}
+			}
+		}
+		_ => {}
+	}
+}
+
+/// Returns the type arguments `t` (or one of the types it extends) instantiates `definition` with
+fn find_generic_ancestor(t: TypeRef, definition: TypeRef) -> Option<Vec<TypeRef>> {
+	if is_same_ref(t, definition) {
+		return definition.generic_type_parameters().cloned();
+	}
+	if let Some(instance) = t.generic_instance() {
+		if is_same_ref(instance.definition, definition) {
+			return Some(instance.type_arguments.clone());
+		}
+	}
+
+	let supertypes = match &*t {
+		Type::Class(c) => c.parent.iter().chain(c.implements.iter()).copied().collect_vec(),
+		Type::Interface(i) => i.extends.clone(),
+		Type::Struct(s) => s.extends.clone(),
+		_ => vec![],
+	};
+	supertypes
+		.into_iter()
+		.find_map(|supertype| find_generic_ancestor(supertype, definition))
+}
+
+impl Types {
+	/// Instantiates a generic class, interface or struct with the given type arguments. Instantiating a type with
+	/// the same type arguments twice returns the same type.
+	pub(super) fn instantiate_generic(&mut self, definition: TypeRef, type_arguments: Vec<TypeRef>) -> TypeRef {
+		let type_parameters = definition
+			.generic_type_parameters()
+			.expect("Expected a generic type")
+			.clone();
+
+		// A generic type instantiated with its own type parameters (e.g. `Cache<T>` inside `Cache`) is the type itself
+		if type_parameters
+			.iter()
+			.zip(type_arguments.iter())
+			.all(|(p, a)| is_same_ref(*p, *a))
+		{
+			return definition;
+		}
+
+		if let Some(instance) = self.generic_instances.iter().find(|t| {
+			let instance = t.generic_instance().unwrap();
+			is_same_ref(instance.definition, definition)
+				&& instance
+					.type_arguments
+					.iter()
+					.zip(type_arguments.iter())
+					.all(|(a, b)| a.is_same_type_as(b))
+		}) {
+			return *instance;
+		}
+
+		let generic_instance = Some(GenericInstance {
+			definition,
+			type_arguments,
+		});
+
+		// Register the instance before instantiating its members, so members referring to the instance resolve to it
+		let dummy_env = SymbolEnv::new(None, self.void(), false, false, Phase::Independent, 0);
+		let instance = match &*definition {
+			Type::Class(c) => self.add_type(Type::Class(Class {
+				name: c.name.clone(),
+				parent: None,
+				implements: vec![],
+				env: dummy_env,
+				fqn: c.fqn.clone(),
+				is_abstract: c.is_abstract,
+				type_parameters: None,
+				generic_instance,
+				phase: c.phase,
+				docs: c.docs.clone(),
+				lifts: None,
+				std_construct_args: c.std_construct_args,
+			})),
+			Type::Interface(i) => self.add_type(Type::Interface(Interface {
+				name: i.name.clone(),
+				docs: i.docs.clone(),
+				extends: vec![],
+				env: dummy_env,
+				type_parameters: None,
+				generic_instance,
+			})),
+			Type::Struct(s) => self.add_type(Type::Struct(Struct {
+				name: s.name.clone(),
+				docs: s.docs.clone(),
+				extends: vec![],
+				env: dummy_env,
+				type_parameters: None,
+				generic_instance,
+			})),
+			_ => panic!("Expected a generic class, interface or struct"),
+		};
+		self.generic_instances.push(instance);
+		self.instantiate_generic_members(definition, instance);
+
+		instance
+	}
+
+	/// Re-instantiates the members of all the instances of `definition`. Instances created while the definition was
+	/// still being declared (e.g. `Pair<V, K>` in a method signature of `Pair<K, V>`) don't have its members yet.
+	pub(super) fn update_generic_instances(&mut self, definition: TypeRef) {
+		let instances = self
+			.generic_instances
+			.iter()
+			.filter(|t| is_same_ref(t.generic_instance().unwrap().definition, definition))
+			.copied()
+			.collect::<Vec<_>>();
+		for instance in instances {
+			self.instantiate_generic_members(definition, instance);
+		}
+	}
+
+	/// Fills in the parents and members of a generic instance from its definition
+	fn instantiate_generic_members(&mut self, definition: TypeRef, mut instance: TypeRef) {
+		let type_parameters = definition
+			.generic_type_parameters()
+			.expect("Expected a generic type")
+			.clone();
+		let type_arguments = instance.generic_instance().unwrap().type_arguments.clone();
+		let type_argument_map = TypeArgumentMap::from_pairs(&type_parameters, &type_arguments);
+
+		match (&*definition, &mut *instance) {
+			(Type::Class(definition), Type::Class(instance)) => {
+				instance.parent = definition
+					.parent
+					.map(|parent| self.substitute_type_parameters(parent, &type_argument_map));
+				instance.implements = definition
+					.implements
+					.iter()
+					.map(|i| self.substitute_type_parameters(*i, &type_argument_map))
+					.collect();
+				// Inherited members are looked up in the (instantiated) parent class
+				let parent_env = instance.parent.and_then(|p| p.as_class().map(|c| c.env.get_ref()));
+				instance.env = self.instantiate_members(&definition.env, parent_env, &type_argument_map);
+			}
+			(Type::Interface(definition), Type::Interface(instance)) => {
+				instance.extends = definition
+					.extends
+					.iter()
+					.map(|i| self.substitute_type_parameters(*i, &type_argument_map))
+					.collect();
+				instance.env = self.instantiate_members(&definition.env, None, &type_argument_map);
+			}
+			(Type::Struct(definition), Type::Struct(instance)) => {
+				instance.extends = definition
+					.extends
+					.iter()
+					.map(|s| self.substitute_type_parameters(*s, &type_argument_map))
+					.collect();
+				instance.env = self.instantiate_members(&definition.env, None, &type_argument_map);
+			}
+			_ => unreachable!(),
+		}
+	}
+
+	/// Creates an environment with the members of `env`, with their type parameters replaced by type arguments
+	fn instantiate_members(
+		&mut self,
+		env: &SymbolEnv,
+		parent: Option<SymbolEnvRef>,
+		type_argument_map: &TypeArgumentMap,
+	) -> SymbolEnv {
+		let mut instance_env = SymbolEnv::new(parent, env.return_type, env.is_init, env.is_function, env.phase, 0);
+		for (name, (_, kind)) in env.symbol_map.iter() {
+			let SymbolKind::Variable(member) = kind else {
+				continue;
+			};
+			let member = VariableInfo {
+				type_: self.substitute_type_parameters(member.type_, type_argument_map),
+				..member.clone()
+			};
+			instance_env
+				.define(
+					&Symbol::new(name, member.name.span.clone()),
+					SymbolKind::Variable(member),
+					StatementIdx::Top,
+				)
+				.expect("Expected members of a generic type to be unique");
+		}
+		instance_env
+	}
+
+	/// Replaces the type parameters in `t` with the type arguments they are mapped to
+	pub(super) fn substitute_type_parameters(&mut self, t: TypeRef, type_argument_map: &TypeArgumentMap) -> TypeRef {
+		if let Some(type_argument) = type_argument_map.get(t) {
+			return type_argument;
+		}
+
+		macro_rules! substitute_inner {
+			($variant:ident, $inner:expr) => {{
+				let inner = self.substitute_type_parameters($inner, type_argument_map);
+				if is_same_ref(inner, $inner) {
+					t
+				} else {
+					self.add_type(Type::$variant(inner))
+				}
+			}};
+		}
+
+		match &*t {
+			Type::Optional(v) => substitute_inner!(Optional, *v),
+			Type::Array(v) => substitute_inner!(Array, *v),
+			Type::MutArray(v) => substitute_inner!(MutArray, *v),
+			Type::Map(v) => substitute_inner!(Map, *v),
+			Type::MutMap(v) => substitute_inner!(MutMap, *v),
+			Type::Set(v) => substitute_inner!(Set, *v),
+			Type::MutSet(v) => substitute_inner!(MutSet, *v),
+			Type::Promise(v) => substitute_inner!(Promise, *v),
+			Type::Union(types) => {
+				let substituted = types
+					.iter()
+					.map(|v| self.substitute_type_parameters(*v, type_argument_map))
+					.collect_vec();
+				if substituted.iter().zip(types.iter()).all(|(s, v)| is_same_ref(*s, *v)) {
+					t
+				} else {
+					self.make_union(substituted)
+				}
+			}
+			Type::Function(sig) => {
+				let this_type = sig
+					.this_type
+					.map(|this_type| self.substitute_type_parameters(this_type, type_argument_map));
+				let parameters = sig
+					.parameters
+					.iter()
+					.map(|p| FunctionParameter {
+						name: p.name.clone(),
+						typeref: self.substitute_type_parameters(p.typeref, type_argument_map),
+						docs: p.docs.clone(),
+						variadic: p.variadic,
+					})
+					.collect_vec();
+				let return_type = self.substitute_type_parameters(sig.return_type, type_argument_map);
+
+				let unchanged = is_same_ref(return_type, sig.return_type)
+					&& this_type.zip(sig.this_type).map_or(true, |(a, b)| is_same_ref(a, b))
+					&& parameters
+						.iter()
+						.zip(sig.parameters.iter())
+						.all(|(a, b)| is_same_ref(a.typeref, b.typeref));
+				if unchanged {
+					return t;
+				}
+
+				self.add_type(Type::Function(FunctionSignature {
+					type_parameters: sig.type_parameters.clone(),
+					this_type,
+					parameters,
+					return_type,
+					phase: sig.phase,
+					js_override: sig.js_override.clone(),
+					docs: sig.docs.clone(),
+				}))
+			}
+			Type::Class(_) | Type::Interface(_) | Type::Struct(_) => {
+				if let Some(instance) = t.generic_instance() {
+					let type_arguments = instance
+						.type_arguments
+						.iter()
+						.map(|a| self.substitute_type_parameters(*a, type_argument_map))
+						.collect_vec();
+					if type_arguments
+						.iter()
+						.zip(instance.type_arguments.iter())
+						.all(|(a, b)| is_same_ref(*a, *b))
+					{
+						t
+					} else {
+						self.instantiate_generic(instance.definition, type_arguments)
+					}
+				} else if let Some(type_parameters) = t.generic_type_parameters() {
+					// A generic type referenced from its own members (e.g. the type of `this`) is instantiated along with them
+					if type_parameters.iter().any(|p| type_argument_map.get(*p).is_some()) {
+						let type_arguments = type_parameters
+							.iter()
+							.map(|p| type_argument_map.get(*p).unwrap_or(*p))
+							.collect_vec();
+						self.instantiate_generic(t, type_arguments)
+					} else {
+						t
+					}
+				} else {
+					t
+				}
+			}
+			_ => t,
+		}
+	}
+}
+
+impl<'a> TypeChecker<'a> {
+	/// Creates the types of the type parameters of a generic declaration
+	pub(super) fn resolve_type_parameters(
+		&mut self,
+		type_parameters: &[ast::TypeParameter],
+		env: &SymbolEnv,
+		statement_idx: usize,
+	) -> Vec<TypeRef> {
+		type_parameters
+			.iter()
+			.map(|p| {
+				let bound = p.bound.as_ref().map(|bound| {
+					self
+						.resolve_user_defined_type(bound, env, statement_idx)
+						.unwrap_or_else(|e| self.type_error(e))
+				});
+				self.types.add_type(Type::TypeParameter(TypeParameter {
+					name: p.name.clone(),
+					bound,
+				}))
+			})
+			.collect()
+	}
+
+	/// Creates an environment in which the type parameters of a generic declaration are defined. It is used as the
+	/// parent environment of the declaration's members.
+	pub(super) fn make_type_parameters_env(&mut self, type_parameters: &[TypeRef], env: &SymbolEnv) -> SymbolEnvRef {
+		let mut type_parameters_env = SymbolEnv::new(
+			Some(env.get_ref()),
+			env.return_type,
+			false,
+			false,
+			env.phase,
+			self.statement_idx,
+		);
+		for t in type_parameters {
+			let name = &t.as_type_parameter().expect("Expected a type parameter").name;
+			if let Err(type_error) = type_parameters_env.define(name, SymbolKind::Type(*t), StatementIdx::Top) {
+				self.type_error(type_error);
+			}
+		}
+		self.types.add_detached_env(type_parameters_env)
+	}
+
+	/// Instantiates a generic type with explicit type arguments (e.g. `Cache<str>`)
+	pub(super) fn instantiate_generic_type(
+		&mut self,
+		t: TypeRef,
+		type_arguments: Vec<TypeRef>,
+		span: &impl Spanned,
+	) -> TypeRef {
+		if t.is_unresolved() || type_arguments.iter().any(|a| a.is_unresolved()) {
+			return self.types.error();
+		}
+
+		let Some(type_parameters) = t.generic_type_parameters().cloned() else {
+			self.spanned_error_with_code(
+				span,
+				DiagnosticCode::InvalidTypeArguments,
+				format!("Type \"{}\" is not generic and cannot have type arguments", t),
+			);
+			return self.types.error();
+		};
+
+		if type_parameters.len() != type_arguments.len() {
+			self.spanned_error_with_code(
+				span,
+				DiagnosticCode::InvalidTypeArguments,
+				format!(
+					"Type \"{}\" expects {} type arguments, but {} were provided",
+					t,
+					type_parameters.len(),
+					type_arguments.len()
+				),
+			);
+			return self.types.error();
+		}
+
+		if !self.validate_type_arguments(&type_parameters, &type_arguments, span) {
+			return self.types.error();
+		}
+
+		self.types.instantiate_generic(t, type_arguments)
+	}
+
+	/// Validates that each type argument satisfies the bound of its type parameter
+	pub(super) fn validate_type_arguments(
+		&mut self,
+		type_parameters: &[TypeRef],
+		type_arguments: &[TypeRef],
+		span: &impl Spanned,
+	) -> bool {
+		let type_argument_map = TypeArgumentMap::from_pairs(type_parameters, type_arguments);
+		let mut valid = true;
+		for (param, arg) in type_parameters.iter().zip(type_arguments.iter()) {
+			let Some(bound) = param.as_type_parameter().and_then(|p| p.bound) else {
+				continue;
+			};
+			// Bounds may refer to other type parameters of the same declaration
+			let bound = self.types.substitute_type_parameters(bound, &type_argument_map);
+			if !arg.is_subtype_of(&bound) && !arg.is_unresolved() {
+				self.spanned_error_with_code(
+					span,
+					DiagnosticCode::InvalidTypeArguments,
+					format!(
+						"Type \"{}\" does not satisfy the bound \"{}\" of type parameter \"{}\"",
+						arg, bound, param
+					),
+				);
+				valid = false;
+			}
+		}
+		valid
+	}
+
+	/// Infers the type arguments of a generic function from the types of the arguments it is called with, and
+	/// returns the function's signature with its type parameters replaced by them
+	pub(super) fn instantiate_generic_function(
+		&mut self,
+		sig: &FunctionSignature,
+		arg_types: &[TypeRef],
+		span: &impl Spanned,
+	) -> Option<FunctionSignature> {
+		let mut inferred = TypeArgumentMap::new();
+		for (i, arg) in arg_types.iter().enumerate() {
+			if let Some(param) = sig.arg_type_at(i) {
+				infer_type_arguments(param, *arg, &sig.type_parameters, &mut inferred);
+			}
+		}
+		self.resolve_inferred_type_arguments(&sig.type_parameters, &inferred, span)?;
+
+		let parameters = sig
+			.parameters
+			.iter()
+			.map(|p| FunctionParameter {
+				name: p.name.clone(),
+				typeref: self.types.substitute_type_parameters(p.typeref, &inferred),
+				docs: p.docs.clone(),
+				variadic: p.variadic,
+			})
+			.collect();
+		Some(FunctionSignature {
+			type_parameters: vec![],
+			this_type: sig.this_type,
+			parameters,
+			return_type: self.types.substitute_type_parameters(sig.return_type, &inferred),
+			phase: sig.phase,
+			js_override: sig.js_override.clone(),
+			docs: sig.docs.clone(),
+		})
+	}
+
+	/// Infers the type arguments of a generic class or struct from the parameter types of its initializer (or its
+	/// field types) and the types of the values passed to them, and instantiates it
+	pub(super) fn infer_generic_type(
+		&mut self,
+		definition: TypeRef,
+		param_and_arg_types: &[(TypeRef, TypeRef)],
+		span: &impl Spanned,
+	) -> TypeRef {
+		let type_parameters = definition
+			.generic_type_parameters()
+			.expect("Expected a generic type")
+			.clone();
+		let mut inferred = TypeArgumentMap::new();
+		for (param, arg) in param_and_arg_types {
+			infer_type_arguments(*param, *arg, &type_parameters, &mut inferred);
+		}
+		let Some(type_arguments) = self.resolve_inferred_type_arguments(&type_parameters, &inferred, span) else {
+			return self.types.error();
+		};
+		self.types.instantiate_generic(definition, type_arguments)
+	}
+
+	/// Returns the inferred type argument of each type parameter, or reports an error if some couldn't be inferred or
+	/// don't satisfy their bounds
+	fn resolve_inferred_type_arguments(
+		&mut self,
+		type_parameters: &[TypeRef],
+		inferred: &TypeArgumentMap,
+		span: &impl Spanned,
+	) -> Option<Vec<TypeRef>> {
+		let mut type_arguments = vec![];
+		for param in type_parameters {
+			let Some(arg) = inferred.get(*param) else {
+				self.spanned_error_with_code(
+					span,
+					DiagnosticCode::InvalidTypeArguments,
+					format!(
+						"Cannot infer the type argument of type parameter \"{}\" from the arguments",
+						param
+					),
+				);
+				return None;
+			};
+			type_arguments.push(arg);
+		}
+
+		if self.validate_type_arguments(type_parameters, &type_arguments, span) {
+			Some(type_arguments)
+		} else {
+			None
+		}
+	}
+}
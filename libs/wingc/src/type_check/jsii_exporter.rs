@@ -0,0 +1,954 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use sha2::{Digest, Sha256};
+use wingii::{
+	fqn::FQN,
+	jsii::{self, CollectionKind, PrimitiveType, TypeReference},
+	type_system::TypeSystem,
+};
+
+use crate::{
+	ast::{AccessModifier, Phase, Symbol},
+	diagnostic::{report_diagnostic, Diagnostic, DiagnosticCode, DiagnosticSeverity},
+	docs::Docs,
+	type_check::{
+		symbol_env::SymbolEnv, Class, Enum, FunctionSignature, Interface, Struct, SymbolKind, Type, TypeRef, Types,
+		VariableInfo, VariableKind, CLASS_INFLIGHT_INIT_NAME, CLASS_INIT_NAME,
+	},
+	CONSTRUCT_BASE_CLASS, WINGSDK_ASSEMBLY_NAME, WINGSDK_DURATION, WINGSDK_MUT_JSON, WINGSDK_RESOURCE,
+};
+
+/// The JSII spec version of the emitted assemblies
+const JSII_SCHEMA: &str = "jsii/0.10.0";
+
+/// Serializes the types defined by a Wing library into a JSII assembly, so the library can be brought by other
+/// Wing code (or used from any other JSII language) like any other JSII module.
+///
+/// The assembly is the inverse of what `JsiiImporter` expects: preflight classes extend `std.Resource` and their
+/// inflight methods are declared in a client interface linked with an `@inflight` tag, structs are datatype
+/// interfaces and all the types are defined at the root of the assembly.
+pub struct JsiiExporter<'a> {
+	types: &'a Types,
+	/// Used for looking up the JSII types referenced by the library and the versions of their assemblies
+	jsii_types: &'a TypeSystem,
+	/// The library's `package.json`, its `name` is the name of the assembly
+	package_json: &'a serde_json::Value,
+	/// The Wing file (as a `WingSpan::file_id`) whose types are exported
+	source_file: &'a str,
+	/// The assemblies referenced by the exported types
+	dependencies: BTreeSet<String>,
+}
+
+impl<'a> JsiiExporter<'a> {
+	pub fn new(
+		types: &'a Types,
+		jsii_types: &'a TypeSystem,
+		package_json: &'a serde_json::Value,
+		source_file: &'a str,
+	) -> Self {
+		Self {
+			types,
+			jsii_types,
+			package_json,
+			source_file,
+			dependencies: BTreeSet::new(),
+		}
+	}
+
+	/// Returns the assembly of the public types (classes, interfaces, structs and enums) defined in the root
+	/// environment of the library. Members whose types can't be represented in JSII are reported as errors.
+	pub fn export(mut self, env: &SymbolEnv) -> jsii::Assembly {
+		let mut types = BTreeMap::new();
+		for (_, kind, _) in env.iter(false) {
+			let SymbolKind::Type(t) = kind else {
+				continue;
+			};
+			for jsii_type in self.export_type(*t) {
+				types.insert(jsii_type_fqn(&jsii_type).to_string(), jsii_type);
+			}
+		}
+
+		let dependencies = self
+			.dependencies
+			.iter()
+			.filter_map(|name| {
+				self
+					.jsii_types
+					.find_assembly(name)
+					.map(|assembly| (name.clone(), assembly.version.clone()))
+			})
+			.collect::<BTreeMap<_, _>>();
+
+		// Like the fingerprints of assemblies compiled by jsii, this is the SHA-256 digest of the types, so it's stable
+		// across compilations and Rust versions
+		let fingerprint = base16ct::lower::encode_string(&Sha256::digest(
+			serde_json::to_string(&types).unwrap_or_default().as_bytes(),
+		));
+
+		let name = self.assembly_name().to_string();
+		let repository = self.repository();
+		jsii::Assembly {
+			author: self.author(),
+			bin: None,
+			bundled: None,
+			contributors: None,
+			dependencies: if dependencies.is_empty() {
+				None
+			} else {
+				Some(dependencies)
+			},
+			description: self.package_field("description").unwrap_or_else(|| name.clone()),
+			docs: None,
+			fingerprint,
+			homepage: self.package_field("homepage").unwrap_or_else(|| repository.url.clone()),
+			jsii_version: format!("wingc {}", env!("CARGO_PKG_VERSION")),
+			keywords: None,
+			license: self
+				.package_field("license")
+				.unwrap_or_else(|| "UNLICENSED".to_string()),
+			name,
+			readme: None,
+			repository,
+			schema: JSII_SCHEMA.to_string(),
+			submodules: None,
+			types: Some(types),
+			version: self.package_field("version").unwrap_or_default(),
+		}
+	}
+
+	/// Returns the JSII types of a type defined by the library (a class might also need a client interface), or
+	/// nothing if the type isn't defined by the library or isn't exported.
+	fn export_type(&mut self, t: TypeRef) -> Vec<jsii::Type> {
+		let Some(name) = self.defined_type_name(t) else {
+			return vec![];
+		};
+		if t.generic_type_parameters().is_some() {
+			self.report(&name, format!("Generic type \"{}\" can't be exported", name));
+			return vec![];
+		}
+
+		match &*t {
+			Type::Class(class) => self.export_class(class),
+			Type::Interface(interface) => self.export_interface(interface),
+			Type::Struct(s) => vec![self.export_struct(s)],
+			Type::Enum(e) => vec![self.export_enum(e)],
+			_ => vec![],
+		}
+	}
+
+	fn export_class(&mut self, class: &Class) -> Vec<jsii::Type> {
+		// Inflight classes have no JSII representation (imported classes are either preflight or phase independent)
+		if class.phase == Phase::Inflight {
+			return vec![];
+		}
+
+		let fqn = self.local_fqn(&class.name);
+		let base = match class.parent {
+			Some(parent) => self.type_fqn(parent).map_err(|e| self.report(&class.name, e)).ok(),
+			None => Some(self.sdk_fqn(WINGSDK_RESOURCE)),
+		};
+		let interfaces = self.interface_fqns(&class.name, &class.implements);
+
+		let initializer = class
+			.env
+			.lookup(&CLASS_INIT_NAME.into(), None)
+			.and_then(SymbolKind::as_variable)
+			.and_then(|init| {
+				let sig = init.type_.as_function_sig()?;
+				let mut parameters = vec![];
+				if class.std_construct_args {
+					self.add_dependency(CONSTRUCT_BASE_CLASS);
+					parameters.push(parameter("scope", named_type(CONSTRUCT_BASE_CLASS)));
+					parameters.push(parameter("id", primitive_type(PrimitiveType::String)));
+				}
+				match self.parameters(sig) {
+					Ok(params) => parameters.extend(params),
+					Err(e) => {
+						self.report(&class.name, e);
+						return None;
+					}
+				}
+				Some(jsii::Callable {
+					docs: export_docs(&sig.docs),
+					location_in_module: None,
+					overrides: None,
+					variadic: variadic(&parameters),
+					parameters: non_empty(parameters),
+					protected: None,
+				})
+			});
+
+		let mut properties = vec![];
+		let mut methods = vec![];
+		let mut inflight_methods = vec![];
+		for (name, kind, _) in class.env.iter(false) {
+			let Some(member) = kind.as_variable() else {
+				continue;
+			};
+			if !is_exported_member(&name, &member) {
+				continue;
+			}
+			if let Some(sig) = member.type_.as_function_sig() {
+				match self.method(&name, &member, sig) {
+					Ok(method) if sig.phase == Phase::Inflight => inflight_methods.push(method),
+					Ok(method) => methods.push(method),
+					Err(e) => self.report(&member.name, e),
+				}
+			} else if member.phase != Phase::Inflight {
+				// inflight fields aren't supported by `JsiiImporter`, they are only available to the library's code
+				match self.property(&name, &member) {
+					Ok(property) => properties.push(property),
+					Err(e) => self.report(&member.name, e),
+				}
+			}
+		}
+
+		let mut result = vec![];
+		let mut docs = export_docs(&class.docs);
+		if !inflight_methods.is_empty() {
+			let client_name = format!("I{}Client", class.name.name);
+			let client_fqn = self.local_fqn_str(&client_name);
+			docs = with_inflight_client(docs, &client_fqn);
+			result.push(self.client_interface(client_name, client_fqn, inflight_methods));
+		}
+
+		result.push(jsii::Type::ClassType(jsii::ClassType {
+			abstract_: class.is_abstract.then_some(true),
+			assembly: self.assembly_name().to_string(),
+			base,
+			docs,
+			fqn,
+			initializer,
+			interfaces: non_empty(interfaces),
+			location_in_module: None,
+			methods: non_empty(methods),
+			name: class.name.name.clone(),
+			namespace: None,
+			properties: non_empty(properties),
+			symbol_id: None,
+		}));
+		result
+	}
+
+	fn export_interface(&mut self, interface: &Interface) -> Vec<jsii::Type> {
+		let interfaces = self.interface_fqns(&interface.name, &interface.extends);
+
+		let mut methods = vec![];
+		let mut inflight_methods = vec![];
+		for (name, kind, _) in interface.env.iter(false) {
+			let Some(member) = kind.as_variable() else {
+				continue;
+			};
+			// the members of the parent interfaces are exported by the parents
+			if !is_exported_member(&name, &member) || is_inherited(&name, &interface.extends) {
+				continue;
+			}
+			let Some(sig) = member.type_.as_function_sig() else {
+				continue;
+			};
+			match self.method(&name, &member, sig) {
+				Ok(method) if sig.phase == Phase::Inflight => inflight_methods.push(method),
+				Ok(method) => methods.push(method),
+				Err(e) => self.report(&member.name, e),
+			}
+		}
+
+		let mut result = vec![];
+		let mut docs = export_docs(&interface.docs);
+		if !inflight_methods.is_empty() {
+			let client_name = format!("{}Client", interface.name.name);
+			let client_fqn = self.local_fqn_str(&client_name);
+			docs = with_inflight_client(docs, &client_fqn);
+			result.push(self.client_interface(client_name, client_fqn, inflight_methods));
+		}
+
+		result.push(jsii::Type::InterfaceType(jsii::InterfaceType {
+			assembly: self.assembly_name().to_string(),
+			datatype: None,
+			docs,
+			fqn: self.local_fqn(&interface.name),
+			interfaces: non_empty(interfaces),
+			location_in_module: None,
+			methods: non_empty(methods),
+			name: interface.name.name.clone(),
+			namespace: None,
+			properties: None,
+			symbol_id: None,
+		}));
+		result
+	}
+
+	/// Returns the interface which declares the inflight methods of a preflight class or interface
+	fn client_interface(&self, name: String, fqn: String, methods: Vec<jsii::Method>) -> jsii::Type {
+		jsii::Type::InterfaceType(jsii::InterfaceType {
+			assembly: self.assembly_name().to_string(),
+			datatype: None,
+			docs: None,
+			fqn,
+			interfaces: None,
+			location_in_module: None,
+			methods: Some(methods),
+			name,
+			namespace: None,
+			properties: None,
+			symbol_id: None,
+		})
+	}
+
+	fn export_struct(&mut self, s: &Struct) -> jsii::Type {
+		let interfaces = self.interface_fqns(&s.name, &s.extends);
+
+		let mut properties = vec![];
+		for (name, kind, _) in s.env.iter(false) {
+			let Some(field) = kind.as_variable() else {
+				continue;
+			};
+			// the fields of the parent structs are exported by the parents
+			if is_inherited(&name, &s.extends) {
+				continue;
+			}
+			match self.property(&name, &field) {
+				Ok(property) => properties.push(property),
+				Err(e) => self.report(&field.name, e),
+			}
+		}
+
+		jsii::Type::InterfaceType(jsii::InterfaceType {
+			assembly: self.assembly_name().to_string(),
+			datatype: Some(true),
+			docs: export_docs(&s.docs),
+			fqn: self.local_fqn(&s.name),
+			interfaces: non_empty(interfaces),
+			location_in_module: None,
+			methods: None,
+			name: s.name.name.clone(),
+			namespace: None,
+			properties: non_empty(properties),
+			symbol_id: None,
+		})
+	}
+
+	fn export_enum(&self, e: &Enum) -> jsii::Type {
+		jsii::Type::EnumType(jsii::EnumType {
+			assembly: self.assembly_name().to_string(),
+			docs: export_docs(&e.docs),
+			fqn: self.local_fqn(&e.name),
+			location_in_module: None,
+			members: e
+				.values
+				.iter()
+				.map(|value| jsii::EnumMember {
+					docs: None,
+					name: value.name.clone(),
+				})
+				.collect(),
+			name: e.name.name.clone(),
+			namespace: None,
+			symbol_id: None,
+		})
+	}
+
+	fn method(&mut self, name: &str, member: &VariableInfo, sig: &FunctionSignature) -> Result<jsii::Method, String> {
+		if !sig.type_parameters.is_empty() {
+			return Err(format!("Generic method \"{}\" can't be exported", name));
+		}
+		let parameters = self.parameters(sig)?;
+		let returns = if sig.return_type.is_void() {
+			None
+		} else {
+			Some(self.optional_value(sig.return_type)?)
+		};
+
+		Ok(jsii::Method {
+			abstract_: None,
+			async_: (sig.phase == Phase::Inflight).then_some(true),
+			docs: member.docs.as_ref().and_then(export_docs),
+			location_in_module: None,
+			name: name.to_string(),
+			overrides: None,
+			variadic: variadic(&parameters),
+			parameters: non_empty(parameters),
+			protected: is_protected(member),
+			returns,
+			static_: is_static(member),
+		})
+	}
+
+	fn parameters(&mut self, sig: &FunctionSignature) -> Result<Vec<jsii::Parameter>, String> {
+		sig
+			.parameters
+			.iter()
+			.map(|param| {
+				// variadic parameters are typed as arrays of their elements in Wing
+				let (type_, variadic) = match &*param.typeref {
+					Type::Array(element) | Type::MutArray(element) if param.variadic => (*element, Some(true)),
+					_ => (*param.typeref.maybe_unwrap_option(), None),
+				};
+				Ok(jsii::Parameter {
+					docs: export_docs(&param.docs),
+					name: param.name.clone(),
+					optional: param.typeref.is_option().then_some(true),
+					type_: self.type_reference(type_)?,
+					variadic,
+				})
+			})
+			.collect()
+	}
+
+	fn property(&mut self, name: &str, member: &VariableInfo) -> Result<jsii::Property, String> {
+		Ok(jsii::Property {
+			abstract_: None,
+			const_: None,
+			docs: member.docs.as_ref().and_then(export_docs),
+			immutable: (!member.reassignable).then_some(true),
+			location_in_module: None,
+			name: name.to_string(),
+			optional: member.type_.is_option().then_some(true),
+			overrides: None,
+			protected: is_protected(member),
+			static_: is_static(member),
+			type_: self.type_reference(*member.type_.maybe_unwrap_option())?,
+		})
+	}
+
+	fn optional_value(&mut self, t: TypeRef) -> Result<jsii::OptionalValue, String> {
+		Ok(jsii::OptionalValue {
+			optional: t.is_option().then_some(true),
+			type_: self.type_reference(*t.maybe_unwrap_option())?,
+		})
+	}
+
+	/// Returns the JSII reference to a Wing type, or a message explaining why the type can't be exported
+	fn type_reference(&mut self, t: TypeRef) -> Result<TypeReference, String> {
+		Ok(match &*t {
+			Type::Anything => primitive_type(PrimitiveType::Any),
+			Type::Number => primitive_type(PrimitiveType::Number),
+			Type::String => primitive_type(PrimitiveType::String),
+			Type::Boolean => primitive_type(PrimitiveType::Boolean),
+			Type::Json => primitive_type(PrimitiveType::Json),
+			Type::Datetime => primitive_type(PrimitiveType::Date),
+			Type::Duration => named_type(&self.sdk_fqn(WINGSDK_DURATION)),
+			Type::MutJson => named_type(&self.sdk_fqn(WINGSDK_MUT_JSON)),
+			Type::Array(element) | Type::MutArray(element) => {
+				collection_type(CollectionKind::Array, self.type_reference(*element)?)
+			}
+			Type::Map(element) | Type::MutMap(element) => {
+				collection_type(CollectionKind::Map, self.type_reference(*element)?)
+			}
+			Type::Union(types) => TypeReference::UnionTypeReference(jsii::UnionTypeReference {
+				union: jsii::UnionTypeReferenceUnion {
+					types: types
+						.iter()
+						.map(|t| self.type_reference(*t))
+						.collect::<Result<Vec<_>, _>>()?,
+				},
+			}),
+			Type::Class(_) | Type::Interface(_) | Type::Struct(_) | Type::Enum(_) => named_type(&self.type_fqn(t)?),
+			Type::Optional(_) => return Err(format!("Nested optional type \"{}\" can't be exported", t)),
+			Type::Set(_) | Type::MutSet(_) => return Err(format!("Set type \"{}\" can't be exported", t)),
+			Type::Function(_) => return Err(format!("Function type \"{}\" can't be exported", t)),
+			Type::Promise(_) => return Err(format!("Promise type \"{}\" can't be exported", t)),
+			Type::TypeParameter(_) => return Err(format!("Type parameter \"{}\" can't be exported", t)),
+			Type::Void | Type::Nil | Type::Unresolved => return Err(format!("Type \"{}\" can't be exported", t)),
+		})
+	}
+
+	/// Returns the fully qualified name of a class, interface, struct or enum, which is either defined by the
+	/// library or imported from a JSII module
+	fn type_fqn(&mut self, t: TypeRef) -> Result<String, String> {
+		if t.generic_instance().is_some() || t.generic_type_parameters().is_some() {
+			return Err(format!("Generic type \"{}\" can't be exported", t));
+		}
+		if let Type::Class(Class { fqn: Some(fqn), .. }) = &*t {
+			self.add_dependency(fqn);
+			return Ok(fqn.clone());
+		}
+		if let Some(name) = self.defined_type_name(t) {
+			return Ok(self.local_fqn(&name));
+		}
+		// JSII interfaces, structs and enums don't record their fqn, so we look them up in the imported libraries
+		if let Some(fqn) = find_library_type(&self.types.libraries, t, "") {
+			self.add_dependency(&fqn);
+			return Ok(fqn);
+		}
+		Err(format!(
+			"Type \"{}\" is defined in another Wing file and can't be exported",
+			t
+		))
+	}
+
+	fn interface_fqns(&mut self, owner: &Symbol, interfaces: &[TypeRef]) -> Vec<String> {
+		interfaces
+			.iter()
+			.filter_map(|i| self.type_fqn(*i).map_err(|e| self.report(owner, e)).ok())
+			.collect()
+	}
+
+	/// Returns the name of a class, interface, struct or enum defined in the library's source file
+	fn defined_type_name(&self, t: TypeRef) -> Option<Symbol> {
+		let name = match &*t {
+			Type::Class(Class { name, .. })
+			| Type::Interface(Interface { name, .. })
+			| Type::Struct(Struct { name, .. })
+			| Type::Enum(Enum { name, .. }) => name,
+			_ => return None,
+		};
+		(name.span.file_id == self.source_file).then(|| name.clone())
+	}
+
+	fn add_dependency(&mut self, fqn: &str) {
+		let assembly = FQN::from(fqn).assembly().to_string();
+		if assembly != self.assembly_name() {
+			self.dependencies.insert(assembly);
+		}
+	}
+
+	fn sdk_fqn(&mut self, type_name: &str) -> String {
+		let fqn = format!("{}.{}", WINGSDK_ASSEMBLY_NAME, type_name);
+		self.add_dependency(&fqn);
+		fqn
+	}
+
+	fn local_fqn(&self, name: &Symbol) -> String {
+		self.local_fqn_str(&name.name)
+	}
+
+	fn local_fqn_str(&self, name: &str) -> String {
+		format!("{}.{}", self.assembly_name(), name)
+	}
+
+	fn assembly_name(&self) -> &str {
+		self.package_json["name"].as_str().unwrap_or_default()
+	}
+
+	fn package_field(&self, field: &str) -> Option<String> {
+		self.package_json[field].as_str().map(|s| s.to_string())
+	}
+
+	/// The author of the package, either a `"name <email> (url)"` string or an object with these fields
+	fn author(&self) -> jsii::Person {
+		let author = &self.package_json["author"];
+		let field = |name: &str| author[name].as_str().map(|s| s.to_string());
+		jsii::Person {
+			email: field("email"),
+			name: author
+				.as_str()
+				.map(|s| s.to_string())
+				.or_else(|| field("name"))
+				.unwrap_or_default(),
+			organization: None,
+			roles: vec!["author".to_string()],
+			url: field("url"),
+		}
+	}
+
+	/// The repository of the package, either a URL or an object with `type`, `url` and `directory` fields
+	fn repository(&self) -> jsii::AssemblyRepository {
+		let repository = &self.package_json["repository"];
+		let field = |name: &str| repository[name].as_str().map(|s| s.to_string());
+		jsii::AssemblyRepository {
+			directory: field("directory"),
+			type_: field("type").unwrap_or_else(|| "git".to_string()),
+			url: repository
+				.as_str()
+				.map(|s| s.to_string())
+				.or_else(|| field("url"))
+				.unwrap_or_default(),
+		}
+	}
+
+	fn report(&self, symbol: &Symbol, message: String) {
+		report_diagnostic(Diagnostic {
+			message,
+			span: Some(symbol.span.clone()),
+			severity: DiagnosticSeverity::Error,
+			code: DiagnosticCode::UnexportableType,
+			annotations: vec![],
+			fixes: vec![],
+		});
+	}
+}
+
+/// Looks up the fully qualified name of a type imported from a JSII module, `prefix` is the fqn of `env`
+fn find_library_type(env: &SymbolEnv, t: TypeRef, prefix: &str) -> Option<String> {
+	for (name, kind, _) in env.iter(false) {
+		let fqn = if prefix.is_empty() {
+			name
+		} else {
+			format!("{}.{}", prefix, name)
+		};
+		match kind {
+			SymbolKind::Type(other) if std::ptr::eq(&**other, &*t) => return Some(fqn),
+			SymbolKind::Namespace(ns) => {
+				if let Some(fqn) = find_library_type(&ns.env, t, &fqn) {
+					return Some(fqn);
+				}
+			}
+			_ => {}
+		}
+	}
+	None
+}
+
+/// Constructors and private members aren't part of the API of a type
+fn is_exported_member(name: &str, member: &VariableInfo) -> bool {
+	name != CLASS_INIT_NAME && name != CLASS_INFLIGHT_INIT_NAME && member.access_modifier != AccessModifier::Private
+}
+
+/// Returns whether a member of a struct or interface is defined by one of its parents
+fn is_inherited(name: &str, parents: &[TypeRef]) -> bool {
+	parents.iter().any(|parent| {
+		let env = match &**parent {
+			Type::Struct(s) => &s.env,
+			Type::Interface(i) => &i.env,
+			_ => return false,
+		};
+		env.lookup(&name.into(), None).is_some()
+	})
+}
+
+fn is_protected(member: &VariableInfo) -> Option<bool> {
+	(member.access_modifier == AccessModifier::Protected).then_some(true)
+}
+
+fn is_static(member: &VariableInfo) -> Option<bool> {
+	matches!(member.kind, VariableKind::StaticMember).then_some(true)
+}
+
+fn variadic(parameters: &[jsii::Parameter]) -> Option<bool> {
+	parameters.last().and_then(|p| p.variadic).filter(|variadic| *variadic)
+}
+
+fn non_empty<T>(items: Vec<T>) -> Option<Vec<T>> {
+	if items.is_empty() {
+		None
+	} else {
+		Some(items)
+	}
+}
+
+fn parameter(name: &str, type_: TypeReference) -> jsii::Parameter {
+	jsii::Parameter {
+		docs: None,
+		name: name.to_string(),
+		optional: None,
+		type_,
+		variadic: None,
+	}
+}
+
+fn primitive_type(primitive: PrimitiveType) -> TypeReference {
+	TypeReference::PrimitiveTypeReference(jsii::PrimitiveTypeReference { primitive })
+}
+
+fn named_type(fqn: &str) -> TypeReference {
+	TypeReference::NamedTypeReference(jsii::NamedTypeReference { fqn: fqn.to_string() })
+}
+
+fn collection_type(kind: CollectionKind, elementtype: TypeReference) -> TypeReference {
+	TypeReference::CollectionTypeReference(Box::new(jsii::CollectionTypeReference {
+		collection: jsii::CollectionTypeReferenceCollection { elementtype, kind },
+	}))
+}
+
+fn jsii_type_fqn(t: &jsii::Type) -> &str {
+	match t {
+		jsii::Type::ClassType(c) => &c.fqn,
+		jsii::Type::EnumType(e) => &e.fqn,
+		jsii::Type::InterfaceType(i) => &i.fqn,
+	}
+}
+
+/// Links a preflight type to the interface declaring its inflight methods, the fqn is escaped since it might
+/// contain an `@` character
+fn with_inflight_client(docs: Option<jsii::Docs>, client_fqn: &str) -> Option<jsii::Docs> {
+	let mut docs = docs.unwrap_or_default();
+	docs
+		.custom
+		.get_or_insert_with(BTreeMap::new)
+		.insert("inflight".to_string(), format!("`{}`", client_fqn));
+	Some(docs)
+}
+
+/// The inverse of `Docs::from(&Option<jsii::Docs>)`
+fn export_docs(docs: &Docs) -> Option<jsii::Docs> {
+	let docs = jsii::Docs {
+		custom: if docs.custom.is_empty() {
+			None
+		} else {
+			Some(docs.custom.clone())
+		},
+		default: docs.default.clone(),
+		deprecated: docs.deprecated.clone(),
+		example: docs.example.clone(),
+		remarks: docs.remarks.clone(),
+		returns: docs.returns.clone(),
+		see: docs.see.clone(),
+		stability: docs.stability.clone(),
+		subclassable: docs.subclassable,
+		summary: docs.summary.clone(),
+	};
+	if docs == jsii::Docs::default() {
+		None
+	} else {
+		Some(docs)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use indexmap::IndexSet;
+	use wingii::fqn::FQN;
+
+	use super::*;
+	use crate::{
+		diagnostic::{WingLocation, WingSpan},
+		type_check::{
+			jsii_importer::{JsiiImportSpec, JsiiImporter},
+			symbol_env::{LookupResult, StatementIdx},
+			FunctionParameter,
+		},
+	};
+
+	const SOURCE_FILE: &str = "/project/main.w";
+
+	fn symbol(name: &str) -> Symbol {
+		Symbol {
+			name: name.to_string(),
+			span: WingSpan {
+				start: WingLocation { line: 0, col: 0 },
+				end: WingLocation { line: 0, col: 0 },
+				file_id: SOURCE_FILE.to_string(),
+			},
+		}
+	}
+
+	fn docs(summary: &str) -> Docs {
+		Docs {
+			summary: Some(summary.to_string()),
+			..Docs::default()
+		}
+	}
+
+	fn define_member(env: &mut SymbolEnv, name: &str, t: TypeRef, phase: Phase, access_modifier: AccessModifier) {
+		env
+			.define(
+				&symbol(name),
+				SymbolKind::make_member_variable(symbol(name), t, false, false, phase, access_modifier, None),
+				StatementIdx::Top,
+			)
+			.unwrap();
+	}
+
+	fn package_json() -> serde_json::Value {
+		serde_json::json!({ "name": "my-lib", "version": "1.2.3", "jsii": {} })
+	}
+
+	/// Defines an enum and a struct in the root environment of the library
+	fn define_types(types: &mut Types, env: &mut SymbolEnv) {
+		let color = types.add_type(Type::Enum(Enum {
+			name: symbol("Color"),
+			docs: docs("A color"),
+			values: IndexSet::from([symbol("RED"), symbol("GREEN")]),
+		}));
+
+		let mut options_env = SymbolEnv::new(None, types.void(), false, false, Phase::Independent, 0);
+		define_member(
+			&mut options_env,
+			"name",
+			types.string(),
+			Phase::Independent,
+			AccessModifier::Public,
+		);
+		let optional_num = types.make_option(types.number());
+		define_member(
+			&mut options_env,
+			"size",
+			optional_num,
+			Phase::Independent,
+			AccessModifier::Public,
+		);
+		define_member(
+			&mut options_env,
+			"color",
+			color,
+			Phase::Independent,
+			AccessModifier::Public,
+		);
+		let tags = types.add_type(Type::Array(types.string()));
+		define_member(
+			&mut options_env,
+			"tags",
+			tags,
+			Phase::Independent,
+			AccessModifier::Public,
+		);
+		let options = types.add_type(Type::Struct(Struct {
+			name: symbol("Options"),
+			docs: docs("Options of a thing"),
+			extends: vec![],
+			env: options_env,
+			type_parameters: None,
+			generic_instance: None,
+		}));
+
+		for (name, t) in [("Color", color), ("Options", options)] {
+			env
+				.define(&symbol(name), SymbolKind::Type(t), StatementIdx::Top)
+				.unwrap();
+		}
+	}
+
+	#[test]
+	fn test_export_round_trip() {
+		let mut types = Types::new();
+		let mut env = SymbolEnv::new(None, types.void(), false, false, Phase::Preflight, 0);
+		define_types(&mut types, &mut env);
+
+		let package_json = package_json();
+		let jsii_types = TypeSystem::new();
+		let assembly = JsiiExporter::new(&types, &jsii_types, &package_json, SOURCE_FILE).export(&env);
+		assert_eq!(assembly.name, "my-lib");
+		assert_eq!(assembly.version, "1.2.3");
+		assert_eq!(assembly.dependencies, None);
+
+		// load the assembly like any other JSII module and import its types
+		let dir = tempfile::tempdir().expect("Failed to create temporary directory");
+		std::fs::write(dir.path().join("package.json"), package_json.to_string()).unwrap();
+		std::fs::write(
+			dir.path().join(wingii::spec::SPEC_FILE_NAME),
+			serde_json::to_string_pretty(&assembly).unwrap(),
+		)
+		.unwrap();
+		let mut jsii_types = TypeSystem::new();
+		let assembly_name = jsii_types.load_module(dir.path().to_str().unwrap()).unwrap();
+
+		let mut imported_types = Types::new();
+		let spec = JsiiImportSpec {
+			assembly_name,
+			namespace_filter: vec![],
+			alias: Symbol::global("lib"),
+			import_statement_idx: 0,
+		};
+		JsiiImporter::new(&spec, &mut imported_types, &jsii_types).import_type(&FQN::from("my-lib.Options"));
+
+		let lookup = |fqn: &str| match imported_types.libraries.lookup_nested_str(fqn, None) {
+			LookupResult::Found(kind, _) => kind.as_type().unwrap(),
+			_ => panic!("{} wasn't imported", fqn),
+		};
+
+		let color = lookup("my-lib.Color");
+		let Type::Enum(color) = &*color else {
+			panic!("Expected Color to be imported as an enum");
+		};
+		assert_eq!(color.docs.summary.as_deref(), Some("A color"));
+		assert_eq!(
+			color.values.iter().map(|v| v.name.as_str()).collect::<Vec<_>>(),
+			["RED", "GREEN"]
+		);
+
+		let options = lookup("my-lib.Options");
+		let options = options.as_struct().unwrap();
+		assert_eq!(options.docs.summary.as_deref(), Some("Options of a thing"));
+		let field = |name: &str| options.env.lookup(&name.into(), None).unwrap().as_variable().unwrap();
+		assert!(field("name").type_.is_string());
+		assert!(!field("name").reassignable);
+		assert_eq!(field("size").type_.to_string(), "num?");
+		assert_eq!(field("color").type_.to_string(), "Color");
+		assert_eq!(field("tags").type_.to_string(), "Array<str>");
+	}
+
+	#[test]
+	fn test_export_preflight_class() {
+		let mut types = Types::new();
+		let mut env = SymbolEnv::new(None, types.void(), false, false, Phase::Preflight, 0);
+
+		let mut class_env = SymbolEnv::new(None, types.void(), false, false, Phase::Preflight, 0);
+		let optional_str = types.make_option(types.string());
+		let get = types.add_type(Type::Function(FunctionSignature {
+			type_parameters: vec![],
+			this_type: None,
+			parameters: vec![FunctionParameter {
+				name: "key".to_string(),
+				typeref: types.string(),
+				docs: Docs::default(),
+				variadic: false,
+			}],
+			return_type: optional_str,
+			phase: Phase::Inflight,
+			js_override: None,
+			docs: Docs::default(),
+		}));
+		define_member(&mut class_env, "get", get, Phase::Inflight, AccessModifier::Public);
+		define_member(
+			&mut class_env,
+			"secret",
+			types.string(),
+			Phase::Preflight,
+			AccessModifier::Private,
+		);
+		let reset = types.add_type(Type::Function(FunctionSignature {
+			type_parameters: vec![],
+			this_type: None,
+			parameters: vec![],
+			return_type: types.void(),
+			phase: Phase::Preflight,
+			js_override: None,
+			docs: Docs::default(),
+		}));
+		define_member(
+			&mut class_env,
+			"reset",
+			reset,
+			Phase::Preflight,
+			AccessModifier::Protected,
+		);
+
+		let store = types.add_type(Type::Class(Class {
+			name: symbol("Store"),
+			parent: None,
+			implements: vec![],
+			env: class_env,
+			fqn: None,
+			is_abstract: false,
+			type_parameters: None,
+			generic_instance: None,
+			phase: Phase::Preflight,
+			docs: docs("A store"),
+			lifts: None,
+			std_construct_args: true,
+		}));
+		env
+			.define(&symbol("Store"), SymbolKind::Type(store), StatementIdx::Top)
+			.unwrap();
+
+		let package_json = package_json();
+		let jsii_types = TypeSystem::new();
+		let assembly = JsiiExporter::new(&types, &jsii_types, &package_json, SOURCE_FILE).export(&env);
+		let assembly_types = assembly.types.unwrap();
+
+		let Some(jsii::Type::ClassType(class)) = assembly_types.get("my-lib.Store") else {
+			panic!("Expected Store to be exported as a class");
+		};
+		assert_eq!(class.base.as_deref(), Some("@winglang/sdk.std.Resource"));
+		assert_eq!(
+			class.docs.as_ref().unwrap().custom.as_ref().unwrap()["inflight"],
+			"`my-lib.IStoreClient`"
+		);
+		// private members aren't exported, inflight methods are declared by the client interface
+		assert_eq!(class.properties, None);
+		let methods = class.methods.as_ref().unwrap();
+		assert_eq!(methods.len(), 1);
+		assert_eq!(methods[0].name, "reset");
+		assert_eq!(methods[0].protected, Some(true));
+		assert_eq!(methods[0].returns, None);
+
+		let Some(jsii::Type::InterfaceType(client)) = assembly_types.get("my-lib.IStoreClient") else {
+			panic!("Expected the inflight client of Store to be exported as an interface");
+		};
+		let get = &client.methods.as_ref().unwrap()[0];
+		assert_eq!(get.name, "get");
+		assert_eq!(get.async_, Some(true));
+		assert_eq!(get.returns.as_ref().unwrap().optional, Some(true));
+	}
+}
@@ -1,5 +1,5 @@
 use crate::{
-	ast::{Phase, Symbol},
+	ast::{AccessModifier, Phase, Symbol},
 	debug,
 	diagnostic::{WingLocation, WingSpan},
 	docs::Docs,
@@ -7,7 +7,8 @@ use crate::{
 		self, symbol_env::StatementIdx, Class, FunctionParameter, FunctionSignature, Interface, Struct, SymbolKind, Type,
 		TypeRef, Types, CLASS_INIT_NAME,
 	},
-	CONSTRUCT_BASE_CLASS, WINGSDK_ASSEMBLY_NAME, WINGSDK_DURATION, WINGSDK_JSON, WINGSDK_MUT_JSON, WINGSDK_RESOURCE,
+	CONSTRUCT_BASE_CLASS, WINGSDK_ASSEMBLY_NAME, WINGSDK_DATETIME, WINGSDK_DURATION, WINGSDK_JSON, WINGSDK_MUT_JSON,
+	WINGSDK_RESOURCE,
 };
 use colored::Colorize;
 use wingii::{
@@ -16,9 +17,11 @@ use wingii::{
 	type_system::TypeSystem,
 };
 
+use std::collections::HashSet;
+
 use super::{
 	symbol_env::{LookupResult, SymbolEnv},
-	Enum, Namespace,
+	Enum, Namespace, NamespaceRef,
 };
 
 trait JsiiInterface {
@@ -64,6 +67,133 @@ pub struct JsiiImportSpec {
 	pub import_statement_idx: usize,
 }
 
+/// Looks up the nested symbol `nested_vec` (e.g. `cloud.Bucket`) in `env` like `SymbolEnv::lookup_nested`, first
+/// importing the JSII types (or nested submodules) it references which weren't imported yet.
+///
+/// Namespaces of JSII assemblies are set up when they're brought, but their types (along with the types referenced
+/// by their signatures) are only imported the first time they're referenced (see `Namespace::jsii_fqn`).
+pub fn lookup_nested_importing<'a>(
+	env: &'a SymbolEnv,
+	nested_vec: &[&Symbol],
+	statement_idx: Option<usize>,
+	wing_types: &mut Types,
+	jsii_types: &TypeSystem,
+) -> LookupResult<'a> {
+	import_nested_on_demand(env, nested_vec, wing_types, jsii_types);
+	env.lookup_nested(nested_vec, statement_idx)
+}
+
+/// Looks up the type with the given fully qualified name (e.g. `@winglang/sdk.std.Json`) in `wing_types.libraries`,
+/// first importing it from JSII if it wasn't imported yet. See `lookup_nested_importing`.
+pub fn lookup_fqn_importing<'a>(fqn: &str, wing_types: &'a mut Types, jsii_types: &TypeSystem) -> LookupResult<'a> {
+	import_fqn_on_demand(fqn, wing_types, jsii_types);
+	let wing_types: &'a Types = wing_types;
+	wing_types.libraries.lookup_nested_str(fqn, None)
+}
+
+/// Imports the JSII types (or nested submodules) referenced by the nested symbol `nested_vec` which weren't imported
+/// yet, so they can then be looked up in `env`. See `lookup_nested_importing`.
+pub(super) fn import_nested_on_demand(
+	env: &SymbolEnv,
+	nested_vec: &[&Symbol],
+	wing_types: &mut Types,
+	jsii_types: &TypeSystem,
+) {
+	let Some((root, nested_vec)) = nested_vec.split_first() else {
+		return;
+	};
+	let LookupResult::Found(SymbolKind::Namespace(ns), _) = env.lookup_ext(root, None) else {
+		return;
+	};
+	import_namespace_members_on_demand(*ns, nested_vec, wing_types, jsii_types);
+}
+
+/// Imports the JSII type with the given fully qualified name if it wasn't imported yet, so it can then be looked up
+/// in `wing_types.libraries`
+fn import_fqn_on_demand(fqn: &str, wing_types: &mut Types, jsii_types: &TypeSystem) {
+	let nested_vec = fqn.split('.').map(Symbol::global).collect::<Vec<_>>();
+	let Some((root, nested_vec)) = nested_vec.split_first() else {
+		return;
+	};
+	let Some(SymbolKind::Namespace(ns)) = wing_types.libraries.lookup(root, None) else {
+		return;
+	};
+	let ns = *ns;
+	import_namespace_members_on_demand(ns, &nested_vec.iter().collect::<Vec<_>>(), wing_types, jsii_types);
+}
+
+/// Imports all the types of a JSII namespace which weren't imported yet (e.g. to list them as completions)
+pub fn import_namespace_on_demand(ns: NamespaceRef, wing_types: &mut Types, jsii_types: &TypeSystem) {
+	if ns.loaded {
+		return;
+	}
+	let Some(jsii_fqn) = ns.jsii_fqn.clone() else {
+		return;
+	};
+	let fqn = FQN::from(jsii_fqn.as_str());
+	let Some(assembly) = jsii_types.find_assembly(fqn.assembly()) else {
+		return;
+	};
+	let jsii_spec = assembly_import_spec(&assembly.name);
+	let submodule = jsii_fqn
+		.strip_prefix(&format!("{}.", assembly.name))
+		.map(|submodule| submodule.to_string());
+	JsiiImporter::new(&jsii_spec, wing_types, jsii_types).deep_import_submodule_to_env(submodule);
+}
+
+fn import_namespace_members_on_demand(
+	mut ns: NamespaceRef,
+	nested_vec: &[&Symbol],
+	wing_types: &mut Types,
+	jsii_types: &TypeSystem,
+) {
+	for symbol in nested_vec {
+		if let Some(fqn) = ns.unimported_jsii_symbol(symbol) {
+			import_jsii_symbol(&fqn, wing_types, jsii_types);
+			if ns.env.lookup(symbol, None).is_none() {
+				ns.missing_jsii_symbols.insert(symbol.name.clone());
+			}
+		}
+		match ns.env.lookup(symbol, None) {
+			Some(SymbolKind::Namespace(next)) => ns = *next,
+			_ => return,
+		}
+	}
+}
+
+/// Imports the JSII type (or submodule) with the given fully qualified name. Returns whether anything was imported.
+fn import_jsii_symbol(fqn: &str, wing_types: &mut Types, jsii_types: &TypeSystem) -> bool {
+	let fqn = FQN::from(fqn);
+	let Some(assembly) = jsii_types.find_assembly(fqn.assembly()) else {
+		return false;
+	};
+	let jsii_spec = assembly_import_spec(&assembly.name);
+	let mut importer = JsiiImporter::new(&jsii_spec, wing_types, jsii_types);
+
+	let is_submodule = assembly
+		.submodules
+		.as_ref()
+		.map_or(false, |submodules| submodules.contains_key(fqn.as_str()));
+	if is_submodule {
+		// Nested submodules aren't set up when the assembly is brought, their types are imported when referenced
+		let fake_type = format!("{}.x", fqn);
+		importer.setup_namespaces_for(&FQN::from(fake_type.as_str()));
+		true
+	} else {
+		importer.import_type(&fqn)
+	}
+}
+
+/// An import spec for importing types of an assembly on demand, after the assembly was brought
+fn assembly_import_spec(assembly_name: &str) -> JsiiImportSpec {
+	JsiiImportSpec {
+		assembly_name: assembly_name.to_string(),
+		namespace_filter: vec![],
+		alias: Symbol::global(assembly_name),
+		import_statement_idx: 0,
+	}
+}
+
 pub struct JsiiImporter<'a> {
 	jsii_spec: &'a JsiiImportSpec,
 
@@ -73,8 +203,15 @@ pub struct JsiiImporter<'a> {
 	wing_types: &'a mut Types,
 }
 
+impl Drop for JsiiImporter<'_> {
+	fn drop(&mut self) {
+		self.wing_types.importing_jsii = false;
+	}
+}
+
 impl<'a> JsiiImporter<'a> {
 	pub fn new(jsii_spec: &'a JsiiImportSpec, wing_types: &'a mut Types, jsii_types: &'a TypeSystem) -> Self {
+		wing_types.importing_jsii = true;
 		Self {
 			jsii_spec,
 			wing_types,
@@ -90,12 +227,14 @@ impl<'a> JsiiImporter<'a> {
 				PrimitiveType::Boolean => self.wing_types.bool(),
 				PrimitiveType::Any => self.wing_types.anything(),
 				PrimitiveType::Json => self.wing_types.json(),
-				PrimitiveType::Date => self.wing_types.anything(), // TODO: https://github.com/winglang/wing/issues/2102
+				PrimitiveType::Date => self.wing_types.datetime(),
 			},
 			TypeReference::NamedTypeReference(named_ref) => {
 				let type_fqn = &named_ref.fqn;
 				if type_fqn == &format!("{}.{}", WINGSDK_ASSEMBLY_NAME, WINGSDK_DURATION) {
 					self.wing_types.duration()
+				} else if type_fqn == &format!("{}.{}", WINGSDK_ASSEMBLY_NAME, WINGSDK_DATETIME) {
+					self.wing_types.datetime()
 				} else if type_fqn == &format!("{}.{}", WINGSDK_ASSEMBLY_NAME, WINGSDK_JSON) {
 					self.wing_types.json()
 				} else if type_fqn == &format!("{}.{}", WINGSDK_ASSEMBLY_NAME, WINGSDK_MUT_JSON) {
@@ -118,18 +257,31 @@ impl<'a> JsiiImporter<'a> {
 					// kind” is only either map or array.
 				}
 			}
-			TypeReference::UnionTypeReference(..) =>
-			// Wing does not support union types, so we'll model it the same way as if
-			// we saw an "any" in a JSII library
-			{
-				self.wing_types.anything()
+			TypeReference::UnionTypeReference(union_ref) => {
+				let types = union_ref
+					.union
+					.types
+					.iter()
+					.map(|t| self.type_ref_to_wing_type(t))
+					.collect::<Vec<_>>();
+				// Libraries use `Json | MutJson` for values which can be any JSON value (e.g. `Json.stringify`), these are
+				// imported as `any` so they keep accepting optional and structured values
+				if types.iter().all(|t| t.is_json()) {
+					self.wing_types.anything()
+				} else {
+					self.wing_types.make_union(types)
+				}
 			}
 		}
 	}
 
 	fn lookup_or_create_type(&mut self, type_fqn: &FQN) -> TypeRef {
 		// Check if this type is already imported
-		if let LookupResult::Found(t, _) = self.wing_types.libraries.lookup_nested_str(type_fqn.as_str(), None) {
+		if let LookupResult::Found(t, _) = self
+			.wing_types
+			.libraries
+			.lookup_nested_str_if_imported(type_fqn.as_str(), None)
+		{
 			return t.as_type().expect(&format!("Expected {} to be a type", type_fqn));
 		}
 		// Define new type and return it
@@ -137,7 +289,7 @@ impl<'a> JsiiImporter<'a> {
 		self
 			.wing_types
 			.libraries
-			.lookup_nested_str(type_fqn.as_str(), None)
+			.lookup_nested_str_if_imported(type_fqn.as_str(), None)
 			.expect(&format!("Expected {} to be defined", type_fqn))
 			.0
 			.as_type()
@@ -148,7 +300,7 @@ impl<'a> JsiiImporter<'a> {
 		let type_str = type_fqn.as_str();
 
 		// check if type is already imported
-		if let LookupResult::Found(sym, ..) = self.wing_types.libraries.lookup_nested_str(type_str, None) {
+		if let LookupResult::Found(sym, ..) = self.wing_types.libraries.lookup_nested_str_if_imported(type_str, None) {
 			if let SymbolKind::Namespace(n) = sym {
 				// We are trying to import a namespace directly, so let's eagerly load all of its types
 				self.deep_import_submodule_to_env(Some(n.name.clone()));
@@ -202,6 +354,8 @@ impl<'a> JsiiImporter<'a> {
 				name: type_name.assembly().to_string(),
 				env: SymbolEnv::new(None, self.wing_types.void(), false, false, Phase::Preflight, 0),
 				loaded: false,
+				jsii_fqn: Some(type_name.assembly().to_string()),
+				missing_jsii_symbols: HashSet::new(),
 			});
 			self
 				.wing_types
@@ -244,6 +398,8 @@ impl<'a> JsiiImporter<'a> {
 					name: namespace_name.to_string(),
 					env: SymbolEnv::new(None, self.wing_types.void(), false, false, Phase::Preflight, 0),
 					loaded: false,
+					jsii_fqn: Some(format!("{}.{}", lookup_str, namespace_name)),
+					missing_jsii_symbols: HashSet::new(),
 				});
 				parent_ns
 					.env
@@ -334,6 +490,8 @@ impl<'a> JsiiImporter<'a> {
 					Phase::Independent, // structs are phase-independent
 					self.jsii_spec.import_statement_idx,
 				), // Dummy env, will be replaced below
+				type_parameters: None,
+				generic_instance: None,
 			})),
 			false => self.wing_types.add_type(Type::Interface(Interface {
 				name: new_type_symbol.clone(),
@@ -347,6 +505,8 @@ impl<'a> JsiiImporter<'a> {
 					iface_env.phase,
 					self.jsii_spec.import_statement_idx,
 				), // Dummy env, will be replaced below
+				type_parameters: None,
+				generic_instance: None,
 			})),
 		};
 
@@ -450,27 +610,19 @@ impl<'a> JsiiImporter<'a> {
 
 				// Define the rest of the arguments and create the method signature
 				if let Some(params) = &m.parameters {
-					if self.has_variadic_parameters(params) {
-						// TODO: support variadic parameters
-						// or TODO: emit compiler warning https://github.com/winglang/wing/issues/1475
-						debug!(
-							"Skipping method {} with variadic parameters (see https://github.com/winglang/wing/issues/397)",
-							m.name
-						);
-						continue;
-					}
-
 					for param in params {
 						fn_params.push(FunctionParameter {
 							name: param.name.clone(),
 							typeref: self.parameter_to_wing_type(&param),
 							docs: Docs::from(&param.docs),
+							variadic: param.variadic.unwrap_or(false),
 						});
 					}
 				}
 				let this_type = if is_static { None } else { Some(wing_type) };
 				let method_sig = self.wing_types.add_type(Type::Function(FunctionSignature {
 					docs: Docs::from(&m.docs),
+					type_parameters: vec![],
 					this_type,
 					parameters: fn_params,
 					return_type,
@@ -482,6 +634,11 @@ impl<'a> JsiiImporter<'a> {
 						.flatten(),
 				}));
 				let sym = Self::jsii_name_to_symbol(&m.name, &m.location_in_module);
+				let access_modifier = if let Some(true) = m.protected {
+					AccessModifier::Protected
+				} else {
+					AccessModifier::Public
+				};
 				class_env
 					.define(
 						&sym,
@@ -491,6 +648,7 @@ impl<'a> JsiiImporter<'a> {
 							false,
 							is_static,
 							member_phase,
+							access_modifier,
 							Some(Docs::from(&m.docs)),
 						),
 						StatementIdx::Top,
@@ -520,6 +678,11 @@ impl<'a> JsiiImporter<'a> {
 				};
 
 				let sym = Self::jsii_name_to_symbol(&p.name, &p.location_in_module);
+				let access_modifier = if let Some(true) = p.protected {
+					AccessModifier::Protected
+				} else {
+					AccessModifier::Public
+				};
 				class_env
 					.define(
 						&sym,
@@ -529,6 +692,7 @@ impl<'a> JsiiImporter<'a> {
 							!matches!(p.immutable, Some(true)),
 							is_static,
 							member_phase,
+							access_modifier,
 							Some(Docs::from(&p.docs)),
 						),
 						StatementIdx::Top,
@@ -581,7 +745,7 @@ impl<'a> JsiiImporter<'a> {
 			let base_class_type = if let LookupResult::Found(base_class_type, _) = self
 				.wing_types
 				.libraries
-				.lookup_nested_str(base_class_fqn.as_str(), None)
+				.lookup_nested_str_if_imported(base_class_fqn.as_str(), None)
 			{
 				base_class_type
 					.as_type()
@@ -592,7 +756,7 @@ impl<'a> JsiiImporter<'a> {
 				self
 					.wing_types
 					.libraries
-					.lookup_nested_str(&base_class_fqn.as_str(), None)
+					.lookup_nested_str_if_imported(&base_class_fqn.as_str(), None)
 					.expect(&format!(
 						"Failed to define base class {} of {}",
 						base_class_name, type_name
@@ -674,6 +838,7 @@ impl<'a> JsiiImporter<'a> {
 			implements,
 			is_abstract: jsii_class.abstract_.unwrap_or(false),
 			type_parameters: type_params,
+			generic_instance: None,
 			phase: class_phase,
 			docs: Docs::from(&jsii_class.docs),
 			std_construct_args: false, // Temporary value, will be updated once we parse the initializer args
@@ -724,10 +889,12 @@ impl<'a> JsiiImporter<'a> {
 						name: param.name.clone(),
 						typeref: self.parameter_to_wing_type(&param),
 						docs: Docs::from(&param.docs),
+						variadic: param.variadic.unwrap_or(false),
 					});
 				}
 			}
 			let method_sig = self.wing_types.add_type(Type::Function(FunctionSignature {
+				type_parameters: vec![],
 				this_type: None, // Initializers are considered static so they have no `this_type`
 				parameters: fn_params,
 				return_type: new_type,
@@ -744,6 +911,7 @@ impl<'a> JsiiImporter<'a> {
 					false,
 					true,
 					member_phase,
+					AccessModifier::Public,
 					Some(Docs::from(&initializer.docs)),
 				),
 				StatementIdx::Top,
@@ -802,17 +970,12 @@ impl<'a> JsiiImporter<'a> {
 		}
 	}
 
-	fn has_variadic_parameters(&self, parameters: &Vec<jsii::Parameter>) -> bool {
-		parameters.iter().any(|p| p.variadic.unwrap_or(false))
-	}
-
 	fn parameter_to_wing_type(&mut self, parameter: &jsii::Parameter) -> TypeRef {
 		let mut param_type = self.type_ref_to_wing_type(&parameter.type_);
 
-		// TODO variadic parameter support https://github.com/winglang/wing/issues/397
+		// A variadic parameter collects the remaining arguments of the call into an array of its type
 		if parameter.variadic.unwrap_or(false) {
-			param_type = self.wing_types.add_type(Type::Array(param_type));
-			param_type = self.wing_types.add_type(Type::Optional(param_type));
+			return self.wing_types.add_type(Type::Array(param_type));
 		}
 
 		if parameter.optional.unwrap_or(false) {
@@ -925,6 +1088,8 @@ impl<'a> JsiiImporter<'a> {
 					name: assembly.name.clone(),
 					env: SymbolEnv::new(None, self.wing_types.void(), false, false, Phase::Preflight, 0),
 					loaded: false,
+					jsii_fqn: Some(assembly.name.clone()),
+					missing_jsii_symbols: HashSet::new(),
 				});
 				self
 					.wing_types
@@ -972,7 +1137,10 @@ impl<'a> JsiiImporter<'a> {
 	fn register_jsii_type(&mut self, fqn: &FQN, symbol: &Symbol, type_ref: TypeRef) {
 		// make this function idempotent
 		if matches!(
-			self.wing_types.libraries.lookup_nested_str(fqn.as_str(), None),
+			self
+				.wing_types
+				.libraries
+				.lookup_nested_str_if_imported(fqn.as_str(), None),
 			LookupResult::Found(..)
 		) {
 			return;
@@ -1018,9 +1186,7 @@ pub fn is_construct_base(fqn: &str) -> bool {
 
 impl From<&Option<jsii::Docs>> for Docs {
 	fn from(value: &Option<jsii::Docs>) -> Self {
-		let Some(docs) = value else {
-			return Docs::default()
-		};
+		let Some(docs) = value else { return Docs::default() };
 
 		let docs = docs.clone();
 
@@ -1048,3 +1214,120 @@ fn test_fqn_is_construct_base() {
 	);
 	assert_eq!(is_construct_base("@winglang/sdk.cloud.Bucket"), false);
 }
+
+#[test]
+fn test_import_types_on_demand() {
+	let jsii_type = |kind: &str, name: &str, extra: serde_json::Value| {
+		let mut jsii_type = serde_json::json!({
+			"kind": kind,
+			"assembly": "lib",
+			"fqn": format!("lib.shapes.{}", name),
+			"name": name,
+			"namespace": "shapes",
+		});
+		jsii_type
+			.as_object_mut()
+			.unwrap()
+			.extend(extra.as_object().unwrap().clone());
+		jsii_type
+	};
+	let assembly = serde_json::json!({
+		"schema": "jsii/0.10.0",
+		"name": "lib",
+		"version": "1.0.0",
+		"author": { "name": "author", "roles": ["author"] },
+		"description": "lib",
+		"fingerprint": "",
+		"homepage": "",
+		"jsiiVersion": "1.0.0",
+		"license": "MIT",
+		"repository": { "type": "git", "url": "" },
+		"submodules": { "lib.shapes": {} },
+		"types": {
+			"lib.shapes.Color": jsii_type("enum", "Color", serde_json::json!({ "members": [{ "name": "RED" }] })),
+			"lib.shapes.Options": jsii_type("interface", "Options", serde_json::json!({
+				"datatype": true,
+				"properties": [{ "name": "color", "immutable": true, "type": { "fqn": "lib.shapes.Color" } }],
+			})),
+			"lib.shapes.Size": jsii_type("enum", "Size", serde_json::json!({ "members": [{ "name": "SMALL" }] })),
+		},
+	});
+	let dir = tempfile::tempdir().expect("Failed to create temporary directory");
+	std::fs::write(
+		dir.path().join("package.json"),
+		r#"{ "name": "lib", "version": "1.0.0", "jsii": {} }"#,
+	)
+	.unwrap();
+	std::fs::write(dir.path().join(wingii::spec::SPEC_FILE_NAME), assembly.to_string()).unwrap();
+	let mut jsii_types = TypeSystem::new();
+	let assembly_name = jsii_types.load_module(dir.path().to_str().unwrap()).unwrap();
+
+	// bringing the assembly only sets up its namespaces
+	let mut wing_types = Types::new();
+	let mut env = SymbolEnv::new(None, wing_types.void(), false, false, Phase::Preflight, 0);
+	let spec = JsiiImportSpec {
+		assembly_name,
+		namespace_filter: vec![],
+		alias: Symbol::global("lib"),
+		import_statement_idx: 0,
+	};
+	let mut importer = JsiiImporter::new(&spec, &mut wing_types, &jsii_types);
+	importer.import_root_types();
+	importer.import_submodules_to_env(&mut env);
+	drop(importer);
+	let is_imported = |wing_types: &Types, name: &str| {
+		let shapes = wing_types.libraries.lookup_nested_str("lib.shapes", None).unwrap().0;
+		shapes.as_namespace().unwrap().env.lookup(&name.into(), None).is_some()
+	};
+	assert!(!is_imported(&wing_types, "Options"));
+	assert!(matches!(
+		env.lookup_nested_str_if_imported("lib.shapes.Options", None),
+		LookupResult::NotFound(_)
+	));
+
+	// looking up a type imports it along with the types it references
+	let nested_vec = ["lib", "shapes", "Options"].map(Symbol::global);
+	let options = lookup_nested_importing(
+		&env,
+		&nested_vec.iter().collect::<Vec<_>>(),
+		None,
+		&mut wing_types,
+		&jsii_types,
+	)
+	.unwrap()
+	.0
+	.as_type()
+	.unwrap();
+	assert!(options.as_struct().is_some());
+	assert!(is_imported(&wing_types, "Options"));
+	assert!(is_imported(&wing_types, "Color"));
+	assert!(!is_imported(&wing_types, "Size"));
+
+	// symbols which aren't in the assembly are not found (and can then be looked up without importing)
+	let nested_vec = ["lib", "shapes", "Missing"].map(Symbol::global);
+	assert!(matches!(
+		lookup_nested_importing(
+			&env,
+			&nested_vec.iter().collect::<Vec<_>>(),
+			None,
+			&mut wing_types,
+			&jsii_types,
+		),
+		LookupResult::NotFound(_)
+	));
+	assert!(matches!(
+		env.lookup_nested_str("lib.shapes.Missing", None),
+		LookupResult::NotFound(_)
+	));
+
+	// importing a namespace imports all of its types
+	let shapes = env
+		.lookup_nested_str("lib.shapes", None)
+		.unwrap()
+		.0
+		.as_namespace_ref()
+		.unwrap();
+	import_namespace_on_demand(shapes, &mut wing_types, &jsii_types);
+	assert!(is_imported(&wing_types, "Size"));
+	assert!(shapes.loaded);
+}
@@ -3,8 +3,8 @@ use derivative::Derivative;
 use duplicate::duplicate_item;
 
 use crate::{
-	ast::{Phase, Symbol},
-	diagnostic::TypeError,
+	ast::{AccessModifier, Phase, Symbol},
+	diagnostic::{DiagnosticAnnotation, DiagnosticCode, TypeError},
 	type_check::{SymbolKind, Type, TypeRef},
 };
 use std::fmt::Debug;
@@ -30,6 +30,11 @@ pub struct SymbolEnv {
 	// Whether this scope is inside of a function
 	pub is_function: bool,
 	pub phase: Phase,
+	// The class this environment is a method of (used for checking access to private and protected members)
+	pub enclosing_class: Option<TypeRef>,
+	// Variables of a parent environment whose type is known to be more specific within this environment
+	// (e.g. `x` is known to be a `str` inside `if x is str { ... }`)
+	narrowed_types: BTreeMap<String, TypeRef>,
 	statement_idx: usize,
 }
 
@@ -171,10 +176,32 @@ impl SymbolEnv {
 			is_init,
 			is_function,
 			phase,
+			enclosing_class: None,
+			narrowed_types: BTreeMap::new(),
 			statement_idx,
 		}
 	}
 
+	/// Marks a variable defined in a parent environment as having a more specific type within this environment.
+	pub fn narrow(&mut self, name: &str, type_: TypeRef) {
+		self.narrowed_types.insert(name.to_string(), type_);
+	}
+
+	/// Returns the narrowed type of a variable (see `narrow`), unless the variable is shadowed by a definition
+	/// between this environment and the one the type was narrowed in.
+	pub fn lookup_narrowed_type(&self, name: &str) -> Option<TypeRef> {
+		let mut env = self;
+		loop {
+			if env.symbol_map.contains_key(name) {
+				return None;
+			}
+			if let Some(type_) = env.narrowed_types.get(name) {
+				return Some(*type_);
+			}
+			env = env.parent.as_ref()?;
+		}
+	}
+
 	/// Used to get an unsafe reference to this symbol environment so it be referenced by
 	/// other types or environments (e.g. as a parent class or parent scope)
 	pub fn get_ref(&self) -> SymbolEnvRef {
@@ -210,11 +237,49 @@ impl SymbolEnv {
 		std::ptr::eq(other, self)
 	}
 
+	/// Checks if code in this environment can access a class member with the given access modifier.
+	/// `member_env` is the environment of the class in which the member is defined.
+	/// Private members are accessible from within the methods of the defining class (including closures
+	/// defined inside them), protected members are also accessible from within the methods of its subclasses.
+	pub fn can_access_member(&self, access_modifier: AccessModifier, member_env: &SymbolEnv) -> bool {
+		if access_modifier == AccessModifier::Public {
+			return true;
+		}
+
+		let mut env = self;
+		loop {
+			if let Some(class_type) = env.enclosing_class {
+				if let Some(class) = class_type.as_class() {
+					if class.env.is_same(member_env)
+						|| (access_modifier == AccessModifier::Protected && class.env.is_child_of(member_env))
+					{
+						return true;
+					}
+				}
+			}
+
+			if let Some(parent) = &env.parent {
+				env = parent;
+			} else {
+				return false;
+			}
+		}
+	}
+
 	pub fn define(&mut self, symbol: &Symbol, kind: SymbolKind, pos: StatementIdx) -> Result<(), TypeError> {
-		if self.symbol_map.contains_key(&symbol.name) {
+		if let Some((_, existing)) = self.symbol_map.get(&symbol.name) {
 			return Err(TypeError {
 				span: symbol.span.clone(),
 				message: format!("Symbol \"{}\" already defined in this scope", symbol.name),
+				code: DiagnosticCode::DuplicateSymbol,
+				annotations: existing
+					.definition_span()
+					.map(|span| DiagnosticAnnotation {
+						message: format!("\"{}\" is first defined here", symbol.name),
+						span,
+					})
+					.into_iter()
+					.collect(),
 			});
 		}
 
@@ -287,12 +352,22 @@ impl SymbolEnv {
 
 	#[allow(clippy::needless_arbitrary_self_type)]
 	#[duplicate_item(
-		lookup_nested LookupResult lookup_ext as_namespace reference(type) SymbolLookupInfo;
-		[lookup_nested] [LookupResult] [lookup_ext] [as_namespace] [& type] [SymbolLookupInfo];
-		[lookup_nested_mut] [LookupResultMut] [lookup_ext_mut] [as_namespace_mut] [&mut type] [SymbolLookupInfoMut];
+		lookup_nested LookupResult lookup_ext as_namespace reference(type) SymbolLookupInfo assert_imported(ns, symb);
+		[lookup_nested] [LookupResult] [lookup_ext] [as_namespace] [& type] [SymbolLookupInfo] [
+			debug_assert!(
+				ns.unimported_jsii_symbol(symb).is_none(),
+				"\"{}\" wasn't imported from JSII yet, look it up with `lookup_nested_importing`",
+				symb.name
+			)
+		];
+		[lookup_nested_if_imported] [LookupResult] [lookup_ext] [as_namespace] [& type] [SymbolLookupInfo] [()];
+		[lookup_nested_mut] [LookupResultMut] [lookup_ext_mut] [as_namespace_mut] [&mut type] [SymbolLookupInfoMut] [()];
 	)]
 	/// Lookup a symbol in the environment, returning a `LookupResult`. The symbol name may be a
-	/// nested symbol (e.g. `foo.bar`) if `nested_vec` is larger than 1.
+	/// nested symbol (e.g. `foo.bar`) if `nested_vec` is larger than 1. Types of JSII namespaces
+	/// are only found if they were imported, so symbols which may be in a JSII namespace should be
+	/// looked up with `jsii_importer::lookup_nested_importing` (this is asserted in debug builds).
+	/// `lookup_nested_if_imported` doesn't assert it, it's only meant for the importer to check what was imported.
 	pub fn lookup_nested(self: reference([Self]), nested_vec: &[&Symbol], statement_idx: Option<usize>) -> LookupResult {
 		let mut it = nested_vec.iter();
 
@@ -321,6 +396,7 @@ impl SymbolEnv {
 			} else {
 				return LookupResult::ExpectedNamespace(prev_symb.clone());
 			};
+			assert_imported([ns], [next_symb]);
 
 			let lookup_result = ns.env.lookup_ext(next_symb, statement_idx);
 			prev_symb = *next_symb;
@@ -339,6 +415,7 @@ impl SymbolEnv {
 	#[duplicate_item(
 		lookup_nested_str LookupResult lookup_nested reference(type);
 		[lookup_nested_str] [LookupResult] [lookup_nested] [& type];
+		[lookup_nested_str_if_imported] [LookupResult] [lookup_nested_if_imported] [& type];
 		[lookup_nested_str_mut] [LookupResultMut] [lookup_nested_mut] [&mut type];
 	)]
 	/// Lookup a symbol in the environment, returning a `LookupResult`. The symbol name may be a
@@ -421,6 +498,7 @@ mod tests {
 		ast::{Phase, Symbol},
 		type_check::{symbol_env::LookupResult, Namespace, SymbolKind, Types},
 	};
+	use std::collections::HashSet;
 
 	use super::{StatementIdx, SymbolEnv};
 
@@ -566,6 +644,8 @@ mod tests {
 			name: "ns1".to_string(),
 			env: SymbolEnv::new(None, types.void(), false, false, Phase::Independent, 0),
 			loaded: false,
+			jsii_fqn: None,
+			missing_jsii_symbols: HashSet::new(),
 		});
 		let ns2 = types.add_namespace(Namespace {
 			name: "ns2".to_string(),
@@ -578,6 +658,8 @@ mod tests {
 				0,
 			),
 			loaded: false,
+			jsii_fqn: None,
+			missing_jsii_symbols: HashSet::new(),
 		});
 
 		// Define ns2 in n1's env
@@ -2,7 +2,7 @@ use crate::{
 	ast::{
 		ArgList, Class, Expr, ExprKind, FunctionBody, FunctionDefinition, FunctionParameter, FunctionSignature, Interface,
 		InterpolatedStringPart, Literal, NewExpr, Reference, Scope, Stmt, StmtKind, Symbol, TypeAnnotation,
-		TypeAnnotationKind, UserDefinedType,
+		TypeAnnotationKind, TypeParameter, UserDefinedType,
 	},
 	dbg_panic,
 };
@@ -75,6 +75,9 @@ pub trait Visit<'ast> {
 	fn visit_type_annotation(&mut self, node: &'ast TypeAnnotation) {
 		visit_type_annotation(self, node)
 	}
+	fn visit_type_parameter(&mut self, node: &'ast TypeParameter) {
+		visit_type_parameter(self, node)
+	}
 	fn visit_symbol(&mut self, node: &'ast Symbol) {
 		visit_symbol(self, node);
 	}
@@ -159,6 +162,22 @@ where
 				v.visit_scope(statements);
 			}
 		}
+		StmtKind::Match {
+			value,
+			arms,
+			else_statements,
+		} => {
+			v.visit_expr(value);
+			for arm in arms {
+				for pattern in &arm.patterns {
+					v.visit_expr(pattern);
+				}
+				v.visit_scope(&arm.statements);
+			}
+			if let Some(statements) = else_statements {
+				v.visit_scope(statements);
+			}
+		}
 		StmtKind::Expression(expr) => {
 			v.visit_expr(&expr);
 		}
@@ -182,10 +201,14 @@ where
 		}
 		StmtKind::Struct {
 			name,
+			type_parameters,
 			extends,
 			fields: members,
 		} => {
 			v.visit_symbol(name);
+			for type_parameter in type_parameters {
+				v.visit_type_parameter(type_parameter);
+			}
 			for extend in extends {
 				v.visit_user_defined_type(extend);
 			}
@@ -225,6 +248,9 @@ where
 	V: Visit<'ast> + ?Sized,
 {
 	v.visit_symbol(&node.name);
+	for type_parameter in &node.type_parameters {
+		v.visit_type_parameter(type_parameter);
+	}
 
 	v.visit_function_definition(&node.initializer);
 	v.visit_function_definition(&node.inflight_initializer);
@@ -232,6 +258,9 @@ where
 	for field in &node.fields {
 		v.visit_symbol(&field.name);
 		v.visit_type_annotation(&field.member_type);
+		if let Some(initializer) = &field.initializer {
+			v.visit_expr(initializer);
+		}
 	}
 
 	for method in &node.methods {
@@ -253,6 +282,14 @@ where
 	V: Visit<'ast> + ?Sized,
 {
 	v.visit_symbol(&node.name);
+	for type_parameter in &node.type_parameters {
+		v.visit_type_parameter(type_parameter);
+	}
+
+	for field in &node.fields {
+		v.visit_symbol(&field.name);
+		v.visit_type_annotation(&field.member_type);
+	}
 
 	for method in &node.methods {
 		v.visit_symbol(&method.0);
@@ -353,6 +390,29 @@ where
 		ExprKind::FunctionClosure(def) => {
 			v.visit_function_definition(def);
 		}
+		ExprKind::Await(exp) | ExprKind::Defer(exp) => {
+			v.visit_expr(exp);
+		}
+		ExprKind::TypeTest { expr, type_ } => {
+			v.visit_expr(expr);
+			v.visit_type_annotation(type_);
+		}
+		ExprKind::Match {
+			value,
+			arms,
+			else_value,
+		} => {
+			v.visit_expr(value);
+			for arm in arms {
+				for pattern in &arm.patterns {
+					v.visit_expr(pattern);
+				}
+				v.visit_expr(&arm.value);
+			}
+			if let Some(else_value) = else_value {
+				v.visit_expr(else_value);
+			}
+		}
 		ExprKind::CompilerDebugPanic => {
 			// Handle the debug panic expression (during visiting)
 			dbg_panic!();
@@ -402,6 +462,10 @@ where
 			v.visit_expr(typeobject);
 			v.visit_symbol(property);
 		}
+		Reference::ElementAccess { object, index } => {
+			v.visit_expr(object);
+			v.visit_expr(index);
+		}
 	}
 }
 
@@ -419,6 +483,10 @@ pub fn visit_function_signature<'ast, V>(v: &mut V, node: &'ast FunctionSignatur
 where
 	V: Visit<'ast> + ?Sized,
 {
+	for type_parameter in &node.type_parameters {
+		v.visit_type_parameter(type_parameter);
+	}
+
 	for param in &node.parameters {
 		v.visit_function_parameter(param);
 	}
@@ -456,6 +524,7 @@ where
 		TypeAnnotationKind::String => {}
 		TypeAnnotationKind::Bool => {}
 		TypeAnnotationKind::Duration => {}
+		TypeAnnotationKind::Datetime => {}
 		TypeAnnotationKind::Void => {}
 		TypeAnnotationKind::Json => {}
 		TypeAnnotationKind::MutJson => {}
@@ -466,7 +535,16 @@ where
 		TypeAnnotationKind::MutMap(t) => v.visit_type_annotation(t),
 		TypeAnnotationKind::Set(t) => v.visit_type_annotation(t),
 		TypeAnnotationKind::MutSet(t) => v.visit_type_annotation(t),
+		TypeAnnotationKind::Promise(t) => v.visit_type_annotation(t),
+		TypeAnnotationKind::Union(types) => {
+			for t in types {
+				v.visit_type_annotation(t);
+			}
+		}
 		TypeAnnotationKind::Function(f) => {
+			for type_parameter in &f.type_parameters {
+				v.visit_type_parameter(type_parameter);
+			}
 			for param in &f.parameters {
 				v.visit_symbol(&param.name);
 				v.visit_type_annotation(&param.type_annotation);
@@ -478,6 +556,9 @@ where
 			for field in &t.fields {
 				v.visit_symbol(field);
 			}
+			for type_argument in &t.type_arguments {
+				v.visit_type_annotation(type_argument);
+			}
 		}
 	}
 }
@@ -490,6 +571,19 @@ where
 	for field in &node.fields {
 		v.visit_symbol(field);
 	}
+	for type_argument in &node.type_arguments {
+		v.visit_type_annotation(type_argument);
+	}
+}
+
+pub fn visit_type_parameter<'ast, V>(v: &mut V, node: &'ast TypeParameter)
+where
+	V: Visit<'ast> + ?Sized,
+{
+	v.visit_symbol(&node.name);
+	if let Some(bound) = &node.bound {
+		v.visit_user_defined_type(bound);
+	}
 }
 
 pub fn visit_symbol<'ast, V>(_v: &mut V, _node: &'ast Symbol)
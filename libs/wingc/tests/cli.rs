@@ -0,0 +1,106 @@
+#![cfg(feature = "cli")]
+
+use std::process::{Command, Output};
+
+fn wingc(args: &[&str]) -> Output {
+	Command::new(env!("CARGO_BIN_EXE_wingc"))
+		.args(args)
+		.current_dir(env!("CARGO_MANIFEST_DIR"))
+		.output()
+		.expect("Failed to run wingc")
+}
+
+#[test]
+fn invalid_args() {
+	let output = wingc(&["build"]);
+	assert_eq!(output.status.code(), Some(2));
+	assert!(String::from_utf8_lossy(&output.stderr).contains("unknown command \"build\""));
+}
+
+#[test]
+fn missing_source_file() {
+	let output = wingc(&["check", "does_not_exist.w"]);
+	assert_eq!(output.status.code(), Some(1));
+	assert!(String::from_utf8_lossy(&output.stderr).contains("Source file cannot be found"));
+}
+
+#[test]
+fn check_valid_file() {
+	let output = wingc(&["check", "../../examples/tests/valid/print.w"]);
+	assert_eq!(
+		output.status.code(),
+		Some(0),
+		"{}",
+		String::from_utf8_lossy(&output.stderr)
+	);
+}
+
+#[test]
+fn check_invalid_file() {
+	let output = wingc(&["check", "../../examples/tests/invalid/access_modifiers.w"]);
+	assert_eq!(output.status.code(), Some(1));
+	assert!(String::from_utf8_lossy(&output.stderr).contains("Cannot access private member"));
+}
+
+#[test]
+fn compile_to_out_dir() {
+	let out_dir = tempfile::tempdir().unwrap();
+	let output = wingc(&[
+		"compile",
+		"../../examples/tests/valid/print.w",
+		"--out-dir",
+		out_dir.path().to_str().unwrap(),
+	]);
+	assert_eq!(
+		output.status.code(),
+		Some(0),
+		"{}",
+		String::from_utf8_lossy(&output.stderr)
+	);
+	assert!(out_dir.path().join("preflight.js").exists());
+}
+
+#[test]
+fn dump_ast() {
+	let output = wingc(&["parse", "--dump-ast", "../../examples/tests/valid/print.w"]);
+	assert_eq!(
+		output.status.code(),
+		Some(0),
+		"{}",
+		String::from_utf8_lossy(&output.stderr)
+	);
+	assert!(String::from_utf8_lossy(&output.stdout).contains("statements"));
+}
+
+#[test]
+fn json_error_format() {
+	let output = wingc(&[
+		"check",
+		"--error-format=json",
+		"../../examples/tests/invalid/access_modifiers.w",
+	]);
+	assert_eq!(output.status.code(), Some(1));
+
+	let stderr = String::from_utf8_lossy(&output.stderr);
+	let diagnostics = stderr
+		.lines()
+		.map(|line| serde_json::from_str::<serde_json::Value>(line).expect("Expected a JSON diagnostic"))
+		.collect::<Vec<_>>();
+	let diagnostic = diagnostics
+		.iter()
+		.find(|d| d["code"] == "E2005")
+		.expect("Expected an inaccessible member diagnostic");
+	assert_eq!(diagnostic["severity"], "error");
+	assert!(diagnostic["span"]["file_id"].is_string());
+	assert!(diagnostic["annotations"][0]["span"].is_object());
+}
+
+#[test]
+fn explain_code() {
+	let output = wingc(&["explain", "E2005"]);
+	assert_eq!(output.status.code(), Some(0));
+	assert!(String::from_utf8_lossy(&output.stdout).starts_with("E2005: "));
+
+	let output = wingc(&["explain", "E9999"]);
+	assert_eq!(output.status.code(), Some(2));
+}
@@ -38,7 +38,21 @@ export interface CompileOptions {
    * copies of the application resources in order to run tests in parallel.
    */
   readonly testing?: boolean;
+  /**
+   * Whether to report compiler warnings as errors.
+   */
+  readonly warningsAsErrors?: boolean;
+  /**
+   * Whether to compile the entrypoint as a library instead of an app: a module exporting its public
+   * types is emitted along with a JSII assembly (`.jsii`) describing them, and nothing is synthesized.
+   */
+  readonly library?: boolean;
   readonly log?: (...args: any[]) => void;
+  /**
+   * Called with the compiler warnings (if any) when compilation succeeds.
+   * When compilation fails, warnings are included in the thrown `CompileError`.
+   */
+  readonly onWarnings?: (warnings: wingCompiler.WingDiagnostic[]) => void;
 }
 
 /**
@@ -87,7 +101,8 @@ export async function compile(entrypoint: string, options: CompileOptions): Prom
   log?.("temp synth dir: %s", tmpSynthDir);
   const synthDir = resolveSynthDir(targetdir, wingFile, options.target, testing);
   log?.("synth dir: %s", synthDir);
-  const workDir = resolve(tmpSynthDir, ".wing");
+  // Libraries aren't synthesized, so their compiled files are the output of the compilation
+  const workDir = options.library ? resolve(targetdir, "lib") : resolve(tmpSynthDir, ".wing");
   log?.("work dir: %s", workDir);
 
   process.env["WING_SOURCE_DIR"] = resolve(wingDir);
@@ -110,7 +125,7 @@ export async function compile(entrypoint: string, options: CompileOptions): Prom
 
   await Promise.all([
     fs.mkdir(workDir, { recursive: true }),
-    fs.mkdir(tmpSynthDir, { recursive: true }),
+    ...(options.library ? [] : [fs.mkdir(tmpSynthDir, { recursive: true })]),
   ]);
 
   const wingc = await wingCompiler.load({
@@ -142,7 +157,14 @@ export async function compile(entrypoint: string, options: CompileOptions): Prom
     errors.push(JSON.parse(data_str));
   }
 
-  const arg = `${normalPath(wingFile)};${normalPath(workDir)};${normalPath(resolve(wingDir))}`;
+  let flags = "";
+  if (options.warningsAsErrors) {
+    flags += ";--warnings-as-errors";
+  }
+  if (options.library) {
+    flags += ";--library";
+  }
+  const arg = `${normalPath(wingFile)};${normalPath(workDir)};${normalPath(resolve(wingDir))}${flags}`;
   log?.(`invoking %s with: "%s"`, WINGC_COMPILE, arg);
   let compileSuccess: boolean;
   try {
@@ -157,6 +179,15 @@ export async function compile(entrypoint: string, options: CompileOptions): Prom
     throw new CompileError(errors);
   }
 
+  const warnings = errors.filter((diagnostic) => diagnostic.severity === "warning");
+  if (warnings.length > 0) {
+    options.onWarnings?.(warnings);
+  }
+
+  if (options.library) {
+    return workDir;
+  }
+
   const artifactPath = resolve(workDir, WINGC_PREFLIGHT);
   log?.("reading artifact from %s", artifactPath);
   const artifact = await fs.readFile(artifactPath, "utf-8");
@@ -9,12 +9,18 @@ export type WingCompilerFunction =
   | "wingc_compile"
   | "wingc_on_did_open_text_document"
   | "wingc_on_did_change_text_document"
+  | "wingc_on_diagnostics"
   | "wingc_on_completion"
   | "wingc_on_signature_help"
   | "wingc_on_goto_definition"
   | "wingc_on_document_symbol"
   | "wingc_on_semantic_tokens"
-  | "wingc_on_hover";
+  | "wingc_on_hover"
+  | "wingc_on_references"
+  | "wingc_on_prepare_rename"
+  | "wingc_on_rename"
+  | "wingc_on_formatting"
+  | "wingc_on_range_formatting";
 
 export interface WingCompilerLoadOptions {
   /**
@@ -208,19 +214,35 @@ const LOW_MASK = 2n ** 32n - 1n;
 const HIGH_MASK = BigInt(32);
 
 // From diagnostic.rs
+export interface WingSpan {
+  start: {
+    line: number;
+    col: number;
+  };
+  end: {
+    line: number;
+    col: number;
+  };
+  file_id: string;
+}
+
 export interface WingDiagnostic {
   message: string;
-  span?: {
-    start: {
-      line: number;
-      col: number;
-    };
-    end: {
-      line: number;
-      col: number;
-    };
-    file_id: string;
-  };
+  severity: "error" | "warning" | "info" | "hint";
+  /** Stable identifier of the diagnostic (e.g. "E2003"), see `wingc explain <code>` */
+  code: string;
+  span?: WingSpan;
+  /** Secondary spans related to the diagnostic */
+  annotations: {
+    message: string;
+    span: WingSpan;
+  }[];
+  /** Suggested fixes, each replacing the text in `span` with `replacement` */
+  fixes: {
+    message: string;
+    span: WingSpan;
+    replacement: string;
+  }[];
 }
 
 /**
@@ -0,0 +1,165 @@
+use std::collections::hash_map::DefaultHasher;
+use std::env;
+use std::fs::{self, File};
+use std::hash::{Hash, Hasher};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::jsii::Assembly;
+use crate::spec;
+use crate::Result;
+
+/// Overrides the directory the cache files are stored in
+pub const CACHE_DIR_ENV: &str = "WING_JSII_CACHE_DIR";
+/// Disables the cache when set to anything other than an empty string, "0" or "false"
+pub const DISABLE_CACHE_ENV: &str = "WING_DISABLE_JSII_CACHE";
+
+/// Bump this whenever the layout of the cache files (or of `jsii::Assembly`) changes
+const CACHE_FORMAT_VERSION: u32 = 1;
+const CACHE_FILE_EXTENSION: &str = "jsii.cache";
+
+/// An on-disk cache of parsed JSII assemblies. Loading a large assembly (e.g. the Wing SDK) from its JSON `.jsii`
+/// file takes a noticeable part of each compilation, so the parsed assembly is stored in a compact binary encoding
+/// (MessagePack) which is much faster to decode.
+///
+/// Each cache file starts with a header recording the files the assembly was read from (the `.jsii` file and the
+/// file it redirects to, if any) along with their size and modification time. The entry is rebuilt whenever any of
+/// them changes, so the cache never needs to be cleared manually. Failing to read or write the cache is never an
+/// error, the assembly is loaded from its `.jsii` file instead.
+#[derive(Clone)]
+pub struct AssemblyCache {
+	dir: PathBuf,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct CacheHeader {
+	format_version: u32,
+	/// Version of the crate which wrote the entry, so entries are rebuilt when `jsii::Assembly` changes
+	wingii_version: String,
+	/// The files the assembly was read from, the first one is the `.jsii` file
+	sources: Vec<SourceStamp>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct SourceStamp {
+	path: String,
+	len: u64,
+	/// Modification time since the unix epoch
+	modified_secs: u64,
+	modified_nanos: u32,
+}
+
+impl SourceStamp {
+	fn of(path: &str) -> Option<Self> {
+		let metadata = fs::metadata(path).ok()?;
+		let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+		Some(Self {
+			path: path.to_string(),
+			len: metadata.len(),
+			modified_secs: modified.as_secs(),
+			modified_nanos: modified.subsec_nanos(),
+		})
+	}
+}
+
+impl AssemblyCache {
+	pub fn new(dir: impl Into<PathBuf>) -> Self {
+		Self { dir: dir.into() }
+	}
+
+	/// Returns the cache configured by the environment: it's stored in `$WING_JSII_CACHE_DIR` or in the user's cache
+	/// directory (`$XDG_CACHE_HOME/wing/jsii`, `%LOCALAPPDATA%\wing\jsii` or `~/.cache/wing/jsii`). Returns `None` if
+	/// the cache is disabled or there's no place to store it.
+	pub fn from_env() -> Option<Self> {
+		let non_empty_var = |name: &str| env::var_os(name).filter(|value| !value.is_empty());
+
+		if let Ok(disable) = env::var(DISABLE_CACHE_ENV) {
+			if !matches!(disable.as_str(), "" | "0" | "false") {
+				return None;
+			}
+		}
+
+		if let Some(dir) = non_empty_var(CACHE_DIR_ENV) {
+			return Some(Self::new(dir));
+		}
+		let user_cache_dir = non_empty_var("XDG_CACHE_HOME")
+			.map(PathBuf::from)
+			.or_else(|| non_empty_var("LOCALAPPDATA").map(PathBuf::from))
+			.or_else(|| non_empty_var("HOME").map(|home| Path::new(&home).join(".cache")))?;
+		Some(Self::new(user_cache_dir.join("wing").join("jsii")))
+	}
+
+	/// Loads the assembly in `assembly_file` (a `.jsii` file), from the cache if it's up to date. Otherwise the
+	/// assembly is parsed from the file and the cache entry is rebuilt.
+	pub fn load_assembly(&self, assembly_file: &str) -> Result<Assembly> {
+		let cache_file = self.cache_file(assembly_file);
+		if let Some(assembly) = self.read(&cache_file, assembly_file) {
+			return Ok(assembly);
+		}
+
+		let mut sources = vec![];
+		let assembly = spec::load_assembly_from_file_with_sources(assembly_file, None, &mut sources)?;
+		// The cache is best effort, the compilation shouldn't fail because the cache directory isn't writable
+		let _ = self.write(&cache_file, &sources, &assembly);
+		Ok(assembly)
+	}
+
+	/// Each assembly file is cached in its own file, named after the hash of the assembly's path
+	fn cache_file(&self, assembly_file: &str) -> PathBuf {
+		let mut hasher = DefaultHasher::new();
+		assembly_file.hash(&mut hasher);
+		self
+			.dir
+			.join(format!("{:016x}.{}", hasher.finish(), CACHE_FILE_EXTENSION))
+	}
+
+	/// Returns the cached assembly, or `None` if there's no entry for `assembly_file` or it's out of date
+	fn read(&self, cache_file: &Path, assembly_file: &str) -> Option<Assembly> {
+		let mut reader = BufReader::new(File::open(cache_file).ok()?);
+		let header: CacheHeader = rmp_serde::from_read(&mut reader).ok()?;
+
+		let is_up_to_date = header.format_version == CACHE_FORMAT_VERSION
+			&& header.wingii_version == env!("CARGO_PKG_VERSION")
+			&& header.sources.first().map(|s| s.path.as_str()) == Some(assembly_file)
+			&& header
+				.sources
+				.iter()
+				.all(|source| SourceStamp::of(&source.path).as_ref() == Some(source));
+		if !is_up_to_date {
+			return None;
+		}
+
+		rmp_serde::from_read(&mut reader).ok()
+	}
+
+	fn write(&self, cache_file: &Path, sources: &[String], assembly: &Assembly) -> Result<()> {
+		let header = CacheHeader {
+			format_version: CACHE_FORMAT_VERSION,
+			wingii_version: env!("CARGO_PKG_VERSION").to_string(),
+			sources: sources
+				.iter()
+				.map(|source| SourceStamp::of(source).ok_or(format!("Unable to stat {}", source)))
+				.collect::<std::result::Result<_, _>>()?,
+		};
+
+		// Write to a temporary file first, so concurrent compilations never read a partially written entry
+		fs::create_dir_all(&self.dir)?;
+		let nanos = SystemTime::now().duration_since(UNIX_EPOCH)?.subsec_nanos();
+		let temp_file = cache_file.with_extension(format!("{}.tmp", nanos));
+		let mut writer = BufWriter::new(File::create(&temp_file)?);
+		// Structs are encoded as maps since the JSII types skip serializing their empty fields
+		rmp_serde::encode::write_named(&mut writer, &header)?;
+		rmp_serde::encode::write_named(&mut writer, assembly)?;
+		writer.flush()?;
+		drop(writer);
+
+		if let Err(err) = fs::rename(&temp_file, cache_file) {
+			let _ = fs::remove_file(&temp_file);
+			return Err(err.into());
+		}
+		Ok(())
+	}
+}
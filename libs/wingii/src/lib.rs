@@ -11,6 +11,7 @@ extern crate serde_json;
 #[cfg(test)]
 mod test;
 
+pub mod cache;
 pub mod fqn;
 // this is public temporarily until reflection API is finalized
 pub mod jsii;
@@ -49,7 +50,18 @@ pub mod spec {
 	}
 
 	pub fn load_assembly_from_file(path_to_file: &str, compression: Option<&str>) -> Result<Assembly> {
+		load_assembly_from_file_with_sources(path_to_file, compression, &mut vec![])
+	}
+
+	/// Like `load_assembly_from_file`, also collecting the paths of the files the assembly was read from into
+	/// `sources` (the assembly file and the file it redirects to, if any)
+	pub fn load_assembly_from_file_with_sources(
+		path_to_file: &str,
+		compression: Option<&str>,
+		sources: &mut Vec<String>,
+	) -> Result<Assembly> {
 		let assembly_path = Path::new(path_to_file);
+		sources.push(path_to_file.to_string());
 
 		let manifest = if Some("gzip") == compression {
 			let assembly_path_gz = File::open(assembly_path)?;
@@ -70,9 +82,10 @@ pub mod spec {
 					.parent()
 					.expect("Assembly path has no parent")
 					.join(&asm_redirect.filename);
-				load_assembly_from_file(
+				load_assembly_from_file_with_sources(
 					path.to_str().expect("JSII redirect path invalid"),
 					Some(&asm_redirect.compression),
+					sources,
 				)
 			}
 		}
@@ -82,6 +95,7 @@ pub mod spec {
 pub mod type_system {
 	type AssemblyName = String;
 
+	use crate::cache::AssemblyCache;
 	use crate::fqn::FQN;
 	use crate::jsii;
 	use crate::jsii::Assembly;
@@ -92,6 +106,8 @@ pub mod type_system {
 
 	pub struct TypeSystem {
 		assemblies: HashMap<String, Assembly>,
+		/// Cache of parsed assemblies, `None` if caching is disabled
+		cache: Option<AssemblyCache>,
 	}
 
 	pub trait QueryableType {}
@@ -103,9 +119,15 @@ pub mod type_system {
 		pub fn new() -> TypeSystem {
 			TypeSystem {
 				assemblies: HashMap::new(),
+				cache: None,
 			}
 		}
 
+		/// Sets the cache of parsed assemblies (disabled by default), `None` disables it
+		pub fn set_cache(&mut self, cache: Option<AssemblyCache>) {
+			self.cache = cache;
+		}
+
 		pub fn includes_assembly(&self, name: &str) -> bool {
 			self.assemblies.contains_key(name)
 		}
@@ -144,7 +166,10 @@ pub mod type_system {
 		}
 
 		fn load_assembly(&self, path: &str) -> Result<Assembly> {
-			spec::load_assembly_from_file(path, None)
+			match &self.cache {
+				Some(cache) => cache.load_assembly(path),
+				None => spec::load_assembly_from_file(path, None),
+			}
 		}
 
 		fn add_assembly(&mut self, assembly: Assembly) -> Result<AssemblyName> {
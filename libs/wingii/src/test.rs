@@ -10,7 +10,7 @@ use std::path::PathBuf;
 mod tests {
 	use flate2::{write::GzEncoder, Compression};
 
-	use crate::{fqn::FQN, jsii::JsiiFile, type_system::TypeSystem};
+	use crate::{cache::AssemblyCache, fqn::FQN, jsii::JsiiFile, type_system::TypeSystem};
 
 	use super::*;
 	use std::{
@@ -101,14 +101,64 @@ mod tests {
 		let i_construct = type_system.find_class(&FQN::from("constructs.IConstruct"));
 		assert_eq!(i_construct, None);
 	}
+
+	#[test]
+	fn can_load_assembly_from_cache() {
+		let cache_dir = create_temp_dir("cache");
+		let cache = AssemblyCache::new(&cache_dir);
+		let assembly_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+			.join("src")
+			.join("fixtures")
+			.join("constructs")
+			.join(spec::SPEC_FILE_NAME);
+		let assembly_file = assembly_file.to_str().unwrap();
+		let expected = spec::load_assembly_from_file(assembly_file, None).unwrap();
+
+		// the first load populates the cache, the second one is decoded from it
+		assert_eq!(cache.load_assembly(assembly_file).unwrap(), expected);
+		assert_eq!(fs::read_dir(&cache_dir).unwrap().count(), 1);
+		assert_eq!(cache.load_assembly(assembly_file).unwrap(), expected);
+
+		// the assembly's types (tagged and untagged enums, skipped fields) survive the binary encoding
+		let mut type_system = TypeSystem::new();
+		type_system.set_cache(Some(cache));
+		let name = type_system
+			.load_module(PathBuf::from(assembly_file).parent().unwrap().to_str().unwrap())
+			.unwrap();
+		assert!(type_system.find_class(&FQN::from("constructs.Construct")).is_some());
+		assert_eq!(type_system.find_assembly(&name), Some(&expected));
+
+		fs::remove_dir_all(cache_dir).unwrap();
+	}
+
+	#[test]
+	fn invalidates_cache_when_assembly_changes() {
+		let cache_dir = create_temp_dir("cache");
+		let cache = AssemblyCache::new(&cache_dir);
+		let assembly_path = create_temp_assembly();
+		let assembly_file = assembly_path.to_str().unwrap();
+
+		assert_eq!(cache.load_assembly(assembly_file).unwrap().version, "1.2.4");
+
+		let mut assembly: serde_json::Value = serde_json::from_str(&fs::read_to_string(&assembly_path).unwrap()).unwrap();
+		assembly["version"] = json!("1.2.5-rc.1");
+		fs::write(&assembly_path, serde_json::to_string_pretty(&assembly).unwrap()).unwrap();
+		assert_eq!(cache.load_assembly(assembly_file).unwrap().version, "1.2.5-rc.1");
+
+		remove_temp_assembly(assembly_path);
+		fs::remove_dir_all(cache_dir).unwrap();
+	}
 }
 
-fn create_temp_assembly() -> PathBuf {
-	let temp_dir = env::temp_dir();
-	let mut temp_assembly_dir = temp_dir.clone();
+fn create_temp_dir(prefix: &str) -> PathBuf {
 	let mut rng = rand::thread_rng();
-	temp_assembly_dir.push(format!("ass-{}", rng.gen::<u32>()));
-	fs::create_dir(&temp_assembly_dir).unwrap();
+	let dir = env::temp_dir().join(format!("{}-{}", prefix, rng.gen::<u32>()));
+	fs::create_dir(&dir).unwrap();
+	dir
+}
+
+fn create_temp_assembly() -> PathBuf {
+	let temp_assembly_dir = create_temp_dir("ass");
 	let assembly_path = temp_assembly_dir.join(spec::SPEC_FILE_NAME);
 	let assembly = json!({
 		"schema": "jsii/0.10.0",
@@ -39,6 +39,10 @@ export function liftObject(scope: IConstruct, obj: any): string {
         });
       }
 
+      if (obj instanceof Date) {
+        return `new Date(${JSON.stringify(obj.toISOString())})`;
+      }
+
       if (obj instanceof Set) {
         return `new Set(${liftObject(scope, Array.from(obj))})`;
       }
@@ -8,15 +8,25 @@ export interface Bundle {
   entrypointPath: string;
   directory: string;
   hash: string;
+  /**
+   * The path of the bundle's source map (only set if a source map was requested).
+   */
+  sourcemapPath?: string;
 }
 
 /**
  * Bundles a javascript entrypoint into a single file.
  * @param entrypoint The javascript entrypoint
  * @param outputDir Defaults to `${entrypoint}.bundle`
+ * @param sourcemap Whether to write a source map next to the bundle (chained through the source maps
+ * of the bundled files, so it points back to the original source code)
  * @returns Bundle information
  */
-export function createBundle(entrypoint: string, outputDir?: string): Bundle {
+export function createBundle(
+  entrypoint: string,
+  outputDir?: string,
+  sourcemap = false
+): Bundle {
   const outdir = resolve(outputDir ?? entrypoint + ".bundle");
   mkdirSync(outdir, { recursive: true });
   const outfile = join(outdir, "index.js");
@@ -33,6 +43,7 @@ export function createBundle(entrypoint: string, outputDir?: string): Bundle {
     platform: "node",
     target: "node16",
     external: ["aws-sdk"],
+    sourcemap: sourcemap ? "external" : false,
   });
 
   if (esbuild.errors.length > 0) {
@@ -42,10 +53,13 @@ export function createBundle(entrypoint: string, outputDir?: string): Bundle {
 
   // the bundled contains line comments with file paths, which are not useful for us, especially
   // since they may contain system-specific paths. sadly, esbuild doesn't have a way to disable
-  // this, so we simply filter those out from the bundle.
+  // this, so we simply filter those out from the bundle (or blank them out when there's a source map,
+  // so the line numbers still match it).
   const outlines = readFileSync(outfile, "utf-8").split("\n");
   const isNotLineComment = (line: string) => !line.startsWith("//");
-  const final = outlines.filter(isNotLineComment).join("\n");
+  const final = sourcemap
+    ? outlines.map((line) => (isNotLineComment(line) ? line : "")).join("\n")
+    : outlines.filter(isNotLineComment).join("\n");
   writeFileSync(outfile, final);
 
   // calculate a md5 hash of the contents of asset.path
@@ -55,5 +69,6 @@ export function createBundle(entrypoint: string, outputDir?: string): Bundle {
     entrypointPath: outfile,
     directory: outdir,
     hash: codeHash,
+    sourcemapPath: sourcemap ? `${outfile}.map` : undefined,
   };
 }
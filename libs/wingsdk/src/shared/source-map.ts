@@ -0,0 +1,43 @@
+import { readFileSync } from "fs";
+import { SourceMap } from "module";
+import { dirname, resolve } from "path";
+
+/**
+ * Rewrites the stack frames that point into a generated file so they point to the original source
+ * code instead, using the generated file's source map.
+ *
+ * Node.js only applies source maps (`--enable-source-maps`) to the modules it loads by itself, so
+ * this is needed for code that runs through the `vm` module.
+ *
+ * @param stack The stack trace to rewrite
+ * @param filepath The path of the generated file
+ * @param sourcemapPath The path of the generated file's source map
+ * @returns The rewritten stack trace
+ */
+export function mapStackTrace(
+  stack: string,
+  filepath: string,
+  sourcemapPath: string
+): string {
+  const sourcemap = new SourceMap(
+    JSON.parse(readFileSync(sourcemapPath, "utf-8"))
+  );
+  const sourcesDir = dirname(sourcemapPath);
+  const frame = new RegExp(`${escapeRegExp(filepath)}:(\\d+):(\\d+)`, "g");
+
+  return stack.replace(frame, (location, line: string, column: string) => {
+    // source map positions are zero-based, stack trace positions are one-based
+    const entry = sourcemap.findEntry(Number(line) - 1, Number(column) - 1);
+    // `findEntry` falls back to the closest mapping before the position, which may belong to code
+    // that was added by the bundler, so only lines that have a mapping of their own are rewritten.
+    if (!entry.originalSource || entry.generatedLine !== Number(line) - 1) {
+      return location;
+    }
+    const source = resolve(sourcesDir, entry.originalSource);
+    return `${source}:${entry.originalLine + 1}:${entry.originalColumn + 1}`;
+  });
+}
+
+function escapeRegExp(text: string) {
+  return text.replace(/[.*+?^${}()|[\]\\]/g, "\\$&");
+}
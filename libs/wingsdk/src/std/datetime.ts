@@ -0,0 +1,178 @@
+// This class is used by Wing to provide JSII subsets of the JS Date object.
+// They should not be consumed directly by users.
+
+import { Code, InflightClient } from "../core";
+
+/**
+ * Represents a single moment in time in a platform-independent format.
+ * All the components of a datetime are expressed in UTC.
+ * @wingType datetime
+ */
+export class Datetime {
+  /**
+   * @internal
+   */
+  public static _toInflightType(): Code {
+    return InflightClient.forType(__filename, this.name);
+  }
+
+  /**
+   * Returns the current moment in time.
+   *
+   * @macro (new Date())
+   *
+   * @returns a datetime representing the current moment in time.
+   */
+  public static utcNow(): Datetime {
+    throw new Error("Macro");
+  }
+
+  /**
+   * Parses a datetime from an ISO-8601 string (e.g. "2023-07-18T20:18:25.177+03:00").
+   *
+   * @macro ((iso) => { const d = new Date(iso); if (isNaN(d.getTime())) {throw new Error("unable to parse \"" + iso + "\" as a datetime")}; return d })($args$)
+   *
+   * @param iso ISO-8601 string.
+   * @returns a datetime representing the moment in time of the string.
+   */
+  public static fromIso(iso: string): Datetime {
+    iso;
+    throw new Error("Macro");
+  }
+
+  /**
+   * Creates a datetime from a number of seconds since the Unix epoch.
+   *
+   * @macro (new Date(($args$) * 1000))
+   *
+   * @param timestamp seconds since the Unix epoch.
+   * @returns a datetime representing the moment in time of the timestamp.
+   */
+  public static fromTimestamp(timestamp: number): Datetime {
+    timestamp;
+    throw new Error("Macro");
+  }
+
+  private constructor() {}
+
+  /**
+   * Returns the number of seconds since the Unix epoch.
+   *
+   * @macro ($self$.getTime() / 1000)
+   *
+   * @returns seconds since the Unix epoch.
+   */
+  public timestamp(): number {
+    throw new Error("Macro");
+  }
+
+  /**
+   * Returns the number of milliseconds since the Unix epoch.
+   *
+   * @macro $self$.getTime()
+   *
+   * @returns milliseconds since the Unix epoch.
+   */
+  public timestampMs(): number {
+    throw new Error("Macro");
+  }
+
+  /**
+   * Returns the year of this datetime.
+   *
+   * @macro $self$.getUTCFullYear()
+   *
+   * @returns the year (e.g. 2023).
+   */
+  public year(): number {
+    throw new Error("Macro");
+  }
+
+  /**
+   * Returns the month of this datetime.
+   *
+   * @macro $self$.getUTCMonth()
+   *
+   * @returns the month of the year (0-11).
+   */
+  public month(): number {
+    throw new Error("Macro");
+  }
+
+  /**
+   * Returns the day of the month of this datetime.
+   *
+   * @macro $self$.getUTCDate()
+   *
+   * @returns the day of the month (1-31).
+   */
+  public dayOfMonth(): number {
+    throw new Error("Macro");
+  }
+
+  /**
+   * Returns the day of the week of this datetime.
+   *
+   * @macro $self$.getUTCDay()
+   *
+   * @returns the day of the week (0-6), where 0 is Sunday.
+   */
+  public dayOfWeek(): number {
+    throw new Error("Macro");
+  }
+
+  /**
+   * Returns the hours of this datetime.
+   *
+   * @macro $self$.getUTCHours()
+   *
+   * @returns the hour of the day (0-23).
+   */
+  public hours(): number {
+    throw new Error("Macro");
+  }
+
+  /**
+   * Returns the minutes of this datetime.
+   *
+   * @macro $self$.getUTCMinutes()
+   *
+   * @returns the minutes of the hour (0-59).
+   */
+  public min(): number {
+    throw new Error("Macro");
+  }
+
+  /**
+   * Returns the seconds of this datetime.
+   *
+   * @macro $self$.getUTCSeconds()
+   *
+   * @returns the seconds of the minute (0-59).
+   */
+  public sec(): number {
+    throw new Error("Macro");
+  }
+
+  /**
+   * Returns the milliseconds of this datetime.
+   *
+   * @macro $self$.getUTCMilliseconds()
+   *
+   * @returns the milliseconds of the second (0-999).
+   */
+  public ms(): number {
+    throw new Error("Macro");
+  }
+
+  /**
+   * Formats this datetime as an ISO-8601 string in UTC.
+   *
+   * @macro $self$.toISOString()
+   *
+   * @returns an ISO-8601 string (e.g. "2023-07-18T17:18:25.177Z").
+   */
+  public toIso(): string {
+    throw new Error("Macro");
+  }
+}
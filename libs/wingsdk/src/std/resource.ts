@@ -171,7 +171,7 @@ export abstract class Resource extends Construct implements IResource {
           return;
         }
 
-        if (obj instanceof Duration) {
+        if (obj instanceof Duration || obj instanceof Date) {
           return;
         }
 
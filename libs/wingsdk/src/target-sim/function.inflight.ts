@@ -10,6 +10,7 @@ import {
 } from "./schema-resources";
 import { IFunctionClient } from "../cloud";
 import { Bundle, createBundle } from "../shared/bundling";
+import { mapStackTrace } from "../shared/source-map";
 import { TraceType } from "../std";
 import {
   ISimulatorContext,
@@ -51,7 +52,7 @@ export class Function implements IFunctionClient, ISimulatorResourceInstance {
       activity: async () => {
         if (!this.bundle) {
           const workdir = await mkdtemp(path.join(tmpdir(), "wing-bundles-"));
-          this.bundle = createBundle(this.filename, workdir);
+          this.bundle = createBundle(this.filename, workdir, true);
         }
 
         const sandboxProcess = {
@@ -84,6 +85,7 @@ export class Function implements IFunctionClient, ISimulatorResourceInstance {
 
         return runSandbox(this.bundle.entrypointPath, payload, {
           timeout: this.timeout,
+          sourcemapPath: this.bundle.sourcemapPath,
           context: {
             process: sandboxProcess,
             $simulator: this.context,
@@ -98,6 +100,7 @@ export class Function implements IFunctionClient, ISimulatorResourceInstance {
 interface RunSandboxOptions {
   readonly context: { [key: string]: any };
   readonly timeout: number;
+  readonly sourcemapPath?: string;
 }
 
 /**
@@ -132,9 +135,21 @@ async function runSandbox(
 
   const code = await readFile(filepath, "utf-8");
 
-  return new Promise(($resolve, $reject) => {
+  // point stack traces of errors thrown by the user's code back to the original source code
+  const sourcemapPath = opts.sourcemapPath;
+  const mapError = (error: any) => {
+    if (sourcemapPath && typeof error?.stack === "string") {
+      error.stack = mapStackTrace(error.stack, filepath, sourcemapPath);
+    }
+    return error;
+  };
+
+  return new Promise(($resolve, reject) => {
+    const $reject = (error: any) => reject(mapError(error));
+
+    // `exports` is defined in the context (rather than by the wrapper), so the lines of the code
+    // stay where the source map expects them.
     const wrapper = [
-      "const exports = {};",
       code,
       `exports.handler(${JSON.stringify(
         payload
@@ -143,6 +158,7 @@ async function runSandbox(
 
     const context = vm.createContext({
       ...ctx,
+      exports: {},
       $resolve,
       $reject,
       require, // to support requiring node.js sdk modules (others will be bundled)
@@ -164,9 +180,13 @@ async function runSandbox(
     forbidGlobal("__dirname");
     forbidGlobal("__filename");
 
-    vm.runInContext(wrapper, context, {
-      timeout: opts.timeout,
-      filename: filepath,
-    });
+    try {
+      vm.runInContext(wrapper, context, {
+        timeout: opts.timeout,
+        filename: filepath,
+      });
+    } catch (error) {
+      throw mapError(error);
+    }
   });
 }
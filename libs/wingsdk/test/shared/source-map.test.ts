@@ -0,0 +1,36 @@
+import { mkdtempSync, writeFileSync } from "fs";
+import { tmpdir } from "os";
+import { join } from "path";
+import { test, expect } from "vitest";
+import { mapStackTrace } from "../../src/shared/source-map";
+
+test("maps stack frames to the original source", () => {
+  const dir = mkdtempSync(join(tmpdir(), "wing-source-map-"));
+  const filepath = join(dir, "index.js");
+  const sourcemapPath = join(dir, "index.js.map");
+  // line 2 of index.js maps to line 3 of main.w, line 3 isn't mapped
+  writeFileSync(
+    sourcemapPath,
+    JSON.stringify({
+      version: 3,
+      file: "index.js",
+      sources: ["main.w"],
+      names: [],
+      mappings: ";AAEA",
+    })
+  );
+
+  const stack = [
+    "Error: boom",
+    `    at handler (${filepath}:2:5)`,
+    `    at ${filepath}:3:1`,
+  ].join("\n");
+
+  expect(mapStackTrace(stack, filepath, sourcemapPath)).toEqual(
+    [
+      "Error: boom",
+      `    at handler (${join(dir, "main.w")}:3:1)`,
+      `    at ${filepath}:3:1`,
+    ].join("\n")
+  );
+});
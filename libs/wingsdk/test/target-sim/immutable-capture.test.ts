@@ -74,6 +74,15 @@ captureTest("duration", () => ({
   ],
 }));
 
+captureTest("datetime", () => ({
+  bindings: { my_capture: { obj: new Date("2023-07-18T17:18:25.177Z") } },
+  inflightCode: [
+    `assert(typeof this.my_capture.getTime === "function")`,
+    `assert(this.my_capture.getTime() === 1689700705177)`,
+    `assert(this.my_capture.toISOString() === "2023-07-18T17:18:25.177Z")`,
+  ],
+}));
+
 captureTest("map", () => ({
   bindings: {
     my_capture: {
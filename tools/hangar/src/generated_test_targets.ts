@@ -42,7 +42,11 @@ export async function compileTest(
 
   for await (const dotFile of walkdir(dotWing)) {
     const subpath = relative(dotWing, dotFile).replace(/\\/g, "/");
-    if (!include.find((f) => subpath.startsWith(f))) {
+    // source maps are generated alongside the files they map and aren't worth snapshotting
+    if (
+      !include.find((f) => subpath.startsWith(f)) ||
+      subpath.endsWith(".map")
+    ) {
       continue;
     }
     let fileContents = await fs.readFile(dotFile, "utf8");